@@ -1,35 +1,21 @@
 use std::error::Error;
-use std::process::Command;
 
 fn main() -> Result<(), Box<dyn Error>> {
-    if cfg!(target_os = "macos") {
-        build_mac_dependencies()?
+    // Use the vendored protoc, unless one is explicitly configured, so no system tooling is required to build
+    if std::env::var_os("PROTOC").is_none() {
+        std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
     }
+    let well_known_types = protoc_bin_vendored::include_path()?;
 
-    tonic_build::configure()
-        .build_server(false)
-        .compile(&["src/proto/vector_service.proto"], &["src/proto/"])?;
-
-    Ok(())
-}
-
-fn build_mac_dependencies() -> Result<(), Box<dyn Error>> {
-    // Install dependencies with homebrew
-    let res = Command::new("brew").args(["install", "protobuf"]).output();
-
-    match res {
-        Ok(output) => {
-            if output.status.success() {
-                println!("Installed dependencies with Homebrew");
-            } else {
-                println!(
-                    "Failed to install dependencies with Homebrew: {}",
-                    String::from_utf8_lossy(&output.stderr)
-                );
-            }
-        }
-        Err(e) => return Err(format!("Failed to install dependencies with Homebrew: {e}").into()),
-    }
+    tonic_build::configure().build_server(false).compile(
+        &["src/proto/vector_service.proto"],
+        &[
+            "src/proto/",
+            well_known_types
+                .to_str()
+                .ok_or("Invalid protoc include path")?,
+        ],
+    )?;
 
     Ok(())
 }
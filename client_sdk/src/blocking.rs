@@ -0,0 +1,449 @@
+//! Blocking wrappers of [`PineconeClient`](crate::client::pinecone_client::PineconeClient) and [`Index`](crate::index::Index),
+//! for CLI tools and simple programs that don't run an async runtime.
+//!
+//! A blocking client owns a tokio runtime, shared with the indexes it returns, and runs every call to completion on it.
+//! The runtime is shut down once the client and all of its indexes are dropped.
+//! Blocking methods must not be called from within an async runtime, which panics; use the async client there.
+//!
+//! The wrappers cover the common operations. Others are available through [`PineconeClient::run`] and [`Index::run`],
+//! which run any future of the wrapped async client or index.
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::runtime::{Builder, Runtime};
+
+use crate::client::config::ClientConfig;
+use crate::client::config_resolution::ResolvedConfig;
+use crate::client::pinecone_client::{self as core_client, ClientBuilder, ProgressCallback};
+use crate::data_types::{
+    Backup, Collection, ConfigureIndexOptions, Db, DeleteResponse, FetchResponse, IndexStats,
+    MetadataValue, OperationMetrics, PingResult, QueryInput, QueryResponse, QueryResult,
+    SparseValues, UpdateResponse, UpsertResponse, Vector,
+};
+use crate::index::{self as core_index, UpsertOptions};
+use crate::utils::errors::{PineconeClientError, PineconeResult};
+
+/// A blocking Pinecone client, see the [module documentation](self). Clones share the client's runtime and caches.
+#[derive(Debug, Clone)]
+pub struct PineconeClient {
+    inner: core_client::PineconeClient,
+    runtime: Arc<Runtime>,
+}
+
+impl PineconeClient {
+    /// See [`core_client::PineconeClient::new`].
+    pub fn new(
+        api_key: Option<&str>,
+        region: Option<&str>,
+        project_id: Option<&str>,
+    ) -> PineconeResult<Self> {
+        Self::new_with_config(api_key, region, project_id, ClientConfig::default())
+    }
+
+    /// See [`core_client::PineconeClient::new_with_config`].
+    pub fn new_with_config(
+        api_key: Option<&str>,
+        region: Option<&str>,
+        project_id: Option<&str>,
+        config: ClientConfig,
+    ) -> PineconeResult<Self> {
+        let runtime = new_runtime()?;
+        let inner = runtime.block_on(core_client::PineconeClient::new_with_config(
+            api_key, region, project_id, config,
+        ))?;
+        Ok(Self::with_runtime(inner, runtime))
+    }
+
+    /// Builds a client with the settings of `builder`, see [`core_client::PineconeClient::builder`].
+    pub fn from_builder(builder: ClientBuilder) -> PineconeResult<Self> {
+        let runtime = new_runtime()?;
+        let inner = runtime.block_on(builder.build())?;
+        Ok(Self::with_runtime(inner, runtime))
+    }
+
+    fn with_runtime(inner: core_client::PineconeClient, runtime: Runtime) -> Self {
+        // Index connections spawn their background tasks on the client's runtime
+        let inner = inner.with_runtime(runtime.handle().clone());
+        PineconeClient {
+            inner,
+            runtime: Arc::new(runtime),
+        }
+    }
+
+    /// The wrapped async client.
+    pub fn as_async(&self) -> &core_client::PineconeClient {
+        &self.inner
+    }
+
+    /// Runs a future to completion on the client's runtime, e.g. one of the wrapped async client's operations
+    /// that the blocking client doesn't wrap.
+    pub fn run<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// See [`core_client::PineconeClient::resolved_config`].
+    pub fn resolved_config(&self) -> &ResolvedConfig {
+        self.inner.resolved_config()
+    }
+
+    /// See [`core_client::PineconeClient::metrics`].
+    pub fn metrics(&self) -> BTreeMap<String, OperationMetrics> {
+        self.inner.metrics()
+    }
+
+    /// See [`core_client::PineconeClient::get_index`].
+    pub fn get_index(&self, index_name: &str) -> PineconeResult<Index> {
+        let index = self.run(self.inner.get_index(index_name))?;
+        Ok(self.wrap_index(index))
+    }
+
+    /// See [`core_client::PineconeClient::index_from_host`].
+    pub fn index_from_host(&self, host: &str, index_name: &str) -> PineconeResult<Index> {
+        let index = self.run(self.inner.index_from_host(host, index_name))?;
+        Ok(self.wrap_index(index))
+    }
+
+    fn wrap_index(&self, inner: core_index::Index) -> Index {
+        Index {
+            inner,
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// See [`core_client::PineconeClient::warm`].
+    pub fn warm(&self, index_names: &[&str]) -> HashMap<String, PineconeResult<()>> {
+        self.run(self.inner.warm(index_names))
+    }
+
+    /// Creates a new index, and waits for it to be ready unless `timeout` is -1,
+    /// see [`core_client::PineconeClient::create_index`].
+    pub fn create_index(
+        &self,
+        db: Db,
+        timeout: Option<i32>,
+        progress: Option<ProgressCallback<'_>>,
+    ) -> PineconeResult<()> {
+        self.run(self.inner.create_index(db, timeout, false, None, progress))
+    }
+
+    /// See [`core_client::PineconeClient::describe_index`].
+    pub fn describe_index(&self, index_name: &str) -> PineconeResult<Db> {
+        self.run(self.inner.describe_index(index_name))
+    }
+
+    /// See [`core_client::PineconeClient::list_indexes`].
+    pub fn list_indexes(&self) -> PineconeResult<Vec<String>> {
+        self.run(self.inner.list_indexes())
+    }
+
+    /// See [`core_client::PineconeClient::list_indexes_full`].
+    pub fn list_indexes_full(&self) -> PineconeResult<Vec<Db>> {
+        self.run(self.inner.list_indexes_full())
+    }
+
+    /// Deletes an index, and waits for it to be deleted unless `timeout` is -1,
+    /// see [`core_client::PineconeClient::delete_index`].
+    pub fn delete_index(&self, index_name: &str, timeout: Option<i32>) -> PineconeResult<()> {
+        self.run(self.inner.delete_index(index_name, timeout, None))
+    }
+
+    /// See [`core_client::PineconeClient::configure_index_with`].
+    pub fn configure_index(
+        &self,
+        index_name: &str,
+        options: ConfigureIndexOptions,
+    ) -> PineconeResult<()> {
+        self.run(self.inner.configure_index_with(index_name, options))
+    }
+
+    /// See [`core_client::PineconeClient::create_collection`].
+    pub fn create_collection(
+        &self,
+        collection_name: &str,
+        source_index: &str,
+    ) -> PineconeResult<()> {
+        self.run(self.inner.create_collection(collection_name, source_index))
+    }
+
+    /// See [`core_client::PineconeClient::describe_collection`].
+    pub fn describe_collection(&self, collection_name: &str) -> PineconeResult<Collection> {
+        self.run(self.inner.describe_collection(collection_name))
+    }
+
+    /// See [`core_client::PineconeClient::list_collections`].
+    pub fn list_collections(&self) -> PineconeResult<Vec<String>> {
+        self.run(self.inner.list_collections())
+    }
+
+    /// See [`core_client::PineconeClient::delete_collection`].
+    pub fn delete_collection(&self, collection_name: &str) -> PineconeResult<()> {
+        self.run(self.inner.delete_collection(collection_name))
+    }
+
+    /// See [`core_client::PineconeClient::create_backup`].
+    pub fn create_backup(
+        &self,
+        index_name: &str,
+        backup_name: Option<&str>,
+        description: Option<&str>,
+    ) -> PineconeResult<Backup> {
+        self.run(
+            self.inner
+                .create_backup(index_name, backup_name, description),
+        )
+    }
+
+    /// See [`core_client::PineconeClient::list_backups`].
+    pub fn list_backups(&self, index_name: Option<&str>) -> PineconeResult<Vec<Backup>> {
+        self.run(self.inner.list_backups(index_name))
+    }
+
+    /// See [`core_client::PineconeClient::describe_backup`].
+    pub fn describe_backup(&self, backup_id: &str) -> PineconeResult<Backup> {
+        self.run(self.inner.describe_backup(backup_id))
+    }
+
+    /// See [`core_client::PineconeClient::delete_backup`].
+    pub fn delete_backup(&self, backup_id: &str) -> PineconeResult<()> {
+        self.run(self.inner.delete_backup(backup_id))
+    }
+
+    /// See [`core_client::PineconeClient::create_index_from_backup`].
+    pub fn create_index_from_backup(
+        &self,
+        backup_id: &str,
+        index_name: &str,
+    ) -> PineconeResult<()> {
+        self.run(self.inner.create_index_from_backup(backup_id, index_name))
+    }
+}
+
+/// A blocking handle to an index, returned by [`PineconeClient::get_index`]. Runs on its client's runtime.
+#[derive(Debug, Clone)]
+pub struct Index {
+    inner: core_index::Index,
+    runtime: Arc<Runtime>,
+}
+
+impl Index {
+    /// The wrapped async index.
+    pub fn as_async(&self) -> &core_index::Index {
+        &self.inner
+    }
+
+    /// Runs a future to completion on the index's runtime, e.g. one of the wrapped async index's operations
+    /// that the blocking index doesn't wrap.
+    pub fn run<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// See [`core_index::Index::namespace`].
+    pub fn namespace(&self) -> &str {
+        self.inner.namespace()
+    }
+
+    /// See [`core_index::Index::with_namespace`].
+    pub fn with_namespace(&self, namespace: &str) -> Index {
+        Index {
+            inner: self.inner.with_namespace(namespace),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// See [`core_index::Index::with_timeout`].
+    pub fn with_timeout(&self, timeout: Duration) -> Index {
+        Index {
+            inner: self.inner.with_timeout(timeout),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// See [`core_index::Index::ping`].
+    pub fn ping(&self) -> PineconeResult<PingResult> {
+        self.run(self.inner.ping())
+    }
+
+    /// See [`core_index::Index::describe`].
+    pub fn describe(&self) -> PineconeResult<Db> {
+        self.run(self.inner.describe())
+    }
+
+    /// See [`core_index::Index::upsert`].
+    pub fn upsert(
+        &self,
+        namespace: Option<&str>,
+        vectors: &[Vector],
+        options: UpsertOptions,
+    ) -> PineconeResult<UpsertResponse> {
+        self.runtime
+            .block_on(self.inner.upsert(namespace, vectors, options))
+    }
+
+    /// See [`core_index::Index::query`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn query(
+        &self,
+        namespace: Option<&str>,
+        values: Option<Vec<f32>>,
+        sparse_values: Option<SparseValues>,
+        top_k: u32,
+        filter: Option<BTreeMap<String, MetadataValue>>,
+        include_values: bool,
+        include_metadata: bool,
+        normalize: Option<bool>,
+    ) -> PineconeResult<QueryResponse> {
+        self.runtime.block_on(self.inner.query(
+            namespace,
+            values,
+            sparse_values,
+            top_k,
+            filter,
+            include_values,
+            include_metadata,
+            normalize,
+        ))
+    }
+
+    /// See [`core_index::Index::query_batch`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn query_batch(
+        &self,
+        namespace: Option<&str>,
+        queries: &[QueryInput],
+        top_k: u32,
+        filter: Option<BTreeMap<String, MetadataValue>>,
+        include_values: bool,
+        include_metadata: bool,
+        normalize: Option<bool>,
+    ) -> PineconeResult<Vec<Vec<QueryResult>>> {
+        self.runtime.block_on(self.inner.query_batch(
+            namespace,
+            queries,
+            top_k,
+            filter,
+            include_values,
+            include_metadata,
+            normalize,
+        ))
+    }
+
+    /// See [`core_index::Index::query_by_id`].
+    pub fn query_by_id(
+        &self,
+        namespace: Option<&str>,
+        id: &str,
+        top_k: u32,
+        filter: Option<BTreeMap<String, MetadataValue>>,
+        include_values: bool,
+        include_metadata: bool,
+    ) -> PineconeResult<QueryResponse> {
+        self.runtime.block_on(self.inner.query_by_id(
+            namespace,
+            id,
+            top_k,
+            filter,
+            include_values,
+            include_metadata,
+        ))
+    }
+
+    /// See [`core_index::Index::fetch`].
+    pub fn fetch(&self, namespace: Option<&str>, ids: &[String]) -> PineconeResult<FetchResponse> {
+        self.runtime.block_on(self.inner.fetch(namespace, ids))
+    }
+
+    /// See [`core_index::Index::list`].
+    pub fn list(
+        &self,
+        namespace: Option<&str>,
+        prefix: Option<&str>,
+        limit: Option<u32>,
+        pagination_token: Option<&str>,
+    ) -> PineconeResult<(Vec<String>, Option<String>)> {
+        self.runtime
+            .block_on(self.inner.list(namespace, prefix, limit, pagination_token))
+    }
+
+    /// See [`core_index::Index::update`].
+    pub fn update(
+        &self,
+        id: &str,
+        values: Option<&Vec<f32>>,
+        sparse_values: Option<SparseValues>,
+        set_metadata: Option<BTreeMap<String, MetadataValue>>,
+        unset_metadata: &[String],
+        namespace: Option<&str>,
+    ) -> PineconeResult<UpdateResponse> {
+        self.runtime.block_on(self.inner.update(
+            id,
+            values,
+            sparse_values,
+            set_metadata,
+            unset_metadata,
+            namespace,
+        ))
+    }
+
+    /// See [`core_index::Index::delete`].
+    pub fn delete(
+        &self,
+        ids: Vec<String>,
+        namespace: Option<&str>,
+    ) -> PineconeResult<DeleteResponse> {
+        self.runtime.block_on(self.inner.delete(ids, namespace))
+    }
+
+    /// See [`core_index::Index::delete_by_metadata`].
+    pub fn delete_by_metadata(
+        &self,
+        filter: Option<BTreeMap<String, MetadataValue>>,
+        namespace: Option<&str>,
+    ) -> PineconeResult<DeleteResponse> {
+        self.runtime
+            .block_on(self.inner.delete_by_metadata(filter, namespace))
+    }
+
+    /// See [`core_index::Index::delete_all`].
+    pub fn delete_all(&self, namespace: Option<&str>) -> PineconeResult<DeleteResponse> {
+        self.runtime.block_on(self.inner.delete_all(namespace))
+    }
+
+    /// See [`core_index::Index::describe_index_stats`].
+    pub fn describe_index_stats(
+        &self,
+        filter: Option<BTreeMap<String, MetadataValue>>,
+    ) -> PineconeResult<IndexStats> {
+        self.runtime
+            .block_on(self.inner.describe_index_stats(filter))
+    }
+}
+
+fn new_runtime() -> PineconeResult<Runtime> {
+    Builder::new_multi_thread()
+        .enable_all()
+        .thread_name("pinecone-blocking")
+        .build()
+        .map_err(|err| {
+            PineconeClientError::Other(format!("Failed to start the client's runtime: {err}"))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocking_client() {
+        let client =
+            PineconeClient::new(Some("api-key"), Some("us-west1-gcp"), Some("project")).unwrap();
+        assert_eq!(client.resolved_config().project_id.value, "project");
+        // Connections are established lazily, so no request is made
+        let index = client
+            .index_from_host("idx-project.svc.us-west1-gcp.pinecone.io", "idx")
+            .unwrap();
+        assert_eq!(index.with_namespace("ns").namespace(), "ns");
+        assert!(client.as_async().runtime().is_some());
+    }
+}
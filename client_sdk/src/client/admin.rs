@@ -0,0 +1,135 @@
+//! Organization-level administration, for API keys with organization admin permissions.
+//!
+//! Unlike the rest of the control plane, which is regional, the admin API is served from a single global endpoint.
+use index_service::apis::configuration;
+use serde::Deserialize;
+
+use super::config::ClientConfig;
+use super::control_plane::ControlPlaneClient;
+use crate::data_types::Project;
+use crate::utils::errors::{PineconeClientError, PineconeResult};
+
+pub const ADMIN_API_URL: &str = "https://api.pinecone.io/admin";
+
+/// Manages the projects of an organization. Created with [`PineconeClient::admin`](super::pinecone_client::PineconeClient::admin).
+/// Rate-limited requests are retried according to the client's retry policy.
+#[derive(Debug, Clone)]
+pub struct AdminClient {
+    configuration: configuration::Configuration,
+    client_config: ClientConfig,
+}
+
+impl AdminClient {
+    pub fn new(api_key: &str, client_config: &ClientConfig) -> PineconeResult<Self> {
+        let mut config = configuration::Configuration::new();
+        config.base_path = ADMIN_API_URL.to_string();
+        config.api_key = Some(configuration::ApiKey {
+            prefix: None,
+            key: api_key.to_string(),
+        });
+        config.user_agent = Some(client_config.user_agent());
+        config.client = client_config.http_client()?;
+        Ok(AdminClient {
+            configuration: config,
+            client_config: client_config.clone(),
+        })
+    }
+
+    /// Sends the admin requests to `url` instead of the public admin endpoint, e.g. to a proxy.
+    pub fn with_base_url(mut self, url: &str) -> Self {
+        self.configuration.base_path = url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Lists the projects of the organization.
+    pub async fn list_projects(&self) -> PineconeResult<Vec<Project>> {
+        #[derive(Deserialize)]
+        struct ProjectList {
+            data: Vec<Project>,
+        }
+        let list: ProjectList = self
+            .client_config
+            .retry_rate_limited("list_projects", || async {
+                let response = ControlPlaneClient::raw_request(
+                    &self.configuration,
+                    reqwest::Method::GET,
+                    "/projects",
+                    None,
+                )
+                .await?;
+                ControlPlaneClient::parse_json_response(response).await
+            })
+            .await?;
+        Ok(list.data)
+    }
+
+    /// Creates a project.
+    ///
+    /// # Arguments
+    /// - `name` - the name of the project. Must be unique within the organization.
+    /// - `max_pods` - the maximal number of pods the project's indexes can use in total. Defaults to the organization's quota.
+    ///
+    /// # Returns
+    /// The new project.
+    pub async fn create_project(
+        &self,
+        name: &str,
+        max_pods: Option<i32>,
+    ) -> PineconeResult<Project> {
+        if name.is_empty() {
+            return Err(PineconeClientError::ValueError(
+                "Project name must not be empty".to_string(),
+            ));
+        }
+        let mut body = serde_json::Map::new();
+        body.insert("name".to_string(), name.into());
+        if let Some(max_pods) = max_pods {
+            body.insert("max_pods".to_string(), max_pods.into());
+        }
+        let body = serde_json::Value::from(body);
+        self.client_config
+            .retry_rate_limited("create_project", || async {
+                let response = ControlPlaneClient::raw_request(
+                    &self.configuration,
+                    reqwest::Method::POST,
+                    "/projects",
+                    Some(body.clone()),
+                )
+                .await?;
+                ControlPlaneClient::parse_json_response(response).await
+            })
+            .await
+    }
+
+    pub async fn describe_project(&self, project_id: &str) -> PineconeResult<Project> {
+        let path = &format!("/projects/{project_id}");
+        self.client_config
+            .retry_rate_limited("describe_project", || async {
+                let response = ControlPlaneClient::raw_request(
+                    &self.configuration,
+                    reqwest::Method::GET,
+                    path,
+                    None,
+                )
+                .await?;
+                ControlPlaneClient::parse_json_response(response).await
+            })
+            .await
+    }
+
+    /// Deletes a project. The project must not have any indexes or collections left.
+    pub async fn delete_project(&self, project_id: &str) -> PineconeResult<()> {
+        let path = &format!("/projects/{project_id}");
+        self.client_config
+            .retry_rate_limited("delete_project", || {
+                ControlPlaneClient::raw_request(
+                    &self.configuration,
+                    reqwest::Method::DELETE,
+                    path,
+                    None,
+                )
+            })
+            .await?;
+        Ok(())
+    }
+}
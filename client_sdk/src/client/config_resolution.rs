@@ -0,0 +1,279 @@
+//! Resolves the client's settings (API key, region and project id) from the places they can be configured in.
+//!
+//! Each setting is taken from the first of these that sets it:
+//! 1. An explicit argument, e.g. `PineconeClient::new(Some(api_key), ..)`.
+//! 2. An environment variable: `PINECONE_API_KEY`, `PINECONE_REGION` or `PINECONE_PROJECT_ID`.
+//! 3. The selected profile of the profile file (see below).
+//! 4. A default: `us-west1-gcp` for the region. The project id defaults to the one the API key belongs to, looked up from the controller.
+//!
+//! The profile file is read from `PINECONE_CONFIG_FILE`, or from `~/.pinecone/config`. It holds INI-style sections, one per profile:
+//! ```text
+//! [default]
+//! api_key = ...
+//! region = us-west1-gcp
+//!
+//! [staging]
+//! api_key = ...
+//! region = eu-west1-gcp
+//! project_id = ...
+//! ```
+//! The `default` profile is used unless another one is selected, with [`ClientConfig::profile`](super::config::ClientConfig::profile)
+//! or the `PINECONE_PROFILE` environment variable.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::utils::errors::{PineconeClientError, PineconeResult};
+use crate::utils::redaction;
+
+pub const API_KEY_ENV: &str = "PINECONE_API_KEY";
+pub const REGION_ENV: &str = "PINECONE_REGION";
+pub const PROJECT_ID_ENV: &str = "PINECONE_PROJECT_ID";
+/// Selects the profile to read from the profile file.
+pub const PROFILE_ENV: &str = "PINECONE_PROFILE";
+/// Overrides the location of the profile file.
+pub const CONFIG_FILE_ENV: &str = "PINECONE_CONFIG_FILE";
+
+pub const DEFAULT_REGION: &str = "us-west1-gcp";
+const DEFAULT_PROFILE: &str = "default";
+
+/// Where a resolved setting came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSource {
+    Argument,
+    Environment(&'static str),
+    Profile {
+        path: PathBuf,
+        profile: String,
+    },
+    Default,
+    /// Looked up from the controller, e.g. the project id of the API key
+    ControlPlane,
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigSource::Argument => write!(f, "argument"),
+            ConfigSource::Environment(var) => write!(f, "environment variable {var}"),
+            ConfigSource::Profile { path, profile } => {
+                write!(f, "profile '{profile}' in {}", path.display())
+            }
+            ConfigSource::Default => write!(f, "default"),
+            ConfigSource::ControlPlane => write!(f, "controller lookup"),
+        }
+    }
+}
+
+/// A setting's value, and where it came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedValue {
+    pub value: String,
+    pub source: ConfigSource,
+}
+
+/// The settings a client was created with, see the [module documentation](self) for the precedence.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ResolvedConfig {
+    pub api_key: ResolvedValue,
+    pub region: ResolvedValue,
+    pub project_id: ResolvedValue,
+}
+
+impl fmt::Debug for ResolvedConfig {
+    // Redacts the API key, which would otherwise end up in logs
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let api_key = ResolvedValue {
+            value: redaction::redact_api_key(&self.api_key.value),
+            source: self.api_key.source.clone(),
+        };
+        f.debug_struct("ResolvedConfig")
+            .field("api_key", &api_key)
+            .field("region", &self.region)
+            .field("project_id", &self.project_id)
+            .finish()
+    }
+}
+
+/// Resolves settings from explicit arguments, environment variables and the selected profile.
+#[derive(Debug, Default)]
+pub struct ConfigResolver {
+    profile_path: PathBuf,
+    profile_name: String,
+    profile: BTreeMap<String, String>,
+}
+
+impl ConfigResolver {
+    /// Loads the selected profile from the profile file.
+    /// A missing file or `default` profile is not an error, but an explicitly selected profile must exist.
+    ///
+    /// # Arguments
+    /// - `profile` - the profile to use. Defaults to `PINECONE_PROFILE`, or to `default`.
+    pub fn load(profile: Option<&str>) -> PineconeResult<Self> {
+        let (profile_name, explicit) = match profile {
+            Some(name) => (name.to_string(), true),
+            None => match env::var(PROFILE_ENV) {
+                Ok(name) => (name, true),
+                Err(_) => (DEFAULT_PROFILE.to_string(), false),
+            },
+        };
+        let profile_path = match env::var(CONFIG_FILE_ENV) {
+            Ok(path) => PathBuf::from(path),
+            Err(_) => default_config_file(),
+        };
+        let profile = match fs::read_to_string(&profile_path) {
+            Ok(contents) => parse_profiles(&contents)
+                .map_err(|err| {
+                    PineconeClientError::ValueError(format!(
+                        "Failed to parse {path}: {err}",
+                        path = profile_path.display()
+                    ))
+                })?
+                .remove(&profile_name),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+            Err(err) => return Err(err.into()),
+        };
+        let profile = match profile {
+            Some(profile) => profile,
+            None if explicit => {
+                return Err(PineconeClientError::ValueError(format!(
+                    "Profile '{profile_name}' was not found in {path}",
+                    path = profile_path.display()
+                )))
+            }
+            None => BTreeMap::new(),
+        };
+        Ok(ConfigResolver {
+            profile_path,
+            profile_name,
+            profile,
+        })
+    }
+
+    /// Resolves a single setting, from `argument`, then the `env_var` environment variable, then the profile's `key`.
+    pub fn resolve(
+        &self,
+        argument: Option<&str>,
+        env_var: &'static str,
+        key: &str,
+    ) -> Option<ResolvedValue> {
+        if let Some(value) = argument {
+            return Some(ResolvedValue {
+                value: value.to_string(),
+                source: ConfigSource::Argument,
+            });
+        }
+        if let Ok(value) = env::var(env_var) {
+            return Some(ResolvedValue {
+                value,
+                source: ConfigSource::Environment(env_var),
+            });
+        }
+        self.profile.get(key).map(|value| ResolvedValue {
+            value: value.clone(),
+            source: ConfigSource::Profile {
+                path: self.profile_path.clone(),
+                profile: self.profile_name.clone(),
+            },
+        })
+    }
+}
+
+fn default_config_file() -> PathBuf {
+    let home = env::var("HOME")
+        .or_else(|_| env::var("USERPROFILE"))
+        .unwrap_or_default();
+    Path::new(&home).join(".pinecone").join("config")
+}
+
+/// Parses the profiles of a profile file. Keys before the first section belong to the `default` profile.
+fn parse_profiles(contents: &str) -> Result<BTreeMap<String, BTreeMap<String, String>>, String> {
+    let mut profiles: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+    let mut current = DEFAULT_PROFILE.to_string();
+    for (line_num, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            current = section.trim().to_string();
+            profiles.entry(current.clone()).or_default();
+            continue;
+        }
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| format!("expected 'key = value' on line {}", line_num + 1))?;
+        profiles
+            .entry(current.clone())
+            .or_default()
+            .insert(key.trim().to_string(), value.trim().to_string());
+    }
+    Ok(profiles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_profiles() {
+        let profiles = parse_profiles(
+            "# comment\nregion = us-west1-gcp\n\n[staging]\napi_key = abc-123\nregion=eu-west1-gcp\n",
+        )
+        .unwrap();
+        assert_eq!(profiles["default"]["region"], "us-west1-gcp");
+        assert_eq!(profiles["staging"]["api_key"], "abc-123");
+        assert_eq!(profiles["staging"]["region"], "eu-west1-gcp");
+        assert!(parse_profiles("[default]\nnot a key value").is_err());
+    }
+
+    #[test]
+    fn test_resolve_precedence() {
+        let resolver = ConfigResolver {
+            profile_path: PathBuf::from("/tmp/config"),
+            profile_name: "staging".to_string(),
+            profile: [
+                ("region".to_string(), "eu-west1-gcp".to_string()),
+                ("project_id".to_string(), "proj".to_string()),
+            ]
+            .into(),
+        };
+        const UNSET_ENV: &str = "PINECONE_TEST_UNSET_VARIABLE";
+        let resolved = resolver.resolve(Some("us-east1-gcp"), UNSET_ENV, "region");
+        assert_eq!(resolved.unwrap().source, ConfigSource::Argument);
+        let resolved = resolver.resolve(None, UNSET_ENV, "region").unwrap();
+        assert_eq!(resolved.value, "eu-west1-gcp");
+        assert_eq!(
+            resolved.source.to_string(),
+            "profile 'staging' in /tmp/config"
+        );
+        assert!(resolver.resolve(None, UNSET_ENV, "api_key").is_none());
+    }
+
+    #[test]
+    fn test_resolved_config_debug_redacts_api_key() {
+        let resolved = |value: &str| ResolvedValue {
+            value: value.to_string(),
+            source: ConfigSource::Argument,
+        };
+        let config = ResolvedConfig {
+            api_key: resolved("0a1b2c3d-4e5f-6a7b-8c9d-0e1f2a3b4c5d"),
+            region: resolved("us-west1-gcp"),
+            project_id: resolved("proj"),
+        };
+        let debug = format!("{config:?}");
+        assert!(!debug.contains("0a1b2c3d"));
+        assert!(debug.contains("****4c5d"));
+        assert!(debug.contains("us-west1-gcp"));
+
+        let config = ResolvedConfig {
+            api_key: resolved("short-key"),
+            ..config
+        };
+        assert!(!format!("{config:?}").contains("short"));
+    }
+}
@@ -1,8 +1,17 @@
+use crate::data_types::Backup;
 use crate::data_types::Collection;
 use crate::data_types::Db;
 use crate::data_types::WhoamiResponse;
+use crate::utils::conversions::unknown_fields;
+use crate::utils::errors::ErrorKind;
 use crate::utils::errors::PineconeClientError;
 use crate::utils::errors::PineconeResult;
+#[cfg(feature = "prometheus")]
+use crate::utils::metrics::Plane;
+use crate::utils::payload_dump;
+use crate::utils::rate_limit;
+use crate::utils::redaction;
+use crate::utils::telemetry;
 use index_service::apis::configuration;
 use index_service::apis::index_operations_api;
 use index_service::apis::index_operations_api::{
@@ -10,47 +19,341 @@ use index_service::apis::index_operations_api::{
 };
 use index_service::models::CreateCollectionRequest;
 use index_service::models::PatchRequest;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Mutex, RwLock};
+use std::time::{Instant, SystemTime};
+
+use super::config::{ClientConfig, RetryPolicy};
+use super::request_dump::{DumpedRequest, RequestDump};
+
+/// The dumped response of a control plane request: the status and raw content of the response if it failed,
+/// see [`payload_dump`](crate::utils::payload_dump).
+fn dumped_response<T>(res: &PineconeResult<T>) -> String {
+    match res {
+        Ok(_) => "OK".to_string(),
+        Err(PineconeClientError::ControlPlaneOperationError { err, status_code }) => {
+            format!("{status_code}\n{err}")
+        }
+        Err(err) => err.to_string(),
+    }
+}
 
 #[derive(Debug)]
 pub struct ControlPlaneClient {
     controller_url: String,
     configuration: configuration::Configuration,
+    // If set, failed requests are dumped into this directory
+    request_dump_dir: Mutex<Option<PathBuf>>,
+    // Controllers that read requests fail over to, in order, when the primary controller is unreachable
+    fallback_controller_urls: RwLock<Vec<String>>,
+    retry_policy: RetryPolicy,
+    // For the debug logs and the metrics
+    client_config: ClientConfig,
 }
 
 impl ControlPlaneClient {
-    pub fn new(controller_url: &str, api_key: &str) -> ControlPlaneClient {
+    pub fn new(
+        controller_url: &str,
+        api_key: &str,
+        client_config: &ClientConfig,
+    ) -> PineconeResult<ControlPlaneClient> {
         let mut config = configuration::Configuration::new();
         config.base_path = controller_url.to_string();
         config.api_key = Some(configuration::ApiKey {
             prefix: None,
             key: api_key.to_string(),
         });
-        config.user_agent = Some("pinecone-rust-client/0.1".to_string());
-        // can pass a custom client here
-        config.client = reqwest::Client::new();
-        ControlPlaneClient {
+        config.user_agent = Some(client_config.user_agent());
+        config.client = client_config.http_client()?;
+        Ok(ControlPlaneClient {
             controller_url: controller_url.to_string(),
             configuration: config,
+            request_dump_dir: Mutex::new(None),
+            fallback_controller_urls: RwLock::new(Vec::new()),
+            retry_policy: client_config.retry_policy.clone(),
+            client_config: client_config.clone(),
+        })
+    }
+
+    /// Sets the controllers that read requests (e.g. `describe_index`) fail over to, in order, when the primary
+    /// controller is unreachable. Requests that modify the control plane's state are only sent to the primary controller.
+    pub fn set_fallback_controllers(&self, controller_urls: Vec<String>) {
+        *self.fallback_controller_urls.write().unwrap() = controller_urls;
+    }
+
+    /// Sends a read request to the primary controller, failing over to the fallback controllers while the
+    /// controllers are unreachable. The controller that served the request is logged, and if all of them are
+    /// unreachable, the returned error lists the controllers that were tried.
+    /// If the round failed with a retryable error (see [`ErrorKind::is_retryable`]), e.g. no controller was reachable
+    /// or the request was rate limited, the whole round is retried according to the retry policy.
+    async fn read<T, F, Fut>(&self, operation: &str, request: F) -> PineconeResult<T>
+    where
+        F: Fn(configuration::Configuration) -> Fut,
+        Fut: Future<Output = PineconeResult<T>>,
+    {
+        let mut retry = 0;
+        let mut rate_limit_retry = 0;
+        loop {
+            match self.read_once(operation, &request).await {
+                Err(err)
+                    if retry < self.retry_policy.max_retries
+                        && err.kind() != ErrorKind::QuotaExceeded
+                        && err.is_retryable() =>
+                {
+                    let backoff = self.retry_policy.delay(retry);
+                    log::warn!("{operation} failed, retrying in {backoff:?}: {err}");
+                    self.record_retry(operation);
+                    tokio::time::sleep(backoff).await;
+                    retry += 1;
+                }
+                Err(err)
+                    if rate_limit_retry < self.retry_policy.max_rate_limit_retries
+                        && err.kind() == ErrorKind::QuotaExceeded =>
+                {
+                    let backoff = self.retry_policy.delay_after(&err, rate_limit_retry);
+                    log::warn!("{operation} was rate limited, retrying in {backoff:?}");
+                    self.record_retry(operation);
+                    tokio::time::sleep(backoff).await;
+                    rate_limit_retry += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+
+    async fn read_once<T, F, Fut>(&self, operation: &str, request: &F) -> PineconeResult<T>
+    where
+        F: Fn(configuration::Configuration) -> Fut,
+        Fut: Future<Output = PineconeResult<T>>,
+    {
+        let fallbacks = self.fallback_controller_urls.read().unwrap().clone();
+        let mut tried = Vec::new();
+        let mut last_err = None;
+        for url in std::iter::once(self.controller_url.clone()).chain(fallbacks) {
+            let config = configuration::Configuration {
+                base_path: url.clone(),
+                ..self.configuration.clone()
+            };
+            match self
+                .observe(
+                    operation,
+                    &url,
+                    self.client_config.http_span(operation).run(request(config)),
+                )
+                .await
+            {
+                Err(PineconeClientError::ControlPlaneConnectionError { region, err }) => {
+                    log::warn!("{operation} failed to reach controller {url}: {err}");
+                    tried.push(url);
+                    last_err = Some((region, err));
+                }
+                res => {
+                    if !tried.is_empty() {
+                        log::info!("{operation} was served by fallback controller {url}");
+                    }
+                    return res;
+                }
+            }
+        }
+        let (region, err) = last_err.unwrap_or_default();
+        Err(PineconeClientError::ControlPlaneConnectionError {
+            region,
+            err: format!("{err}. Tried controllers: {}", tried.join(", ")),
+        })
+    }
+
+    /// Sets the directory into which failed requests are dumped. Pass `None` to disable dumping.
+    pub fn set_request_dump_dir(&self, dir: Option<PathBuf>) {
+        *self.request_dump_dir.lock().unwrap() = dir;
+    }
+
+    /// Sends a request that modifies the control plane's state, retrying it while it's rate limited.
+    async fn retry_rate_limited<T, F, Fut>(&self, operation: &str, request: F) -> PineconeResult<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = PineconeResult<T>>,
+    {
+        let attempts = AtomicU32::new(0);
+        self.client_config
+            .retry_rate_limited(operation, || {
+                if attempts.fetch_add(1, Ordering::Relaxed) > 0 {
+                    self.record_retry(operation);
+                }
+                self.observe(operation, &self.controller_url, request())
+            })
+            .await
+    }
+
+    /// Awaits a single request attempt to the controller at `url`, logging its outcome and recording it into the
+    /// client's metrics and latency histograms.
+    async fn observe<T>(
+        &self,
+        operation: &str,
+        url: &str,
+        request: impl Future<Output = PineconeResult<T>>,
+    ) -> PineconeResult<T> {
+        let start = Instant::now();
+        let res = request.await;
+        let elapsed = start.elapsed();
+        #[cfg(feature = "prometheus")]
+        self.client_config.record_metrics_request(
+            operation,
+            Plane::Control,
+            elapsed,
+            res.as_ref().err().map(PineconeClientError::kind),
+        );
+        self.client_config
+            .record_latency(operation, elapsed, res.is_err());
+        self.client_config.debug_log(operation, || {
+            let api_key = self
+                .configuration
+                .api_key
+                .as_ref()
+                .map(|api_key| redaction::redact_api_key(&api_key.key))
+                .unwrap_or_default();
+            let outcome = match &res {
+                Ok(_) => "succeeded".to_string(),
+                Err(PineconeClientError::ControlPlaneOperationError { status_code, .. }) => {
+                    format!("failed with status {status_code}")
+                }
+                Err(err) => format!("failed: {err}"),
+            };
+            format!(
+                "{operation} request to {url} with API key {api_key} {outcome} in {:.1} ms",
+                elapsed.as_secs_f64() * 1000.0
+            )
+        });
+        res
+    }
+
+    #[cfg_attr(not(feature = "prometheus"), allow(unused_variables))]
+    fn record_retry(&self, operation: &str) {
+        #[cfg(feature = "prometheus")]
+        self.client_config
+            .record_metrics_retry(operation, Plane::Control);
+    }
+
+    /// Sends a request that modifies the control plane's state. Rate-limited requests are retried.
+    /// If the request fails and a dump directory is set, the request is dumped so it can be replayed later.
+    pub async fn send(&self, request: DumpedRequest) -> PineconeResult<()> {
+        self.client_config.debug_log(request.operation(), || {
+            format!(
+                "{} request: {}",
+                request.operation(),
+                redaction::format_payload(&request, self.client_config.log_full_payloads)
+            )
+        });
+        let res = self
+            .retry_rate_limited(request.operation(), || async {
+                payload_dump::dump("request", request.operation(), &self.controller_url, || {
+                    serde_json::to_string_pretty(&request).unwrap_or_default()
+                });
+                let res = self.send_once(&request).await;
+                payload_dump::dump(
+                    "response",
+                    request.operation(),
+                    &self.controller_url,
+                    || dumped_response(&res),
+                );
+                res
+            })
+            .await;
+        if let Err(err) = &res {
+            self.dump_failed_request(request, err);
+        }
+        res
+    }
+
+    async fn send_once(&self, request: &DumpedRequest) -> PineconeResult<()> {
+        let config = &self.configuration;
+        match request {
+            DumpedRequest::CreateIndex { payload } => {
+                index_operations_api::create_index(config, Some(payload.clone()))
+                    .await
+                    .map(|_| ())
+                    .map_err(PineconeClientError::from)
+            }
+            DumpedRequest::DeleteIndex { name } => index_operations_api::delete_index(config, name)
+                .await
+                .map(|_| ())
+                .map_err(PineconeClientError::from),
+            DumpedRequest::ConfigureIndex { name, payload } => {
+                index_operations_api::configure_index(config, name, Some(payload.clone()))
+                    .await
+                    .map(|_| ())
+                    .map_err(PineconeClientError::from)
+            }
+            DumpedRequest::CreateCollection { payload } => {
+                index_operations_api::create_collection(config, Some(payload.clone()))
+                    .await
+                    .map(|_| ())
+                    .map_err(PineconeClientError::from)
+            }
+            DumpedRequest::DeleteCollection { name } => {
+                index_operations_api::delete_collection(config, name)
+                    .await
+                    .map(|_| ())
+                    .map_err(PineconeClientError::from)
+            }
+        }
+    }
+
+    /// Re-issues a request previously dumped to `dump_file`.
+    pub async fn replay(&self, dump_file: &Path) -> PineconeResult<()> {
+        let dump = RequestDump::read(dump_file)?;
+        self.send(dump.request).await
+    }
+
+    fn dump_failed_request(&self, request: DumpedRequest, err: &PineconeClientError) {
+        let dir = match self.request_dump_dir.lock().unwrap().clone() {
+            Some(dir) => dir,
+            None => return,
+        };
+        let operation = request.operation();
+        // Failing to write the dump shouldn't hide the original error
+        match RequestDump::new(request, err, &self.controller_url).write(&dir) {
+            Ok(path) => log::warn!(
+                "{operation} failed. The request was dumped to {path}, use `replay()` to re-issue it",
+                path = path.display()
+            ),
+            Err(dump_err) => log::warn!("Failed to dump the failed {operation} request: {dump_err}"),
         }
     }
 
     pub async fn create_index(&self, index: Db) -> PineconeResult<()> {
-        index_operations_api::create_index(&self.configuration, Some(index.into())).await?;
-        Ok(())
+        self.send(DumpedRequest::CreateIndex {
+            payload: index.try_into()?,
+        })
+        .await
     }
 
     pub async fn delete_index(&self, name: &str) -> PineconeResult<()> {
-        index_operations_api::delete_index(&self.configuration, name).await?;
-        Ok(())
+        self.send(DumpedRequest::DeleteIndex {
+            name: name.to_string(),
+        })
+        .await
     }
 
     pub async fn describe_index(&self, name: &str) -> PineconeResult<Db> {
-        let response = index_operations_api::describe_index(&self.configuration, name).await?;
+        let response = self
+            .read("describe_index", |config| async move {
+                Ok(index_operations_api::describe_index(&config, name).await?)
+            })
+            .await?;
         match response
             .entity
             .ok_or(PineconeClientError::ControlPlaneParsingError {})?
         {
-            DescribeIndexSuccess::Status200(entity) => Db::try_from(entity),
+            DescribeIndexSuccess::Status200(entity) => {
+                let extra = unknown_fields(&response.content, &entity);
+                let mut db = Db::try_from(entity)?;
+                db.extra.0.extend(extra.0);
+                Ok(db)
+            }
             DescribeIndexSuccess::UnknownValue(val) => {
                 Err(PineconeClientError::Other(val.to_string()))
             }
@@ -58,7 +361,11 @@ impl ControlPlaneClient {
     }
 
     pub async fn list_indexes(&self) -> PineconeResult<Vec<String>> {
-        let response = index_operations_api::list_indexes(&self.configuration).await?;
+        let response = self
+            .read("list_indexes", |config| async move {
+                Ok(index_operations_api::list_indexes(&config).await?)
+            })
+            .await?;
         match response
             .entity
             .ok_or(PineconeClientError::ControlPlaneParsingError {})?
@@ -70,33 +377,39 @@ impl ControlPlaneClient {
         }
     }
 
-    pub async fn configure_index(
-        &self,
-        name: &str,
-        pod_type: Option<String>,
-        replicas: Option<i32>,
-    ) -> PineconeResult<()> {
-        let patch_request = PatchRequest { pod_type, replicas };
-        index_operations_api::configure_index(&self.configuration, name, Some(patch_request))
-            .await?;
-        Ok(())
+    pub async fn configure_index(&self, name: &str, patch: PatchRequest) -> PineconeResult<()> {
+        self.send(DumpedRequest::ConfigureIndex {
+            name: name.to_string(),
+            payload: patch,
+        })
+        .await
     }
 
     pub async fn create_collection(&self, collection: Collection) -> PineconeResult<()> {
         let collection_request = CreateCollectionRequest::from(collection);
-        index_operations_api::create_collection(&self.configuration, Some(collection_request))
-            .await?;
-        Ok(())
+        self.send(DumpedRequest::CreateCollection {
+            payload: collection_request,
+        })
+        .await
     }
 
     pub async fn describe_collection(&self, collection_name: &str) -> PineconeResult<Collection> {
-        let response =
-            index_operations_api::describe_collection(&self.configuration, collection_name).await?;
+        let response = self
+            .read("describe_collection", |config| async move {
+                Ok(index_operations_api::describe_collection(&config, collection_name).await?)
+            })
+            .await?;
         match response
             .entity
             .ok_or(PineconeClientError::ControlPlaneParsingError {})?
         {
-            DescribeCollectionSuccess::Status200(entity) => Ok(Collection::from(entity)),
+            DescribeCollectionSuccess::Status200(entity) => {
+                let extra = unknown_fields(&response.content, &entity);
+                Ok(Collection {
+                    extra,
+                    ..Collection::from(entity)
+                })
+            }
             DescribeCollectionSuccess::UnknownValue(val) => {
                 Err(PineconeClientError::Other(val.to_string()))
             }
@@ -104,12 +417,18 @@ impl ControlPlaneClient {
     }
 
     pub async fn delete_collection(&self, collection_name: &str) -> PineconeResult<()> {
-        index_operations_api::delete_collection(&self.configuration, collection_name).await?;
-        Ok(())
+        self.send(DumpedRequest::DeleteCollection {
+            name: collection_name.to_string(),
+        })
+        .await
     }
 
     pub async fn list_collections(&self) -> PineconeResult<Vec<String>> {
-        let response = index_operations_api::list_collections(&self.configuration).await?;
+        let response = self
+            .read("list_collections", |config| async move {
+                Ok(index_operations_api::list_collections(&config).await?)
+            })
+            .await?;
         match response
             .entity
             .ok_or(PineconeClientError::ControlPlaneParsingError {})?
@@ -121,8 +440,173 @@ impl ControlPlaneClient {
         }
     }
 
+    /// Sends a request to an endpoint that the generated OpenAPI client doesn't cover, e.g. the backup endpoints.
+    /// Responses with an error status are returned as a `ControlPlaneOperationError`, or as a
+    /// `ControlPlaneRateLimitError` with the response's `Retry-After`.
+    pub(super) async fn raw_request(
+        config: &configuration::Configuration,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<serde_json::Value>,
+    ) -> PineconeResult<reqwest::Response> {
+        let operation = format!("{method} {path}");
+        let mut request = config
+            .client
+            .request(method, format!("{}{path}", config.base_path));
+        if let Some(api_key) = &config.api_key {
+            request = request.header("Api-Key", &api_key.key);
+        }
+        if let Some(user_agent) = &config.user_agent {
+            request = request.header(reqwest::header::USER_AGENT, user_agent);
+        }
+        payload_dump::dump("request", &operation, &config.base_path, || {
+            body.as_ref()
+                .and_then(|body| serde_json::to_string_pretty(body).ok())
+                .unwrap_or_default()
+        });
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        let mut trace_headers = reqwest::header::HeaderMap::new();
+        telemetry::inject_current(&mut trace_headers);
+        let response = request.headers(trace_headers).send().await.map_err(|e| {
+            PineconeClientError::ControlPlaneConnectionError {
+                region: " ".to_string(),
+                err: e.to_string(),
+            }
+        })?;
+        let status = response.status();
+        if status.is_success() {
+            payload_dump::dump("response", &operation, &config.base_path, || {
+                status.to_string()
+            });
+            return Ok(response);
+        }
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| rate_limit::parse_retry_after(value, SystemTime::now()));
+        let content = response.text().await.unwrap_or_default();
+        payload_dump::dump("response", &operation, &config.base_path, || {
+            format!("{status}\n{content}")
+        });
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(PineconeClientError::ControlPlaneRateLimitError {
+                err: content,
+                retry_after,
+            });
+        }
+        Err(PineconeClientError::ControlPlaneOperationError {
+            err: content,
+            status_code: status.to_string(),
+        })
+    }
+
+    pub(super) async fn parse_json_response<T: DeserializeOwned>(
+        response: reqwest::Response,
+    ) -> PineconeResult<T> {
+        let content = response
+            .text()
+            .await
+            .map_err(|_| PineconeClientError::ControlPlaneParsingError {})?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Creates a backup of an index.
+    ///
+    /// # Arguments
+    /// - `index_name` - the index to back up.
+    /// - `backup_name` - an optional name for the backup.
+    /// - `description` - an optional description of the backup.
+    pub async fn create_backup(
+        &self,
+        index_name: &str,
+        backup_name: Option<&str>,
+        description: Option<&str>,
+    ) -> PineconeResult<Backup> {
+        let mut body = serde_json::Map::new();
+        if let Some(backup_name) = backup_name {
+            body.insert("name".to_string(), backup_name.into());
+        }
+        if let Some(description) = description {
+            body.insert("description".to_string(), description.into());
+        }
+        let (path, body) = (
+            &format!("/indexes/{index_name}/backups"),
+            serde_json::Value::from(body),
+        );
+        self.retry_rate_limited("create_backup", || async {
+            let response = Self::raw_request(
+                &self.configuration,
+                reqwest::Method::POST,
+                path,
+                Some(body.clone()),
+            )
+            .await?;
+            Self::parse_json_response(response).await
+        })
+        .await
+    }
+
+    /// Lists the backups of all indexes in the project, or only of `index_name` if set.
+    pub async fn list_backups(&self, index_name: Option<&str>) -> PineconeResult<Vec<Backup>> {
+        #[derive(Deserialize)]
+        struct BackupList {
+            data: Vec<Backup>,
+        }
+        let path = match index_name {
+            Some(index_name) => format!("/indexes/{index_name}/backups"),
+            None => "/backups".to_string(),
+        };
+        let path = &path;
+        let list: BackupList = self
+            .read("list_backups", |config| async move {
+                let response = Self::raw_request(&config, reqwest::Method::GET, path, None).await?;
+                Self::parse_json_response(response).await
+            })
+            .await?;
+        Ok(list.data)
+    }
+
+    pub async fn describe_backup(&self, backup_id: &str) -> PineconeResult<Backup> {
+        let path = &format!("/backups/{backup_id}");
+        self.read("describe_backup", |config| async move {
+            let response = Self::raw_request(&config, reqwest::Method::GET, path, None).await?;
+            Self::parse_json_response(response).await
+        })
+        .await
+    }
+
+    pub async fn delete_backup(&self, backup_id: &str) -> PineconeResult<()> {
+        let path = &format!("/backups/{backup_id}");
+        self.retry_rate_limited("delete_backup", || {
+            Self::raw_request(&self.configuration, reqwest::Method::DELETE, path, None)
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Creates a new index named `index_name`, restored from a backup.
+    pub async fn create_index_from_backup(
+        &self,
+        backup_id: &str,
+        index_name: &str,
+    ) -> PineconeResult<()> {
+        let path = &format!("/backups/{backup_id}/create-index");
+        self.retry_rate_limited("create_index_from_backup", || {
+            Self::raw_request(
+                &self.configuration,
+                reqwest::Method::POST,
+                path,
+                Some(serde_json::json!({ "name": index_name })),
+            )
+        })
+        .await?;
+        Ok(())
+    }
+
     pub async fn whoami(&self) -> PineconeResult<WhoamiResponse> {
-        let rq_client = self.configuration.client.clone();
         let api_key = self
             .configuration
             .api_key
@@ -135,22 +619,12 @@ impl ControlPlaneClient {
                 "Api key empty or not provided".into(),
             ));
         }
-        let response = rq_client
-            .get(&format!("{}/actions/whoami", self.controller_url))
-            .header("Api-Key", api_key)
-            .send()
-            .await
-            .map_err(|e| PineconeClientError::ControlPlaneConnectionError {
-                region: " ".to_string(),
-                err: e.to_string(),
-            })?;
-        let json_repsonse = response.json::<WhoamiResponse>().await.map_err(|e| {
-            PineconeClientError::ControlPlaneConnectionError {
-                region: " ".to_string(),
-                err: e.to_string(),
-            }
-        })?;
-        Ok(json_repsonse)
+        self.read("whoami", |config| async move {
+            let response =
+                Self::raw_request(&config, reqwest::Method::GET, "/actions/whoami", None).await?;
+            Self::parse_json_response(response).await
+        })
+        .await
     }
 }
 
@@ -159,9 +633,15 @@ mod control_plane_tests {
     use std::collections::BTreeMap;
 
     use super::ControlPlaneClient;
+    use crate::client::config::ClientConfig;
     use crate::data_types::Collection;
     use crate::data_types::Db;
+    use crate::utils::errors::{ErrorKind, PineconeClientError};
+    use index_service::models::PatchRequest;
     use std::env;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
     struct ClientContext {
         client: ControlPlaneClient,
@@ -173,7 +653,12 @@ mod control_plane_tests {
                 env::var("PINECONE_REGION").unwrap_or_else(|_| "internal-beta".to_string())
             );
             let api_key = env::var("PINECONE_API_KEY").unwrap_or_else(|_| "".to_string());
-            let client = ControlPlaneClient::new(controller_uri.as_str(), api_key.as_str());
+            let client = ControlPlaneClient::new(
+                controller_uri.as_str(),
+                api_key.as_str(),
+                &ClientConfig::default(),
+            )
+            .unwrap();
             ClientContext { client }
         }
     }
@@ -221,7 +706,13 @@ mod control_plane_tests {
         let context = ClientContext::new();
         let response = context
             .client
-            .configure_index("test-index", None, Some(2))
+            .configure_index(
+                "test-index",
+                PatchRequest {
+                    replicas: Some(2),
+                    ..Default::default()
+                },
+            )
             .await;
         println!("{:?}", response);
         assert!(response.is_ok());
@@ -286,4 +777,53 @@ mod control_plane_tests {
         println!("{:?}", response);
         assert!(response.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_read_failover() {
+        // Nothing listens on port 1, so all controllers are unreachable
+        let client =
+            ControlPlaneClient::new("http://127.0.0.1:1", "key", &ClientConfig::default()).unwrap();
+        client.set_fallback_controllers(vec!["http://127.0.0.2:1".to_string()]);
+        match client.list_indexes().await {
+            Err(PineconeClientError::ControlPlaneConnectionError { err, .. }) => {
+                assert!(err.ends_with("Tried controllers: http://127.0.0.1:1, http://127.0.0.2:1"))
+            }
+            other => panic!("expected a ControlPlaneConnectionError, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_whoami_unauthorized() {
+        // A controller that rejects every request's API key
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let controller_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+        let served = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                served.fetch_add(1, Ordering::SeqCst);
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf).await;
+                let _ = stream
+                    .write_all(
+                        b"HTTP/1.1 401 Unauthorized\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+                    )
+                    .await;
+            }
+        });
+
+        let client =
+            ControlPlaneClient::new(&controller_url, "bad-key", &ClientConfig::default()).unwrap();
+        client.set_fallback_controllers(vec!["http://127.0.0.2:1".to_string()]);
+        let err = client.whoami().await.unwrap_err();
+        match &err {
+            PineconeClientError::ControlPlaneOperationError { status_code, .. } => {
+                assert_eq!(status_code, "401 Unauthorized")
+            }
+            other => panic!("expected a ControlPlaneOperationError, got {other:?}"),
+        }
+        assert_eq!(err.kind(), ErrorKind::Auth);
+        // Neither retried nor failed over
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
 }
@@ -0,0 +1,29 @@
+//! Informational messages of long-running client operations, e.g. while waiting for an index to be deleted.
+//!
+//! The SDK never writes to stdout: messages go to the client's [`EventSink`], which by default forwards them to the
+//! `log` crate at info level. Applications can route them elsewhere with their own sink, or silence them.
+use std::fmt;
+
+/// Receives the informational messages of a client and its indexes.
+pub trait EventSink: fmt::Debug + Send + Sync {
+    /// Called with a message about `operation`, e.g. `"delete_index"`.
+    fn event(&self, operation: &str, message: &str);
+}
+
+/// The default sink, which logs every message at info level.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogEventSink;
+
+impl EventSink for LogEventSink {
+    fn event(&self, operation: &str, message: &str) {
+        log::info!("{operation}: {message}");
+    }
+}
+
+/// A sink that drops every message.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SilentEventSink;
+
+impl EventSink for SilentEventSink {
+    fn event(&self, _operation: &str, _message: &str) {}
+}
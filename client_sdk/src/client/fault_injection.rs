@@ -0,0 +1,158 @@
+//! Fault injection for the data plane, meant for resilience testing.
+//!
+//! A [`FaultInjector`] attached to an `Index` (see `Index::with_fault_injector()`) intercepts every data plane request,
+//! and can delay it, fail it, or simulate a connection dropped after the request was sent.
+//! Faults are scripted rather than random, so tests can deterministically verify retry and circuit-breaker behavior.
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tonic::metadata::MetadataMap;
+use tonic::Status;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fault {
+    /// Delays the request by the given duration before sending it.
+    Latency(Duration),
+    /// Fails the request with an `UNAVAILABLE` status, without sending it.
+    Unavailable,
+    /// Sends the request, but discards the response and fails with an `UNAVAILABLE` status,
+    /// as if the connection was dropped before the response arrived.
+    DropConnection,
+    /// Fails the request with a `RESOURCE_EXHAUSTED` status, without sending it, as if the index rate limited it.
+    /// If set, the duration is sent as the status's `retry-after` metadata entry, in seconds.
+    ResourceExhausted(Option<Duration>),
+}
+
+#[derive(Debug, Default)]
+pub struct FaultInjector {
+    // Faults injected into the next requests, in order
+    scripted: Mutex<VecDeque<Fault>>,
+    // Fault injected into every request once the scripted faults run out
+    persistent: Mutex<Option<Fault>>,
+    injected_count: AtomicU64,
+}
+
+impl FaultInjector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Injects `fault` into the next `count` requests, after any previously scripted faults.
+    pub fn inject_next(&self, fault: Fault, count: usize) -> &Self {
+        let mut scripted = self.scripted.lock().unwrap();
+        for _ in 0..count {
+            scripted.push_back(fault.clone());
+        }
+        drop(scripted);
+        self
+    }
+
+    /// Injects `fault` into every request that doesn't have a scripted fault, until cleared.
+    pub fn inject_always(&self, fault: Fault) -> &Self {
+        *self.persistent.lock().unwrap() = Some(fault);
+        self
+    }
+
+    /// Removes all scripted and persistent faults.
+    pub fn clear(&self) {
+        self.scripted.lock().unwrap().clear();
+        *self.persistent.lock().unwrap() = None;
+    }
+
+    /// The number of faults injected so far.
+    pub fn injected_count(&self) -> u64 {
+        self.injected_count.load(Ordering::Relaxed)
+    }
+
+    fn next_fault(&self) -> Option<Fault> {
+        let fault = self
+            .scripted
+            .lock()
+            .unwrap()
+            .pop_front()
+            .or_else(|| self.persistent.lock().unwrap().clone());
+        if fault.is_some() {
+            self.injected_count.fetch_add(1, Ordering::Relaxed);
+        }
+        fault
+    }
+
+    /// Runs the request `fut`, applying the next fault (if any).
+    pub(crate) async fn run<F, T>(&self, fut: F) -> Result<T, Status>
+    where
+        F: Future<Output = Result<T, Status>>,
+    {
+        match self.next_fault() {
+            None => fut.await,
+            Some(Fault::Latency(latency)) => {
+                tokio::time::sleep(latency).await;
+                fut.await
+            }
+            Some(Fault::Unavailable) => Err(Status::unavailable("Injected fault: unavailable")),
+            Some(Fault::DropConnection) => {
+                let _ = fut.await;
+                Err(Status::unavailable("Injected fault: connection dropped"))
+            }
+            Some(Fault::ResourceExhausted(retry_after)) => {
+                let mut metadata = MetadataMap::new();
+                if let Some(retry_after) = retry_after {
+                    metadata.insert(
+                        "retry-after",
+                        retry_after
+                            .as_secs_f64()
+                            .to_string()
+                            .parse()
+                            .expect("a number is valid metadata"),
+                    );
+                }
+                Err(Status::with_metadata(
+                    tonic::Code::ResourceExhausted,
+                    "Injected fault: resource exhausted",
+                    metadata,
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    #[tokio::test]
+    async fn test_scripted_faults() {
+        let injector = FaultInjector::new();
+        injector
+            .inject_next(Fault::Unavailable, 2)
+            .inject_next(Fault::DropConnection, 1);
+        let sent = AtomicUsize::new(0);
+        let request = || async {
+            sent.fetch_add(1, Ordering::Relaxed);
+            Ok::<_, Status>(())
+        };
+
+        for _ in 0..3 {
+            let err = injector.run(request()).await.unwrap_err();
+            assert_eq!(err.code(), tonic::Code::Unavailable);
+        }
+        // Only the dropped connection actually sent its request
+        assert_eq!(sent.load(Ordering::Relaxed), 1);
+        assert!(injector.run(request()).await.is_ok());
+        assert_eq!(injector.injected_count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_persistent_fault() {
+        let injector = FaultInjector::new();
+        injector.inject_always(Fault::Unavailable);
+        for _ in 0..5 {
+            assert!(injector.run(async { Ok(()) }).await.is_err());
+        }
+        injector.clear();
+        assert!(injector.run(async { Ok(()) }).await.is_ok());
+    }
+}
@@ -1,42 +1,732 @@
-use self::dataplane_client::UpdateResponse;
-pub use self::dataplane_client::{
-    ScoredVector as GrpcScoredVector, SparseValues as GrpcSparseValues, Vector as GrpcVector,
-};
+use super::config::{ClientConfig, TlsConfig};
+#[cfg(any(test, feature = "fault-injection"))]
+use super::fault_injection::FaultInjector;
+use super::proxy::{ProxyConfig, ProxyConnector};
 use crate::data_types::{
-    IndexStats, MetadataValue, NamespaceStats, QueryResult, SparseValues, Vector,
+    DeleteResponse, IndexStats, MetadataValue, NamespaceStats, PayloadDump, QueryResult,
+    SparseValues, UpdateResponse, Vector,
+};
+use crate::proto;
+use crate::proto::vector_service_client::VectorServiceClient;
+use crate::proto::{DescribeIndexStatsRequest, QueryRequest, UpsertRequest};
+pub use crate::proto::{
+    ScoredVector as GrpcScoredVector, SparseValues as GrpcSparseValues, Vector as GrpcVector,
 };
+use crate::utils::client_stats::ClientStatsRecorder;
 use crate::utils::conversions;
-use crate::utils::errors::PineconeResult;
-use dataplane_client::vector_service_client::VectorServiceClient;
-use dataplane_client::{DescribeIndexStatsRequest, QueryRequest, UpsertRequest};
+use crate::utils::errors::{ErrorKind, PineconeClientError, PineconeResult};
+use crate::utils::latency::{LatencyRecorder, Phase};
+#[cfg(feature = "prometheus")]
+use crate::utils::metrics::Plane;
+use crate::utils::payload_dump::{self, PayloadSink};
+use crate::utils::rate_limit;
+use crate::utils::redaction;
+use crate::utils::telemetry::RequestSpan;
+use prost::Message;
 use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tokio::runtime::Handle;
 use tonic::metadata::Ascii;
 use tonic::{
-    metadata::MetadataValue as TonicMetadataVal, service::interceptor::InterceptedService,
-    service::Interceptor, transport::Channel, Request, Status,
+    metadata::MetadataValue as TonicMetadataVal,
+    service::interceptor::InterceptedService,
+    service::Interceptor,
+    transport::{Certificate, Channel, ClientTlsConfig, Endpoint, Identity},
+    Request, Response, Status,
 };
 
-mod dataplane_client {
-    tonic::include_proto!("_");
+/// The connectivity state of the gRPC channel to an index.
+/// tonic doesn't expose the channel's internal state, so it is derived from the outcome of the most recent request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// The channel was established, but no request was sent through it yet.
+    Idle = 0,
+    /// The channel is being (re)established.
+    Connecting = 1,
+    /// The most recent request reached the server.
+    Ready = 2,
+    /// The most recent request failed due to a transport error. The channel will try to recover on the next request.
+    TransientFailure = 3,
+}
+
+impl ConnectionState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConnectionState::Idle => "idle",
+            ConnectionState::Connecting => "connecting",
+            ConnectionState::Ready => "ready",
+            ConnectionState::TransientFailure => "transient_failure",
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => ConnectionState::Idle,
+            1 => ConnectionState::Connecting,
+            2 => ConnectionState::Ready,
+            _ => ConnectionState::TransientFailure,
+        }
+    }
+}
+
+impl fmt::Display for ConnectionState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
 }
 
+/// Keeps track of the connection state, shared between all clones of a client
+#[derive(Debug, Clone)]
+struct ConnectionStateTracker(Arc<AtomicU8>);
+
+impl ConnectionStateTracker {
+    fn new(state: ConnectionState) -> Self {
+        ConnectionStateTracker(Arc::new(AtomicU8::new(state as u8)))
+    }
+
+    fn get(&self) -> ConnectionState {
+        ConnectionState::from_u8(self.0.load(Ordering::Relaxed))
+    }
+
+    fn set(&self, state: ConnectionState) {
+        self.0.store(state as u8, Ordering::Relaxed);
+    }
+
+    /// Updates the connection state according to the outcome of a request.
+    /// Any response from the server, including an error status, means the channel is healthy.
+    fn track<T>(&self, res: Result<T, Status>) -> Result<T, Status> {
+        match &res {
+            Err(status) if is_transport_error(status) => {
+                self.set(ConnectionState::TransientFailure)
+            }
+            _ => self.set(ConnectionState::Ready),
+        }
+        res
+    }
+}
+
+/// Classifies a failure to establish a channel by walking the error's source chain,
+/// since `tonic::transport::Error` doesn't expose what went wrong.
+fn connect_error(url: &str, err: &tonic::transport::Error) -> PineconeClientError {
+    let mut chain = vec![err.to_string()];
+    let mut refused = false;
+    let mut invalid_data = false;
+    let mut source = err.source();
+    while let Some(cause) = source {
+        if let Some(io_err) = cause.downcast_ref::<std::io::Error>() {
+            refused |= io_err.kind() == std::io::ErrorKind::ConnectionRefused;
+            invalid_data |= io_err.kind() == std::io::ErrorKind::InvalidData;
+        }
+        chain.push(cause.to_string());
+        source = cause.source();
+    }
+    let full = chain.join(": ").to_lowercase();
+    let (url, err) = (url.to_string(), chain.join(": "));
+    if refused {
+        PineconeClientError::ConnectionRefusedError { url, err }
+    } else if full.contains("dns error") || full.contains("failed to lookup address") {
+        PineconeClientError::DnsResolutionError { url, err }
+    } else if invalid_data
+        || ["tls", "certificate", "handshake"]
+            .iter()
+            .any(|s| full.contains(s))
+    {
+        PineconeClientError::TlsError { url, err }
+    } else {
+        PineconeClientError::EndpointConnectionError { url, err }
+    }
+}
+
+/// Whether the request failed because of the connection, rather than being rejected by the server.
+/// An `UNAVAILABLE` status sent by the server (e.g. when the index is overloaded) came over a healthy connection,
+/// so only statuses caused by a `tonic::transport::Error` count.
+pub(crate) fn is_transport_error(status: &Status) -> bool {
+    matches!(status.source(), Some(err) if err.is::<tonic::transport::Error>())
+}
+
+/// The metadata entry carrying a request's ID, see [`ClientConfig::request_ids`].
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Generates a random request ID.
+pub fn new_request_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+/// Adds the ID of the failed request to `status`, both to its message and as its `x-request-id` metadata entry.
+fn tag_request_id(status: Status, request_id: &str) -> Status {
+    let mut metadata = status.metadata().clone();
+    if let Ok(value) = request_id.parse() {
+        metadata.insert(REQUEST_ID_HEADER, value);
+    }
+    Status::with_details_and_metadata(
+        status.code(),
+        format!("{} (request ID: {request_id})", status.message()),
+        status.details().to_vec().into(),
+        metadata,
+    )
+}
+
+/// Spawns the background tasks of a gRPC channel on a given runtime, instead of the runtime `connect()` is called from.
+#[derive(Clone)]
+struct HandleExecutor(Handle);
+
+impl<F> hyper::rt::Executor<F> for HandleExecutor
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    fn execute(&self, fut: F) {
+        self.0.spawn(fut);
+    }
+}
+
+/// How the channel to an index is established.
+#[derive(Debug, Clone, Default)]
+pub struct ChannelOptions {
+    /// If set, it is sent as the TLS server name (SNI) and the server's certificate is validated against it,
+    /// instead of the endpoint's host. Needed when connecting through a private endpoint (e.g. PrivateLink)
+    /// whose DNS name differs from the index's certificate.
+    pub tls_domain: Option<String>,
+    /// The proxy to tunnel the connection through. If `None`, the proxy environment variables apply,
+    /// see [`proxy`](super::proxy).
+    pub proxy: Option<ProxyConfig>,
+    /// Custom CA certificates and the client certificate for mutual TLS. Only applies to `https://` endpoints.
+    pub tls: TlsConfig,
+    /// How long establishing the connection may take, including the TLS handshake.
+    pub connect_timeout: Option<Duration>,
+    /// The user agent sent with every request, see [`ClientConfig::user_agent`].
+    pub user_agent: Option<String>,
+    /// How long each request may take. Sent as the request's gRPC timeout, which is enforced by both ends.
+    pub request_timeout: Option<Duration>,
+    /// Whether the channel is established by the first request instead of when connecting.
+    /// Connection failures of a lazy channel (e.g. DNS resolution) are then returned by the requests, as transport errors.
+    pub lazy: bool,
+}
+
+type VectorServiceGrpcClient = VectorServiceClient<InterceptedService<Channel, ApiKeyInterceptor>>;
+
 #[derive(Debug, Clone)]
 pub struct DataplaneGrpcClient {
-    inner: VectorServiceClient<InterceptedService<Channel, ApiKeyInterceptor>>,
+    // Shared between clones, so reconnecting one client reconnects all of them
+    inner: Arc<RwLock<VectorServiceGrpcClient>>,
+    index_endpoint_url: String,
+    api_token: TonicMetadataVal<Ascii>,
+    state: ConnectionStateTracker,
+    runtime: Option<Handle>,
+    options: ChannelOptions,
+    deadline: Option<Instant>,
+    request_id: Option<String>,
+    latency: Option<LatencyRecorder>,
+    config: Option<ClientConfig>,
+    stats: Option<Arc<ClientStatsRecorder>>,
+    payload_sink: Option<PayloadSink>,
+    #[cfg(any(test, feature = "fault-injection"))]
+    fault_injector: Option<Arc<FaultInjector>>,
 }
 
 impl DataplaneGrpcClient {
     // TODO: this method shouldn't be public or exposed to python
-    pub async fn connect(
+    pub async fn connect(index_endpoint_url: String, api_key: &str) -> PineconeResult<Self> {
+        Self::connect_with_runtime(index_endpoint_url, api_key, None, ChannelOptions::default())
+            .await
+    }
+
+    /// Connects to the index, spawning the channel's background tasks on `runtime`.
+    /// If `runtime` is `None`, they are spawned on the runtime this method is awaited from.
+    pub async fn connect_with_runtime(
         index_endpoint_url: String,
         api_key: &str,
-    ) -> Result<Self, Box<dyn std::error::Error>> {
-        let channel = Channel::from_shared(index_endpoint_url)?.connect().await?;
-        let token: TonicMetadataVal<_> = api_key.parse()?;
-        let add_api_key_interceptor = ApiKeyInterceptor { api_token: token };
-        let inner = VectorServiceClient::with_interceptor(channel, add_api_key_interceptor);
+        runtime: Option<Handle>,
+        options: ChannelOptions,
+    ) -> PineconeResult<Self> {
+        let token: TonicMetadataVal<_> = api_key.parse().map_err(|_| {
+            PineconeClientError::ValueError("API key contains invalid characters".to_string())
+        })?;
+        let inner =
+            Self::connect_channel(&index_endpoint_url, &token, runtime.as_ref(), &options).await?;
+        Ok(Self::new(
+            inner,
+            index_endpoint_url,
+            token,
+            runtime,
+            options,
+        ))
+    }
+
+    fn new(
+        inner: VectorServiceGrpcClient,
+        index_endpoint_url: String,
+        api_token: TonicMetadataVal<Ascii>,
+        runtime: Option<Handle>,
+        options: ChannelOptions,
+    ) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(inner)),
+            index_endpoint_url,
+            api_token,
+            state: ConnectionStateTracker::new(ConnectionState::Idle),
+            runtime,
+            options,
+            deadline: None,
+            request_id: None,
+            latency: None,
+            config: None,
+            stats: None,
+            payload_sink: None,
+            #[cfg(any(test, feature = "fault-injection"))]
+            fault_injector: None,
+        }
+    }
+
+    async fn connect_channel(
+        index_endpoint_url: &str,
+        api_token: &TonicMetadataVal<Ascii>,
+        runtime: Option<&Handle>,
+        options: &ChannelOptions,
+    ) -> PineconeResult<VectorServiceGrpcClient> {
+        if options.lazy {
+            return Self::lazy_channel(index_endpoint_url, api_token, runtime, options);
+        }
+        let endpoint = Self::endpoint(index_endpoint_url, runtime, options)?;
+        let host = endpoint.uri().host().unwrap_or_default();
+        let channel = match ProxyConfig::resolve(options.proxy.as_ref(), host) {
+            Some(proxy) => {
+                log::debug!("Connecting to {index_endpoint_url} through proxy {proxy:?}");
+                endpoint
+                    .connect_with_connector(ProxyConnector::new(proxy))
+                    .await
+            }
+            None => endpoint.connect().await,
+        }
+        .map_err(|e| connect_error(index_endpoint_url, &e))?;
+        Ok(Self::with_api_key(channel, api_token))
+    }
+
+    /// Creates a channel that connects on its first request. The endpoint's host is resolved on every (re)connection.
+    fn lazy_channel(
+        index_endpoint_url: &str,
+        api_token: &TonicMetadataVal<Ascii>,
+        runtime: Option<&Handle>,
+        options: &ChannelOptions,
+    ) -> PineconeResult<VectorServiceGrpcClient> {
+        let endpoint = Self::endpoint(index_endpoint_url, runtime, options)?;
+        let host = endpoint.uri().host().unwrap_or_default();
+        let channel = match ProxyConfig::resolve(options.proxy.as_ref(), host) {
+            Some(proxy) => {
+                log::debug!("Connecting to {index_endpoint_url} through proxy {proxy:?}");
+                endpoint.connect_with_connector_lazy(ProxyConnector::new(proxy))
+            }
+            None => endpoint.connect_lazy(),
+        };
+        Ok(Self::with_api_key(channel, api_token))
+    }
+
+    fn endpoint(
+        index_endpoint_url: &str,
+        runtime: Option<&Handle>,
+        options: &ChannelOptions,
+    ) -> PineconeResult<Endpoint> {
+        let mut endpoint = Channel::from_shared(index_endpoint_url.to_string()).map_err(|e| {
+            PineconeClientError::InvalidEndpointError {
+                url: index_endpoint_url.to_string(),
+                err: e.to_string(),
+            }
+        })?;
+        if let Some(runtime) = runtime {
+            endpoint = endpoint.executor(HandleExecutor(runtime.clone()));
+        }
+        if let Some(user_agent) = &options.user_agent {
+            endpoint = endpoint.user_agent(user_agent.as_str()).map_err(|e| {
+                PineconeClientError::ValueError(format!("Invalid user agent '{user_agent}': {e}"))
+            })?;
+        }
+        if let Some(connect_timeout) = options.connect_timeout {
+            endpoint = endpoint.connect_timeout(connect_timeout);
+        }
+        let is_https = endpoint.uri().scheme_str() == Some("https");
+        if options.tls_domain.is_some() || (is_https && !options.tls.is_empty()) {
+            let mut tls_config = ClientTlsConfig::new();
+            if let Some(tls_domain) = &options.tls_domain {
+                tls_config = tls_config.domain_name(tls_domain);
+            }
+            if let Some(ca_certificates) = &options.tls.ca_certificates {
+                tls_config = tls_config.ca_certificate(Certificate::from_pem(ca_certificates));
+            }
+            if let Some(identity) = &options.tls.client_identity {
+                tls_config = tls_config.identity(Identity::from_pem(
+                    &identity.certificate,
+                    &identity.private_key,
+                ));
+            }
+            endpoint =
+                endpoint
+                    .tls_config(tls_config)
+                    .map_err(|e| PineconeClientError::TlsError {
+                        url: index_endpoint_url.to_string(),
+                        err: e.to_string(),
+                    })?;
+        }
+        Ok(endpoint)
+    }
 
-        Ok(Self { inner })
+    fn with_api_key(
+        channel: Channel,
+        api_token: &TonicMetadataVal<Ascii>,
+    ) -> VectorServiceGrpcClient {
+        let add_api_key_interceptor = ApiKeyInterceptor {
+            api_token: api_token.clone(),
+        };
+        VectorServiceClient::with_interceptor(channel, add_api_key_interceptor)
+    }
+
+    /// Replaces the channel with a lazy one, after the channel failed with a transport error.
+    /// The new channel re-resolves the endpoint when it connects, so the client recovers from dropped connections
+    /// and from the index moving to a different address. Requests already in flight complete on the old channel.
+    fn reset_channel(&self) {
+        match Self::lazy_channel(
+            &self.index_endpoint_url,
+            &self.api_token,
+            self.runtime.as_ref(),
+            &self.options,
+        ) {
+            Ok(inner) => {
+                log::debug!(
+                    "Resetting the channel to {url} after a transport error",
+                    url = self.index_endpoint_url
+                );
+                *self.inner.write().unwrap() = inner;
+            }
+            Err(err) => log::warn!("Failed to reset the channel: {err}"),
+        }
+    }
+
+    /// Tears down the underlying gRPC channel and establishes a new one, re-resolving the index endpoint.
+    /// All clones of this client switch to the new channel. Requests that are already in flight complete on the old one.
+    pub async fn reconnect(&self) -> PineconeResult<()> {
+        self.state.set(ConnectionState::Connecting);
+        match Self::connect_channel(
+            &self.index_endpoint_url,
+            &self.api_token,
+            self.runtime.as_ref(),
+            &self.options,
+        )
+        .await
+        {
+            Ok(inner) => {
+                *self.inner.write().unwrap() = inner;
+                self.state.set(ConnectionState::Idle);
+                Ok(())
+            }
+            Err(err) => {
+                self.state.set(ConnectionState::TransientFailure);
+                Err(err)
+            }
+        }
+    }
+
+    /// Returns a clone of this client, whose requests carry `request_id` as their `x-request-id` metadata entry.
+    pub fn with_request_id(&self, request_id: String) -> Self {
+        Self {
+            request_id: Some(request_id),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a clone of this client, which dumps the payloads of its requests and responses into `sink`,
+    /// see [`payload_dump`](crate::utils::payload_dump).
+    pub fn with_payload_sink(&self, sink: PayloadSink) -> Self {
+        Self {
+            payload_sink: Some(sink),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a clone of this client, in which every request must complete before `deadline`.
+    /// Each request's gRPC timeout is set to the time remaining until the deadline when it is sent,
+    /// so an operation split into multiple requests respects the overall deadline.
+    pub fn with_deadline(&self, deadline: Instant) -> Self {
+        Self {
+            deadline: Some(deadline),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a clone of this client, which records the timings of its requests into `recorder`.
+    pub fn with_latency_recorder(&self, recorder: LatencyRecorder) -> Self {
+        Self {
+            latency: Some(recorder),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a clone of this client, which logs its requests and responses at debug level according to `config`.
+    pub fn with_config(&self, config: ClientConfig) -> Self {
+        Self {
+            config: Some(config),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a clone of this client, which counts its requests into `stats`, if diagnostics are enabled by its configuration.
+    pub fn with_stats(&self, stats: Arc<ClientStatsRecorder>) -> Self {
+        Self {
+            stats: Some(stats),
+            ..self.clone()
+        }
+    }
+
+    fn time<T>(&self, phase: Phase, f: impl FnOnce() -> T) -> T {
+        match &self.latency {
+            Some(recorder) => recorder.time(phase, f),
+            None => f(),
+        }
+    }
+
+    /// Returns a clone of this client, in which every request goes through `fault_injector`.
+    #[cfg(any(test, feature = "fault-injection"))]
+    pub fn with_fault_injector(&self, fault_injector: Arc<FaultInjector>) -> Self {
+        Self {
+            fault_injector: Some(fault_injector),
+            ..self.clone()
+        }
+    }
+
+    /// Sends a request, retrying transient failures and rate-limited requests according to the configured retry policy.
+    /// The retries respect the client's deadline. All attempts carry the same request ID, if any, which the error includes.
+    async fn call<M, T, F, Fut>(
+        &self,
+        operation: &str,
+        message: M,
+        send: F,
+    ) -> Result<Response<T>, Status>
+    where
+        M: Message + Clone,
+        T: Message,
+        F: Fn(VectorServiceGrpcClient, Request<M>) -> Fut,
+        Fut: Future<Output = Result<Response<T>, Status>>,
+    {
+        let retry_policy = self
+            .config
+            .as_ref()
+            .map(|config| config.retry_policy.clone())
+            .unwrap_or_default();
+        let request_len = message.encoded_len();
+        let request_id = self.request_id.clone().or_else(|| {
+            self.config
+                .as_ref()
+                .filter(|config| config.request_ids)
+                .map(|_| new_request_id())
+        });
+        if let Some(config) = &self.config {
+            config.debug_log(operation, || {
+                format!(
+                    "{operation} request to {url} with API key {api_key}{request_id}: {message}",
+                    url = self.index_endpoint_url,
+                    api_key =
+                        redaction::redact_api_key(self.api_token.to_str().unwrap_or_default()),
+                    request_id = request_id
+                        .as_ref()
+                        .map(|id| format!(", request ID {id}"))
+                        .unwrap_or_default(),
+                    message = redaction::format_payload(&message, config.log_full_payloads)
+                )
+            });
+        }
+        let payload_sink = self
+            .payload_sink
+            .clone()
+            .or_else(payload_dump::current_sink);
+        // Only keep a copy of the message while it may still be retried
+        let mut message = Some(message);
+        let mut retry = 0;
+        let mut rate_limit_retry = 0;
+        let res = loop {
+            let can_retry = retry < retry_policy.max_retries
+                || rate_limit_retry < retry_policy.max_rate_limit_retries;
+            let attempt = if can_retry {
+                message.clone()
+            } else {
+                message.take()
+            }
+            .expect("the message is kept until the last attempt");
+            if let Some(sink) = &payload_sink {
+                sink.dump(&PayloadDump {
+                    operation: operation.to_string(),
+                    direction: "request".to_string(),
+                    target: self.index_endpoint_url.clone(),
+                    payload: format!("{attempt:?}"),
+                });
+            }
+            let span = self.span(operation);
+            let mut request = match self.request(attempt, request_id.as_deref()) {
+                Ok(request) => request,
+                Err(status) => {
+                    span.end(Some(&status));
+                    break Err(status);
+                }
+            };
+            span.inject(request.metadata_mut());
+            let res = self
+                .send_once(operation, request_len, send(self.client(), request))
+                .await;
+            span.end(res.as_ref().err().map(|status| status as &dyn fmt::Display));
+            if let Some(sink) = &payload_sink {
+                sink.dump(&PayloadDump {
+                    operation: operation.to_string(),
+                    direction: "response".to_string(),
+                    target: self.index_endpoint_url.clone(),
+                    payload: match &res {
+                        Ok(response) => format!("{:?}", response.get_ref()),
+                        Err(status) => format!("{:?}: {}", status.code(), status.message()),
+                    },
+                });
+            }
+            let kind = res.as_ref().err().map(ErrorKind::from_grpc);
+            let backoff = match (&res, kind) {
+                (Err(_), Some(kind))
+                    if retry < retry_policy.max_retries
+                        && kind != ErrorKind::QuotaExceeded
+                        && kind.is_retryable() =>
+                {
+                    retry += 1;
+                    retry_policy.delay(retry - 1)
+                }
+                (Err(status), Some(ErrorKind::QuotaExceeded))
+                    if rate_limit_retry < retry_policy.max_rate_limit_retries =>
+                {
+                    rate_limit_retry += 1;
+                    rate_limit::grpc_retry_after(status)
+                        .unwrap_or_else(|| retry_policy.delay(rate_limit_retry - 1))
+                }
+                _ => break res,
+            };
+            if let Some(deadline) = self.deadline {
+                if Instant::now() + backoff >= deadline {
+                    break res;
+                }
+            }
+            if let (Some(config), Err(status)) = (&self.config, &res) {
+                config.debug_log(operation, || {
+                    format!(
+                        "{operation} request to {url} failed with {:?}, retrying in {backoff:?}",
+                        status.code(),
+                        url = self.index_endpoint_url
+                    )
+                });
+            }
+            if let (Some(config), Some(stats)) = (&self.config, &self.stats) {
+                config.record_client_retries(stats, 1);
+            }
+            #[cfg(feature = "prometheus")]
+            if let Some(config) = &self.config {
+                config.record_metrics_retry(operation, Plane::Data);
+            }
+            tokio::time::sleep(backoff).await;
+        };
+        match request_id {
+            Some(request_id) => res.map_err(|status| tag_request_id(status, &request_id)),
+            None => res,
+        }
+    }
+
+    /// Sends a request once, and updates the connection state according to its outcome.
+    async fn send_once<T: Message>(
+        &self,
+        operation: &str,
+        request_len: usize,
+        request: impl Future<Output = Result<Response<T>, Status>>,
+    ) -> Result<Response<T>, Status> {
+        let start = Instant::now();
+        #[cfg(any(test, feature = "fault-injection"))]
+        let res = match &self.fault_injector {
+            Some(fault_injector) => fault_injector.run(request).await,
+            None => request.await,
+        };
+        #[cfg(not(any(test, feature = "fault-injection")))]
+        let res = request.await;
+        let elapsed = start.elapsed();
+        if let Some(recorder) = &self.latency {
+            recorder.record(Phase::Network, elapsed);
+        }
+        if let (Some(config), Some(stats)) = (&self.config, &self.stats) {
+            let response_len = res.as_ref().ok().map(|r| r.get_ref().encoded_len());
+            config.record_client_stats(stats, operation, request_len, response_len, elapsed);
+        }
+        if let Some(config) = &self.config {
+            #[cfg(feature = "prometheus")]
+            config.record_metrics_request(
+                operation,
+                Plane::Data,
+                elapsed,
+                res.as_ref().err().map(ErrorKind::from_grpc),
+            );
+            config.record_latency(operation, elapsed, res.is_err());
+            config.debug_log(operation, || {
+                let outcome = match &res {
+                    Ok(response) => format!("succeeded, response size {} bytes", response.get_ref().encoded_len()),
+                    Err(status) => format!("failed with {:?}: {}", status.code(), status.message()),
+                };
+                format!(
+                    "{operation} request to {url} (request size {request_len} bytes) {outcome} in {:.1} ms",
+                    elapsed.as_secs_f64() * 1000.0,
+                    url = self.index_endpoint_url
+                )
+            });
+        }
+        let failed_before = self.state.get() == ConnectionState::TransientFailure;
+        let res = self.state.track(res);
+        // Only the first failure resets the channel, so concurrent requests failing together don't reset it repeatedly
+        if !failed_before && res.as_ref().err().is_some_and(is_transport_error) {
+            self.reset_channel();
+        }
+        res
+    }
+
+    fn request<T>(&self, message: T, request_id: Option<&str>) -> Result<Request<T>, Status> {
+        let mut request = Request::new(message);
+        if let Some(request_id) = request_id {
+            let value = request_id.parse().map_err(|_| {
+                Status::invalid_argument(format!("Invalid request ID '{request_id}'"))
+            })?;
+            request.metadata_mut().insert(REQUEST_ID_HEADER, value);
+        }
+        let mut timeout = self.options.request_timeout;
+        if let Some(deadline) = self.deadline {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(Status::deadline_exceeded(
+                    "Deadline exceeded before the request was sent",
+                ));
+            }
+            timeout = Some(timeout.map_or(remaining, |timeout| timeout.min(remaining)));
+        }
+        if let Some(timeout) = timeout {
+            request.set_timeout(timeout);
+        }
+        Ok(request)
+    }
+
+    // Clients without a configuration follow the default configuration's diagnostics setting
+    fn span(&self, operation: &str) -> RequestSpan {
+        match &self.config {
+            Some(config) => config.grpc_span(operation, &self.index_endpoint_url),
+            None => ClientConfig::default().grpc_span(operation, &self.index_endpoint_url),
+        }
+    }
+
+    // Cloning the client is cheap, and avoids holding the lock across requests
+    fn client(&self) -> VectorServiceGrpcClient {
+        self.inner.read().unwrap().clone()
+    }
+
+    /// The current state of the underlying gRPC channel.
+    pub fn connection_state(&self) -> ConnectionState {
+        self.state.get()
     }
 
     pub async fn upsert(
@@ -44,12 +734,13 @@ impl DataplaneGrpcClient {
         namespace: &str,
         vectors: &[Vector],
     ) -> Result<u32, tonic::Status> {
-        let grpc_vectors: Vec<GrpcVector> = vectors.iter().map(|v| v.clone().into()).collect();
+        let message = self.time(Phase::Serialization, || UpsertRequest {
+            namespace: namespace.to_string(),
+            vectors: vectors.iter().map(|v| v.clone().into()).collect(),
+        });
         let res = self
-            .inner
-            .upsert(UpsertRequest {
-                namespace: namespace.to_string(),
-                vectors: grpc_vectors,
+            .call("upsert", message, |mut client, request| async move {
+                client.upsert(request).await
             })
             .await?;
         Ok(res.into_inner().upserted_count)
@@ -67,38 +758,66 @@ impl DataplaneGrpcClient {
         include_values: bool,
         include_metadata: bool,
     ) -> PineconeResult<Vec<QueryResult>> {
-        let sparse_vectors = sparse_values.map(|sparse_vector| sparse_vector.into());
+        let message = self.time(Phase::Serialization, || QueryRequest {
+            namespace: namespace.to_string(),
+            id: id.unwrap_or_default(),
+            vector: values.unwrap_or_default(),
+            sparse_vector: sparse_values.map(|sparse_vector| sparse_vector.into()),
+            top_k,
+            filter: filter.map(conversions::hashmap_to_prost_struct),
+            include_values,
+            include_metadata,
+            queries: Vec::default(), // Deprecated
+        });
         let res = self
-            .inner
-            .query(QueryRequest {
-                namespace: namespace.to_string(),
-                id: id.unwrap_or_default(),
-                vector: values.unwrap_or_default(),
-                sparse_vector: sparse_vectors,
-                top_k,
-                filter: filter.map(conversions::hashmap_to_prost_struct),
-                include_values,
-                include_metadata,
-                queries: Vec::default(), // Deprecated
+            .call("query", message, |mut client, request| async move {
+                client.query(request).await
             })
             .await?;
 
-        res.into_inner()
-            .matches
-            .into_iter()
-            .map(|sv| sv.try_into())
-            .collect()
+        self.time(Phase::Deserialization, || {
+            res.into_inner()
+                .matches
+                .into_iter()
+                .map(|sv| sv.try_into())
+                .collect()
+        })
+    }
+
+    /// Sends a single `describe_index_stats` request without a filter, which the index answers without scanning any
+    /// vectors, and returns its round-trip time. The request isn't retried, so the latency reflects a single attempt.
+    pub async fn ping(&self) -> Result<Duration, Status> {
+        let message = DescribeIndexStatsRequest { filter: None };
+        let request_len = message.encoded_len();
+        let span = self.span("ping");
+        let mut request = self.request(message, None)?;
+        span.inject(request.metadata_mut());
+        let start = Instant::now();
+        let res = self
+            .send_once(
+                "ping",
+                request_len,
+                self.client().describe_index_stats(request),
+            )
+            .await;
+        span.end(res.as_ref().err().map(|status| status as &dyn fmt::Display));
+        res?;
+        Ok(start.elapsed())
     }
 
     pub async fn describe_index_stats(
         &mut self,
         filter: Option<BTreeMap<String, MetadataValue>>,
     ) -> Result<IndexStats, tonic::Status> {
+        let message = self.time(Phase::Serialization, || DescribeIndexStatsRequest {
+            filter: filter.map(conversions::hashmap_to_prost_struct),
+        });
         let res = self
-            .inner
-            .describe_index_stats(DescribeIndexStatsRequest {
-                filter: filter.map(conversions::hashmap_to_prost_struct),
-            })
+            .call(
+                "describe_index_stats",
+                message,
+                |mut client, request| async move { client.describe_index_stats(request).await },
+            )
             .await?
             .into_inner();
         let ns_summaries = res.namespaces;
@@ -126,20 +845,54 @@ impl DataplaneGrpcClient {
         namespace: &str,
         ids: &[String],
     ) -> PineconeResult<HashMap<String, Vector>> {
+        let message = self.time(Phase::Serialization, || proto::FetchRequest {
+            namespace: namespace.to_string(),
+            ids: ids.to_owned(),
+        });
         let res = self
-            .inner
-            .fetch(dataplane_client::FetchRequest {
-                namespace: namespace.to_string(),
-                ids: ids.to_owned(),
+            .call("fetch", message, |mut client, request| async move {
+                client.fetch(request).await
             })
             .await?;
-        let fetch_response = res.into_inner();
-        let vectors = fetch_response.vectors;
-        let mut fetch_vectors: HashMap<String, Vector> = HashMap::with_capacity(vectors.len());
-        for (id, vector) in vectors {
-            fetch_vectors.insert(id, vector.try_into()?);
-        }
-        Ok(fetch_vectors)
+        self.time(Phase::Deserialization, || {
+            let vectors = res.into_inner().vectors;
+            let mut fetch_vectors: HashMap<String, Vector> = HashMap::with_capacity(vectors.len());
+            for (id, vector) in vectors {
+                fetch_vectors.insert(id, vector.try_into()?);
+            }
+            Ok(fetch_vectors)
+        })
+    }
+
+    /// Lists a page of vector ids.
+    ///
+    /// # Returns
+    /// The page's ids, and the token of the next page, or `None` if this is the last page.
+    pub async fn list(
+        &mut self,
+        namespace: &str,
+        prefix: Option<&str>,
+        limit: Option<u32>,
+        pagination_token: Option<&str>,
+    ) -> PineconeResult<(Vec<String>, Option<String>)> {
+        let message = self.time(Phase::Serialization, || proto::ListRequest {
+            prefix: prefix.unwrap_or_default().to_string(),
+            limit: limit.unwrap_or_default(),
+            pagination_token: pagination_token.unwrap_or_default().to_string(),
+            namespace: namespace.to_string(),
+        });
+        let res = self
+            .call("list", message, |mut client, request| async move {
+                client.list(request).await
+            })
+            .await?
+            .into_inner();
+        let ids = res.vectors.into_iter().map(|item| item.id).collect();
+        let next = res
+            .pagination
+            .map(|pagination| pagination.next)
+            .filter(|next| !next.is_empty());
+        Ok((ids, next))
     }
 
     pub async fn delete(
@@ -148,16 +901,19 @@ impl DataplaneGrpcClient {
         namespace: &str,
         filter: Option<BTreeMap<String, MetadataValue>>,
         delete_all: bool,
-    ) -> Result<(), tonic::Status> {
-        self.inner
-            .delete(dataplane_client::DeleteRequest {
-                namespace: namespace.into(),
-                ids: ids.unwrap_or_default(),
-                delete_all,
-                filter: filter.map(conversions::hashmap_to_prost_struct),
+    ) -> Result<DeleteResponse, tonic::Status> {
+        let message = self.time(Phase::Serialization, || proto::DeleteRequest {
+            namespace: namespace.into(),
+            ids: ids.unwrap_or_default(),
+            delete_all,
+            filter: filter.map(conversions::hashmap_to_prost_struct),
+        });
+        let res = self
+            .call("delete", message, |mut client, request| async move {
+                client.delete(request).await
             })
             .await?;
-        Ok(())
+        Ok(res.into_inner().into())
     }
 
     pub async fn update(
@@ -168,20 +924,22 @@ impl DataplaneGrpcClient {
         set_metadata: Option<BTreeMap<String, MetadataValue>>,
         namespace: &str,
     ) -> Result<UpdateResponse, tonic::Status> {
+        let message = self.time(Phase::Serialization, || proto::UpdateRequest {
+            id: id.into(),
+            values: match vector {
+                Some(vec) => vec.clone(),
+                None => Vec::new(),
+            },
+            sparse_values: sparse_values.map(|sparse_values| sparse_values.into()),
+            set_metadata: set_metadata.map(conversions::hashmap_to_prost_struct),
+            namespace: namespace.into(),
+        });
         let res = self
-            .inner
-            .update(dataplane_client::UpdateRequest {
-                id: id.into(),
-                values: match vector {
-                    Some(vec) => vec.clone(),
-                    None => Vec::new(),
-                },
-                sparse_values: sparse_values.map(|sparse_values| sparse_values.into()),
-                set_metadata: set_metadata.map(conversions::hashmap_to_prost_struct),
-                namespace: namespace.into(),
+            .call("update", message, |mut client, request| async move {
+                client.update(request).await
             })
             .await?;
-        Ok(res.into_inner())
+        Ok(res.into_inner().into())
     }
 }
 
@@ -208,17 +966,23 @@ impl Interceptor for ApiKeyInterceptor {
 /// TODO: this function shouldn't be exposed by the python client
 pub async fn get_internal_grpc_client(
     index_endpoint_url: String,
-) -> Result<DataplaneGrpcClient, Box<dyn std::error::Error>> {
+) -> PineconeResult<DataplaneGrpcClient> {
     // TODO: Theoretically this method could have simply been one line:
     // VectorServiceClient::connect(index_endpoint_url).await?
     // But than the return type would be different, DataplaneGrpcClient would need to be Generic.
     // so TODO: Find a better way to expose an inner stateless, authentication-less, gRPC client
 
-    let channel = Channel::from_shared(index_endpoint_url)?.connect().await?;
-    let token: TonicMetadataVal<_> = "".parse()?;
-    let add_api_key_interceptor = ApiKeyInterceptor { api_token: token };
-    let inner = VectorServiceClient::with_interceptor(channel, add_api_key_interceptor);
-    Ok(DataplaneGrpcClient { inner })
+    let token = TonicMetadataVal::from_static("");
+    let options = ChannelOptions::default();
+    let inner =
+        DataplaneGrpcClient::connect_channel(&index_endpoint_url, &token, None, &options).await?;
+    Ok(DataplaneGrpcClient::new(
+        inner,
+        index_endpoint_url,
+        token,
+        None,
+        options,
+    ))
 }
 
 // todo: add better tests
@@ -226,7 +990,13 @@ pub async fn get_internal_grpc_client(
 mod tests {
     use crate::data_types::SparseValues;
 
-    use super::DataplaneGrpcClient;
+    use super::{ChannelOptions, ConnectionState, ConnectionStateTracker, DataplaneGrpcClient};
+    use crate::client::config::{ClientConfig, RetryPolicy};
+    use crate::client::fault_injection::{Fault, FaultInjector};
+    use crate::utils::errors::PineconeClientError;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+    use tonic::Status;
     const INDEX_ENDPOINT: &str = "";
     const KEY: &str = "";
 
@@ -263,6 +1033,189 @@ mod tests {
         vectors
     }
 
+    #[test]
+    fn test_connection_state_tracking() {
+        let tracker = ConnectionStateTracker::new(ConnectionState::Idle);
+        assert_eq!(tracker.get(), ConnectionState::Idle);
+        // A status sent by the server, even `UNAVAILABLE`, means the connection is healthy.
+        // Transport failures are covered by `test_lazy_reconnect`
+        let _ = tracker.track::<()>(Err(Status::unavailable("overloaded")));
+        assert_eq!(tracker.get(), ConnectionState::Ready);
+        assert!(!super::is_transport_error(&Status::unavailable(
+            "overloaded"
+        )));
+        let _ = tracker.track::<()>(Err(Status::not_found("not found")));
+        assert_eq!(tracker.get(), ConnectionState::Ready);
+        let _ = tracker.clone().track(Ok(()));
+        assert_eq!(tracker.get(), ConnectionState::Ready);
+    }
+
+    #[tokio::test]
+    async fn test_connect_errors() {
+        let res = DataplaneGrpcClient::connect("not a url".to_string(), KEY).await;
+        assert!(matches!(
+            res,
+            Err(PineconeClientError::InvalidEndpointError { .. })
+        ));
+        // Nothing listens on port 1, so the connection is refused
+        let res = DataplaneGrpcClient::connect("http://127.0.0.1:1".to_string(), KEY).await;
+        assert!(matches!(
+            res,
+            Err(PineconeClientError::ConnectionRefusedError { .. })
+        ));
+        let res = DataplaneGrpcClient::connect("http://nonexistent.invalid".to_string(), KEY).await;
+        assert!(matches!(
+            res,
+            Err(PineconeClientError::DnsResolutionError { .. })
+        ));
+    }
+
+    /// Starts a server that accepts connections, but never responds. Returns its URL.
+    async fn silent_server() -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        serve_silently(listener);
+        url
+    }
+
+    fn serve_silently(listener: tokio::net::TcpListener) {
+        tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                connections.push(stream);
+            }
+        });
+    }
+
+    #[tokio::test]
+    async fn test_lazy_reconnect() {
+        // Reserve a port with nothing listening on it yet
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let options = ChannelOptions {
+            request_timeout: Some(Duration::from_millis(100)),
+            lazy: true,
+            ..Default::default()
+        };
+        let mut client =
+            DataplaneGrpcClient::connect_with_runtime(format!("http://{addr}"), KEY, None, options)
+                .await
+                .unwrap();
+        assert_eq!(client.connection_state(), ConnectionState::Idle);
+        let status = client.describe_index_stats(None).await.unwrap_err();
+        assert!(super::is_transport_error(&status));
+        assert_eq!(client.connection_state(), ConnectionState::TransientFailure);
+
+        // Once the server is up, the next request reaches it, and times out waiting for a response
+        serve_silently(tokio::net::TcpListener::bind(addr).await.unwrap());
+        let status = client.describe_index_stats(None).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Cancelled);
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout() {
+        let url = silent_server().await;
+        let options = ChannelOptions {
+            request_timeout: Some(Duration::from_millis(100)),
+            ..Default::default()
+        };
+        let mut client = DataplaneGrpcClient::connect_with_runtime(url, KEY, None, options)
+            .await
+            .unwrap();
+        let status = client.describe_index_stats(None).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Cancelled);
+    }
+
+    /// Connects to a silent server, with a fault injector attached to the client.
+    async fn faulty_client(
+        config: ClientConfig,
+        options: ChannelOptions,
+    ) -> (DataplaneGrpcClient, Arc<FaultInjector>) {
+        let url = silent_server().await;
+        let injector = Arc::new(FaultInjector::new());
+        let client = DataplaneGrpcClient::connect_with_runtime(url, KEY, None, options)
+            .await
+            .unwrap()
+            .with_config(config)
+            .with_fault_injector(injector.clone());
+        (client, injector)
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_retries() {
+        let options = ChannelOptions {
+            request_timeout: Some(Duration::from_millis(100)),
+            ..Default::default()
+        };
+        let (mut client, injector) = faulty_client(ClientConfig::default(), options).await;
+        injector.inject_next(Fault::ResourceExhausted(Some(Duration::from_millis(10))), 2);
+        // The rate-limited attempts are retried, until the request reaches the server and times out
+        let status = client.describe_index_stats(None).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Cancelled);
+        assert_eq!(injector.injected_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_deadline_across_retries() {
+        let mut config = ClientConfig::default();
+        config.retry_policy = RetryPolicy {
+            max_retries: 100,
+            initial_backoff: Duration::from_millis(50),
+            jitter: false,
+            ..Default::default()
+        };
+        let (client, injector) = faulty_client(config, ChannelOptions::default()).await;
+        injector.inject_always(Fault::Unavailable);
+        let start = Instant::now();
+        let mut client = client.with_deadline(start + Duration::from_millis(300));
+        let status = client.describe_index_stats(None).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unavailable);
+        // The retries stop once the next backoff would end past the deadline
+        assert!(start.elapsed() < Duration::from_millis(300));
+        assert!(injector.injected_count() >= 2);
+    }
+
+    #[tokio::test]
+    async fn test_ping_is_not_retried() {
+        let mut config = ClientConfig::default();
+        config.retry_policy = RetryPolicy {
+            max_retries: 3,
+            ..Default::default()
+        };
+        let (client, injector) = faulty_client(config, ChannelOptions::default()).await;
+        injector.inject_next(Fault::Unavailable, 1);
+        let status = client.ping().await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unavailable);
+        assert_eq!(injector.injected_count(), 1);
+        // An `UNAVAILABLE` status isn't a transport failure, so the connection is still considered healthy
+        assert_eq!(client.connection_state(), ConnectionState::Ready);
+    }
+
+    #[tokio::test]
+    async fn test_request_ids() {
+        let mut config = ClientConfig::default();
+        config.request_ids = true;
+        let (mut client, injector) = faulty_client(config, ChannelOptions::default()).await;
+        injector.inject_always(Fault::Unavailable);
+        let err = PineconeClientError::from(client.describe_index_stats(None).await.unwrap_err());
+        let request_id = err.request_id().unwrap().to_string();
+        assert_eq!(request_id.len(), 36);
+        assert!(err.to_string().contains(&request_id));
+
+        // An explicit ID takes precedence over the generated ones
+        let mut client = client.with_request_id("upsert-1".to_string());
+        let err = PineconeClientError::from(client.describe_index_stats(None).await.unwrap_err());
+        assert_eq!(err.request_id(), Some("upsert-1"));
+        let status = client
+            .with_request_id("not\nvalid".to_string())
+            .describe_index_stats(None)
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
     #[tokio::test]
     async fn test_upsert() {
         let mut client = DataplaneGrpcClient::connect(INDEX_ENDPOINT.to_string(), KEY)
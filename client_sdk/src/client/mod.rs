@@ -1,3 +1,11 @@
-mod control_plane;
+pub mod admin;
+pub mod config;
+pub mod config_resolution;
+pub(crate) mod control_plane;
+pub mod events;
+#[cfg(any(test, feature = "fault-injection"))]
+pub mod fault_injection;
 pub mod grpc;
 pub mod pinecone_client;
+pub mod proxy;
+pub mod request_dump;
@@ -1,23 +1,231 @@
+use futures::stream::{self, StreamExt};
+use index_service::models::PatchRequest;
 use pyo3::Python;
-use std::io::Write;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard, RwLock};
 use std::time::{Duration, Instant};
-use std::{env, io};
+use tokio::runtime::Handle;
+use tokio_util::sync::CancellationToken;
 
+use super::admin::AdminClient;
+use super::config::{AuditHook, ClientConfig, ClientIdentity, IndexEndpoint, RetryPolicy};
+use super::config_resolution::{
+    ConfigResolver, ConfigSource, ResolvedConfig, ResolvedValue, API_KEY_ENV, DEFAULT_REGION,
+    PROJECT_ID_ENV, REGION_ENV,
+};
 use super::control_plane::ControlPlaneClient;
-use super::grpc::DataplaneGrpcClient;
-use crate::data_types::{Collection, Db};
+use super::events::EventSink;
+use super::grpc::{ChannelOptions, DataplaneGrpcClient};
+use super::proxy::ProxyConfig;
+use crate::data_types::{
+    AuditEvent, Backup, Collection, ConfigureIndexOptions, Db, IndexProgress, OperationMetrics,
+    PodType,
+};
 use crate::index::Index;
-use crate::utils::errors::PineconeClientError::IndexConnectionError;
+use crate::utils::cancellation::run_cancellable;
+use crate::utils::control_plane_cache::ControlPlaneCache;
+use crate::utils::conversions::validate_new_index;
+use crate::utils::debug_sampling::DebugLogSampling;
 use crate::utils::errors::{PineconeClientError, PineconeResult};
+#[cfg(feature = "prometheus")]
+use crate::utils::metrics::PrometheusMetrics;
+use crate::utils::redaction;
+use crate::utils::regions::{check_region, unreachable_region_error, SUPPORTED_ENVIRONMENTS};
 
-const DEAULT_PINECONE_REGION: &str = "us-west1-gcp";
+// The maximal number of concurrent requests issued by `describe_indexes()`, to avoid getting rate limited
+const DESCRIBE_INDEXES_CONCURRENCY: usize = 10;
+// The maximal number of indexes `warm()` connects to concurrently
+const WARM_CONCURRENCY: usize = 10;
 
-#[derive(Debug)]
+/// Builds a [`PineconeClient`], see [`PineconeClient::builder`]. Unset settings are resolved like in [`PineconeClient::new`].
+#[derive(Debug, Default)]
+pub struct ClientBuilder {
+    api_key: Option<String>,
+    region: Option<String>,
+    project_id: Option<String>,
+    config: ClientConfig,
+}
+
+impl ClientBuilder {
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    pub fn region(mut self, region: impl Into<String>) -> Self {
+        self.region = Some(region.into());
+        self
+    }
+
+    pub fn project_id(mut self, project_id: impl Into<String>) -> Self {
+        self.project_id = Some(project_id.into());
+        self
+    }
+
+    /// The profile to read settings from, see [`ClientConfig::profile`].
+    pub fn profile(mut self, profile: impl Into<String>) -> Self {
+        self.config.profile = Some(profile.into());
+        self
+    }
+
+    /// See [`ClientConfig::diagnostics`].
+    pub fn diagnostics(mut self, diagnostics: bool) -> Self {
+        self.config.diagnostics = diagnostics;
+        self
+    }
+
+    /// Sends control plane requests to `controller_url`, instead of the region's public controller.
+    pub fn controller_url(mut self, controller_url: impl Into<String>) -> Self {
+        self.config.controller_url = Some(controller_url.into());
+        self
+    }
+
+    /// Appends `user_agent` to the SDK's user agent, e.g. to identify the application making the requests.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.config.user_agent_suffix = Some(user_agent.into());
+        self
+    }
+
+    /// Attributes the requests to a partner integration, see [`ClientConfig::source_tag`].
+    pub fn source_tag(mut self, source_tag: impl Into<String>) -> Self {
+        self.config.source_tag = Some(source_tag.into());
+        self
+    }
+
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.config.retry_policy = retry_policy;
+        self
+    }
+
+    /// Trusts the PEM-encoded CA certificates in `pem`, in addition to the system's root certificates,
+    /// for both control plane requests and index connections.
+    pub fn ca_certificates(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.config.tls.ca_certificates = Some(pem.into());
+        self
+    }
+
+    /// Presents a client certificate to servers that require mutual TLS, see [`ClientIdentity`].
+    pub fn client_identity(
+        mut self,
+        certificate_pem: impl Into<Vec<u8>>,
+        private_key_pem: impl Into<Vec<u8>>,
+    ) -> Self {
+        self.config.tls.client_identity = Some(ClientIdentity {
+            certificate: certificate_pem.into(),
+            private_key: private_key_pem.into(),
+        });
+        self
+    }
+
+    /// See [`ClientConfig::connect_timeout`].
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.config.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// See [`ClientConfig::request_timeout`].
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.config.request_timeout = Some(timeout);
+        self
+    }
+
+    /// See [`ClientConfig::request_ids`].
+    pub fn request_ids(mut self, enabled: bool) -> Self {
+        self.config.request_ids = enabled;
+        self
+    }
+
+    /// See [`ClientConfig::metrics`].
+    #[cfg(feature = "prometheus")]
+    pub fn metrics(mut self, metrics: PrometheusMetrics) -> Self {
+        self.config.metrics = Some(metrics);
+        self
+    }
+
+    /// See [`ClientConfig::event_sink`]. Pass [`SilentEventSink`](super::events::SilentEventSink) to silence the client.
+    pub fn event_sink(mut self, sink: impl EventSink + 'static) -> Self {
+        self.config.event_sink = Arc::new(sink);
+        self
+    }
+
+    /// See [`ClientConfig::log_full_payloads`].
+    pub fn log_full_payloads(mut self, enabled: bool) -> Self {
+        self.config.log_full_payloads = enabled;
+        self
+    }
+
+    /// See [`ClientConfig::operation_timeout`].
+    pub fn operation_timeout(mut self, timeout: Duration) -> Self {
+        self.config.operation_timeout = Some(timeout);
+        self
+    }
+
+    /// Sends control plane requests and index connections through `proxy`, instead of the one set in the environment.
+    pub fn proxy(mut self, proxy: ProxyConfig) -> Self {
+        self.config.proxy = Some(proxy);
+        self
+    }
+
+    /// Connects to an index through a private endpoint, see [`IndexEndpoint`].
+    pub fn index_endpoint(
+        mut self,
+        index_name: impl Into<String>,
+        endpoint: IndexEndpoint,
+    ) -> Self {
+        self.config
+            .index_endpoints
+            .insert(index_name.into(), endpoint);
+        self
+    }
+
+    pub async fn build(self) -> PineconeResult<PineconeClient> {
+        PineconeClient::new_with_config(
+            self.api_key.as_deref(),
+            self.region.as_deref(),
+            self.project_id.as_deref(),
+            self.config,
+        )
+        .await
+    }
+}
+
+/// Receives the status of an index while [`PineconeClient::create_index`] waits for it to be ready.
+pub type ProgressCallback<'a> = &'a (dyn Fn(&IndexProgress) + Send + Sync);
+
+/// A client for a Pinecone project.
+///
+/// Clones are cheap and share the control plane connection, the index connection cache, the control plane cache and
+/// the index endpoint overrides, so a single client can be cloned into the state of every task or request handler.
+#[derive(Clone)]
 pub struct PineconeClient {
     pub api_key: String,
     pub region: String,
     pub project_id: String,
-    control_plane_client: ControlPlaneClient,
+    control_plane_client: Arc<ControlPlaneClient>,
+    // Connected indexes, keyed by name, so repeated `get_index()` calls reuse the same gRPC channel
+    index_cache: Arc<Mutex<HashMap<String, Index>>>,
+    // The runtime to spawn the background tasks of index connections on, if different from the calling runtime
+    runtime: Option<Handle>,
+    control_plane_cache: Arc<RwLock<Option<Arc<ControlPlaneCache>>>>,
+    // Seeded from `config.index_endpoints`, and shared between clones so `set_index_endpoint()` applies to all of them
+    index_endpoints: Arc<RwLock<BTreeMap<String, IndexEndpoint>>>,
+    config: ClientConfig,
+    resolved_config: ResolvedConfig,
+}
+
+impl fmt::Debug for PineconeClient {
+    // Redacts the API key, which would otherwise end up in logs, and leaves out the connections and caches
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PineconeClient")
+            .field("api_key", &redaction::redact_api_key(&self.api_key))
+            .field("region", &self.region)
+            .field("project_id", &self.project_id)
+            .field("config", &self.config)
+            .field("resolved_config", &self.resolved_config)
+            .finish_non_exhaustive()
+    }
 }
 
 impl PineconeClient {
@@ -26,48 +234,117 @@ impl PineconeClient {
         region: Option<&str>,
         project_id: Option<&str>,
     ) -> PineconeResult<Self> {
-        let api_key = match api_key {
-                Some(s) => Ok(s.to_string()),
-                None => env::var("PINECONE_API_KEY").map_err(|_| PineconeClientError::ValueError("Please provide a valid API key or set the 'PINECONE_API_KEY' environment variable".to_string()))
-            }?;
-        let region = match region {
-            Some(s) => s.to_string(),
-            None => {
-                env::var("PINECONE_REGION").unwrap_or_else(|_| DEAULT_PINECONE_REGION.to_string())
-            }
-        };
+        Self::new_with_config(api_key, region, project_id, ClientConfig::default()).await
+    }
+
+    /// Returns a builder for a client, to set the connection settings that [`PineconeClient::new`] doesn't take.
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::default()
+    }
+
+    /// Like [`PineconeClient::new`], with an explicit client-wide configuration instead of the one derived from the environment.
+    pub async fn new_with_config(
+        api_key: Option<&str>,
+        region: Option<&str>,
+        project_id: Option<&str>,
+        config: ClientConfig,
+    ) -> PineconeResult<Self> {
+        let resolver = ConfigResolver::load(config.profile.as_deref())?;
+        let api_key = resolver
+            .resolve(api_key, API_KEY_ENV, "api_key")
+            .ok_or_else(|| {
+                PineconeClientError::ValueError(format!(
+                    "Please provide a valid API key or set the '{API_KEY_ENV}' environment variable"
+                ))
+            })?;
+        let region = resolver
+            .resolve(region, REGION_ENV, "region")
+            .unwrap_or_else(|| ResolvedValue {
+                value: DEFAULT_REGION.to_string(),
+                source: ConfigSource::Default,
+            });
         // Check if region is empty. For cases where the user sets the region to an empty string
-        if region.is_empty() {
-            return Err(PineconeClientError::ValueError(
-                "Please provide a valid region or set the 'PINECONE_REGION' environment variable"
-                    .to_string(),
-            ));
+        if region.value.is_empty() {
+            return Err(PineconeClientError::ValueError(format!(
+                "Please provide a valid region or set the '{REGION_ENV}' environment variable"
+            )));
         }
+        // The region only determines the controller if no controller URL is set
+        let region_sets_controller = config.controller_url.is_none();
+        if region_sets_controller {
+            check_region(&region.value);
+        }
+        let controller_url = config
+            .controller_url
+            .clone()
+            .unwrap_or_else(|| PineconeClient::get_controller_url(&region.value));
         let control_plane_client =
-            ControlPlaneClient::new(&PineconeClient::get_controller_url(&region), &api_key);
-        let project_id = match project_id {
-            Some(id) => id.to_string(),
-            None => PineconeClient::get_project_id(&control_plane_client)
-                .await
-                .map_err(|e| match e {
-                    PineconeClientError::ControlPlaneConnectionError { err, .. } => {
-                        PineconeClientError::ControlPlaneConnectionError {
-                            err,
-                            region: region.clone(),
+            ControlPlaneClient::new(&controller_url, &api_key.value, &config)?;
+        let project_id = match resolver.resolve(project_id, PROJECT_ID_ENV, "project_id") {
+            Some(project_id) => project_id,
+            None => ResolvedValue {
+                value: PineconeClient::get_project_id(&control_plane_client)
+                    .await
+                    .map_err(|e| match e {
+                        PineconeClientError::ControlPlaneConnectionError { err, .. } => {
+                            let err = PineconeClientError::ControlPlaneConnectionError {
+                                err,
+                                region: region.value.clone(),
+                            };
+                            // A typo in the region is the likeliest reason for an unreachable controller
+                            if region_sets_controller {
+                                unreachable_region_error(&region.value, err)
+                            } else {
+                                err
+                            }
                         }
-                    }
-                    _ => e,
-                })?,
+                        _ => e,
+                    })?,
+                source: ConfigSource::ControlPlane,
+            },
         };
-
-        Ok(PineconeClient {
+        let resolved_config = ResolvedConfig {
             api_key,
             region,
             project_id,
-            control_plane_client,
+        };
+
+        Ok(PineconeClient {
+            api_key: resolved_config.api_key.value.clone(),
+            region: resolved_config.region.value.clone(),
+            project_id: resolved_config.project_id.value.clone(),
+            resolved_config,
+            control_plane_client: Arc::new(control_plane_client),
+            index_cache: Arc::new(Mutex::new(HashMap::new())),
+            runtime: None,
+            control_plane_cache: Arc::new(RwLock::new(None)),
+            index_endpoints: Arc::new(RwLock::new(config.index_endpoints.clone())),
+            config,
         })
     }
 
+    /// Returns a client for the organization's admin API, e.g. to manage projects.
+    /// Requires an API key with organization admin permissions.
+    pub fn admin(&self) -> PineconeResult<AdminClient> {
+        AdminClient::new(&self.api_key, &self.config)
+    }
+
+    /// The API key, region and project id this client was created with, and where each of them came from.
+    /// See [`config_resolution`](super::config_resolution) for the precedence.
+    pub fn resolved_config(&self) -> &ResolvedConfig {
+        &self.resolved_config
+    }
+
+    /// The public Pinecone environments (regions) a client can be created in.
+    /// The control plane doesn't expose this list, so the SDK's built-in list is returned.
+    /// Dedicated or private environments are not included.
+    pub fn list_supported_environments() -> Vec<String> {
+        SUPPORTED_ENVIRONMENTS
+            .iter()
+            .map(|env| env.to_string())
+            .collect()
+    }
+
     fn get_index_url(&self, index_name: &str) -> String {
         let output = format!(
             "https://{index_name}-{project_id}.svc.{region}.pinecone.io:443",
@@ -83,18 +360,142 @@ impl PineconeClient {
         output
     }
 
+    /// The settings of the channels to indexes, from the client-wide configuration.
+    fn channel_options(&self, tls_domain: Option<String>) -> ChannelOptions {
+        ChannelOptions {
+            tls_domain,
+            proxy: self.config.proxy.clone(),
+            tls: self.config.tls.clone(),
+            user_agent: Some(self.config.user_agent()),
+            connect_timeout: self.config.connect_timeout,
+            request_timeout: self.config.request_timeout,
+            lazy: true,
+        }
+    }
+
     async fn get_dataplane_grpc_client(
         &self,
         index_name: &str,
     ) -> PineconeResult<DataplaneGrpcClient> {
-        let index_endpoint_url = self.get_index_url(index_name);
-        let client = DataplaneGrpcClient::connect(index_endpoint_url, &self.api_key)
-            .await
-            .map_err(|e| IndexConnectionError {
-                index: index_name.to_string(),
-                err: e.to_string(),
-            })?;
-        Ok(client)
+        let endpoint = self
+            .index_endpoints
+            .read()
+            .unwrap()
+            .get(index_name)
+            .cloned();
+        let index_endpoint_url = endpoint
+            .as_ref()
+            .and_then(|endpoint| endpoint.url.clone())
+            .unwrap_or_else(|| self.get_index_url(index_name));
+        DataplaneGrpcClient::connect_with_runtime(
+            index_endpoint_url,
+            &self.api_key,
+            self.runtime.clone(),
+            self.channel_options(endpoint.and_then(|endpoint| endpoint.tls_domain.clone())),
+        )
+        .await
+    }
+
+    /// Makes indexes returned by [`PineconeClient::get_index`] run their connections' background tasks on `runtime`,
+    /// so the client can be embedded in an existing async application without starting another runtime.
+    /// By default, background tasks are spawned on whichever runtime `get_index()` is awaited from.
+    pub fn with_runtime(mut self, runtime: Handle) -> Self {
+        self.runtime = Some(runtime);
+        self
+    }
+
+    /// Makes `get_index()` connect to the given index through a private endpoint, see [`IndexEndpoint`].
+    /// Pass `None` to connect through the index's public URL again.
+    /// An already connected index is evicted from the connection cache, so the next `get_index()` call reconnects.
+    /// The endpoint applies to all clones of this client.
+    pub fn set_index_endpoint(&self, index_name: &str, endpoint: Option<IndexEndpoint>) {
+        let mut endpoints = self.index_endpoints.write().unwrap();
+        match endpoint {
+            Some(endpoint) => endpoints.insert(index_name.to_string(), endpoint),
+            None => endpoints.remove(index_name),
+        };
+        drop(endpoints);
+        self.invalidate_index(index_name);
+    }
+
+    /// The runtime set by [`PineconeClient::with_runtime`], if any.
+    pub fn runtime(&self) -> Option<&Handle> {
+        self.runtime.as_ref()
+    }
+
+    /// Enables dumping failed control plane requests (e.g. `create_index`) as JSON files into `dir`.
+    /// Dumped requests can be re-issued with [`PineconeClient::replay`]. Pass `None` to disable dumping.
+    pub fn set_request_dump_dir(&self, dir: Option<PathBuf>) {
+        self.control_plane_client.set_request_dump_dir(dir);
+    }
+
+    /// Sets controllers (e.g. `https://controller.eu-west1-gcp.pinecone.io`) that read operations, such as
+    /// `describe_index()` and `list_indexes()`, fail over to, in order, when this client's region's controller is unreachable.
+    /// Operations that create, delete or configure indexes or collections are never failed over.
+    pub fn set_fallback_controllers(&self, controller_urls: Vec<String>) {
+        self.control_plane_client
+            .set_fallback_controllers(controller_urls);
+    }
+
+    /// The latency percentiles and counts of the requests made by this client and all of its indexes since the client
+    /// was created, by operation (e.g. "query", "list_indexes"). Every attempt of a retried request counts separately.
+    pub fn metrics(&self) -> BTreeMap<String, OperationMetrics> {
+        self.config.request_metrics()
+    }
+
+    /// Sets the sampling of debug log messages, e.g. of data plane requests and responses, for this client and all of its indexes.
+    pub fn set_debug_log_sampling(&self, sampling: DebugLogSampling) {
+        self.config.set_debug_sampling(sampling);
+    }
+
+    /// Sets a hook that is invoked with an [`AuditEvent`] after every mutating operation made through this client
+    /// or any of its indexes: upserts, updates, deletes, and index creations and deletions. Pass `None` to remove it.
+    pub fn set_audit_hook(&self, hook: Option<AuditHook>) {
+        self.config.set_audit_hook(hook);
+    }
+
+    fn audit(&self, operation: &str, index_name: &str, res: &PineconeResult<()>) {
+        let event = AuditEvent {
+            operation: operation.to_string(),
+            index: index_name.to_string(),
+            ..Default::default()
+        };
+        self.config.audit(event, &BTreeMap::new(), res);
+    }
+
+    /// Re-issues a failed request, previously dumped to `dump_file`.
+    pub async fn replay(&self, dump_file: &Path) -> PineconeResult<()> {
+        let res = self.control_plane_client.replay(dump_file).await;
+        self.clear_control_plane_cache();
+        res
+    }
+
+    /// Enables caching the responses of `list_indexes()` and `describe_index()` for `ttl`.
+    /// Cached responses are invalidated when indexes are created, deleted or configured through this client.
+    /// Changes made by other clients are only seen after the TTL expires. The cache is shared by all clones of this client.
+    pub fn enable_control_plane_cache(&self, ttl: Duration) {
+        *self.control_plane_cache.write().unwrap() = Some(Arc::new(ControlPlaneCache::new(ttl)));
+    }
+
+    pub fn disable_control_plane_cache(&self) {
+        *self.control_plane_cache.write().unwrap() = None;
+    }
+
+    /// Removes all responses from the control plane cache, if it is enabled.
+    pub fn clear_control_plane_cache(&self) {
+        if let Some(cache) = self.control_plane_cache() {
+            cache.clear();
+        }
+    }
+
+    fn invalidate_control_plane_cache(&self, index_name: &str) {
+        if let Some(cache) = self.control_plane_cache() {
+            cache.invalidate(index_name);
+        }
+    }
+
+    fn control_plane_cache(&self) -> Option<Arc<ControlPlaneCache>> {
+        self.control_plane_cache.read().unwrap().clone()
     }
 
     async fn get_project_id(control_plane_client: &ControlPlaneClient) -> PineconeResult<String> {
@@ -102,12 +503,60 @@ impl PineconeClient {
         Ok(whoami_response.project_name)
     }
 
+    /// Verifies that an index's source collection exists and is ready, and that the index's dimension matches the collection's.
+    /// An unset dimension (0) is taken from the collection, and is otherwise required.
+    async fn check_source_collection(&self, db: &mut Db) -> PineconeResult<()> {
+        let source = match &db.source_collection {
+            Some(source) => source,
+            None => return Ok(()),
+        };
+        let collection = self
+            .control_plane_client
+            .describe_collection(source)
+            .await
+            .map_err(|err| {
+                PineconeClientError::ValueError(format!(
+                    "Failed to describe source collection '{source}'. Please verify that it exists using `client.list_collections()`. \nUnderlying Error: {err}"
+                ))
+            })?;
+        if collection.status.as_deref() != Some("Ready") {
+            return Err(PineconeClientError::ValueError(format!(
+                "Source collection '{source}' is not ready yet (status: {status}). Please wait for it to be ready before creating an index from it",
+                status = collection.status.as_deref().unwrap_or("unknown")
+            )));
+        }
+        match collection.dimension {
+            Some(dimension) if db.dimension == 0 => db.dimension = dimension,
+            Some(dimension) if db.dimension != dimension => {
+                return Err(PineconeClientError::ValueError(format!(
+                    "The index's dimension ({index_dimension}) doesn't match the dimension of source collection '{source}' ({dimension})",
+                    index_dimension = db.dimension
+                )))
+            }
+            None if db.dimension == 0 => {
+                return Err(PineconeClientError::ValueError(format!(
+                    "The dimension of source collection '{source}' is unknown. Please pass the index's dimension explicitly"
+                )))
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Creates a new index, and waits for it to be ready unless `timeout` is -1.
+    /// If `cancellation_token` is cancelled while waiting, the wait is aborted. The index creation itself is not rolled back.
+    /// While waiting, `progress` is called with the index's status every time it is polled, e.g. to render a progress bar.
+    /// If `check_signals` is set, the wait is also aborted by Python signals (e.g. Ctrl+C); only meaningful when the
+    /// future is driven from Python's main thread.
     pub async fn create_index(
         &self,
         db: Db,
         timeout: Option<i32>,
-        py: Option<Python<'_>>,
+        check_signals: bool,
+        cancellation_token: Option<&CancellationToken>,
+        progress: Option<ProgressCallback<'_>>,
     ) -> PineconeResult<()> {
+        let mut db = db;
         // If timeout is -ve and not -1 throw an error
         let name = db.name.clone();
         // If timeout is -ve and not -1 throw an error
@@ -116,101 +565,333 @@ impl PineconeClient {
                 "Timeout must be -1 or a positive integer".to_string(),
             ));
         }
-        self.control_plane_client.create_index(db).await?;
+        // Fail before describing the source collection, if any
+        validate_new_index(&db)?;
+        self.check_source_collection(&mut db).await?;
+        let res = self.control_plane_client.create_index(db).await;
+        self.invalidate_control_plane_cache(&name);
+        self.audit("create_index", &name, &res);
+        res?;
         // If -1 then don't wait for index to be ready
         if timeout == Some(-1) {
             return Ok(());
         }
         // block until index is ready
-        let mut new_index = self.describe_index(&name).await?;
+        // The cache is bypassed while waiting, since it would hide status changes
+        let mut new_index = self.control_plane_client.describe_index(&name).await?;
         let start_time = Instant::now();
         let max_timeout = Duration::from_secs(timeout.unwrap_or(300) as u64);
-        if let Some(py) = py {
-            py.run(
-                "print(\"Waiting for index to be ready...\", flush=True)",
-                None,
-                None,
-            )
-            .map_err(|_| PineconeClientError::Other("Failed to print to stdout".to_string()))?;
-        } else {
-            println!("Waiting for index to be ready...");
-            io::stdout().flush()?;
-        }
-        while new_index.status != Some("Ready".to_string()) {
-            if let Some(py) = py {
-                Python::check_signals(py)
-                    .map_err(|_| {
-                        let msg = "Interrupted. Index status unknown. Please call describe_index() to check status";
-                        println!("{}", msg);
-                        io::stdout().flush().unwrap();
-                        PineconeClientError::KeyboardInterrupt(
-                            msg.into(),
-                        )
-                    })?;
+        self.config.event(
+            "create_index",
+            &format!("Waiting for index {name} to be ready..."),
+        );
+        let report = |index: &Db| {
+            if let Some(progress) = progress {
+                progress(&IndexProgress {
+                    index: name.clone(),
+                    status: index.status.clone().unwrap_or_default(),
+                    elapsed: start_time.elapsed().as_secs_f64(),
+                    ready: index.status.as_deref() == Some("Ready"),
+                });
             }
-            if start_time.elapsed() > max_timeout {
-                return Err(PineconeClientError::Other(
-                    "Index creation timed out. Please call describe_index() to check status."
-                        .to_string(),
-                ));
+        };
+        report(&new_index);
+        let wait_until_ready = async {
+            while new_index.status != Some("Ready".to_string()) {
+                if check_signals {
+                    Python::with_gil(|py| py.check_signals())
+                        .map_err(|_| {
+                            let msg = "Interrupted. Index status unknown. Please call describe_index() to check status";
+                            self.config.event("create_index", msg);
+                            PineconeClientError::KeyboardInterrupt(
+                                msg.into(),
+                            )
+                        })?;
+                }
+                if start_time.elapsed() > max_timeout {
+                    return Err(PineconeClientError::Other(
+                        "Index creation timed out. Please call describe_index() to check status."
+                            .to_string(),
+                    ));
+                }
+                new_index = self.control_plane_client.describe_index(&name).await?;
+                report(&new_index);
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
             }
-            new_index = self.describe_index(&name).await?;
-            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
-        }
-        Ok(())
+            Ok(())
+        };
+        run_cancellable(cancellation_token, "create_index()", wait_until_ready).await
     }
 
+    /// Returns an `Index` connected to the given index.
+    /// The connection is established by the first request (see [`PineconeClient::warm`] to establish it upfront), and
+    /// re-established automatically after it drops, so `Index` handles can be kept for the lifetime of the application.
+    /// Connected indexes are cached by name, so subsequent calls reuse the same underlying gRPC channel
+    /// instead of dialing a new one. Use [`PineconeClient::invalidate_index`] to force a reconnect.
+    /// Like for [`PineconeClient::index_from_host`], the index's description is fetched lazily, so this makes no control plane request.
     pub async fn get_index(&self, index_name: &str) -> PineconeResult<Index> {
-        Ok(Index::new(
+        if let Some(index) = self.cached_index(index_name) {
+            return Ok(index);
+        }
+        let mut index = Index::new(
             index_name.to_string(),
             self.get_dataplane_grpc_client(index_name).await?,
-        ))
+        );
+        let description = self
+            .control_plane_cache()
+            .and_then(|cache| cache.description(index_name));
+        index.set_control_plane_client(self.control_plane_client.clone(), description);
+        index.set_config(self.config.clone());
+        self.lock_index_cache()
+            .insert(index_name.to_string(), index.clone());
+        Ok(index)
+    }
+
+    /// Returns an `Index` connected to a known index host, without any control plane request.
+    /// Services that cache their indexes' hosts can use it to start up without depending on the controller's availability.
+    /// The index's description (e.g. its metric) is fetched lazily, only by operations that need it.
+    ///
+    /// # Arguments
+    /// - `host` - the index's host, as returned by `describe_index()`, e.g. `my-index-abc123.svc.us-east1-gcp.pinecone.io`.
+    ///   A URL with a scheme and port is used as is.
+    /// - `index_name` - the index's name. The connection is cached under it, like those of [`PineconeClient::get_index`].
+    pub async fn index_from_host(&self, host: &str, index_name: &str) -> PineconeResult<Index> {
+        if let Some(index) = self.cached_index(index_name) {
+            return Ok(index);
+        }
+        let url = if host.contains("://") {
+            host.to_string()
+        } else {
+            format!("https://{host}")
+        };
+        let dataplane_client = DataplaneGrpcClient::connect_with_runtime(
+            url,
+            &self.api_key,
+            self.runtime.clone(),
+            self.channel_options(None),
+        )
+        .await?;
+        let mut index = Index::new(index_name.to_string(), dataplane_client);
+        index.set_control_plane_client(self.control_plane_client.clone(), None);
+        index.set_config(self.config.clone());
+        self.lock_index_cache()
+            .insert(index_name.to_string(), index.clone());
+        Ok(index)
+    }
+
+    /// Connects to the given indexes concurrently, and health-checks each of them with a `describe_index_stats` request,
+    /// so the first user-facing request doesn't pay for DNS resolution, the TLS handshake and the channel setup.
+    /// The connected indexes are cached, so subsequent [`PineconeClient::get_index`] calls reuse them.
+    ///
+    /// # Arguments
+    /// - `index_names` - the names of the indexes to connect to.
+    ///
+    /// # Returns
+    /// A map from each index name to the outcome of its warm-up.
+    /// Indexes that failed the health check are logged and removed from the connection cache, and don't affect the others.
+    pub async fn warm(&self, index_names: &[&str]) -> HashMap<String, PineconeResult<()>> {
+        // Owned names keep the returned future `Send`, so it can be spawned
+        let index_names: Vec<String> = index_names.iter().map(|name| name.to_string()).collect();
+        stream::iter(index_names)
+            .map(|name| async move {
+                let res = self.warm_index(&name).await;
+                (name, res)
+            })
+            .buffer_unordered(WARM_CONCURRENCY)
+            .collect()
+            .await
+    }
+
+    async fn warm_index(&self, index_name: &str) -> PineconeResult<()> {
+        let res = match self.get_index(index_name).await {
+            Ok(index) => index.describe_index_stats(None).await.map(|_| ()),
+            Err(err) => Err(err),
+        };
+        if let Err(err) = &res {
+            log::warn!("Failed to warm up the connection to index '{index_name}': {err}");
+            self.invalidate_index(index_name);
+        }
+        res
+    }
+
+    /// Removes the given index from the connection cache, so the next `get_index()` call reconnects.
+    pub fn invalidate_index(&self, index_name: &str) {
+        self.lock_index_cache().remove(index_name);
+    }
+
+    /// Removes all indexes from the connection cache.
+    pub fn clear_index_cache(&self) {
+        self.lock_index_cache().clear();
+    }
+
+    fn cached_index(&self, index_name: &str) -> Option<Index> {
+        self.lock_index_cache().get(index_name).cloned()
+    }
+
+    fn lock_index_cache(&self) -> MutexGuard<'_, HashMap<String, Index>> {
+        // The cache is always left in a consistent state, so a poisoned lock can be safely reused
+        self.index_cache
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
     }
 
     pub async fn describe_index(&self, index_name: &str) -> PineconeResult<Db> {
-        self.control_plane_client.describe_index(index_name).await
+        let cache = self.control_plane_cache();
+        if let Some(db) = cache
+            .as_ref()
+            .and_then(|cache| cache.description(index_name))
+        {
+            return Ok(db);
+        }
+        let db = self.control_plane_client.describe_index(index_name).await?;
+        if let Some(cache) = cache {
+            cache.set_description(index_name, &db);
+        }
+        Ok(db)
+    }
+
+    /// Describes several indexes concurrently.
+    ///
+    /// # Arguments
+    /// - `index_names` - the names of the indexes to describe.
+    ///
+    /// # Returns
+    /// A map from each index name to its description, or to the error describing that index failed with.
+    /// A failure to describe one index doesn't affect the others.
+    pub async fn describe_indexes(
+        &self,
+        index_names: &[&str],
+    ) -> HashMap<String, PineconeResult<Db>> {
+        // Owned names keep the returned future `Send`, so it can be spawned
+        let index_names: Vec<String> = index_names.iter().map(|name| name.to_string()).collect();
+        stream::iter(index_names)
+            .map(|name| async move {
+                let res = self.describe_index(&name).await;
+                (name, res)
+            })
+            .buffer_unordered(DESCRIBE_INDEXES_CONCURRENCY)
+            .collect()
+            .await
+    }
+
+    /// Lists all indexes, with their full descriptions.
+    /// The indexes are described concurrently, instead of one `describe_index()` round-trip after the other.
+    ///
+    /// # Returns
+    /// The descriptions of all indexes, sorted by name. Indexes deleted while listing are left out.
+    pub async fn list_indexes_full(&self) -> PineconeResult<Vec<Db>> {
+        let names = self.list_indexes().await?;
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        let mut descriptions = self
+            .describe_indexes(&names)
+            .await
+            .into_iter()
+            .collect::<Vec<_>>();
+        descriptions.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let mut dbs = Vec::with_capacity(descriptions.len());
+        for (_, db) in descriptions {
+            match db {
+                Ok(db) => dbs.push(db),
+                Err(PineconeClientError::ControlPlaneOperationError { status_code, .. })
+                    if status_code.starts_with("404") => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(dbs)
     }
 
     pub async fn list_indexes(&self) -> PineconeResult<Vec<String>> {
-        self.control_plane_client.list_indexes().await
+        let cache = self.control_plane_cache();
+        if let Some(list) = cache.as_ref().and_then(|cache| cache.index_list()) {
+            return Ok(list);
+        }
+        let list = self.control_plane_client.list_indexes().await?;
+        if let Some(cache) = cache {
+            cache.set_index_list(&list);
+        }
+        Ok(list)
     }
 
-    pub async fn delete_index(&self, index_name: &str, timeout: Option<i32>) -> PineconeResult<()> {
+    /// Deletes an index, and waits for the deletion to complete unless `timeout` is -1.
+    /// If `cancellation_token` is cancelled while waiting, the wait is aborted. The index deletion itself is not rolled back.
+    pub async fn delete_index(
+        &self,
+        index_name: &str,
+        timeout: Option<i32>,
+        cancellation_token: Option<&CancellationToken>,
+    ) -> PineconeResult<()> {
         // If timeout is -ve and not -1 throw an error
         if timeout.is_some() && timeout.unwrap() < -1 {
             return Err(PineconeClientError::ValueError(
                 "Timeout must be -1 or a positive integer".to_string(),
             ));
         }
-        self.control_plane_client.delete_index(index_name).await?;
+        let res = self.control_plane_client.delete_index(index_name).await;
+        self.invalidate_control_plane_cache(index_name);
+        self.audit("delete_index", index_name, &res);
+        res?;
+        self.invalidate_index(index_name);
         if timeout == Some(-1) {
             return Ok(());
         }
         // block until index is deleted
-        println!("Verifying delete...");
+        self.config.event(
+            "delete_index",
+            &format!("Verifying delete of index {index_name}..."),
+        );
         let start_time = Instant::now();
         let max_timeout = Duration::from_secs(timeout.unwrap_or(300) as u64);
-        while self.list_indexes().await?.contains(&index_name.to_string()) {
-            if start_time.elapsed() > max_timeout {
-                return Err(PineconeClientError::Other(
-                    "Index deletion timed out. Please call describe_index to check status."
-                        .to_string(),
-                ));
+        let wait_until_deleted = async {
+            while self
+                .control_plane_client
+                .list_indexes()
+                .await?
+                .contains(&index_name.to_string())
+            {
+                if start_time.elapsed() > max_timeout {
+                    return Err(PineconeClientError::Other(
+                        "Index deletion timed out. Please call describe_index to check status."
+                            .to_string(),
+                    ));
+                }
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
             }
-            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
-        }
-        Ok(())
+            Ok(())
+        };
+        run_cancellable(cancellation_token, "delete_index()", wait_until_deleted).await
     }
 
     pub async fn configure_index(
         &self,
         index_name: &str,
-        pod_type: Option<String>,
+        pod_type: Option<PodType>,
         replicas: Option<i32>,
     ) -> PineconeResult<()> {
-        self.control_plane_client
-            .configure_index(index_name, pod_type, replicas)
-            .await
+        self.configure_index_with(
+            index_name,
+            ConfigureIndexOptions {
+                pod_type,
+                replicas,
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    /// Changes an index's settings. See [`ConfigureIndexOptions`] for the settings, and which of them can be combined.
+    /// The combination is validated before the request is sent.
+    pub async fn configure_index_with(
+        &self,
+        index_name: &str,
+        options: ConfigureIndexOptions,
+    ) -> PineconeResult<()> {
+        let patch = PatchRequest::try_from(options)?;
+        let res = self
+            .control_plane_client
+            .configure_index(index_name, patch)
+            .await;
+        self.invalidate_control_plane_cache(index_name);
+        res
     }
 
     pub async fn create_collection(
@@ -243,15 +924,100 @@ impl PineconeClient {
             .delete_collection(collection_name)
             .await
     }
+
+    /// Creates a backup of an index.
+    ///
+    /// # Arguments
+    /// - `index_name` - the index to back up.
+    /// - `backup_name` - an optional name for the backup.
+    /// - `description` - an optional description of the backup.
+    ///
+    /// # Returns
+    /// The new backup. It can be restored once its status is "Ready".
+    pub async fn create_backup(
+        &self,
+        index_name: &str,
+        backup_name: Option<&str>,
+        description: Option<&str>,
+    ) -> PineconeResult<Backup> {
+        self.control_plane_client
+            .create_backup(index_name, backup_name, description)
+            .await
+    }
+
+    /// Lists the backups of all indexes in the project, or only of `index_name` if set.
+    pub async fn list_backups(&self, index_name: Option<&str>) -> PineconeResult<Vec<Backup>> {
+        self.control_plane_client.list_backups(index_name).await
+    }
+
+    pub async fn describe_backup(&self, backup_id: &str) -> PineconeResult<Backup> {
+        self.control_plane_client.describe_backup(backup_id).await
+    }
+
+    pub async fn delete_backup(&self, backup_id: &str) -> PineconeResult<()> {
+        self.control_plane_client.delete_backup(backup_id).await
+    }
+
+    /// Creates a new index named `index_name`, restored from a backup.
+    /// Like `create_index()`, the index can only be used once it is ready.
+    pub async fn create_index_from_backup(
+        &self,
+        backup_id: &str,
+        index_name: &str,
+    ) -> PineconeResult<()> {
+        let res = self
+            .control_plane_client
+            .create_index_from_backup(backup_id, index_name)
+            .await;
+        self.invalidate_control_plane_cache(index_name);
+        self.audit("create_index_from_backup", index_name, &res);
+        res
+    }
 }
 
 mod tests {
     #[tokio::test]
     async fn test_env_vars() {
         use super::*;
+        use std::env;
         env::set_var("PINECONE_API_KEY", "");
         env::set_var("PINECONE_REGION", "");
         let client = PineconeClient::new(None, None, None).await.unwrap();
         println!("{:?}", client);
     }
+
+    #[tokio::test]
+    async fn test_clones_share_state() {
+        use super::*;
+        fn assert_send_sync<T: Send + Sync + 'static>() {}
+        assert_send_sync::<PineconeClient>();
+
+        let client = PineconeClient::new(Some("api-key"), Some("us-west1-gcp"), Some("project"))
+            .await
+            .unwrap();
+        let clone = client.clone();
+        clone.enable_control_plane_cache(Duration::from_secs(60));
+        assert!(client.control_plane_cache().is_some());
+        let endpoint = IndexEndpoint {
+            url: Some("https://vpce.example.com:443".to_string()),
+            tls_domain: None,
+        };
+        clone.set_index_endpoint("idx", Some(endpoint));
+        assert!(client.index_endpoints.read().unwrap().contains_key("idx"));
+        let index = clone
+            .index_from_host("idx-project.svc.us-west1-gcp.pinecone.io", "idx")
+            .await
+            .unwrap();
+        assert_eq!(client.cached_index("idx").unwrap().name, index.name);
+    }
+
+    #[tokio::test]
+    async fn test_unlisted_region() {
+        use super::*;
+        // Close to a listed environment, but may be a new one, so it's only rejected once its controller is unreachable
+        let client = PineconeClient::new(Some("api-key"), Some("us-west2-gcp"), Some("project"))
+            .await
+            .unwrap();
+        assert_eq!(client.region, "us-west2-gcp");
+    }
 }
@@ -0,0 +1,135 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use index_service::models::{CreateCollectionRequest, CreateRequest, PatchRequest};
+use serde::{Deserialize, Serialize};
+
+use crate::utils::errors::{PineconeClientError, PineconeResult};
+
+/// A control plane request, in a form that can be written to a dump file and re-issued later.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "operation", rename_all = "snake_case")]
+pub enum DumpedRequest {
+    CreateIndex { payload: CreateRequest },
+    DeleteIndex { name: String },
+    ConfigureIndex { name: String, payload: PatchRequest },
+    CreateCollection { payload: CreateCollectionRequest },
+    DeleteCollection { name: String },
+}
+
+impl DumpedRequest {
+    pub fn operation(&self) -> &'static str {
+        match self {
+            DumpedRequest::CreateIndex { .. } => "create_index",
+            DumpedRequest::DeleteIndex { .. } => "delete_index",
+            DumpedRequest::ConfigureIndex { .. } => "configure_index",
+            DumpedRequest::CreateCollection { .. } => "create_collection",
+            DumpedRequest::DeleteCollection { .. } => "delete_collection",
+        }
+    }
+}
+
+/// A failed request, along with the metadata needed to reproduce it.
+/// Credentials are never written to the dump: the request is re-issued with the credentials of the replaying client.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestDump {
+    #[serde(flatten)]
+    pub request: DumpedRequest,
+    pub error: String,
+    pub controller_url: String,
+    pub client_version: String,
+    /// Milliseconds since the unix epoch
+    pub timestamp: u128,
+}
+
+impl RequestDump {
+    pub fn new(request: DumpedRequest, error: &PineconeClientError, controller_url: &str) -> Self {
+        RequestDump {
+            request,
+            error: error.to_string(),
+            controller_url: controller_url.to_string(),
+            client_version: env!("CARGO_PKG_VERSION").to_string(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Writes the dump as a JSON file into `dir`, and returns the file's path.
+    /// Dumps of the same operation from the same millisecond are numbered (`-1`, `-2`, ...) instead of overwriting each other.
+    pub fn write(&self, dir: &Path) -> PineconeResult<PathBuf> {
+        fs::create_dir_all(dir)?;
+        let contents = serde_json::to_vec_pretty(self)?;
+        let stem = format!(
+            "{timestamp}-{operation}",
+            timestamp = self.timestamp,
+            operation = self.request.operation()
+        );
+        let mut attempt = 0;
+        loop {
+            let path = match attempt {
+                0 => dir.join(format!("{stem}.json")),
+                n => dir.join(format!("{stem}-{n}.json")),
+            };
+            // Only creates new files, so concurrent writers, even from other processes, never write to the same file
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    file.write_all(&contents)?;
+                    return Ok(path);
+                }
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists => attempt += 1,
+                Err(err) => return Err(err.into()),
+            }
+        }
+    }
+
+    pub fn read(path: &Path) -> PineconeResult<Self> {
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dump_roundtrip() {
+        let request = DumpedRequest::ConfigureIndex {
+            name: "test-index".to_string(),
+            payload: PatchRequest {
+                replicas: Some(2),
+                ..Default::default()
+            },
+        };
+        let dump = RequestDump::new(
+            request.clone(),
+            &PineconeClientError::Other("bad request".to_string()),
+            "https://controller.test.pinecone.io",
+        );
+        let dir = std::env::temp_dir().join(format!("pinecone-dump-test-{}", dump.timestamp));
+        let path = dump.write(&dir).unwrap();
+        // A dump with the same timestamp and operation doesn't overwrite the first one
+        let second_path = dump.write(&dir).unwrap();
+        let read = RequestDump::read(&path).unwrap();
+        let second_read = RequestDump::read(&second_path).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(read.request, request);
+        assert!(read.error.contains("bad request"));
+        assert!(path
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .ends_with("-configure_index.json"));
+        assert_ne!(second_path, path);
+        assert!(second_path
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .ends_with("-configure_index-1.json"));
+        assert_eq!(second_read.request, request);
+    }
+}
@@ -1,17 +1,23 @@
 use derivative::Derivative;
 
-use pyo3::types::{PyDict, PyList};
-use serde::Deserialize;
+use pyo3::exceptions::{PyIndexError, PyKeyError};
+use pyo3::types::{PyDict, PyIterator, PyList, PyType};
+use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
 use std::vec::Vec;
 
 use pyo3::prelude::*;
 use pyo3::types::IntoPyDict;
 
+use crate::utils::errors::{PineconeClientError, PineconeResult};
+use crate::utils::python_conversions::DenseValues;
+
 const SHORT_PRINT_LEN: usize = 5;
 
-#[derive(Debug, Default, Clone)]
-#[pyclass]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[pyclass(module = "pinecone")]
 #[pyo3(get_all)]
 #[pyo3(text_signature = "(indices, values)")]
 pub struct SparseValues {
@@ -23,8 +29,11 @@ pub struct SparseValues {
 impl SparseValues {
     #[new]
     #[pyo3(signature = (indices, values))]
-    pub fn new(indices: Vec<u32>, values: Vec<f32>) -> Self {
-        Self { indices, values }
+    pub fn new(indices: Vec<u32>, values: DenseValues) -> Self {
+        Self {
+            indices,
+            values: values.into(),
+        }
     }
 
     pub fn __repr__(&self) -> Result<String, PyErr> {
@@ -34,11 +43,29 @@ impl SparseValues {
             values = &self.values.chunks(5).next().unwrap_or(&Vec::<f32>::new())
         ))
     }
+
+    pub fn __getstate__<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        self.pickle_state(py)
+    }
+
+    pub fn __setstate__(&mut self, state: &PyDict) -> PyResult<()> {
+        *self = Self::from_pickle_state(state)?;
+        Ok(())
+    }
+
+    pub fn __reduce__(slf: &PyCell<Self>) -> PyResult<(PyObject, (&PyDict,))> {
+        reduce_pickled(slf)
+    }
+
+    #[classmethod]
+    fn _from_state(_cls: &PyType, state: &PyDict) -> PyResult<Self> {
+        Self::from_pickle_state(state)
+    }
 }
 
-#[derive(Debug, Default, Clone)]
-#[pyclass]
-#[pyo3(get_all)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[pyclass(module = "pinecone")]
+#[pyo3(get_all, mapping)]
 #[pyo3(text_signature = "(id, values, sparse_values=None, metadata=None)")]
 pub struct Vector {
     pub id: String,
@@ -53,13 +80,13 @@ impl Vector {
     #[pyo3(signature = (id, values, sparse_values=None, metadata=None))]
     pub fn new(
         id: String,
-        values: Vec<f32>,
+        values: DenseValues,
         sparse_values: Option<SparseValues>,
         metadata: Option<BTreeMap<String, MetadataValue>>,
     ) -> Self {
         Self {
             id,
-            values,
+            values: values.into(),
             sparse_values,
             metadata,
         }
@@ -78,13 +105,126 @@ impl Vector {
         ];
         key_vals.into_py_dict(py)
     }
+
+    // Mapping protocol, so the object can be used like the dict returned by `to_dict()`
+
+    pub fn keys<'a>(&self, py: Python<'a>) -> &'a PyList {
+        self.to_dict(py).keys()
+    }
+
+    pub fn values<'a>(&self, py: Python<'a>) -> &'a PyList {
+        self.to_dict(py).values()
+    }
+
+    pub fn items<'a>(&self, py: Python<'a>) -> &'a PyList {
+        self.to_dict(py).items()
+    }
+
+    pub fn __getitem__<'a>(&self, py: Python<'a>, key: &str) -> PyResult<&'a PyAny> {
+        mapping_getitem(self.to_dict(py), key)
+    }
+
+    #[pyo3(signature = (key, default=None))]
+    pub fn get(&self, py: Python, key: &str, default: Option<PyObject>) -> PyObject {
+        mapping_get(py, self.to_dict(py), key, default)
+    }
+
+    pub fn __contains__(&self, py: Python, key: &str) -> bool {
+        self.to_dict(py).contains(key).unwrap_or(false)
+    }
+
+    pub fn __len__(&self, py: Python) -> usize {
+        self.to_dict(py).len()
+    }
+
+    pub fn __iter__<'a>(&self, py: Python<'a>) -> PyResult<&'a PyIterator> {
+        PyIterator::from_object(py, self.to_dict(py).keys())
+    }
+
+    pub fn __getstate__<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        self.pickle_state(py)
+    }
+
+    pub fn __setstate__(&mut self, state: &PyDict) -> PyResult<()> {
+        *self = Self::from_pickle_state(state)?;
+        Ok(())
+    }
+
+    pub fn __reduce__(slf: &PyCell<Self>) -> PyResult<(PyObject, (&PyDict,))> {
+        reduce_pickled(slf)
+    }
+
+    #[classmethod]
+    fn _from_state(_cls: &PyType, state: &PyDict) -> PyResult<Self> {
+        Self::from_pickle_state(state)
+    }
 }
 
-#[derive(Debug)]
-#[pyclass]
+#[derive(Debug, Clone)]
+#[pyclass(module = "pinecone")]
 #[pyo3(get_all)]
 pub struct UpsertResponse {
     pub upserted_count: u32,
+    /// The ids of the upserted vectors, if requested
+    pub upserted_ids: Option<Vec<String>>,
+    /// Throughput report of a batched upsert, or None if the vectors were upserted in a single request
+    pub report: Option<UpsertReport>,
+    /// The status of every batch of a batched upsert, in order, or None if the vectors were upserted in a single request
+    pub batches: Option<Vec<UpsertBatchStatus>>,
+    /// The ids of the vectors in failed batches. Only set when partial failures are allowed, otherwise failures raise an error
+    pub failed_ids: Vec<String>,
+    /// Client-side latency breakdown of the upsert, or None if it wasn't measured
+    pub timing: Option<LatencyBreakdown>,
+}
+
+/// The outcome of a single batch of a batched upsert.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[pyclass(module = "pinecone")]
+#[pyo3(get_all)]
+pub struct UpsertBatchStatus {
+    pub vector_count: u32,
+    pub upserted_count: u32,
+    /// The error the batch failed with, or None if it succeeded
+    pub error: Option<String>,
+}
+
+#[pymethods]
+impl UpsertBatchStatus {
+    pub fn __repr__(&self, py: Python) -> Result<String, PyErr> {
+        Ok("UpsertBatchStatus:\n".to_string() + pretty_print_dict(self.to_dict(py), 2)?.as_str())
+    }
+
+    pub fn to_dict<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        let key_vals: Vec<(&str, PyObject)> = vec![
+            ("vector_count", self.vector_count.to_object(py)),
+            ("upserted_count", self.upserted_count.to_object(py)),
+            ("error", self.error.to_object(py)),
+        ];
+        key_vals.into_py_dict(py)
+    }
+
+    /// Whether all of the batch's vectors were upserted.
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none() && self.upserted_count == self.vector_count
+    }
+
+    pub fn __getstate__<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        self.pickle_state(py)
+    }
+
+    pub fn __setstate__(&mut self, state: &PyDict) -> PyResult<()> {
+        *self = Self::from_pickle_state(state)?;
+        Ok(())
+    }
+
+    pub fn __reduce__(slf: &PyCell<Self>) -> PyResult<(PyObject, (&PyDict,))> {
+        reduce_pickled(slf)
+    }
+
+    #[classmethod]
+    fn _from_state(_cls: &PyType, state: &PyDict) -> PyResult<Self> {
+        Self::from_pickle_state(state)
+    }
 }
 
 #[pymethods]
@@ -93,93 +233,1116 @@ impl UpsertResponse {
         Ok("UpsertResponse:\n".to_string() + pretty_print_dict(self.to_dict(py), 2)?.as_str())
     }
 
-    pub fn to_dict<'a>(&self, py: Python<'a>) -> &'a PyDict {
-        let key_vals: Vec<(&str, PyObject)> =
-            vec![("upserted_count", self.upserted_count.to_object(py))];
-        key_vals.into_py_dict(py)
+    pub fn to_dict<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        let mut key_vals: Vec<(&str, PyObject)> =
+            vec![("upserted_count", self.upserted_count.to_object(py))];
+        if let Some(upserted_ids) = &self.upserted_ids {
+            key_vals.push(("upserted_ids", upserted_ids.to_object(py)));
+        }
+        if let Some(report) = &self.report {
+            key_vals.push(("report", report.to_dict(py).to_object(py)));
+        }
+        if let Some(batches) = &self.batches {
+            let batches: Vec<&PyDict> = batches.iter().map(|b| b.to_dict(py)).collect();
+            key_vals.push(("batches", batches.to_object(py)));
+        }
+        if !self.failed_ids.is_empty() {
+            key_vals.push(("failed_ids", self.failed_ids.to_object(py)));
+        }
+        if let Some(timing) = &self.timing {
+            key_vals.push(("timing", timing.to_dict(py).to_object(py)));
+        }
+        key_vals.into_py_dict(py)
+    }
+
+    pub fn __getstate__<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        self.pickle_state(py)
+    }
+
+    pub fn __setstate__(&mut self, state: &PyDict) -> PyResult<()> {
+        *self = Self::from_pickle_state(state)?;
+        Ok(())
+    }
+
+    pub fn __reduce__(slf: &PyCell<Self>) -> PyResult<(PyObject, (&PyDict,))> {
+        reduce_pickled(slf)
+    }
+
+    #[classmethod]
+    fn _from_state(_cls: &PyType, state: &PyDict) -> PyResult<Self> {
+        Self::from_pickle_state(state)
+    }
+}
+
+/// The response of an update operation.
+/// Currently empty, so fields the server adds in the future (e.g. usage) can be surfaced without breaking the API.
+#[derive(Debug, Default, Clone)]
+#[pyclass(module = "pinecone")]
+#[pyo3(get_all)]
+pub struct UpdateResponse {}
+
+#[pymethods]
+impl UpdateResponse {
+    pub fn __repr__(&self, py: Python) -> Result<String, PyErr> {
+        Ok("UpdateResponse:\n".to_string() + pretty_print_dict(self.to_dict(py), 2)?.as_str())
+    }
+
+    pub fn to_dict<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        PyDict::new(py)
+    }
+
+    pub fn __getstate__<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        self.pickle_state(py)
+    }
+
+    pub fn __setstate__(&mut self, state: &PyDict) -> PyResult<()> {
+        *self = Self::from_pickle_state(state)?;
+        Ok(())
+    }
+
+    pub fn __reduce__(slf: &PyCell<Self>) -> PyResult<(PyObject, (&PyDict,))> {
+        reduce_pickled(slf)
+    }
+
+    #[classmethod]
+    fn _from_state(_cls: &PyType, state: &PyDict) -> PyResult<Self> {
+        Self::from_pickle_state(state)
+    }
+}
+
+/// The response of a delete operation (by ids, by metadata filter or of a whole namespace).
+/// Currently empty, so fields the server adds in the future (e.g. usage) can be surfaced without breaking the API.
+#[derive(Debug, Default, Clone)]
+#[pyclass(module = "pinecone")]
+#[pyo3(get_all)]
+pub struct DeleteResponse {}
+
+#[pymethods]
+impl DeleteResponse {
+    pub fn __repr__(&self, py: Python) -> Result<String, PyErr> {
+        Ok("DeleteResponse:\n".to_string() + pretty_print_dict(self.to_dict(py), 2)?.as_str())
+    }
+
+    pub fn to_dict<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        PyDict::new(py)
+    }
+
+    pub fn __getstate__<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        self.pickle_state(py)
+    }
+
+    pub fn __setstate__(&mut self, state: &PyDict) -> PyResult<()> {
+        *self = Self::from_pickle_state(state)?;
+        Ok(())
+    }
+
+    pub fn __reduce__(slf: &PyCell<Self>) -> PyResult<(PyObject, (&PyDict,))> {
+        reduce_pickled(slf)
+    }
+
+    #[classmethod]
+    fn _from_state(_cls: &PyType, state: &PyDict) -> PyResult<Self> {
+        Self::from_pickle_state(state)
+    }
+}
+
+/// A summary of a batched upsert, meant for publishing ingestion metrics. All durations are in seconds.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+#[pyclass(module = "pinecone")]
+#[pyo3(get_all)]
+pub struct UpsertReport {
+    pub total_vectors: u64,
+    pub batch_count: u32,
+    /// End-to-end duration of the upsert
+    pub duration: f64,
+    /// Vectors upserted per second
+    pub throughput: f64,
+    /// Number of batches re-sent after the index reported it was overloaded
+    pub retries: u32,
+    pub latency_p50: f64,
+    pub latency_p90: f64,
+    pub latency_p99: f64,
+    pub latency_max: f64,
+}
+
+#[pymethods]
+impl UpsertReport {
+    pub fn __repr__(&self, py: Python) -> Result<String, PyErr> {
+        Ok("UpsertReport:\n".to_string() + pretty_print_dict(self.to_dict(py), 2)?.as_str())
+    }
+
+    pub fn to_dict<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        let key_vals: Vec<(&str, PyObject)> = vec![
+            ("total_vectors", self.total_vectors.to_object(py)),
+            ("batch_count", self.batch_count.to_object(py)),
+            ("duration", self.duration.to_object(py)),
+            ("throughput", self.throughput.to_object(py)),
+            ("retries", self.retries.to_object(py)),
+            ("latency_p50", self.latency_p50.to_object(py)),
+            ("latency_p90", self.latency_p90.to_object(py)),
+            ("latency_p99", self.latency_p99.to_object(py)),
+            ("latency_max", self.latency_max.to_object(py)),
+        ];
+        key_vals.into_py_dict(py)
+    }
+
+    /// Returns the report as a JSON string.
+    pub fn to_json(&self) -> String {
+        // Serializing a struct of plain numbers can't fail
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    pub fn __getstate__<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        self.pickle_state(py)
+    }
+
+    pub fn __setstate__(&mut self, state: &PyDict) -> PyResult<()> {
+        *self = Self::from_pickle_state(state)?;
+        Ok(())
+    }
+
+    pub fn __reduce__(slf: &PyCell<Self>) -> PyResult<(PyObject, (&PyDict,))> {
+        reduce_pickled(slf)
+    }
+
+    #[classmethod]
+    fn _from_state(_cls: &PyType, state: &PyDict) -> PyResult<Self> {
+        Self::from_pickle_state(state)
+    }
+}
+
+/// A snapshot of the data plane request counters of an index, see [`Index::client_stats`](crate::index::Index::client_stats).
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+#[pyclass(module = "pinecone")]
+#[pyo3(get_all)]
+pub struct ClientStats {
+    /// Number of requests sent, by operation (e.g. "upsert", "query")
+    pub requests: BTreeMap<String, u64>,
+    pub total_requests: u64,
+    /// Encoded size of the sent requests, in bytes
+    pub bytes_sent: u64,
+    /// Encoded size of the received responses, in bytes
+    pub bytes_received: u64,
+    /// Number of requests that failed
+    pub errors: u64,
+    /// Number of batches re-sent after the index reported it was overloaded
+    pub retries: u64,
+    /// Average request latency, in seconds
+    pub average_latency: f64,
+}
+
+#[pymethods]
+impl ClientStats {
+    pub fn __repr__(&self, py: Python) -> Result<String, PyErr> {
+        Ok("ClientStats:\n".to_string() + pretty_print_dict(self.to_dict(py), 2)?.as_str())
+    }
+
+    pub fn to_dict<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        let key_vals: Vec<(&str, PyObject)> = vec![
+            ("requests", self.requests.to_object(py)),
+            ("total_requests", self.total_requests.to_object(py)),
+            ("bytes_sent", self.bytes_sent.to_object(py)),
+            ("bytes_received", self.bytes_received.to_object(py)),
+            ("errors", self.errors.to_object(py)),
+            ("retries", self.retries.to_object(py)),
+            ("average_latency", self.average_latency.to_object(py)),
+        ];
+        key_vals.into_py_dict(py)
+    }
+
+    /// Returns the stats as a JSON string.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    pub fn __getstate__<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        self.pickle_state(py)
+    }
+
+    pub fn __setstate__(&mut self, state: &PyDict) -> PyResult<()> {
+        *self = Self::from_pickle_state(state)?;
+        Ok(())
+    }
+
+    pub fn __reduce__(slf: &PyCell<Self>) -> PyResult<(PyObject, (&PyDict,))> {
+        reduce_pickled(slf)
+    }
+
+    #[classmethod]
+    fn _from_state(_cls: &PyType, state: &PyDict) -> PyResult<Self> {
+        Self::from_pickle_state(state)
+    }
+}
+
+/// The requests of a single operation made by a client and its indexes, see
+/// [`PineconeClient::metrics`](crate::client::pinecone_client::PineconeClient::metrics). All latencies are in seconds.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+#[pyclass(module = "pinecone")]
+#[pyo3(get_all)]
+pub struct OperationMetrics {
+    /// Number of requests sent, counting every attempt of retried requests
+    pub requests: u64,
+    /// Number of requests that failed
+    pub errors: u64,
+    pub latency_mean: f64,
+    pub latency_p50: f64,
+    pub latency_p90: f64,
+    pub latency_p99: f64,
+    pub latency_max: f64,
+}
+
+#[pymethods]
+impl OperationMetrics {
+    pub fn __repr__(&self, py: Python) -> Result<String, PyErr> {
+        Ok("OperationMetrics:\n".to_string() + pretty_print_dict(self.to_dict(py), 2)?.as_str())
+    }
+
+    pub fn to_dict<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        let key_vals: Vec<(&str, PyObject)> = vec![
+            ("requests", self.requests.to_object(py)),
+            ("errors", self.errors.to_object(py)),
+            ("latency_mean", self.latency_mean.to_object(py)),
+            ("latency_p50", self.latency_p50.to_object(py)),
+            ("latency_p90", self.latency_p90.to_object(py)),
+            ("latency_p99", self.latency_p99.to_object(py)),
+            ("latency_max", self.latency_max.to_object(py)),
+        ];
+        key_vals.into_py_dict(py)
+    }
+
+    pub fn __getstate__<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        self.pickle_state(py)
+    }
+
+    pub fn __setstate__(&mut self, state: &PyDict) -> PyResult<()> {
+        *self = Self::from_pickle_state(state)?;
+        Ok(())
+    }
+
+    pub fn __reduce__(slf: &PyCell<Self>) -> PyResult<(PyObject, (&PyDict,))> {
+        reduce_pickled(slf)
+    }
+
+    #[classmethod]
+    fn _from_state(_cls: &PyType, state: &PyDict) -> PyResult<Self> {
+        Self::from_pickle_state(state)
+    }
+}
+
+/// The outcome of a successful [`Index::ping`](crate::index::Index::ping).
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+#[pyclass(module = "pinecone")]
+#[pyo3(get_all)]
+pub struct PingResult {
+    /// Round-trip time of the ping request, in seconds
+    pub latency: f64,
+    /// The connection state after the ping, see [`ConnectionState`](crate::client::grpc::ConnectionState)
+    pub connection_state: String,
+}
+
+#[pymethods]
+impl PingResult {
+    pub fn __repr__(&self, py: Python) -> Result<String, PyErr> {
+        Ok("PingResult:\n".to_string() + pretty_print_dict(self.to_dict(py), 2)?.as_str())
+    }
+
+    pub fn to_dict<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        let key_vals: Vec<(&str, PyObject)> = vec![
+            ("latency", self.latency.to_object(py)),
+            ("connection_state", self.connection_state.to_object(py)),
+        ];
+        key_vals.into_py_dict(py)
+    }
+
+    pub fn __getstate__<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        self.pickle_state(py)
+    }
+
+    pub fn __setstate__(&mut self, state: &PyDict) -> PyResult<()> {
+        *self = Self::from_pickle_state(state)?;
+        Ok(())
+    }
+
+    pub fn __reduce__(slf: &PyCell<Self>) -> PyResult<(PyObject, (&PyDict,))> {
+        reduce_pickled(slf)
+    }
+
+    #[classmethod]
+    fn _from_state(_cls: &PyType, state: &PyDict) -> PyResult<Self> {
+        Self::from_pickle_state(state)
+    }
+}
+
+/// The result of evaluating an index's recall against known nearest neighbors. All latencies are in seconds.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+#[pyclass(module = "pinecone")]
+#[pyo3(get_all)]
+pub struct RecallReport {
+    pub top_k: u32,
+    pub query_count: u32,
+    /// Mean recall@k over all queries
+    pub recall: f64,
+    /// Lowest recall@k of a single query
+    pub min_recall: f64,
+    pub latency_mean: f64,
+    pub latency_p50: f64,
+    pub latency_p90: f64,
+    pub latency_p99: f64,
+}
+
+#[pymethods]
+impl RecallReport {
+    pub fn __repr__(&self, py: Python) -> Result<String, PyErr> {
+        Ok("RecallReport:\n".to_string() + pretty_print_dict(self.to_dict(py), 2)?.as_str())
+    }
+
+    pub fn to_dict<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        let key_vals: Vec<(&str, PyObject)> = vec![
+            ("top_k", self.top_k.to_object(py)),
+            ("query_count", self.query_count.to_object(py)),
+            ("recall", self.recall.to_object(py)),
+            ("min_recall", self.min_recall.to_object(py)),
+            ("latency_mean", self.latency_mean.to_object(py)),
+            ("latency_p50", self.latency_p50.to_object(py)),
+            ("latency_p90", self.latency_p90.to_object(py)),
+            ("latency_p99", self.latency_p99.to_object(py)),
+        ];
+        key_vals.into_py_dict(py)
+    }
+
+    /// Returns the report as a JSON string.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    pub fn __getstate__<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        self.pickle_state(py)
+    }
+
+    pub fn __setstate__(&mut self, state: &PyDict) -> PyResult<()> {
+        *self = Self::from_pickle_state(state)?;
+        Ok(())
+    }
+
+    pub fn __reduce__(slf: &PyCell<Self>) -> PyResult<(PyObject, (&PyDict,))> {
+        reduce_pickled(slf)
+    }
+
+    #[classmethod]
+    fn _from_state(_cls: &PyType, state: &PyDict) -> PyResult<Self> {
+        Self::from_pickle_state(state)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[pyclass(module = "pinecone")]
+#[pyo3(get_all, mapping)]
+pub struct QueryResult {
+    pub id: String,
+    pub score: f32,
+    pub values: Option<Vec<f32>>,
+    pub sparse_values: Option<SparseValues>,
+    pub metadata: Option<BTreeMap<String, MetadataValue>>,
+    /// The namespace the result was found in. Only set by queries spanning several namespaces
+    pub namespace: Option<String>,
+}
+
+#[pymethods]
+impl QueryResult {
+    pub fn __repr__(&self, py: Python) -> Result<String, PyErr> {
+        Ok("QueryResult:\n".to_string() + pretty_print_dict(self.to_dict(py), 2)?.as_str())
+    }
+
+    pub fn to_dict<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        let mut key_vals: Vec<(&str, PyObject)> = vec![
+            ("id", self.id.to_object(py)),
+            ("score", self.score.to_object(py)),
+            ("values", self.values.to_object(py)),
+            ("sparse_values", self.sparse_values.to_object(py)),
+            ("metadata", self.metadata.to_object(py)),
+        ];
+        if let Some(namespace) = &self.namespace {
+            key_vals.push(("namespace", namespace.to_object(py)));
+        }
+        key_vals.into_py_dict(py)
+    }
+
+    // Mapping protocol, so the object can be used like the dict returned by `to_dict()`
+
+    pub fn keys<'a>(&self, py: Python<'a>) -> &'a PyList {
+        self.to_dict(py).keys()
+    }
+
+    pub fn values<'a>(&self, py: Python<'a>) -> &'a PyList {
+        self.to_dict(py).values()
+    }
+
+    pub fn items<'a>(&self, py: Python<'a>) -> &'a PyList {
+        self.to_dict(py).items()
+    }
+
+    pub fn __getitem__<'a>(&self, py: Python<'a>, key: &str) -> PyResult<&'a PyAny> {
+        mapping_getitem(self.to_dict(py), key)
+    }
+
+    #[pyo3(signature = (key, default=None))]
+    pub fn get(&self, py: Python, key: &str, default: Option<PyObject>) -> PyObject {
+        mapping_get(py, self.to_dict(py), key, default)
+    }
+
+    pub fn __contains__(&self, py: Python, key: &str) -> bool {
+        self.to_dict(py).contains(key).unwrap_or(false)
+    }
+
+    pub fn __len__(&self, py: Python) -> usize {
+        self.to_dict(py).len()
+    }
+
+    pub fn __iter__<'a>(&self, py: Python<'a>) -> PyResult<&'a PyIterator> {
+        PyIterator::from_object(py, self.to_dict(py).keys())
+    }
+
+    pub fn __getstate__<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        self.pickle_state(py)
+    }
+
+    pub fn __setstate__(&mut self, state: &PyDict) -> PyResult<()> {
+        *self = Self::from_pickle_state(state)?;
+        Ok(())
+    }
+
+    pub fn __reduce__(slf: &PyCell<Self>) -> PyResult<(PyObject, (&PyDict,))> {
+        reduce_pickled(slf)
+    }
+
+    #[classmethod]
+    fn _from_state(_cls: &PyType, state: &PyDict) -> PyResult<Self> {
+        Self::from_pickle_state(state)
+    }
+}
+
+/// The results of a query, most similar first, along with the query's client-side latency breakdown.
+#[derive(Debug, Clone, Default)]
+#[pyclass(module = "pinecone")]
+#[pyo3(get_all, sequence)]
+pub struct QueryResponse {
+    pub results: Vec<QueryResult>,
+    /// Client-side latency breakdown of the query, or None if it wasn't measured
+    pub timing: Option<LatencyBreakdown>,
+}
+
+#[pymethods]
+impl QueryResponse {
+    pub fn __repr__(&self, py: Python) -> Result<String, PyErr> {
+        Ok("QueryResponse:\n".to_string() + pretty_print_dict(self.to_dict(py), 2)?.as_str())
+    }
+
+    pub fn to_dict<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        let results: Vec<&PyDict> = self.results.iter().map(|r| r.to_dict(py)).collect();
+        let mut key_vals: Vec<(&str, PyObject)> = vec![("results", results.to_object(py))];
+        if let Some(timing) = &self.timing {
+            key_vals.push(("timing", timing.to_dict(py).to_object(py)));
+        }
+        key_vals.into_py_dict(py)
+    }
+
+    // Sequence protocol, so the response can be used like the list of its results
+
+    pub fn __len__(&self) -> usize {
+        self.results.len()
+    }
+
+    pub fn __getitem__(&self, index: isize) -> PyResult<QueryResult> {
+        let len = self.results.len() as isize;
+        let position = if index < 0 { index + len } else { index };
+        if !(0..len).contains(&position) {
+            return Err(PyIndexError::new_err("query result index out of range"));
+        }
+        Ok(self.results[position as usize].clone())
+    }
+
+    pub fn __iter__<'a>(&self, py: Python<'a>) -> PyResult<&'a PyIterator> {
+        PyIterator::from_object(py, self.results.clone().into_py(py).into_ref(py))
+    }
+
+    pub fn __getstate__<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        self.pickle_state(py)
+    }
+
+    pub fn __setstate__(&mut self, state: &PyDict) -> PyResult<()> {
+        *self = Self::from_pickle_state(state)?;
+        Ok(())
+    }
+
+    pub fn __reduce__(slf: &PyCell<Self>) -> PyResult<(PyObject, (&PyDict,))> {
+        reduce_pickled(slf)
+    }
+
+    #[classmethod]
+    fn _from_state(_cls: &PyType, state: &PyDict) -> PyResult<Self> {
+        Self::from_pickle_state(state)
+    }
+}
+
+/// The fetched vectors by id, along with the fetch's client-side latency breakdown.
+/// Ids that weren't found are missing from `vectors`.
+#[derive(Debug, Clone, Default)]
+#[pyclass(module = "pinecone")]
+#[pyo3(get_all, mapping)]
+pub struct FetchResponse {
+    pub vectors: HashMap<String, Vector>,
+    /// Client-side latency breakdown of the fetch, or None if it wasn't measured
+    pub timing: Option<LatencyBreakdown>,
+}
+
+#[pymethods]
+impl FetchResponse {
+    pub fn __repr__(&self, py: Python) -> Result<String, PyErr> {
+        Ok("FetchResponse:\n".to_string() + pretty_print_dict(self.to_dict(py), 2)?.as_str())
+    }
+
+    pub fn to_dict<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        let vectors: Vec<(&str, &PyDict)> = self
+            .vectors
+            .iter()
+            .map(|(id, vector)| (id.as_str(), vector.to_dict(py)))
+            .collect();
+        let mut key_vals: Vec<(&str, PyObject)> =
+            vec![("vectors", vectors.into_py_dict(py).to_object(py))];
+        if let Some(timing) = &self.timing {
+            key_vals.push(("timing", timing.to_dict(py).to_object(py)));
+        }
+        key_vals.into_py_dict(py)
+    }
+
+    // Mapping protocol, so the response can be used like the dict of its vectors
+
+    pub fn keys(&self) -> Vec<String> {
+        self.vectors.keys().cloned().collect()
+    }
+
+    pub fn values(&self) -> Vec<Vector> {
+        self.vectors.values().cloned().collect()
+    }
+
+    pub fn items(&self) -> Vec<(String, Vector)> {
+        self.vectors.clone().into_iter().collect()
+    }
+
+    pub fn __getitem__(&self, id: &str) -> PyResult<Vector> {
+        self.vectors
+            .get(id)
+            .cloned()
+            .ok_or_else(|| PyKeyError::new_err(id.to_string()))
+    }
+
+    #[pyo3(signature = (id, default=None))]
+    pub fn get(&self, py: Python, id: &str, default: Option<PyObject>) -> PyObject {
+        match self.vectors.get(id) {
+            Some(vector) => vector.clone().into_py(py),
+            None => default.unwrap_or_else(|| py.None()),
+        }
+    }
+
+    pub fn __contains__(&self, id: &str) -> bool {
+        self.vectors.contains_key(id)
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.vectors.len()
+    }
+
+    pub fn __iter__<'a>(&self, py: Python<'a>) -> PyResult<&'a PyIterator> {
+        PyIterator::from_object(py, PyList::new(py, self.vectors.keys()))
+    }
+
+    pub fn __getstate__<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        self.pickle_state(py)
+    }
+
+    pub fn __setstate__(&mut self, state: &PyDict) -> PyResult<()> {
+        *self = Self::from_pickle_state(state)?;
+        Ok(())
+    }
+
+    pub fn __reduce__(slf: &PyCell<Self>) -> PyResult<(PyObject, (&PyDict,))> {
+        reduce_pickled(slf)
+    }
+
+    #[classmethod]
+    fn _from_state(_cls: &PyType, state: &PyDict) -> PyResult<Self> {
+        Self::from_pickle_state(state)
+    }
+}
+
+/// Query or fetch results in columnar form, built in one pass over the results.
+/// Each column holds one entry per result, in the order of the results.
+#[derive(Debug)]
+#[pyclass(module = "pinecone")]
+#[pyo3(get_all)]
+pub struct ColumnarResult {
+    /// The ids of the results, as a list of str.
+    pub ids: Py<PyList>,
+    /// The scores of query results as a float32 numpy array, or None for fetch results.
+    pub scores: Option<PyObject>,
+    /// The dense values of the results as a 2D float32 numpy array (one row per result), or None if values weren't requested.
+    pub values: Option<PyObject>,
+    /// The sparse values of the results as a list of `SparseValues` (or None for results without sparse values),
+    /// or None if none of the results has sparse values.
+    pub sparse_values: Option<Py<PyList>>,
+    /// The metadata of the results as a list of dicts (or None for results without metadata), or None if metadata wasn't requested.
+    pub metadata: Option<Py<PyList>>,
+    /// Client-side latency breakdown of the query or fetch, including building the columns, or None if it wasn't measured.
+    pub timing: Option<LatencyBreakdown>,
+}
+
+#[pymethods]
+impl ColumnarResult {
+    pub fn __repr__(&self, py: Python) -> Result<String, PyErr> {
+        Ok("ColumnarResult:\n".to_string() + pretty_print_dict(self.to_dict(py), 2)?.as_str())
+    }
+
+    pub fn to_dict<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        let key_vals: Vec<(&str, PyObject)> = vec![
+            ("ids", self.ids.to_object(py)),
+            ("scores", self.scores.to_object(py)),
+            ("values", self.values.to_object(py)),
+            ("sparse_values", self.sparse_values.to_object(py)),
+            ("metadata", self.metadata.to_object(py)),
+            (
+                "timing",
+                self.timing.as_ref().map(|t| t.to_dict(py)).to_object(py),
+            ),
+        ];
+        key_vals.into_py_dict(py)
+    }
+
+    pub fn __len__(&self, py: Python) -> usize {
+        self.ids.as_ref(py).len()
+    }
+
+    pub fn __getstate__<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        self.pickle_state(py)
+    }
+
+    pub fn __setstate__(&mut self, state: &PyDict) -> PyResult<()> {
+        *self = Self::from_pickle_state(state)?;
+        Ok(())
+    }
+
+    pub fn __reduce__(slf: &PyCell<Self>) -> PyResult<(PyObject, (&PyDict,))> {
+        reduce_pickled(slf)
+    }
+
+    #[classmethod]
+    fn _from_state(_cls: &PyType, state: &PyDict) -> PyResult<Self> {
+        Self::from_pickle_state(state)
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct WhoamiResponse {
+    pub project_name: String,
+    pub user_label: String,
+    pub user_name: String,
+}
+
+/// A backup of an index, from which new indexes can be created.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[pyclass(module = "pinecone")]
+#[pyo3(get_all)]
+pub struct Backup {
+    pub backup_id: String,
+    pub source_index_name: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub dimension: Option<i32>,
+    #[serde(default)]
+    pub metric: Option<String>,
+    #[serde(default)]
+    pub record_count: Option<i64>,
+    #[serde(default)]
+    pub namespace_count: Option<i32>,
+    #[serde(default)]
+    pub size_bytes: Option<i64>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+}
+
+#[pymethods]
+impl Backup {
+    pub fn __repr__(&self, py: Python) -> Result<String, PyErr> {
+        Ok("Backup:\n".to_string() + pretty_print_dict(self.to_dict(py), 2)?.as_str())
+    }
+
+    pub fn to_dict<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        let key_vals: Vec<(&str, PyObject)> = vec![
+            ("backup_id", self.backup_id.to_object(py)),
+            ("source_index_name", self.source_index_name.to_object(py)),
+            ("name", self.name.to_object(py)),
+            ("description", self.description.to_object(py)),
+            ("status", self.status.to_object(py)),
+            ("dimension", self.dimension.to_object(py)),
+            ("metric", self.metric.to_object(py)),
+            ("record_count", self.record_count.to_object(py)),
+            ("namespace_count", self.namespace_count.to_object(py)),
+            ("size_bytes", self.size_bytes.to_object(py)),
+            ("created_at", self.created_at.to_object(py)),
+        ];
+        key_vals.into_py_dict(py)
+    }
+
+    pub fn __getstate__<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        self.pickle_state(py)
+    }
+
+    pub fn __setstate__(&mut self, state: &PyDict) -> PyResult<()> {
+        *self = Self::from_pickle_state(state)?;
+        Ok(())
+    }
+
+    pub fn __reduce__(slf: &PyCell<Self>) -> PyResult<(PyObject, (&PyDict,))> {
+        reduce_pickled(slf)
+    }
+
+    #[classmethod]
+    fn _from_state(_cls: &PyType, state: &PyDict) -> PyResult<Self> {
+        Self::from_pickle_state(state)
+    }
+}
+
+/// A project of an organization, as returned by the admin API.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct Project {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub max_pods: Option<i32>,
+    #[serde(default)]
+    pub organization_id: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[pyclass(module = "pinecone")]
+#[pyo3(get_all)]
+pub struct NamespaceStats {
+    pub vector_count: u32,
+}
+
+#[pymethods]
+impl NamespaceStats {
+    pub fn to_dict<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        let key_vals: Vec<(&str, PyObject)> =
+            vec![("vector_count", self.vector_count.to_object(py))];
+        key_vals.into_py_dict(py)
+    }
+
+    pub fn __getstate__<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        self.pickle_state(py)
+    }
+
+    pub fn __setstate__(&mut self, state: &PyDict) -> PyResult<()> {
+        *self = Self::from_pickle_state(state)?;
+        Ok(())
+    }
+
+    pub fn __reduce__(slf: &PyCell<Self>) -> PyResult<(PyObject, (&PyDict,))> {
+        reduce_pickled(slf)
+    }
+
+    #[classmethod]
+    fn _from_state(_cls: &PyType, state: &PyDict) -> PyResult<Self> {
+        Self::from_pickle_state(state)
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[pyclass(module = "pinecone")]
+#[pyo3(get_all)]
+pub struct IndexStats {
+    pub namespaces: HashMap<String, NamespaceStats>,
+    pub dimension: u32,
+    pub index_fullness: f32,
+    pub total_vector_count: u32,
+}
+
+#[pymethods]
+impl IndexStats {
+    pub fn __repr__(&self, py: Python) -> Result<String, PyErr> {
+        Ok("Index statistics:\n".to_string() + pretty_print_dict(self.to_dict(py), 2)?.as_str())
+    }
+
+    pub fn to_dict<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        let key_vals: Vec<(&str, PyObject)> = vec![
+            ("namespaces", self.namespaces.to_object(py)),
+            ("dimension", self.dimension.to_object(py)),
+            ("index_fullness", self.index_fullness.to_object(py)),
+            ("total_vector_count", self.total_vector_count.to_object(py)),
+        ];
+        key_vals.into_py_dict(py)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    pub fn __getstate__<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        self.pickle_state(py)
+    }
+
+    pub fn __setstate__(&mut self, state: &PyDict) -> PyResult<()> {
+        *self = Self::from_pickle_state(state)?;
+        Ok(())
+    }
+
+    pub fn __reduce__(slf: &PyCell<Self>) -> PyResult<(PyObject, (&PyDict,))> {
+        reduce_pickled(slf)
+    }
+
+    #[classmethod]
+    fn _from_state(_cls: &PyType, state: &PyDict) -> PyResult<Self> {
+        Self::from_pickle_state(state)
+    }
+}
+
+/// Client-side latency breakdown of a single operation, in seconds.
+/// When an operation is split into multiple requests, the per-phase timings are summed across all requests,
+/// so they may add up to more than `total` if the requests ran concurrently.
+#[derive(Debug, Default, Clone)]
+#[pyclass(module = "pinecone")]
+#[pyo3(get_all)]
+pub struct LatencyBreakdown {
+    pub operation: String,
+    /// Converting the operation's input from Python objects
+    pub input_conversion: f64,
+    /// Building the gRPC request messages
+    pub serialization: f64,
+    /// Sending the requests and waiting for the responses
+    pub network: f64,
+    /// Converting the gRPC responses
+    pub deserialization: f64,
+    /// Converting the operation's result into Python objects
+    pub python_conversion: f64,
+    /// End-to-end duration of the operation
+    pub total: f64,
+}
+
+#[pymethods]
+impl LatencyBreakdown {
+    pub fn __repr__(&self, py: Python) -> Result<String, PyErr> {
+        Ok("LatencyBreakdown:\n".to_string() + pretty_print_dict(self.to_dict(py), 2)?.as_str())
+    }
+
+    pub fn to_dict<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        let key_vals: Vec<(&str, PyObject)> = vec![
+            ("operation", self.operation.to_object(py)),
+            ("input_conversion", self.input_conversion.to_object(py)),
+            ("serialization", self.serialization.to_object(py)),
+            ("network", self.network.to_object(py)),
+            ("deserialization", self.deserialization.to_object(py)),
+            ("python_conversion", self.python_conversion.to_object(py)),
+            ("total", self.total.to_object(py)),
+        ];
+        key_vals.into_py_dict(py)
+    }
+
+    pub fn __getstate__<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        self.pickle_state(py)
+    }
+
+    pub fn __setstate__(&mut self, state: &PyDict) -> PyResult<()> {
+        *self = Self::from_pickle_state(state)?;
+        Ok(())
+    }
+
+    pub fn __reduce__(slf: &PyCell<Self>) -> PyResult<(PyObject, (&PyDict,))> {
+        reduce_pickled(slf)
+    }
+
+    #[classmethod]
+    fn _from_state(_cls: &PyType, state: &PyDict) -> PyResult<Self> {
+        Self::from_pickle_state(state)
     }
 }
 
-#[derive(Debug)]
-#[pyclass]
-#[pyo3(get_all, mapping)]
-pub struct QueryResult {
-    pub id: String,
-    pub score: f32,
-    pub values: Option<Vec<f32>>,
-    pub sparse_values: Option<SparseValues>,
-    pub metadata: Option<BTreeMap<String, MetadataValue>>,
+/// The status of an index being created, passed to the progress callback of
+/// [`PineconeClient::create_index`](crate::client::pinecone_client::PineconeClient::create_index).
+#[derive(Debug, Default, Clone, PartialEq)]
+#[pyclass(module = "pinecone")]
+#[pyo3(get_all)]
+pub struct IndexProgress {
+    pub index: String,
+    /// The index's status, e.g. "Initializing"
+    pub status: String,
+    /// Time since the wait started, in seconds
+    pub elapsed: f64,
+    pub ready: bool,
 }
 
 #[pymethods]
-impl QueryResult {
+impl IndexProgress {
     pub fn __repr__(&self, py: Python) -> Result<String, PyErr> {
-        Ok("QueryResult:\n".to_string() + pretty_print_dict(self.to_dict(py), 2)?.as_str())
+        Ok("IndexProgress:\n".to_string() + pretty_print_dict(self.to_dict(py), 2)?.as_str())
     }
 
     pub fn to_dict<'a>(&self, py: Python<'a>) -> &'a PyDict {
         let key_vals: Vec<(&str, PyObject)> = vec![
-            ("id", self.id.to_object(py)),
-            ("score", self.score.to_object(py)),
-            ("values", self.values.to_object(py)),
-            ("sparse_values", self.sparse_values.to_object(py)),
-            ("metadata", self.metadata.to_object(py)),
+            ("index", self.index.to_object(py)),
+            ("status", self.status.to_object(py)),
+            ("elapsed", self.elapsed.to_object(py)),
+            ("ready", self.ready.to_object(py)),
         ];
         key_vals.into_py_dict(py)
     }
-}
 
-#[derive(Deserialize, Debug)]
-pub struct WhoamiResponse {
-    pub project_name: String,
-    pub user_label: String,
-    pub user_name: String,
+    pub fn __getstate__<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        self.pickle_state(py)
+    }
+
+    pub fn __setstate__(&mut self, state: &PyDict) -> PyResult<()> {
+        *self = Self::from_pickle_state(state)?;
+        Ok(())
+    }
+
+    pub fn __reduce__(slf: &PyCell<Self>) -> PyResult<(PyObject, (&PyDict,))> {
+        reduce_pickled(slf)
+    }
+
+    #[classmethod]
+    fn _from_state(_cls: &PyType, state: &PyDict) -> PyResult<Self> {
+        Self::from_pickle_state(state)
+    }
 }
 
-#[derive(Deserialize, Debug, Clone)]
-#[pyclass]
+/// A payload sent or received by a dumped call, see [`payload_dump`](crate::utils::payload_dump).
+#[derive(Debug, Default, Clone, PartialEq)]
+#[pyclass(module = "pinecone")]
 #[pyo3(get_all)]
-pub struct NamespaceStats {
-    pub vector_count: u32,
+pub struct PayloadDump {
+    pub operation: String,
+    /// `request` or `response`
+    pub direction: String,
+    /// The URL of the index or controller the request was sent to
+    pub target: String,
+    pub payload: String,
 }
 
 #[pymethods]
-impl NamespaceStats {
+impl PayloadDump {
+    pub fn __repr__(&self, py: Python) -> Result<String, PyErr> {
+        Ok("PayloadDump:\n".to_string() + pretty_print_dict(self.to_dict(py), 2)?.as_str())
+    }
+
     pub fn to_dict<'a>(&self, py: Python<'a>) -> &'a PyDict {
-        let key_vals: Vec<(&str, PyObject)> =
-            vec![("vector_count", self.vector_count.to_object(py))];
+        let key_vals: Vec<(&str, PyObject)> = vec![
+            ("operation", self.operation.to_object(py)),
+            ("direction", self.direction.to_object(py)),
+            ("target", self.target.to_object(py)),
+            ("payload", self.payload.to_object(py)),
+        ];
         key_vals.into_py_dict(py)
     }
+
+    pub fn __getstate__<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        self.pickle_state(py)
+    }
+
+    pub fn __setstate__(&mut self, state: &PyDict) -> PyResult<()> {
+        *self = Self::from_pickle_state(state)?;
+        Ok(())
+    }
+
+    pub fn __reduce__(slf: &PyCell<Self>) -> PyResult<(PyObject, (&PyDict,))> {
+        reduce_pickled(slf)
+    }
+
+    #[classmethod]
+    fn _from_state(_cls: &PyType, state: &PyDict) -> PyResult<Self> {
+        Self::from_pickle_state(state)
+    }
 }
 
-#[derive(Deserialize, Debug)]
-#[pyclass]
+/// A record of a mutating operation (e.g. upsert, delete or create_index), passed to the audit hook.
+#[derive(Debug, Default, Clone)]
+#[pyclass(module = "pinecone")]
 #[pyo3(get_all)]
-pub struct IndexStats {
-    pub namespaces: HashMap<String, NamespaceStats>,
-    pub dimension: u32,
-    pub index_fullness: f32,
-    pub total_vector_count: u32,
+pub struct AuditEvent {
+    pub operation: String,
+    pub index: String,
+    /// The namespace the operation applied to, or None for operations on whole indexes
+    pub namespace: Option<String>,
+    /// The number of vectors the operation applied to, if known
+    pub count: Option<u64>,
+    /// Caller-supplied context, e.g. the user or job on whose behalf the operation was made
+    pub context: BTreeMap<String, String>,
+    pub success: bool,
+    /// The error the operation failed with, if any
+    pub error: Option<String>,
+    /// The time the operation completed at, in seconds since the Unix epoch
+    pub timestamp: f64,
 }
 
 #[pymethods]
-impl IndexStats {
+impl AuditEvent {
     pub fn __repr__(&self, py: Python) -> Result<String, PyErr> {
-        Ok("Index statistics:\n".to_string() + pretty_print_dict(self.to_dict(py), 2)?.as_str())
+        Ok("AuditEvent:\n".to_string() + pretty_print_dict(self.to_dict(py), 2)?.as_str())
     }
 
     pub fn to_dict<'a>(&self, py: Python<'a>) -> &'a PyDict {
         let key_vals: Vec<(&str, PyObject)> = vec![
-            ("namespaces", self.namespaces.to_object(py)),
-            ("dimension", self.dimension.to_object(py)),
-            ("index_fullness", self.index_fullness.to_object(py)),
-            ("total_vector_count", self.total_vector_count.to_object(py)),
+            ("operation", self.operation.to_object(py)),
+            ("index", self.index.to_object(py)),
+            ("namespace", self.namespace.to_object(py)),
+            ("count", self.count.to_object(py)),
+            ("context", self.context.to_object(py)),
+            ("success", self.success.to_object(py)),
+            ("error", self.error.to_object(py)),
+            ("timestamp", self.timestamp.to_object(py)),
         ];
         key_vals.into_py_dict(py)
     }
+
+    pub fn __getstate__<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        self.pickle_state(py)
+    }
+
+    pub fn __setstate__(&mut self, state: &PyDict) -> PyResult<()> {
+        *self = Self::from_pickle_state(state)?;
+        Ok(())
+    }
+
+    pub fn __reduce__(slf: &PyCell<Self>) -> PyResult<(PyObject, (&PyDict,))> {
+        reduce_pickled(slf)
+    }
+
+    #[classmethod]
+    fn _from_state(_cls: &PyType, state: &PyDict) -> PyResult<Self> {
+        Self::from_pickle_state(state)
+    }
+}
+
+/// A single query of [`Index::query_batch()`](crate::index::Index::query_batch).
+#[derive(Debug, Clone)]
+pub enum QueryInput {
+    /// Query by the id of a vector already upserted to the namespace
+    Id(String),
+    /// Query by a new vector's dense values, and optionally its sparse values
+    Values {
+        values: Vec<f32>,
+        sparse_values: Option<SparseValues>,
+    },
 }
 
-#[derive(FromPyObject, Debug, Clone)]
+/// Serialized as the plain JSON value, e.g. `"drama"` or `2020`.
+#[derive(FromPyObject, Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
 pub enum MetadataValue {
     StringVal(String),
     BoolVal(bool),
@@ -188,31 +1351,428 @@ pub enum MetadataValue {
     DictVal(BTreeMap<String, MetadataValue>),
 }
 
-#[derive(Derivative, Default, Debug, Clone)]
-#[pyclass]
+impl MetadataValue {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            MetadataValue::StringVal(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            MetadataValue::NumberVal(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            MetadataValue::BoolVal(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[MetadataValue]> {
+        match self {
+            MetadataValue::ListVal(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as a list of strings, or None if it isn't a list or any of its items isn't a string.
+    pub fn as_list_str(&self) -> Option<Vec<&str>> {
+        self.as_list()?.iter().map(MetadataValue::as_str).collect()
+    }
+
+    pub fn as_dict(&self) -> Option<&BTreeMap<String, MetadataValue>> {
+        match self {
+            MetadataValue::DictVal(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+/// Typed accessors for a metadata map. Each accessor returns None if the key is missing or holds a value of another type.
+pub trait MetadataMapExt {
+    fn get_str(&self, key: &str) -> Option<&str>;
+    fn get_f64(&self, key: &str) -> Option<f64>;
+    fn get_bool(&self, key: &str) -> Option<bool>;
+    fn get_list_str(&self, key: &str) -> Option<Vec<&str>>;
+}
+
+impl MetadataMapExt for BTreeMap<String, MetadataValue> {
+    fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key)?.as_str()
+    }
+
+    fn get_f64(&self, key: &str) -> Option<f64> {
+        self.get(key)?.as_f64()
+    }
+
+    fn get_bool(&self, key: &str) -> Option<bool> {
+        self.get(key)?.as_bool()
+    }
+
+    fn get_list_str(&self, key: &str) -> Option<Vec<&str>> {
+        self.get(key)?.as_list_str()
+    }
+}
+
+/// Typed accessors for the metadata of a vector. Each accessor returns None if the vector has no metadata,
+/// or if the key is missing or holds a value of another type.
+impl MetadataMapExt for Option<BTreeMap<String, MetadataValue>> {
+    fn get_str(&self, key: &str) -> Option<&str> {
+        self.as_ref()?.get_str(key)
+    }
+
+    fn get_f64(&self, key: &str) -> Option<f64> {
+        self.as_ref()?.get_f64(key)
+    }
+
+    fn get_bool(&self, key: &str) -> Option<bool> {
+        self.as_ref()?.get_bool(key)
+    }
+
+    fn get_list_str(&self, key: &str) -> Option<Vec<&str>> {
+        self.as_ref()?.get_list_str(key)
+    }
+}
+
+macro_rules! impl_metadata_accessors {
+    ($($ty:ty),*) => {
+        $(
+            impl MetadataMapExt for $ty {
+                fn get_str(&self, key: &str) -> Option<&str> {
+                    self.metadata.get_str(key)
+                }
+
+                fn get_f64(&self, key: &str) -> Option<f64> {
+                    self.metadata.get_f64(key)
+                }
+
+                fn get_bool(&self, key: &str) -> Option<bool> {
+                    self.metadata.get_bool(key)
+                }
+
+                fn get_list_str(&self, key: &str) -> Option<Vec<&str>> {
+                    self.metadata.get_list_str(key)
+                }
+            }
+        )*
+    };
+}
+
+impl_metadata_accessors!(Vector, QueryResult);
+
+/// The similarity metric of an index. Converted to and from its name (e.g. `'cosine'`) when passed to and from Python.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Metric {
+    #[default]
+    Cosine,
+    Euclidean,
+    DotProduct,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 3] = [Metric::Cosine, Metric::Euclidean, Metric::DotProduct];
+
+    /// The metric's name in the control plane API.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Metric::Cosine => "cosine",
+            Metric::Euclidean => "euclidean",
+            Metric::DotProduct => "dotproduct",
+        }
+    }
+}
+
+impl fmt::Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Metric {
+    type Err = PineconeClientError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Metric::ALL
+            .into_iter()
+            .find(|metric| metric.as_str() == s)
+            .ok_or_else(|| {
+                PineconeClientError::ValueError(format!(
+                    "Invalid metric '{s}'. Expected one of {}",
+                    Metric::ALL.map(|metric| metric.as_str()).join(", ")
+                ))
+            })
+    }
+}
+
+/// The size of a pod, which multiplies the capacity of its [`PodType`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PodSize {
+    #[default]
+    X1,
+    X2,
+    X4,
+    X8,
+}
+
+impl PodSize {
+    pub const ALL: [PodSize; 4] = [PodSize::X1, PodSize::X2, PodSize::X4, PodSize::X8];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PodSize::X1 => "x1",
+            PodSize::X2 => "x2",
+            PodSize::X4 => "x4",
+            PodSize::X8 => "x8",
+        }
+    }
+}
+
+/// The type of the pods of a pod-based index, e.g. `PodType::S1(PodSize::X2)` for `'s1.x2'`.
+/// Converted to and from its name when passed to and from Python.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PodType {
+    /// Storage-optimized pods.
+    S1(PodSize),
+    /// Performance-optimized pods.
+    P1(PodSize),
+    /// Pods optimized for the highest throughput.
+    P2(PodSize),
+}
+
+impl Default for PodType {
+    fn default() -> Self {
+        PodType::P1(PodSize::X1)
+    }
+}
+
+impl PodType {
+    const FAMILIES: [&'static str; 3] = ["s1", "p1", "p2"];
+
+    pub fn size(&self) -> PodSize {
+        match self {
+            PodType::S1(size) | PodType::P1(size) | PodType::P2(size) => *size,
+        }
+    }
+
+    fn family(&self) -> &'static str {
+        match self {
+            PodType::S1(_) => "s1",
+            PodType::P1(_) => "p1",
+            PodType::P2(_) => "p2",
+        }
+    }
+}
+
+impl fmt::Display for PodType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.family(), self.size().as_str())
+    }
+}
+
+impl FromStr for PodType {
+    type Err = PineconeClientError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let pod_type = s.split_once('.').and_then(|(family, size)| {
+            let size = PodSize::ALL.into_iter().find(|s| s.as_str() == size)?;
+            match family {
+                "s1" => Some(PodType::S1(size)),
+                "p1" => Some(PodType::P1(size)),
+                "p2" => Some(PodType::P2(size)),
+                _ => None,
+            }
+        });
+        pod_type.ok_or_else(|| {
+            PineconeClientError::ValueError(format!(
+                "Invalid pod_type '{s}'. Expected one of {} followed by '.' and one of {}, e.g. 's1.x2'",
+                PodType::FAMILIES.join(", "),
+                PodSize::ALL.map(|size| size.as_str()).join(", ")
+            ))
+        })
+    }
+}
+
+// Index settings are serialized by name, like in the control plane API
+macro_rules! impl_serde_by_name {
+    ($($ty:ident),*) => {$(
+        impl Serialize for $ty {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                String::deserialize(deserializer)?
+                    .parse()
+                    .map_err(serde::de::Error::custom)
+            }
+        }
+    )*};
+}
+
+impl_serde_by_name!(Metric, PodType);
+
+#[derive(Derivative, Default, Debug, Clone, Serialize, Deserialize)]
+#[pyclass(module = "pinecone")]
 #[pyo3(get_all, mapping)]
 pub struct Db {
     pub name: String,
     pub dimension: i32,
-    pub metric: Option<String>,
+    pub metric: Option<Metric>,
     pub replicas: Option<i32>,
     pub shards: Option<i32>,
     pub pods: Option<i32>,
     pub source_collection: Option<String>,
     pub metadata_config: Option<BTreeMap<String, Vec<String>>>,
-    pub pod_type: Option<String>,
+    pub pod_type: Option<PodType>,
     pub status: Option<String>,
+    /// Fields of the controller's response that this version of the client doesn't know about yet
+    #[serde(default)]
+    pub extra: ExtraFields,
 }
 
-#[derive(Derivative, Default, Debug, Clone)]
-#[pyclass]
+#[derive(Derivative, Default, Debug, Clone, Serialize, Deserialize)]
+#[pyclass(module = "pinecone")]
 #[pyo3(get_all, mapping)]
 pub struct Collection {
     pub name: String,
     pub source: String,
     pub vector_count: Option<i32>,
     pub size: Option<i32>,
+    pub dimension: Option<i32>,
     pub status: Option<String>,
+    /// Fields of the controller's response that this version of the client doesn't know about yet
+    #[serde(default)]
+    pub extra: ExtraFields,
+}
+
+impl Db {
+    /// Returns a builder for the settings of a new index, to pass to
+    /// [`PineconeClient::create_index`](crate::client::pinecone_client::PineconeClient::create_index).
+    pub fn builder(name: impl Into<String>) -> CreateIndexBuilder {
+        CreateIndexBuilder {
+            db: Db {
+                name: name.into(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Builds the settings of a new index, see [`Db::builder`].
+/// The combination of settings is validated by [`build()`](Self::build), instead of being rejected by the control plane.
+#[derive(Debug, Clone)]
+pub struct CreateIndexBuilder {
+    db: Db,
+}
+
+impl CreateIndexBuilder {
+    /// Required, unless the index is created from a source collection, whose dimension it then takes.
+    pub fn dimension(mut self, dimension: i32) -> Self {
+        self.db.dimension = dimension;
+        self
+    }
+
+    pub fn metric(mut self, metric: Metric) -> Self {
+        self.db.metric = Some(metric);
+        self
+    }
+
+    pub fn pod_type(mut self, pod_type: PodType) -> Self {
+        self.db.pod_type = Some(pod_type);
+        self
+    }
+
+    /// The total number of pods. Must be the number of shards times the number of replicas.
+    pub fn pods(mut self, pods: i32) -> Self {
+        self.db.pods = Some(pods);
+        self
+    }
+
+    pub fn replicas(mut self, replicas: i32) -> Self {
+        self.db.replicas = Some(replicas);
+        self
+    }
+
+    pub fn shards(mut self, shards: i32) -> Self {
+        self.db.shards = Some(shards);
+        self
+    }
+
+    pub fn source_collection(mut self, source_collection: impl Into<String>) -> Self {
+        self.db.source_collection = Some(source_collection.into());
+        self
+    }
+
+    /// Only indexes the given metadata fields, instead of all of them.
+    pub fn indexed_metadata_fields<S: Into<String>>(
+        mut self,
+        fields: impl IntoIterator<Item = S>,
+    ) -> Self {
+        let fields = fields.into_iter().map(Into::into).collect();
+        self.db.metadata_config = Some(BTreeMap::from([("indexed".to_string(), fields)]));
+        self
+    }
+
+    /// Validates the settings, see [`validate_new_index`](crate::utils::conversions::validate_new_index).
+    pub fn build(self) -> PineconeResult<Db> {
+        crate::utils::conversions::validate_new_index(&self.db)?;
+        Ok(self.db)
+    }
+}
+
+/// Response fields that the typed control plane models don't know about, keyed by their dotted path in the response,
+/// e.g. `database.new_setting`. Converted into a dict when passed to Python.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtraFields(pub BTreeMap<String, serde_json::Value>);
+
+/// The settings changed by [`PineconeClient::configure_index_with`](crate::client::pinecone_client::PineconeClient::configure_index_with).
+/// Unset settings are left unchanged. Pod settings (`pod_type`, `replicas`, `pods`) only apply to pod-based indexes,
+/// and serverless settings (`embed`, `read_capacity`) only to serverless indexes, so the two can't be combined.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ConfigureIndexOptions {
+    pub pod_type: Option<PodType>,
+    pub replicas: Option<i32>,
+    /// The total number of pods. Must be a multiple of the number of replicas.
+    pub pods: Option<i32>,
+    pub deletion_protection: Option<bool>,
+    /// Tags to add or change. A tag with an empty value is removed.
+    pub tags: Option<BTreeMap<String, String>>,
+    pub embed: Option<EmbedConfig>,
+    pub read_capacity: Option<ReadCapacity>,
+}
+
+/// The integrated embedding settings of a serverless index.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct EmbedConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Maps the embedded fields of a record to the fields of its text, e.g. `{"text": "chunk_text"}`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field_map: Option<BTreeMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_parameters: Option<BTreeMap<String, serde_json::Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub write_parameters: Option<BTreeMap<String, serde_json::Value>>,
+}
+
+/// The read capacity of a serverless index.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReadCapacity {
+    /// Scales automatically with the load, billed per request.
+    OnDemand,
+    /// A fixed number of dedicated read nodes.
+    Dedicated {
+        node_type: String,
+        replicas: i32,
+        shards: i32,
+    },
 }
 
 #[pymethods]
@@ -233,9 +1793,28 @@ impl Db {
             ("source_collection", self.source_collection.to_object(py)),
             ("metadata_config", self.metadata_config.to_object(py)),
             ("status", self.status.to_object(py)),
+            ("extra", self.extra.to_object(py)),
         ];
         key_vals.into_py_dict(py)
     }
+
+    pub fn __getstate__<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        self.pickle_state(py)
+    }
+
+    pub fn __setstate__(&mut self, state: &PyDict) -> PyResult<()> {
+        *self = Self::from_pickle_state(state)?;
+        Ok(())
+    }
+
+    pub fn __reduce__(slf: &PyCell<Self>) -> PyResult<(PyObject, (&PyDict,))> {
+        reduce_pickled(slf)
+    }
+
+    #[classmethod]
+    fn _from_state(_cls: &PyType, state: &PyDict) -> PyResult<Self> {
+        Self::from_pickle_state(state)
+    }
 }
 
 #[pymethods]
@@ -250,10 +1829,246 @@ impl Collection {
             ("source", self.source.to_object(py)),
             ("vector_count", self.vector_count.to_object(py)),
             ("size", self.size.to_object(py)),
+            ("dimension", self.dimension.to_object(py)),
             ("status", self.status.to_object(py)),
+            ("extra", self.extra.to_object(py)),
         ];
         key_vals.into_py_dict(py)
     }
+
+    pub fn __getstate__<'a>(&self, py: Python<'a>) -> &'a PyDict {
+        self.pickle_state(py)
+    }
+
+    pub fn __setstate__(&mut self, state: &PyDict) -> PyResult<()> {
+        *self = Self::from_pickle_state(state)?;
+        Ok(())
+    }
+
+    pub fn __reduce__(slf: &PyCell<Self>) -> PyResult<(PyObject, (&PyDict,))> {
+        reduce_pickled(slf)
+    }
+
+    #[classmethod]
+    fn _from_state(_cls: &PyType, state: &PyDict) -> PyResult<Self> {
+        Self::from_pickle_state(state)
+    }
+}
+
+/// The state of a pickled data type, a dict of all of its fields.
+trait PickleState: Sized {
+    fn pickle_state<'a>(&self, py: Python<'a>) -> &'a PyDict;
+    fn from_pickle_state(state: &PyDict) -> PyResult<Self>;
+}
+
+macro_rules! impl_pickle_state {
+    ($ty:ident {}) => {
+        impl PickleState for $ty {
+            fn pickle_state<'a>(&self, py: Python<'a>) -> &'a PyDict {
+                PyDict::new(py)
+            }
+
+            fn from_pickle_state(_state: &PyDict) -> PyResult<Self> {
+                Ok($ty {})
+            }
+        }
+    };
+    ($ty:ident { $($field:ident),* }) => {
+        impl PickleState for $ty {
+            fn pickle_state<'a>(&self, py: Python<'a>) -> &'a PyDict {
+                let key_vals: Vec<(&str, PyObject)> =
+                    vec![$((stringify!($field), self.$field.clone().into_py(py))),*];
+                key_vals.into_py_dict(py)
+            }
+
+            fn from_pickle_state(state: &PyDict) -> PyResult<Self> {
+                Ok($ty {
+                    $($field: mapping_getitem(state, stringify!($field))?.extract()?),*
+                })
+            }
+        }
+    };
+}
+
+impl_pickle_state!(SparseValues { indices, values });
+impl_pickle_state!(UpsertResponse {
+    upserted_count,
+    upserted_ids,
+    report,
+    batches,
+    failed_ids,
+    timing
+});
+impl_pickle_state!(QueryResponse { results, timing });
+impl_pickle_state!(FetchResponse { vectors, timing });
+impl_pickle_state!(UpdateResponse {});
+impl_pickle_state!(DeleteResponse {});
+impl_pickle_state!(Db {
+    name,
+    dimension,
+    metric,
+    replicas,
+    shards,
+    pods,
+    source_collection,
+    metadata_config,
+    pod_type,
+    status,
+    extra
+});
+impl_pickle_state!(Collection {
+    name,
+    source,
+    vector_count,
+    size,
+    dimension,
+    status,
+    extra
+});
+impl_pickle_state!(Vector {
+    id,
+    values,
+    sparse_values,
+    metadata
+});
+impl_pickle_state!(UpsertBatchStatus {
+    vector_count,
+    upserted_count,
+    error
+});
+impl_pickle_state!(UpsertReport {
+    total_vectors,
+    batch_count,
+    duration,
+    throughput,
+    retries,
+    latency_p50,
+    latency_p90,
+    latency_p99,
+    latency_max
+});
+impl_pickle_state!(ClientStats {
+    requests,
+    total_requests,
+    bytes_sent,
+    bytes_received,
+    errors,
+    retries,
+    average_latency
+});
+impl_pickle_state!(OperationMetrics {
+    requests,
+    errors,
+    latency_mean,
+    latency_p50,
+    latency_p90,
+    latency_p99,
+    latency_max
+});
+impl_pickle_state!(PingResult {
+    latency,
+    connection_state
+});
+impl_pickle_state!(RecallReport {
+    top_k,
+    query_count,
+    recall,
+    min_recall,
+    latency_mean,
+    latency_p50,
+    latency_p90,
+    latency_p99
+});
+impl_pickle_state!(QueryResult {
+    id,
+    score,
+    values,
+    sparse_values,
+    metadata,
+    namespace
+});
+impl_pickle_state!(ColumnarResult {
+    ids,
+    scores,
+    values,
+    sparse_values,
+    metadata,
+    timing
+});
+impl_pickle_state!(Backup {
+    backup_id,
+    source_index_name,
+    name,
+    description,
+    status,
+    dimension,
+    metric,
+    record_count,
+    namespace_count,
+    size_bytes,
+    created_at
+});
+impl_pickle_state!(NamespaceStats { vector_count });
+impl_pickle_state!(IndexStats {
+    namespaces,
+    dimension,
+    index_fullness,
+    total_vector_count
+});
+impl_pickle_state!(LatencyBreakdown {
+    operation,
+    input_conversion,
+    serialization,
+    network,
+    deserialization,
+    python_conversion,
+    total
+});
+impl_pickle_state!(IndexProgress {
+    index,
+    status,
+    elapsed,
+    ready
+});
+impl_pickle_state!(PayloadDump {
+    operation,
+    direction,
+    target,
+    payload
+});
+impl_pickle_state!(AuditEvent {
+    operation,
+    index,
+    namespace,
+    count,
+    context,
+    success,
+    error,
+    timestamp
+});
+
+/// Pickles a data type as a call to its `_from_state` class method, since most data types can't be constructed from Python.
+/// The class must be exported from the `pinecone` module, for pickle to find it.
+fn reduce_pickled<T: PickleState + pyo3::PyClass>(
+    slf: &PyCell<T>,
+) -> PyResult<(PyObject, (&PyDict,))> {
+    let from_state = slf.get_type().getattr("_from_state")?;
+    Ok((
+        from_state.into(),
+        (slf.try_borrow()?.pickle_state(slf.py()),),
+    ))
+}
+
+fn mapping_getitem<'a>(dict: &'a PyDict, key: &str) -> PyResult<&'a PyAny> {
+    dict.get_item(key)
+        .ok_or_else(|| PyKeyError::new_err(key.to_string()))
+}
+
+fn mapping_get(py: Python, dict: &PyDict, key: &str, default: Option<PyObject>) -> PyObject {
+    match dict.get_item(key) {
+        Some(val) => val.into(),
+        None => default.unwrap_or_else(|| py.None()),
+    }
 }
 
 fn pretty_print_dict(dict: &PyDict, indent: usize) -> Result<String, PyErr> {
@@ -298,3 +2113,139 @@ fn pretty_print_dict(dict: &PyDict, indent: usize) -> Result<String, PyErr> {
     }
     Ok(msg)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_accessors() {
+        let metadata: BTreeMap<String, MetadataValue> = [
+            ("genre", MetadataValue::StringVal("drama".to_string())),
+            ("year", MetadataValue::NumberVal(2020.0)),
+            ("featured", MetadataValue::BoolVal(true)),
+            (
+                "tags",
+                MetadataValue::ListVal(vec![
+                    MetadataValue::StringVal("a".to_string()),
+                    MetadataValue::StringVal("b".to_string()),
+                ]),
+            ),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect();
+        let result = QueryResult {
+            id: "1".to_string(),
+            score: 0.5,
+            values: None,
+            sparse_values: None,
+            metadata: Some(metadata),
+            namespace: None,
+        };
+
+        assert_eq!(result.get_str("genre"), Some("drama"));
+        assert_eq!(result.get_f64("year"), Some(2020.0));
+        assert_eq!(result.get_bool("featured"), Some(true));
+        assert_eq!(result.get_list_str("tags"), Some(vec!["a", "b"]));
+        // Missing keys and mismatched types
+        assert_eq!(result.get_str("missing"), None);
+        assert_eq!(result.get_f64("genre"), None);
+        assert_eq!(Vector::default().get_str("genre"), None);
+    }
+
+    #[test]
+    fn test_index_setting_names() {
+        for metric in Metric::ALL {
+            assert_eq!(metric.to_string().parse::<Metric>().unwrap(), metric);
+        }
+        assert!("cos".parse::<Metric>().is_err());
+        assert_eq!(
+            "s1.x2".parse::<PodType>().unwrap(),
+            PodType::S1(PodSize::X2)
+        );
+        assert_eq!(PodType::P2(PodSize::X8).to_string(), "p2.x8");
+        for invalid in ["s1.x3", "s2.x1", "s1", ""] {
+            assert!(invalid.parse::<PodType>().is_err());
+        }
+    }
+
+    #[test]
+    fn test_create_index_builder() {
+        let db = Db::builder("my-index")
+            .dimension(8)
+            .metric(Metric::DotProduct)
+            .pod_type(PodType::P1(PodSize::X2))
+            .replicas(2)
+            .shards(2)
+            .pods(4)
+            .indexed_metadata_fields(["genre"])
+            .build()
+            .unwrap();
+        assert_eq!(db.metric, Some(Metric::DotProduct));
+        assert_eq!(db.metadata_config.unwrap()["indexed"], vec!["genre"]);
+        // The dimension is taken from the source collection
+        assert!(Db::builder("my-index")
+            .source_collection("my-collection")
+            .build()
+            .is_ok());
+
+        let invalid = [
+            Db::builder("My_Index").dimension(8),
+            Db::builder("-index").dimension(8),
+            Db::builder("index").dimension(-1),
+            // Neither a dimension nor a source collection
+            Db::builder("index"),
+            Db::builder("index").dimension(8).replicas(0),
+            Db::builder("index")
+                .dimension(8)
+                .shards(2)
+                .replicas(2)
+                .pods(3),
+            Db::builder("index").dimension(8).replicas(3).pods(4),
+        ];
+        for builder in invalid {
+            assert!(builder.build().is_err());
+        }
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let vector = Vector {
+            id: "v1".to_string(),
+            values: vec![0.5, 1.0],
+            sparse_values: Some(SparseValues {
+                indices: vec![3],
+                values: vec![0.25],
+            }),
+            metadata: Some(BTreeMap::from([
+                (
+                    "genre".to_string(),
+                    MetadataValue::StringVal("drama".to_string()),
+                ),
+                ("year".to_string(), MetadataValue::NumberVal(2020.0)),
+            ])),
+        };
+        let json = serde_json::to_value(&vector).unwrap();
+        assert_eq!(json["metadata"]["genre"], "drama");
+        let parsed: Vector = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.get_f64("year"), Some(2020.0));
+        assert_eq!(parsed.sparse_values.unwrap().indices, vec![3]);
+
+        let db = Db::builder("idx")
+            .dimension(8)
+            .metric(Metric::Euclidean)
+            .pod_type(PodType::S1(PodSize::X4))
+            .build()
+            .unwrap();
+        let json = serde_json::to_value(&db).unwrap();
+        assert_eq!(json["metric"], "euclidean");
+        assert_eq!(json["pod_type"], "s1.x4");
+        let parsed: Db = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.pod_type, db.pod_type);
+
+        let collection: Collection =
+            serde_json::from_str(r#"{"name": "coll", "source": "idx"}"#).unwrap();
+        assert_eq!(collection.extra, ExtraFields::default());
+    }
+}
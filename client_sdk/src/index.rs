@@ -1,15 +1,101 @@
-use crate::client::grpc::DataplaneGrpcClient;
-use crate::data_types::MetadataValue;
-use crate::data_types::{QueryResult, UpsertResponse, Vector};
+use crate::client::config::{ClientConfig, RetryPolicy};
+use crate::client::control_plane::ControlPlaneClient;
+#[cfg(any(test, feature = "fault-injection"))]
+use crate::client::fault_injection::FaultInjector;
+use crate::client::grpc::{new_request_id, ConnectionState, DataplaneGrpcClient};
+use crate::data_types::{
+    AuditEvent, ClientStats, Db, DeleteResponse, LatencyBreakdown, MetadataValue, Metric,
+    PingResult,
+};
+use crate::data_types::{
+    FetchResponse, QueryInput, QueryResponse, QueryResult, RecallReport, UpdateResponse,
+    UpsertBatchStatus, UpsertResponse, Vector,
+};
+use crate::utils::batching::{
+    fetch_batch_size, split_by_size, MAX_FETCH_RESPONSE_BYTES, MAX_UPSERT_REQUEST_BYTES,
+};
+use crate::utils::cancellation::run_cancellable;
+use crate::utils::client_stats::ClientStatsRecorder;
+use crate::utils::concurrency::{
+    run_adaptive_capped, run_adaptive_settled, run_adaptive_with_stats, AimdController,
+};
 use crate::utils::errors::{PineconeClientError, PineconeResult};
+use crate::utils::evaluation::{recall_at_k, recall_report};
+use crate::utils::fetch_cache::FetchCache;
+use crate::utils::filter_validation::validate_filter;
+use crate::utils::latency::{CallLatency, Phase};
+use crate::utils::normalization::{
+    is_far_from_unit_norm, l2_norm, l2_normalize, normalize_vectors, sample_mean_norm,
+};
+use crate::utils::payload_dump::PayloadSink;
+use crate::utils::similarity;
+use futures::stream::{self, Stream, TryStreamExt};
 use std::collections::{BTreeMap, HashMap};
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
 
 use crate::data_types::{IndexStats, SparseValues};
 
-#[derive(Clone)]
+/// Fetch requests with more ids than this are split into concurrent batches.
+/// Batches are made smaller for high dimensional indexes, to bound their response size.
+const FETCH_BATCH_SIZE: usize = 1000;
+
+/// Optional arguments for [`Index::upsert`].
+#[derive(Debug, Clone, Default)]
+pub struct UpsertOptions {
+    /// If set, the vectors are split into batches of this size, which are upserted concurrently.
+    /// The number of concurrent requests is adjusted automatically according to the index's responsiveness.
+    /// Batches are split further to stay under the request size limit, which also applies when `batch_size` isn't set.
+    pub batch_size: Option<u32>,
+    /// If set, at most this many batches are upserted concurrently.
+    /// Otherwise, the concurrency is only bounded by the automatically tuned limit.
+    pub max_concurrency: Option<usize>,
+    /// Whether to L2-normalize the vectors' dense values before upserting. Defaults to the Index setting.
+    pub normalize: Option<bool>,
+    /// If set, cancelling the token aborts the upsert, including any in-flight batches.
+    /// Batches that were already upserted are not rolled back.
+    pub cancellation_token: Option<CancellationToken>,
+    /// Whether to return the ids of the upserted vectors in [`UpsertResponse::upserted_ids`].
+    pub return_ids: bool,
+    /// Whether a batched upsert keeps going after some of its batches failed, instead of failing on the first error.
+    /// The failed batches are then reported in [`UpsertResponse::batches`] and [`UpsertResponse::failed_ids`], so they can be retried.
+    pub allow_partial_failure: bool,
+    /// Sent as the `x-request-id` metadata entry of the upsert request, so retrying a failed upsert with the same ID lets
+    /// the service and observability tooling recognize it as the same upsert. Batches carry `{request_id}-{batch number}`.
+    /// Defaults to a generated ID if [`ClientConfig::request_ids`] is set.
+    pub request_id: Option<String>,
+}
+
+/// A handle to an index's data plane, returned by [`PineconeClient::get_index`](crate::client::pinecone_client::PineconeClient::get_index).
+///
+/// Operations take `&self`, so a single handle (or an `Arc` of it) can be used concurrently from several tasks.
+/// Clones are cheap and share the underlying connection.
+#[derive(Debug, Clone)]
 pub struct Index {
     pub name: String,
     dataplane_client: DataplaneGrpcClient,
+    namespace: String,
+    normalize: bool,
+    control_plane_client: Option<Arc<ControlPlaneClient>>,
+    // The last known description of the index, shared between clones
+    description: Arc<RwLock<Option<Db>>>,
+    timeout: Option<Duration>,
+    fetch_cache: Option<Arc<FetchCache>>,
+    // Shared between clones, so the concurrency limit learned by one batched operation carries over to the next
+    concurrency: Arc<Mutex<AimdController>>,
+    // Shared between clones, so the norm warning is only logged once per index handle
+    norm_warning_logged: Arc<AtomicBool>,
+    // Shared between clones, so the description is fetched for the diagnostics at most once
+    description_requested: Arc<AtomicBool>,
+    // The latency breakdown of the most recent operation, shared between clones
+    last_latency: Arc<Mutex<Option<LatencyBreakdown>>>,
+    // Request counters, shared between clones
+    stats: Arc<ClientStatsRecorder>,
+    config: ClientConfig,
+    audit_context: BTreeMap<String, String>,
 }
 
 impl Index {
@@ -17,6 +103,337 @@ impl Index {
         Index {
             name: index_name,
             dataplane_client,
+            namespace: String::new(),
+            normalize: false,
+            control_plane_client: None,
+            timeout: None,
+            fetch_cache: None,
+            description: Arc::new(RwLock::new(None)),
+            concurrency: Arc::new(Mutex::new(AimdController::default())),
+            norm_warning_logged: Arc::new(AtomicBool::new(false)),
+            description_requested: Arc::new(AtomicBool::new(false)),
+            last_latency: Arc::new(Mutex::new(None)),
+            stats: Arc::new(ClientStatsRecorder::default()),
+            config: ClientConfig::default(),
+            audit_context: BTreeMap::new(),
+        }
+    }
+
+    /// Returns a lightweight view of this index, in which every operation defaults to `namespace`
+    /// instead of the default (`""`) namespace. The namespace can still be overridden per call.
+    /// The view shares the underlying connection with this index.
+    pub fn with_namespace(&self, namespace: &str) -> Index {
+        Index {
+            namespace: namespace.to_string(),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a lightweight view of this index, in which the audit events of mutating operations carry `context`
+    /// (e.g. the user or job on whose behalf the operations are made), in addition to the audit hook's own context.
+    /// See [`ClientConfig::set_audit_hook`].
+    pub fn with_audit_context(&self, context: BTreeMap<String, String>) -> Index {
+        Index {
+            audit_context: context,
+            ..self.clone()
+        }
+    }
+
+    fn audit<T>(
+        &self,
+        operation: &str,
+        namespace: Option<&str>,
+        count: Option<u64>,
+        res: &PineconeResult<T>,
+    ) {
+        let event = AuditEvent {
+            operation: operation.to_string(),
+            index: self.name.clone(),
+            namespace: Some(self.resolve_namespace(namespace).to_string()),
+            count,
+            ..Default::default()
+        };
+        self.config.audit(event, &self.audit_context, res);
+    }
+
+    /// The namespace used by operations that don't explicitly specify one.
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// Returns a lightweight view of this index, in which every operation must complete within `timeout`,
+    /// instead of the client's [`operation_timeout`](ClientConfig::operation_timeout).
+    /// For operations split into multiple requests (e.g. batched upserts), the timeout applies to the whole operation:
+    /// each request is given the time remaining until the overall deadline, and retries stop once it would be exceeded.
+    pub fn with_timeout(&self, timeout: Duration) -> Index {
+        Index {
+            timeout: Some(timeout),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a lightweight view of this index, whose operations are retried according to `retry_policy` instead of the
+    /// client's, e.g. without retries for `delete_all()`, or with more retries for `fetch()`.
+    pub fn with_retry_policy(&self, retry_policy: RetryPolicy) -> Index {
+        let mut config = self.config.clone();
+        config.retry_policy = retry_policy;
+        Index {
+            config,
+            ..self.clone()
+        }
+    }
+
+    /// The retry policy applied to every operation.
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.config.retry_policy
+    }
+
+    /// The timeout applied to every operation, if any.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout.or(self.config.operation_timeout)
+    }
+
+    /// Returns a view of this index, which dumps the payloads of its data plane requests and responses into `sink`,
+    /// e.g. to troubleshoot requests the index rejects. See [`payload_dump`](crate::utils::payload_dump).
+    pub fn with_payload_dump(&self, sink: PayloadSink) -> Index {
+        Index {
+            dataplane_client: self.dataplane_client.with_payload_sink(sink),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a view of this index, in which every data plane request goes through `fault_injector`.
+    /// Meant for testing how code built on top of the SDK handles latencies and transient failures.
+    #[cfg(any(test, feature = "fault-injection"))]
+    pub fn with_fault_injector(&self, fault_injector: Arc<FaultInjector>) -> Index {
+        Index {
+            dataplane_client: self.dataplane_client.with_fault_injector(fault_injector),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a data plane client for a single operation, carrying the operation's deadline
+    /// and recording its requests' timings into `call`.
+    fn client(&self, call: &CallLatency) -> DataplaneGrpcClient {
+        let client = self
+            .dataplane_client
+            .with_latency_recorder(call.recorder().clone())
+            .with_config(self.config.clone())
+            .with_stats(self.stats.clone());
+        match self.timeout() {
+            Some(timeout) => client.with_deadline(Instant::now() + timeout),
+            None => client,
+        }
+    }
+
+    fn start_call(&self, operation: &str) -> CallLatency {
+        CallLatency::start(operation, self.last_latency.clone())
+    }
+
+    /// The client-side latency breakdown of the most recent data plane operation made through this index
+    /// (or any of its views), whether it succeeded or not.
+    pub fn last_latency(&self) -> Option<LatencyBreakdown> {
+        self.last_latency.lock().unwrap().clone()
+    }
+
+    /// Adds time spent outside of the SDK (e.g. converting the operation's input or output by the caller)
+    /// to the latency breakdown of the most recent operation. The duration is also added to the operation's total.
+    pub fn record_latency(&self, phase: Phase, duration: Duration) {
+        if let Some(breakdown) = self.last_latency.lock().unwrap().as_mut() {
+            breakdown.add_external(phase, duration);
+        }
+    }
+
+    /// A snapshot of the data plane request counters of this index (and all of its views), since it was connected
+    /// or since the last [`Index::reset_client_stats`] call.
+    pub fn client_stats(&self) -> ClientStats {
+        self.stats.snapshot()
+    }
+
+    /// Resets the counters reported by [`Index::client_stats`].
+    pub fn reset_client_stats(&self) {
+        self.stats.reset();
+    }
+
+    fn resolve_namespace<'a>(&'a self, namespace: Option<&'a str>) -> &'a str {
+        namespace.unwrap_or(&self.namespace)
+    }
+
+    /// The current state of the connection to the index.
+    /// The state is derived from the outcome of the most recent request made through this index (or any of its views).
+    pub fn connection_state(&self) -> ConnectionState {
+        self.dataplane_client.connection_state()
+    }
+
+    /// Checks the connection to the index with a lightweight round-trip, e.g. at startup or in a service's health check.
+    /// The ping is a single `describe_index_stats` request, which is neither retried nor recorded as the last operation's
+    /// latency. It respects the index's timeout, if any.
+    ///
+    /// # Returns
+    /// The ping's round-trip latency and the resulting connection state, or the error the ping failed with.
+    pub async fn ping(&self) -> PineconeResult<PingResult> {
+        let client = match self.timeout() {
+            Some(timeout) => self
+                .dataplane_client
+                .with_deadline(Instant::now() + timeout),
+            None => self.dataplane_client.clone(),
+        };
+        let latency = client
+            .with_config(self.config.clone())
+            .with_stats(self.stats.clone())
+            .ping()
+            .await?;
+        Ok(PingResult {
+            latency: latency.as_secs_f64(),
+            connection_state: self.connection_state().as_str().to_string(),
+        })
+    }
+
+    /// Tears down the connection to the index and establishes a new one, re-resolving the index endpoint.
+    /// Useful for recovering from a stuck connection without recreating the client.
+    /// All views of this index (e.g. created by `with_namespace()`) use the new connection.
+    pub async fn reconnect(&self) -> PineconeResult<()> {
+        self.dataplane_client.reconnect().await
+    }
+
+    pub(crate) fn set_config(&mut self, config: ClientConfig) {
+        self.config = config;
+    }
+
+    pub(crate) fn set_control_plane_client(
+        &mut self,
+        control_plane_client: Arc<ControlPlaneClient>,
+        description: Option<Db>,
+    ) {
+        self.control_plane_client = Some(control_plane_client);
+        *self.description.write().unwrap() = description;
+    }
+
+    /// Describes the index by calling the control plane, and refreshes the cached index description.
+    ///
+    /// # Returns
+    /// `Ok(Db)` with the index description, or an error if the control plane call failed.
+    pub async fn describe(&self) -> PineconeResult<Db> {
+        let control_plane_client = self.control_plane_client.as_ref().ok_or_else(|| {
+            PineconeClientError::Other(format!(
+                "Index '{name}' was not created by a Pinecone client and can't be described",
+                name = self.name
+            ))
+        })?;
+        let db = control_plane_client.describe_index(&self.name).await?;
+        *self.description.write().unwrap() = Some(db.clone());
+        Ok(db)
+    }
+
+    /// Returns the cached index description, describing the index only if it wasn't described before.
+    async fn cached_description(&self) -> PineconeResult<Db> {
+        let cached = self.description.read().unwrap().clone();
+        match cached {
+            Some(db) => Ok(db),
+            None => self.describe().await,
+        }
+    }
+
+    /// The dimension of the index's vectors. Cached after the first call.
+    pub async fn dimension(&self) -> PineconeResult<i32> {
+        Ok(self.cached_description().await?.dimension)
+    }
+
+    /// The distance metric used by the index. Cached after the first call.
+    pub async fn metric(&self) -> PineconeResult<Option<Metric>> {
+        Ok(self.cached_description().await?.metric)
+    }
+
+    // The metric of the cached description, without describing the index, so data plane calls never wait for the control plane.
+    // `None` if the index wasn't described yet.
+    fn cached_metric(&self) -> Option<Metric> {
+        self.description
+            .read()
+            .unwrap()
+            .as_ref()
+            .map(|db| db.metric.unwrap_or_default())
+    }
+
+    /// Logs a warning if the index's metric is sensitive to vector norms, but the vectors sent to it
+    /// are far from being unit-normed - which is a common cause for unexpected query scores.
+    fn warn_if_not_unit_norm(&self, norm: Option<f32>, operation: &str) {
+        // Only the cached metric is used, so data plane calls never wait for the control plane
+        let metric = match self.description.read().unwrap().as_ref() {
+            Some(db) => db.metric,
+            None => {
+                self.describe_in_background();
+                return;
+            }
+        };
+        let metric = match metric {
+            Some(metric @ (Metric::Cosine | Metric::DotProduct)) => metric,
+            _ => return,
+        };
+        let norm = match norm {
+            Some(norm) if is_far_from_unit_norm(norm) => norm,
+            _ => return,
+        };
+        if self.norm_warning_logged.swap(true, Ordering::Relaxed) {
+            return;
+        }
+        log::warn!(
+            "Index '{name}' uses the '{metric}' metric, but the vectors passed to {operation} have an average L2 norm of {norm:.2}. \
+            This may lead to unexpected scores. Consider normalizing your vectors, for example by passing `normalize=True`.",
+            name = self.name,
+        );
+    }
+
+    /// Fetches the index description in the background, so the diagnostics of later calls can use it.
+    /// Only done once, and never if diagnostics are disabled or the index has no control plane client.
+    fn describe_in_background(&self) {
+        if !self.config.diagnostics || self.description_requested.swap(true, Ordering::Relaxed) {
+            return;
+        }
+        let control_plane_client = match &self.control_plane_client {
+            Some(control_plane_client) => control_plane_client.clone(),
+            None => return,
+        };
+        let (name, description) = (self.name.clone(), self.description.clone());
+        tokio::spawn(async move {
+            match control_plane_client.describe_index(&name).await {
+                Ok(db) => {
+                    description.write().unwrap().get_or_insert(db);
+                }
+                Err(err) => log::debug!("Failed to describe index '{name}': {err}"),
+            }
+        });
+    }
+
+    /// Sets whether dense vector values are L2-normalized before being sent to the index.
+    /// This is the default behavior for `upsert()` and `query()`, and can be overridden per call.
+    pub fn set_normalize(&mut self, normalize: bool) {
+        self.normalize = normalize;
+    }
+
+    /// Whether dense vector values are L2-normalized before being sent to the index by default.
+    pub fn normalize(&self) -> bool {
+        self.normalize
+    }
+
+    /// Enables a local LRU cache of fetched vectors, holding up to `capacity` vectors for `ttl` each.
+    /// Cached vectors are invalidated when they are upserted, updated or deleted through this index (or any of its views).
+    /// Changes made by other clients are only seen after the TTL expires.
+    pub fn enable_fetch_cache(&mut self, capacity: usize, ttl: Duration) -> PineconeResult<()> {
+        let capacity = NonZeroUsize::new(capacity).ok_or_else(|| {
+            PineconeClientError::ValueError("capacity must be a positive integer".to_string())
+        })?;
+        self.fetch_cache = Some(Arc::new(FetchCache::new(capacity, ttl)));
+        Ok(())
+    }
+
+    pub fn disable_fetch_cache(&mut self) {
+        self.fetch_cache = None;
+    }
+
+    /// Removes all vectors from the fetch cache, if it is enabled.
+    pub fn clear_fetch_cache(&self) {
+        if let Some(cache) = &self.fetch_cache {
+            cache.clear();
         }
     }
 
@@ -24,25 +441,145 @@ impl Index {
     /// If a new value is upserted for an existing vector id, it will overwrite the previous value.
     ///
     /// # Arguments
-    /// - `namespace` - the name of the namespace to which data will be upserted
+    /// - `namespace` - the name of the namespace to which data will be upserted. Defaults to the Index's namespace.
     /// - `vectors` - a list of vectors to be upserted to the index.
+    /// - `options` - additional upsert options, see [`UpsertOptions`].
     ///
     /// # Returns
-    /// `Ok(list_ids)` with a list of vector ids that were successfully upserted to the Index, or the underlying gRPC error on failure.
-
+    /// An [`UpsertResponse`] with the number of upserted vectors, or the underlying gRPC error on failure.
+    /// If `return_ids` is set, the response also lists the ids of the vectors in every successfully upserted batch.
+    /// When the vectors are upserted in several batches, the response also carries an [`UpsertReport`](crate::data_types::UpsertReport)
+    /// with the upsert's throughput and batch latencies,
+    /// along with the status of every batch. With [`UpsertOptions::allow_partial_failure`], failed batches don't fail the upsert,
+    /// and their vectors' ids are listed in [`UpsertResponse::failed_ids`].
+    /// The response's [`timing`](UpsertResponse::timing) holds the upsert's client-side latency breakdown.
     pub async fn upsert(
-        &mut self,
-        namespace: &str,
+        &self,
+        namespace: Option<&str>,
         vectors: &[Vector],
-        batch_size: Option<u32>,
+        options: UpsertOptions,
     ) -> PineconeResult<UpsertResponse> {
-        if batch_size.is_some() {
-            todo!("Add proper upsert batching")
+        let res = self.upsert_unaudited(namespace, vectors, options).await;
+        let count = match &res {
+            Ok(response) => response.upserted_count as u64,
+            Err(_) => vectors.len() as u64,
+        };
+        self.audit("upsert", namespace, Some(count), &res);
+        res
+    }
+
+    async fn upsert_unaudited(
+        &self,
+        namespace: Option<&str>,
+        vectors: &[Vector],
+        options: UpsertOptions,
+    ) -> PineconeResult<UpsertResponse> {
+        if options.batch_size == Some(0) {
+            return Err(PineconeClientError::ValueError(
+                "batch_size must be a positive integer".to_string(),
+            ));
+        }
+        if options.max_concurrency == Some(0) {
+            return Err(PineconeClientError::ValueError(
+                "max_concurrency must be a positive integer".to_string(),
+            ));
         }
 
-        let upserted_count = self.dataplane_client.upsert(namespace, vectors).await?;
+        let call = self.start_call("upsert()");
+        let normalized_vectors;
+        let vectors = if options.normalize.unwrap_or(self.normalize) {
+            let mut vectors = vectors.to_vec();
+            normalize_vectors(&mut vectors)?;
+            normalized_vectors = vectors;
+            &normalized_vectors
+        } else {
+            self.warn_if_not_unit_norm(sample_mean_norm(vectors), "upsert()");
+            vectors
+        };
 
-        if upserted_count != vectors.len() as u32 {
+        let namespace = self.resolve_namespace(namespace).to_string();
+        let mut client = self.client(&call);
+        // Batches are also bounded by the request size limit, so large vectors or metadata never exceed it
+        let batches = split_by_size(
+            vectors,
+            options.batch_size.map_or(usize::MAX, |size| size as usize),
+            MAX_UPSERT_REQUEST_BYTES,
+        );
+        let upsert = async {
+            if options.batch_size.is_some() || batches.len() > 1 {
+                let start = Instant::now();
+                let namespace = &namespace;
+                // Each batch carries its own request ID, derived from the upsert's
+                let request_id = options
+                    .request_id
+                    .clone()
+                    .or_else(|| self.config.request_ids.then(new_request_id));
+                let numbered_batches: Vec<(usize, &[Vector])> =
+                    batches.iter().copied().enumerate().collect();
+                let batch_client = |number: usize| match &request_id {
+                    Some(request_id) => client.with_request_id(format!("{request_id}-{number}")),
+                    None => client.clone(),
+                };
+                let (results, stats) = if options.allow_partial_failure {
+                    run_adaptive_settled(
+                        &numbered_batches,
+                        &self.concurrency,
+                        options.max_concurrency,
+                        |&(number, batch)| {
+                            let mut client = batch_client(number);
+                            async move { Ok(client.upsert(namespace, batch).await?) }
+                        },
+                    )
+                    .await
+                } else {
+                    let (counts, stats) = run_adaptive_capped(
+                        &numbered_batches,
+                        &self.concurrency,
+                        options.max_concurrency,
+                        |&(number, batch)| {
+                            let mut client = batch_client(number);
+                            async move { Ok(client.upsert(namespace, batch).await?) }
+                        },
+                    )
+                    .await?;
+                    (counts.into_iter().map(Ok).collect(), stats)
+                };
+                self.config
+                    .record_client_retries(&self.stats, stats.retries);
+                let upserted_count: u32 = results.iter().flatten().sum();
+                let report = stats.upsert_report(upserted_count as u64, start.elapsed());
+                self.config.debug_log("upsert", || {
+                    format!("Batched upsert report: {}", report.to_json())
+                });
+                let batch_results = batches.into_iter().zip(results).collect();
+                Ok((batch_results, Some(report)))
+            } else {
+                if let Some(request_id) = &options.request_id {
+                    client = client.with_request_id(request_id.clone());
+                }
+                let upserted_count = client.upsert(&namespace, vectors).await?;
+                Ok((vec![(vectors, Ok(upserted_count))], None))
+            }
+        };
+        let res = run_cancellable(options.cancellation_token.as_ref(), "upsert()", upsert).await;
+        if let Some(cache) = &self.fetch_cache {
+            cache.invalidate(&namespace, vectors.iter().map(|v| v.id.as_str()));
+        }
+        let (batch_results, report): (Vec<(&[Vector], PineconeResult<u32>)>, _) = res?;
+        let batch_succeeded = |batch: &[Vector], res: &PineconeResult<u32>| matches!(res, Ok(count) if *count as usize == batch.len());
+        let upserted_count: u32 = batch_results
+            .iter()
+            .filter_map(|(_, res)| res.as_ref().ok())
+            .sum();
+        let upserted_ids = options.return_ids.then(|| {
+            batch_results
+                .iter()
+                .filter(|(batch, res)| batch_succeeded(batch, res))
+                .flat_map(|(batch, _)| batch.iter().map(|v| v.id.clone()))
+                .collect()
+        });
+
+        if !options.allow_partial_failure && upserted_count != vectors.len() as u32 {
             return Err(PineconeClientError::Other(format!(
                 "Failed to upsert all vectors. Upserted {} out of {} vectors",
                 upserted_count,
@@ -50,7 +587,29 @@ impl Index {
             )));
         }
 
-        Ok(UpsertResponse { upserted_count })
+        let failed_ids = batch_results
+            .iter()
+            .filter(|(batch, res)| !batch_succeeded(batch, res))
+            .flat_map(|(batch, _)| batch.iter().map(|v| v.id.clone()))
+            .collect();
+        let batches = report.is_some().then(|| {
+            batch_results
+                .iter()
+                .map(|(batch, res)| UpsertBatchStatus {
+                    vector_count: batch.len() as u32,
+                    upserted_count: *res.as_ref().unwrap_or(&0),
+                    error: res.as_ref().err().map(ToString::to_string),
+                })
+                .collect()
+        });
+        Ok(UpsertResponse {
+            upserted_count,
+            upserted_ids,
+            report,
+            batches,
+            failed_ids,
+            timing: Some(call.breakdown()),
+        })
     }
 
     /// Query
@@ -60,31 +619,46 @@ impl Index {
     /// To query by the id of already upserted vector, use `Index.query_by_id()`
     ///
     /// # Arguments
-    /// - `namespace` - the name of the namespace in which vectors will be queried
+    /// - `namespace` - the name of the namespace in which vectors will be queried. Defaults to the Index's namespace.
     /// - `values` - The values for a new, unseen query vector. This should be the same length as the dimension of the index being queried. The results will be the `top_k` vectors closest to the given vector. Can not be used together with `id`
     /// - `sparse_values` - The query vector's sparse values.
     /// - `top_k` - The number of results to return for each query.
     /// - `filter` - The filter to apply. You can use vector metadata to limit your search. See <https://www.pinecone.io/docs/metadata-filtering/`>
     /// - `include_values` - Indicates whether vector values are included in the response.
     /// - `include_metadata` - Indicates whether metadata is included in the response as well as the ids.
+    /// - `normalize` - whether to L2-normalize the query vector's dense values. Defaults to the Index setting.
     ///
     /// # Returns
-    /// A list of QueryResults
+    /// The QueryResults, along with the query's client-side latency breakdown in [`timing`](QueryResponse::timing).
     #[allow(clippy::too_many_arguments)]
     pub async fn query(
-        &mut self,
-        namespace: &str,
+        &self,
+        namespace: Option<&str>,
         values: Option<Vec<f32>>,
         sparse_values: Option<SparseValues>,
         top_k: u32,
         filter: Option<BTreeMap<String, MetadataValue>>,
         include_values: bool,
         include_metadata: bool,
-    ) -> PineconeResult<Vec<QueryResult>> {
-        let res = self
-            .dataplane_client
+        normalize: Option<bool>,
+    ) -> PineconeResult<QueryResponse> {
+        if let Some(filter) = &filter {
+            validate_filter(filter)?;
+        }
+        let call = self.start_call("query()");
+        let mut values = values;
+        if normalize.unwrap_or(self.normalize) {
+            if let Some(values) = values.as_mut() {
+                l2_normalize(values)?;
+            }
+        } else {
+            self.warn_if_not_unit_norm(values.as_deref().map(l2_norm), "query()");
+        }
+        let namespace = self.resolve_namespace(namespace).to_string();
+        let results = self
+            .client(&call)
             .query(
-                namespace,
+                &namespace,
                 None,
                 values,
                 sparse_values,
@@ -94,8 +668,261 @@ impl Index {
                 include_metadata,
             )
             .await?;
+        Ok(QueryResponse {
+            results,
+            timing: Some(call.breakdown()),
+        })
+    }
 
-        Ok(res)
+    /// Query batch
+    ///
+    /// Runs several queries concurrently over the index's shared connection, all with the same options.
+    /// The number of concurrent queries is tuned automatically, backing off when the index is overloaded.
+    ///
+    /// # Arguments
+    /// - `namespace` - the name of the namespace in which vectors will be queried. Defaults to the Index's namespace.
+    /// - `queries` - the queries, each either a vector id or a new vector's values.
+    /// - `top_k`, `filter`, `include_values`, `include_metadata`, `normalize` - applied to every query, see [`query()`](Self::query).
+    ///
+    /// # Returns
+    /// The QueryResults of each query, in the same order as `queries`, or the first error.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn query_batch(
+        &self,
+        namespace: Option<&str>,
+        queries: &[QueryInput],
+        top_k: u32,
+        filter: Option<BTreeMap<String, MetadataValue>>,
+        include_values: bool,
+        include_metadata: bool,
+        normalize: Option<bool>,
+    ) -> PineconeResult<Vec<Vec<QueryResult>>> {
+        if let Some(filter) = &filter {
+            validate_filter(filter)?;
+        }
+        let call = self.start_call("query_batch()");
+        let normalize = normalize.unwrap_or(self.normalize);
+        let queries = queries
+            .iter()
+            .map(|query| match query {
+                QueryInput::Values {
+                    values,
+                    sparse_values,
+                } => {
+                    let mut values = values.clone();
+                    if normalize {
+                        l2_normalize(&mut values)?;
+                    }
+                    Ok((None, Some(values), sparse_values.clone()))
+                }
+                QueryInput::Id(id) => Ok((Some(id.clone()), None, None)),
+            })
+            .collect::<PineconeResult<Vec<_>>>()?;
+        if !normalize {
+            let norm = queries
+                .iter()
+                .find_map(|(_, values, _)| values.as_deref().map(l2_norm));
+            self.warn_if_not_unit_norm(norm, "query_batch()");
+        }
+        let namespace = self.resolve_namespace(namespace).to_string();
+        let client = self.client(&call);
+        let (namespace, filter) = (&namespace, &filter);
+        let (results, stats) = run_adaptive_with_stats(
+            &queries,
+            &self.concurrency,
+            |(id, values, sparse_values)| {
+                let mut client = client.clone();
+                async move {
+                    client
+                        .query(
+                            namespace,
+                            id.clone(),
+                            values.clone(),
+                            sparse_values.clone(),
+                            top_k,
+                            filter.clone(),
+                            include_values,
+                            include_metadata,
+                        )
+                        .await
+                }
+            },
+        )
+        .await?;
+        self.config
+            .record_client_retries(&self.stats, stats.retries);
+        Ok(results)
+    }
+
+    /// Query namespaces
+    ///
+    /// Runs the same query against several namespaces concurrently, and merges the results into a single ranking.
+    /// Scores are comparable across namespaces of the same index, so the merged results are ordered by score according to the index's metric.
+    ///
+    /// # Arguments
+    /// - `namespaces` - the namespaces to query. Must not be empty.
+    /// - `values`, `sparse_values`, `top_k`, `filter`, `include_values`, `include_metadata`, `normalize` - the query, see [`query()`](Self::query).
+    /// - `metric` - the index's metric, which orders the merged results. Defaults to the metric of the cached index description.
+    ///   Required if the index wasn't described yet, since the index is never described by this call.
+    ///
+    /// # Returns
+    /// The `top_k` most similar QueryResults across all namespaces, each with the namespace it was found in, or the first error.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn query_namespaces(
+        &self,
+        namespaces: &[String],
+        values: Option<Vec<f32>>,
+        sparse_values: Option<SparseValues>,
+        top_k: u32,
+        filter: Option<BTreeMap<String, MetadataValue>>,
+        include_values: bool,
+        include_metadata: bool,
+        normalize: Option<bool>,
+        metric: Option<Metric>,
+    ) -> PineconeResult<Vec<QueryResult>> {
+        if namespaces.is_empty() {
+            return Err(PineconeClientError::ValueError(
+                "At least one namespace is required".to_string(),
+            ));
+        }
+        if let Some(filter) = &filter {
+            validate_filter(filter)?;
+        }
+        let metric = metric.or_else(|| self.cached_metric()).ok_or_else(|| {
+            PineconeClientError::ValueError(format!(
+                "The metric of index '{name}' isn't known yet, pass `metric` to order the merged results",
+                name = self.name
+            ))
+        })?;
+        let call = self.start_call("query_namespaces()");
+        let mut values = values;
+        if normalize.unwrap_or(self.normalize) {
+            if let Some(values) = values.as_mut() {
+                l2_normalize(values)?;
+            }
+        } else {
+            self.warn_if_not_unit_norm(values.as_deref().map(l2_norm), "query_namespaces()");
+        }
+        let client = self.client(&call);
+        let (values, sparse_values, filter) = (&values, &sparse_values, &filter);
+        let (results, stats) =
+            run_adaptive_with_stats(namespaces, &self.concurrency, |namespace| {
+                let mut client = client.clone();
+                async move {
+                    let results = client
+                        .query(
+                            namespace,
+                            None,
+                            values.clone(),
+                            sparse_values.clone(),
+                            top_k,
+                            filter.clone(),
+                            include_values,
+                            include_metadata,
+                        )
+                        .await?;
+                    Ok(results
+                        .into_iter()
+                        .map(|result| QueryResult {
+                            namespace: Some(namespace.clone()),
+                            ..result
+                        })
+                        .collect::<Vec<_>>())
+                }
+            })
+            .await?;
+        self.config
+            .record_client_retries(&self.stats, stats.retries);
+        merge_ranked(results, metric, top_k)
+    }
+
+    /// Query, then fetch
+    ///
+    /// Runs the query without values and metadata, narrows the results down with `select`, and then fetches values and metadata
+    /// only for the selected results. With a large `top_k`, e.g. the candidates of a reranking pipeline, this keeps the query's
+    /// response small, as values and metadata are only transferred for the final results.
+    ///
+    /// # Arguments
+    /// - `namespace`, `values`, `sparse_values`, `top_k`, `filter`, `normalize` - the query, see [`query()`](Self::query).
+    /// - `include_values` - whether to fetch the selected results' values.
+    /// - `include_metadata` - whether to fetch the selected results' metadata.
+    /// - `select` - picks the final results from the query's results (ids and scores only), e.g. by score or by an external reranker.
+    ///
+    /// # Returns
+    /// The selected QueryResults, in the order returned by `select`, with their values and metadata.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn query_then_fetch(
+        &self,
+        namespace: Option<&str>,
+        values: Option<Vec<f32>>,
+        sparse_values: Option<SparseValues>,
+        top_k: u32,
+        filter: Option<BTreeMap<String, MetadataValue>>,
+        normalize: Option<bool>,
+        include_values: bool,
+        include_metadata: bool,
+        select: impl FnOnce(Vec<QueryResult>) -> Vec<QueryResult>,
+    ) -> PineconeResult<Vec<QueryResult>> {
+        let candidates = self
+            .query(
+                namespace,
+                values,
+                sparse_values,
+                top_k,
+                filter,
+                false,
+                false,
+                normalize,
+            )
+            .await?
+            .results;
+        self.fetch_into_results(
+            namespace,
+            select(candidates),
+            include_values,
+            include_metadata,
+        )
+        .await
+    }
+
+    /// Fills in the values and metadata of query results, by fetching their vectors.
+    /// This is the second phase of [`query_then_fetch()`](Self::query_then_fetch).
+    ///
+    /// # Arguments
+    /// - `namespace` - the namespace that was queried. Defaults to the Index's namespace.
+    /// - `results` - the query results to fill in.
+    /// - `include_values` - whether to fill in the values and sparse values.
+    /// - `include_metadata` - whether to fill in the metadata.
+    ///
+    /// # Returns
+    /// The results, in the same order. Results whose vectors were deleted since the query are left as they are.
+    pub async fn fetch_into_results(
+        &self,
+        namespace: Option<&str>,
+        mut results: Vec<QueryResult>,
+        include_values: bool,
+        include_metadata: bool,
+    ) -> PineconeResult<Vec<QueryResult>> {
+        if results.is_empty() || !(include_values || include_metadata) {
+            return Ok(results);
+        }
+        let ids: Vec<String> = results.iter().map(|r| r.id.clone()).collect();
+        let mut vectors = self.fetch(namespace, &ids).await?.vectors;
+        for result in results.iter_mut() {
+            // Duplicate ids (e.g. returned twice by `select`) share the fetched vector
+            let vector = match vectors.get_mut(&result.id) {
+                Some(vector) => vector,
+                None => continue,
+            };
+            if include_values {
+                result.values = Some(vector.values.clone());
+                result.sparse_values = vector.sparse_values.clone();
+            }
+            if include_metadata {
+                result.metadata = vector.metadata.clone();
+            }
+        }
+        Ok(results)
     }
 
     /// Query by id
@@ -105,7 +932,7 @@ impl Index {
     /// To query by new unseen vector use `Index.query()`
     ///
     /// # Arguments
-    /// - `namespace` - the name of the namespace in which vectors will be queried
+    /// - `namespace` - the name of the namespace in which vectors will be queried. Defaults to the Index's namespace.
     /// - `id` - An id of a vector already upserted to the relevant namespace. The results will be the `top_k` nearest neighbours of the vector with the given id. Can not be used together with `values`.
     /// - `top_k` - The number of results to return for each query.
     /// - `filter` - The filter to apply. You can use vector metadata to limit your search. See <https://www.pinecone.io/docs/metadata-filtering/`>
@@ -113,20 +940,25 @@ impl Index {
     /// - `include_metadata` - Indicates whether metadata is included in the response as well as the ids.
     ///
     /// # Returns
-    /// A list QueryResults
+    /// The QueryResults, along with the query's client-side latency breakdown in [`timing`](QueryResponse::timing).
     pub async fn query_by_id(
-        &mut self,
-        namespace: &str,
+        &self,
+        namespace: Option<&str>,
         id: &str,
         top_k: u32,
         filter: Option<BTreeMap<String, MetadataValue>>,
         include_values: bool,
         include_metadata: bool,
-    ) -> PineconeResult<Vec<QueryResult>> {
-        let res = self
-            .dataplane_client
+    ) -> PineconeResult<QueryResponse> {
+        if let Some(filter) = &filter {
+            validate_filter(filter)?;
+        }
+        let call = self.start_call("query_by_id()");
+        let namespace = self.resolve_namespace(namespace).to_string();
+        let results = self
+            .client(&call)
             .query(
-                namespace,
+                &namespace,
                 Some(id.into()),
                 None,
                 None,
@@ -136,8 +968,63 @@ impl Index {
                 include_metadata,
             )
             .await?;
+        Ok(QueryResponse {
+            results,
+            timing: Some(call.breakdown()),
+        })
+    }
 
-        Ok(res)
+    /// Evaluate recall
+    ///
+    /// Runs the given queries one at a time, and compares their results to the known nearest neighbors.
+    /// The queries are sent sequentially, so the reported latencies aren't skewed by concurrent requests.
+    ///
+    /// # Arguments
+    /// - `namespace` - the name of the namespace to query. Defaults to the Index's namespace.
+    /// - `queries` - the query vectors' dense values.
+    /// - `ground_truth` - the ids of each query's true nearest neighbors, most similar first.
+    ///   See [`brute_force_neighbors`](crate::utils::evaluation::brute_force_neighbors) for computing them from exported vectors.
+    /// - `top_k` - the number of results to request per query, i.e. the `k` of recall@k.
+    /// - `filter` - an optional filter applied to every query.
+    ///
+    /// # Returns
+    /// The mean recall@k along with query latency statistics.
+    pub async fn evaluate_recall(
+        &self,
+        namespace: Option<&str>,
+        queries: &[Vec<f32>],
+        ground_truth: &[Vec<String>],
+        top_k: u32,
+        filter: Option<BTreeMap<String, MetadataValue>>,
+    ) -> PineconeResult<RecallReport> {
+        if queries.len() != ground_truth.len() {
+            return Err(PineconeClientError::ValueError(format!(
+                "Got {queries} queries but ground truth for {truth} queries",
+                queries = queries.len(),
+                truth = ground_truth.len()
+            )));
+        }
+        let mut recalls = Vec::with_capacity(queries.len());
+        let mut latencies = Vec::with_capacity(queries.len());
+        for (query, truth) in queries.iter().zip(ground_truth) {
+            let start = Instant::now();
+            let results = self
+                .query(
+                    namespace,
+                    Some(query.clone()),
+                    None,
+                    top_k,
+                    filter.clone(),
+                    false,
+                    false,
+                    None,
+                )
+                .await?;
+            latencies.push(start.elapsed());
+            let result_ids: Vec<String> = results.results.into_iter().map(|r| r.id).collect();
+            recalls.push(recall_at_k(&result_ids, truth, top_k as usize));
+        }
+        Ok(recall_report(top_k, &recalls, &latencies))
     }
 
     /// Describe index stats
@@ -151,10 +1038,14 @@ impl Index {
     /// # Returns
     /// A map of number of vectors per namespace, total vectors and the index fulness.
     pub async fn describe_index_stats(
-        &mut self,
+        &self,
         filter: Option<BTreeMap<String, MetadataValue>>,
     ) -> PineconeResult<IndexStats> {
-        let res = self.dataplane_client.describe_index_stats(filter).await?;
+        if let Some(filter) = &filter {
+            validate_filter(filter)?;
+        }
+        let call = self.start_call("describe_index_stats()");
+        let res = self.client(&call).describe_index_stats(filter).await?;
         Ok(res)
     }
 
@@ -163,16 +1054,151 @@ impl Index {
     /// The Fetch operation retrieves the vectors with the given ids from the index.
     ///
     /// # Arguments
-    /// - `namespace` - the name of the namespace in which vectors will be fetched
+    /// - `namespace` - the name of the namespace in which vectors will be fetched. Defaults to the Index's namespace.
     /// - `ids` - A list of ids of vectors already upserted to the relevant namespace.
+    ///   Large lists of ids are fetched in concurrent batches.
     ///
+    /// # Returns
+    /// The fetched vectors by id, along with the fetch's client-side latency breakdown in [`timing`](FetchResponse::timing).
     pub async fn fetch(
-        &mut self,
+        &self,
+        namespace: Option<&str>,
+        ids: &[String],
+    ) -> PineconeResult<FetchResponse> {
+        let call = self.start_call("fetch()");
+        let namespace = self.resolve_namespace(namespace).to_string();
+        let vectors = match &self.fetch_cache {
+            Some(cache) => {
+                let (mut vectors, missing_ids) = cache.get_many(&namespace, ids);
+                if !missing_ids.is_empty() {
+                    let fetched = self.fetch_uncached(&call, &namespace, &missing_ids).await?;
+                    cache.insert_many(&namespace, &fetched);
+                    vectors.extend(fetched);
+                }
+                vectors
+            }
+            None => self.fetch_uncached(&call, &namespace, ids).await?,
+        };
+        Ok(FetchResponse {
+            vectors,
+            timing: Some(call.breakdown()),
+        })
+    }
+
+    async fn fetch_uncached(
+        &self,
+        call: &CallLatency,
         namespace: &str,
         ids: &[String],
     ) -> PineconeResult<HashMap<String, Vector>> {
-        let res = self.dataplane_client.fetch(namespace, ids).await?;
-        Ok(res)
+        let mut client = self.client(call);
+        // Only a cached dimension is used, splitting a fetch should never trigger a control plane call
+        let dimension = self
+            .description
+            .read()
+            .unwrap()
+            .as_ref()
+            .map(|db| db.dimension.max(0) as usize);
+        let batch_size = fetch_batch_size(dimension, FETCH_BATCH_SIZE, MAX_FETCH_RESPONSE_BYTES);
+        if ids.len() <= batch_size {
+            return client.fetch(namespace, ids).await;
+        }
+        // Large fetches are split into concurrent batches, to stay within the per-request and response size limits
+        let batches: Vec<&[String]> = ids.chunks(batch_size).collect();
+        let (res, stats) = run_adaptive_with_stats(&batches, &self.concurrency, |batch| {
+            let mut client = client.clone();
+            async move { client.fetch(namespace, batch).await }
+        })
+        .await?;
+        self.config
+            .record_client_retries(&self.stats, stats.retries);
+        Ok(res.into_iter().flatten().collect())
+    }
+
+    /// List
+    ///
+    /// Lists a page of the ids of the vectors in a namespace.
+    ///
+    /// # Arguments
+    /// - `namespace` - the namespace to list. Defaults to the Index's namespace.
+    /// - `prefix` - if set, only ids starting with this prefix are listed.
+    /// - `limit` - the maximal number of ids in the page. Defaults to the server's page size.
+    /// - `pagination_token` - the token returned with the previous page. Starts from the first page if `None`.
+    ///
+    /// # Returns
+    /// The page's ids, and the token of the next page, or `None` if this is the last page.
+    pub async fn list(
+        &self,
+        namespace: Option<&str>,
+        prefix: Option<&str>,
+        limit: Option<u32>,
+        pagination_token: Option<&str>,
+    ) -> PineconeResult<(Vec<String>, Option<String>)> {
+        let call = self.start_call("list()");
+        let namespace = self.resolve_namespace(namespace).to_string();
+        self.client(&call)
+            .list(&namespace, prefix, limit, pagination_token)
+            .await
+    }
+
+    /// Fetches a page of the vectors in a namespace, by listing a page of ids and fetching them.
+    /// This is a single step of [`iter_namespace()`](Self::iter_namespace).
+    ///
+    /// # Returns
+    /// The page's vectors in listing order, and the token of the next page, or `None` if this is the last page.
+    /// Vectors deleted between the listing and the fetch are skipped.
+    pub async fn scan_page(
+        &self,
+        namespace: Option<&str>,
+        page_size: u32,
+        pagination_token: Option<&str>,
+    ) -> PineconeResult<(Vec<Vector>, Option<String>)> {
+        let (ids, next) = self
+            .list(namespace, None, Some(page_size), pagination_token)
+            .await?;
+        if ids.is_empty() {
+            return Ok((Vec::new(), next));
+        }
+        let mut vectors = self.fetch(namespace, &ids).await?.vectors;
+        let page = ids.iter().filter_map(|id| vectors.remove(id)).collect();
+        Ok((page, next))
+    }
+
+    /// Iterates over every vector in a namespace, e.g. to export or re-embed it.
+    /// Pages of `page_size` ids are listed and then fetched, one page at a time.
+    ///
+    /// # Arguments
+    /// - `namespace` - the namespace to scan. Defaults to the Index's namespace.
+    /// - `page_size` - the number of vectors listed and fetched per page.
+    ///
+    /// # Returns
+    /// A stream of the namespace's vectors, which ends after the first error.
+    /// Vectors upserted during the scan may or may not be included.
+    pub fn iter_namespace(
+        &self,
+        namespace: Option<&str>,
+        page_size: u32,
+    ) -> impl Stream<Item = PineconeResult<Vector>> {
+        let index = self.clone();
+        let namespace = self.resolve_namespace(namespace).to_string();
+        // The state is `None` once the last page was fetched
+        let pages = stream::try_unfold(
+            Some((index, namespace, None::<String>)),
+            move |state| async move {
+                let (index, namespace, token) = match state {
+                    Some(state) => state,
+                    None => return PineconeResult::Ok(None),
+                };
+                let (page, next) = index
+                    .scan_page(Some(&namespace), page_size, token.as_deref())
+                    .await?;
+                let state = next.map(|next| (index, namespace, Some(next)));
+                Ok(Some((page, state)))
+            },
+        );
+        pages
+            .map_ok(|page| stream::iter(page.into_iter().map(Ok)))
+            .try_flatten()
     }
 
     /// Update
@@ -182,20 +1208,86 @@ impl Index {
     /// - `id` - The id of the vector to be updated
     /// - `values` - Optional new values for the vector
     /// - `set_metadata` - Optional new metadata keys and values to be updated
-    /// - `namespace` - The name of the namespace in which vectors will be updated
+    /// - `unset_metadata` - Metadata keys to remove from the vector.
+    ///   The API can't remove metadata keys, so the vector is fetched, modified and upserted back instead.
+    ///   Concurrent writes to the same vector may be lost in between.
+    /// - `namespace` - The name of the namespace in which vectors will be updated. Defaults to the Index's namespace.
     ///
     pub async fn update(
-        &mut self,
+        &self,
         id: &str,
         values: Option<&Vec<f32>>,
         sparse_values: Option<SparseValues>,
         set_metadata: Option<BTreeMap<String, MetadataValue>>,
+        unset_metadata: &[String],
+        namespace: Option<&str>,
+    ) -> PineconeResult<UpdateResponse> {
+        let call = self.start_call("update()");
+        let namespace = self.resolve_namespace(namespace).to_string();
+        let res = if unset_metadata.is_empty() {
+            self.client(&call)
+                .update(id, values, sparse_values, set_metadata, &namespace)
+                .await
+                .map_err(PineconeClientError::from)
+        } else {
+            self.rewrite_vector(
+                &call,
+                &namespace,
+                id,
+                values,
+                sparse_values,
+                set_metadata,
+                unset_metadata,
+            )
+            .await
+        };
+        if let Some(cache) = &self.fetch_cache {
+            cache.invalidate(&namespace, [id]);
+        }
+        self.audit("update", Some(&namespace), Some(1), &res);
+        res
+    }
+
+    /// Applies an update that the API doesn't support, by fetching the vector and upserting the updated vector back.
+    #[allow(clippy::too_many_arguments)]
+    async fn rewrite_vector(
+        &self,
+        call: &CallLatency,
         namespace: &str,
-    ) -> PineconeResult<()> {
-        self.dataplane_client
-            .update(id, values, sparse_values, set_metadata, namespace)
-            .await?;
-        Ok(())
+        id: &str,
+        values: Option<&Vec<f32>>,
+        sparse_values: Option<SparseValues>,
+        set_metadata: Option<BTreeMap<String, MetadataValue>>,
+        unset_metadata: &[String],
+    ) -> PineconeResult<UpdateResponse> {
+        let mut client = self.client(call);
+        let mut vector = client
+            .fetch(namespace, &[id.to_string()])
+            .await?
+            .remove(id)
+            .ok_or_else(|| {
+                PineconeClientError::ValueError(format!(
+                    "Cannot update vector '{id}': it was not found in namespace '{namespace}'"
+                ))
+            })?;
+        if let Some(values) = values {
+            vector.values = values.clone();
+        }
+        if sparse_values.is_some() {
+            vector.sparse_values = sparse_values;
+        }
+        let mut metadata = vector.metadata.take().unwrap_or_default();
+        metadata.extend(set_metadata.unwrap_or_default());
+        for key in unset_metadata {
+            metadata.remove(key);
+        }
+        vector.metadata = if metadata.is_empty() {
+            None
+        } else {
+            Some(metadata)
+        };
+        client.upsert(namespace, &[vector]).await?;
+        Ok(UpdateResponse {})
     }
 
     /// Delete
@@ -203,13 +1295,25 @@ impl Index {
     ///
     /// # Arguments
     /// - `ids` - ids of the vectors to be deleted
-    /// - `namespace` - the name of the namespace in which vectors will be deleted
+    /// - `namespace` - the name of the namespace in which vectors will be deleted. Defaults to the Index's namespace.
     ///
-    pub async fn delete(&mut self, ids: Vec<String>, namespace: &str) -> PineconeResult<()> {
-        self.dataplane_client
-            .delete(Some(ids), namespace, None, false)
-            .await?;
-        Ok(())
+    pub async fn delete(
+        &self,
+        ids: Vec<String>,
+        namespace: Option<&str>,
+    ) -> PineconeResult<DeleteResponse> {
+        let call = self.start_call("delete()");
+        let namespace = self.resolve_namespace(namespace).to_string();
+        let res = self
+            .client(&call)
+            .delete(Some(ids.clone()), &namespace, None, false)
+            .await;
+        if let Some(cache) = &self.fetch_cache {
+            cache.invalidate(&namespace, ids.iter().map(String::as_str));
+        }
+        let res = res.map_err(PineconeClientError::from);
+        self.audit("delete", Some(&namespace), Some(ids.len() as u64), &res);
+        res
     }
 
     /// Delete by filter
@@ -217,29 +1321,192 @@ impl Index {
     ///
     /// # Arguments
     /// - `filter` - filter to be applied to delete the vectors
-    /// - `namespace` - the name of the namespace in which vectors will be deleted
+    /// - `namespace` - the name of the namespace in which vectors will be deleted. Defaults to the Index's namespace.
     ///
     pub async fn delete_by_metadata(
-        &mut self,
+        &self,
         filter: Option<BTreeMap<String, MetadataValue>>,
-        namespace: &str,
-    ) -> PineconeResult<()> {
-        self.dataplane_client
-            .delete(None, namespace, filter, false)
-            .await?;
-        Ok(())
+        namespace: Option<&str>,
+    ) -> PineconeResult<DeleteResponse> {
+        if let Some(filter) = &filter {
+            validate_filter(filter)?;
+        }
+        let call = self.start_call("delete_by_metadata()");
+        let namespace = self.resolve_namespace(namespace).to_string();
+        let res = self
+            .client(&call)
+            .delete(None, &namespace, filter, false)
+            .await;
+        // The deleted ids aren't known, so the whole namespace is invalidated
+        if let Some(cache) = &self.fetch_cache {
+            cache.invalidate_namespace(&namespace);
+        }
+        let res = res.map_err(PineconeClientError::from);
+        self.audit("delete_by_metadata", Some(&namespace), None, &res);
+        res
     }
 
     /// Delete all
     /// The delete all operation deletes all the vectors from a given namespace.
     ///
     /// # Arguments
-    /// - `namespace` - the name of the namespace in which vectors will be deleted
+    /// - `namespace` - the name of the namespace in which vectors will be deleted. Defaults to the Index's namespace.
     ///
-    pub async fn delete_all(&mut self, namespace: &str) -> PineconeResult<()> {
-        self.dataplane_client
-            .delete(None, namespace, None, true)
-            .await?;
-        Ok(())
+    pub async fn delete_all(&self, namespace: Option<&str>) -> PineconeResult<DeleteResponse> {
+        let call = self.start_call("delete_all()");
+        let namespace = self.resolve_namespace(namespace).to_string();
+        let res = self
+            .client(&call)
+            .delete(None, &namespace, None, true)
+            .await;
+        if let Some(cache) = &self.fetch_cache {
+            cache.invalidate_namespace(&namespace);
+        }
+        let res = res.map_err(PineconeClientError::from);
+        self.audit("delete_all", Some(&namespace), None, &res);
+        res
+    }
+}
+
+/// Merges the results of the same query in several namespaces into a single ranking of the `top_k` best results.
+fn merge_ranked(
+    results: Vec<Vec<QueryResult>>,
+    metric: Metric,
+    top_k: u32,
+) -> PineconeResult<Vec<QueryResult>> {
+    let merged = results.into_iter().flatten().collect();
+    similarity::sort_results(merged, metric.as_str(), Some(top_k as usize))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::grpc::ChannelOptions;
+
+    fn result(id: &str, score: f32, namespace: &str) -> QueryResult {
+        QueryResult {
+            id: id.to_string(),
+            score,
+            values: None,
+            sparse_values: None,
+            metadata: None,
+            namespace: Some(namespace.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_merge_ranked() {
+        let results = || {
+            vec![
+                vec![result("a1", 0.9, "a"), result("a2", 0.2, "a")],
+                vec![result("b1", 0.5, "b"), result("b2", 0.1, "b")],
+            ]
+        };
+        let ids = |results: Vec<QueryResult>| {
+            results
+                .into_iter()
+                .map(|result| format!("{}/{}", result.namespace.unwrap(), result.id))
+                .collect::<Vec<_>>()
+        };
+        let merged = merge_ranked(results(), Metric::Cosine, 3).unwrap();
+        assert_eq!(ids(merged), ["a/a1", "b/b1", "a/a2"]);
+        // Euclidean scores are distances, so the lowest scores rank first
+        let merged = merge_ranked(results(), Metric::Euclidean, 2).unwrap();
+        assert_eq!(ids(merged), ["b/b2", "a/a2"]);
+    }
+
+    // An index without a control plane client, whose lazy channel points at a port with nothing listening on it
+    async fn unreachable_index() -> Index {
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let options = ChannelOptions {
+            lazy: true,
+            ..Default::default()
+        };
+        let client = DataplaneGrpcClient::connect_with_runtime(
+            format!("http://{addr}"),
+            "key",
+            None,
+            options,
+        )
+        .await
+        .unwrap();
+        Index::new("idx".to_string(), client).with_retry_policy(RetryPolicy {
+            max_retries: 0,
+            ..Default::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_describe_in_background() {
+        let mut index = unreachable_index().await;
+        let control_plane_client =
+            ControlPlaneClient::new("http://127.0.0.1:1", "key", &ClientConfig::default()).unwrap();
+        index.set_control_plane_client(Arc::new(control_plane_client), None);
+
+        // Diagnostics are the only reason to describe the index without being asked to
+        let mut config = ClientConfig::default();
+        config.diagnostics = false;
+        index.set_config(config.clone());
+        index.warn_if_not_unit_norm(Some(5.0), "query()");
+        assert!(!index.description_requested.load(Ordering::Relaxed));
+
+        config.diagnostics = true;
+        index.set_config(config);
+        index.warn_if_not_unit_norm(Some(5.0), "query()");
+        assert!(index.description_requested.load(Ordering::Relaxed));
+        assert!(index.description.read().unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_query_namespaces_without_control_plane() {
+        let index = unreachable_index().await;
+        let namespaces = ["a".to_string(), "b".to_string()];
+
+        // The metric isn't known, so it must be passed rather than guessed
+        assert_eq!(index.cached_metric(), None);
+        let err = index
+            .query_namespaces(
+                &namespaces,
+                Some(vec![0.5, 0.5]),
+                None,
+                2,
+                None,
+                false,
+                false,
+                None,
+                None,
+            )
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, PineconeClientError::ValueError(msg) if msg.contains("`metric`")),
+            "{err}"
+        );
+
+        *index.description.write().unwrap() = Some(Db {
+            metric: Some(Metric::DotProduct),
+            ..Default::default()
+        });
+        assert_eq!(index.cached_metric(), Some(Metric::DotProduct));
+
+        // The index has no control plane client, so the query must go straight to the data plane
+        let err = index
+            .query_namespaces(
+                &namespaces,
+                Some(vec![0.5, 0.5]),
+                None,
+                2,
+                None,
+                false,
+                false,
+                None,
+                None,
+            )
+            .await
+            .unwrap_err();
+        assert!(!err.to_string().contains("can't be described"), "{err}");
     }
 }
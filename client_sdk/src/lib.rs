@@ -1,4 +1,18 @@
+pub mod blocking;
 pub mod client;
 pub mod data_types;
 pub mod index;
+#[cfg(feature = "proto")]
+pub mod proto;
+#[cfg(not(feature = "proto"))]
+pub(crate) mod proto;
+pub mod upsert_writer;
 pub mod utils;
+
+#[cfg(feature = "derive")]
+pub use client_sdk_derive::PineconeMetadata;
+pub use utils::metadata_mapping::PineconeMetadata;
+
+// Lets the code generated by `client_sdk_derive` refer to `::client_sdk` from within this crate's tests
+#[cfg(test)]
+extern crate self as client_sdk;
@@ -0,0 +1,7 @@
+//! The gRPC request and response types of the data plane, generated from `proto/vector_service.proto`.
+//!
+//! These are an implementation detail of [`DataplaneGrpcClient`](crate::client::grpc::DataplaneGrpcClient),
+//! and are only public with the `proto` feature enabled, for advanced users who need to build requests directly.
+//! The proto file declares no package, since the server exposes the service under the unqualified `VectorService` name,
+//! so the generated code is included here under a named module instead of tonic's default `_`.
+tonic::include_proto!("_");
@@ -0,0 +1,291 @@
+//! A buffered writer for streaming ingestion, e.g. of vectors consumed from a message queue one at a time.
+//!
+//! Vectors written to an [`UpsertWriter`] are buffered, and upserted by a background task once `batch_size` vectors
+//! are buffered, or once the oldest buffered vector has waited for `flush_interval`, whichever comes first.
+use std::future::Future;
+use std::time::Duration;
+
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+use crate::data_types::Vector;
+use crate::index::{Index, UpsertOptions};
+use crate::utils::errors::{PineconeClientError, PineconeResult};
+
+const DEFAULT_BATCH_SIZE: usize = 100;
+const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Settings of an [`UpsertWriter`].
+#[derive(Debug, Clone)]
+pub struct UpsertWriterConfig {
+    /// The namespace to upsert into. Defaults to the Index's namespace.
+    pub namespace: Option<String>,
+    /// The number of buffered vectors that triggers a flush.
+    pub batch_size: usize,
+    /// The longest time a vector is buffered before it is flushed.
+    pub flush_interval: Duration,
+    /// The number of vectors that can be waiting for the background task before `write()` waits for room.
+    /// Defaults to `batch_size`.
+    pub max_pending: Option<usize>,
+    /// Options for every upsert. With a `batch_size` here too, each flush is split into concurrent requests.
+    pub upsert_options: UpsertOptions,
+}
+
+impl Default for UpsertWriterConfig {
+    fn default() -> Self {
+        UpsertWriterConfig {
+            namespace: None,
+            batch_size: DEFAULT_BATCH_SIZE,
+            flush_interval: DEFAULT_FLUSH_INTERVAL,
+            max_pending: None,
+            upsert_options: UpsertOptions::default(),
+        }
+    }
+}
+
+enum Command {
+    Write(Vector),
+    Flush(oneshot::Sender<PineconeResult<()>>),
+}
+
+/// Buffers vectors and upserts them to an index in the background.
+///
+/// Failed upserts don't stop the writer. The first error since the last [`flush()`](Self::flush) is returned by the next
+/// `flush()` or [`close()`](Self::close), so callers that need to know which vectors made it should flush periodically,
+/// e.g. before acknowledging the messages they consumed.
+pub struct UpsertWriter {
+    sender: mpsc::Sender<Command>,
+    worker: JoinHandle<PineconeResult<()>>,
+}
+
+impl UpsertWriter {
+    /// Starts a writer for `index`. Must be called from within a tokio runtime, which runs the background task.
+    pub fn new(index: Index, config: UpsertWriterConfig) -> PineconeResult<Self> {
+        let namespace = config.namespace.clone();
+        let options = config.upsert_options.clone();
+        Self::with_sink(config, move |vectors: Vec<Vector>| {
+            let index = index.clone();
+            let namespace = namespace.clone();
+            let options = options.clone();
+            async move {
+                index
+                    .upsert(namespace.as_deref(), &vectors, options)
+                    .await
+                    .map(|_| ())
+            }
+        })
+    }
+
+    fn with_sink<S, Fut>(config: UpsertWriterConfig, sink: S) -> PineconeResult<Self>
+    where
+        S: FnMut(Vec<Vector>) -> Fut + Send + 'static,
+        Fut: Future<Output = PineconeResult<()>> + Send + 'static,
+    {
+        if config.batch_size == 0 {
+            return Err(PineconeClientError::ValueError(
+                "batch_size must be greater than 0".to_string(),
+            ));
+        }
+        if config.flush_interval.is_zero() {
+            return Err(PineconeClientError::ValueError(
+                "flush_interval must be greater than 0".to_string(),
+            ));
+        }
+        let (sender, receiver) =
+            mpsc::channel(config.max_pending.unwrap_or(config.batch_size).max(1));
+        let worker = tokio::spawn(run_worker(receiver, config, sink));
+        Ok(UpsertWriter { sender, worker })
+    }
+
+    /// Adds a vector to the buffer. Waits if the background task is falling behind.
+    pub async fn write(&self, vector: Vector) -> PineconeResult<()> {
+        self.sender
+            .send(Command::Write(vector))
+            .await
+            .map_err(|_| closed())
+    }
+
+    /// Upserts all the vectors written so far, and waits for them to be upserted.
+    ///
+    /// # Returns
+    /// The first error of the upserts since the last call to `flush()`, if any.
+    pub async fn flush(&self) -> PineconeResult<()> {
+        let (reply, response) = oneshot::channel();
+        self.sender
+            .send(Command::Flush(reply))
+            .await
+            .map_err(|_| closed())?;
+        response.await.map_err(|_| closed())?
+    }
+
+    /// Upserts the remaining vectors and stops the background task.
+    ///
+    /// # Returns
+    /// The first error of the upserts since the last call to `flush()`, if any.
+    pub async fn close(self) -> PineconeResult<()> {
+        drop(self.sender);
+        self.worker
+            .await
+            .map_err(|err| PineconeClientError::Other(format!("Upsert writer failed: {err}")))?
+    }
+}
+
+fn closed() -> PineconeClientError {
+    PineconeClientError::Other("The upsert writer's background task has stopped".to_string())
+}
+
+async fn run_worker<S, Fut>(
+    mut receiver: mpsc::Receiver<Command>,
+    config: UpsertWriterConfig,
+    mut sink: S,
+) -> PineconeResult<()>
+where
+    S: FnMut(Vec<Vector>) -> Fut,
+    Fut: Future<Output = PineconeResult<()>>,
+{
+    let mut buffer = Vec::with_capacity(config.batch_size);
+    let mut error = None;
+    // When the oldest buffered vector is due, if any
+    let mut deadline = None;
+    loop {
+        let command = match deadline {
+            Some(due) => tokio::select! {
+                command = receiver.recv() => command,
+                _ = tokio::time::sleep_until(due) => {
+                    flush_buffer(&mut buffer, &mut sink, &mut error).await;
+                    deadline = None;
+                    continue;
+                }
+            },
+            None => receiver.recv().await,
+        };
+        match command {
+            Some(Command::Write(vector)) => {
+                if buffer.is_empty() {
+                    deadline = Some(Instant::now() + config.flush_interval);
+                }
+                buffer.push(vector);
+                if buffer.len() >= config.batch_size {
+                    flush_buffer(&mut buffer, &mut sink, &mut error).await;
+                    deadline = None;
+                }
+            }
+            Some(Command::Flush(reply)) => {
+                flush_buffer(&mut buffer, &mut sink, &mut error).await;
+                deadline = None;
+                // The caller may have stopped waiting, which is fine
+                let _ = reply.send(error.take().map_or(Ok(()), Err));
+            }
+            None => {
+                flush_buffer(&mut buffer, &mut sink, &mut error).await;
+                return error.map_or(Ok(()), Err);
+            }
+        }
+    }
+}
+
+async fn flush_buffer<S, Fut>(
+    buffer: &mut Vec<Vector>,
+    sink: &mut S,
+    error: &mut Option<PineconeClientError>,
+) where
+    S: FnMut(Vec<Vector>) -> Fut,
+    Fut: Future<Output = PineconeResult<()>>,
+{
+    if buffer.is_empty() {
+        return;
+    }
+    let count = buffer.len();
+    if let Err(err) = sink(std::mem::take(buffer)).await {
+        log::warn!("Upsert writer failed to upsert {count} vectors: {err}");
+        error.get_or_insert(err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    fn vector(id: &str) -> Vector {
+        Vector {
+            id: id.to_string(),
+            values: vec![0.5, 0.5],
+            ..Default::default()
+        }
+    }
+
+    fn recording_writer(
+        config: UpsertWriterConfig,
+    ) -> (UpsertWriter, Arc<Mutex<Vec<Vec<String>>>>) {
+        let batches = Arc::new(Mutex::new(Vec::new()));
+        let recorded = batches.clone();
+        let writer = UpsertWriter::with_sink(config, move |vectors: Vec<Vector>| {
+            let ids: Vec<String> = vectors.into_iter().map(|v| v.id).collect();
+            let failed = ids.iter().any(|id| id == "bad");
+            recorded.lock().unwrap().push(ids);
+            async move {
+                if failed {
+                    Err(PineconeClientError::Other("rejected".to_string()))
+                } else {
+                    Ok(())
+                }
+            }
+        })
+        .unwrap();
+        (writer, batches)
+    }
+
+    #[tokio::test]
+    async fn test_flushes_full_batches() {
+        let (writer, batches) = recording_writer(UpsertWriterConfig {
+            batch_size: 2,
+            flush_interval: Duration::from_secs(60),
+            ..Default::default()
+        });
+        for id in ["a", "b", "c"] {
+            writer.write(vector(id)).await.unwrap();
+        }
+        writer.flush().await.unwrap();
+        assert_eq!(*batches.lock().unwrap(), vec![vec!["a", "b"], vec!["c"]]);
+        writer.write(vector("d")).await.unwrap();
+        writer.close().await.unwrap();
+        assert_eq!(batches.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_flushes_after_interval() {
+        let (writer, batches) = recording_writer(UpsertWriterConfig {
+            batch_size: 100,
+            flush_interval: Duration::from_millis(10),
+            ..Default::default()
+        });
+        writer.write(vector("a")).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(*batches.lock().unwrap(), vec![vec!["a"]]);
+        writer.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_reports_errors_on_flush() {
+        let (writer, _) = recording_writer(UpsertWriterConfig {
+            batch_size: 1,
+            ..Default::default()
+        });
+        writer.write(vector("bad")).await.unwrap();
+        writer.write(vector("good")).await.unwrap();
+        assert!(writer.flush().await.is_err());
+        // The error is only reported once
+        writer.flush().await.unwrap();
+        writer.close().await.unwrap();
+        assert!(UpsertWriter::with_sink(
+            UpsertWriterConfig {
+                batch_size: 0,
+                ..Default::default()
+            },
+            |_| async { Ok(()) }
+        )
+        .is_err());
+    }
+}
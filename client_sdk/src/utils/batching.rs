@@ -0,0 +1,135 @@
+use crate::data_types::{MetadataValue, Vector};
+
+/// The maximal size of an upsert request accepted by the service.
+pub const MAX_UPSERT_REQUEST_BYTES: usize = 2 * 1024 * 1024;
+
+/// The response size that fetch requests are split to stay under.
+pub const MAX_FETCH_RESPONSE_BYTES: usize = 4 * 1024 * 1024;
+
+/// The assumed size of a fetched vector's id, metadata and sparse values, on top of its dense values.
+const FETCH_VECTOR_OVERHEAD_BYTES: usize = 2048;
+
+/// Leaves room for the request's namespace and the protobuf framing, which aren't part of the estimates.
+const REQUEST_OVERHEAD_BYTES: usize = 1024;
+
+/// Estimates the size of a vector once encoded into an upsert request.
+/// The estimate errs on the large side, so batches built from it stay under the request size limit.
+pub fn estimated_encoded_len(vector: &Vector) -> usize {
+    let sparse_len = vector
+        .sparse_values
+        .as_ref()
+        .map(|sparse| sparse.indices.len() * 5 + sparse.values.len() * 4 + 8)
+        .unwrap_or(0);
+    let metadata_len = vector
+        .metadata
+        .as_ref()
+        .map(|metadata| metadata_struct_len(metadata.iter()))
+        .unwrap_or(0);
+    vector.id.len() + vector.values.len() * 4 + sparse_len + metadata_len + 16
+}
+
+fn metadata_struct_len<'a>(fields: impl Iterator<Item = (&'a String, &'a MetadataValue)>) -> usize {
+    fields
+        .map(|(key, value)| key.len() + metadata_value_len(value) + 8)
+        .sum()
+}
+
+fn metadata_value_len(value: &MetadataValue) -> usize {
+    match value {
+        MetadataValue::StringVal(v) => v.len() + 6,
+        MetadataValue::BoolVal(_) => 4,
+        MetadataValue::NumberVal(_) => 11,
+        MetadataValue::ListVal(values) => values.iter().map(metadata_value_len).sum::<usize>() + 6,
+        MetadataValue::DictVal(fields) => metadata_struct_len(fields.iter()) + 6,
+    }
+}
+
+/// Splits vectors into consecutive batches of at most `max_count` vectors, whose estimated encoded size is at most `max_bytes`.
+/// A single vector larger than `max_bytes` gets a batch of its own, and is left for the service to reject.
+pub fn split_by_size(vectors: &[Vector], max_count: usize, max_bytes: usize) -> Vec<&[Vector]> {
+    let max_bytes = max_bytes.saturating_sub(REQUEST_OVERHEAD_BYTES);
+    let mut batches = Vec::new();
+    let mut start = 0;
+    let mut batch_bytes = 0;
+    for (i, vector) in vectors.iter().enumerate() {
+        let len = estimated_encoded_len(vector);
+        if i > start && (i - start >= max_count || batch_bytes + len > max_bytes) {
+            batches.push(&vectors[start..i]);
+            start = i;
+            batch_bytes = 0;
+        }
+        batch_bytes += len;
+    }
+    if start < vectors.len() {
+        batches.push(&vectors[start..]);
+    }
+    batches
+}
+
+/// The number of ids to fetch per request, so the fetched vectors fit in a response of `max_bytes`.
+///
+/// # Arguments
+/// - `dimension` - the index's dimension, if known. Otherwise, only `max_ids` applies.
+/// - `max_ids` - the maximal number of ids per request.
+/// - `max_bytes` - the maximal response size.
+pub fn fetch_batch_size(dimension: Option<usize>, max_ids: usize, max_bytes: usize) -> usize {
+    match dimension {
+        Some(dimension) => {
+            (max_bytes / (dimension * 4 + FETCH_VECTOR_OVERHEAD_BYTES)).clamp(1, max_ids)
+        }
+        None => max_ids.max(1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vectors(count: usize, dimension: usize) -> Vec<Vector> {
+        (0..count)
+            .map(|i| Vector {
+                id: i.to_string(),
+                values: vec![0.5; dimension],
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fetch_batch_size() {
+        assert_eq!(fetch_batch_size(None, 1000, MAX_FETCH_RESPONSE_BYTES), 1000);
+        assert_eq!(
+            fetch_batch_size(Some(8), 1000, MAX_FETCH_RESPONSE_BYTES),
+            1000
+        );
+        let batch_size = fetch_batch_size(Some(1536), 1000, MAX_FETCH_RESPONSE_BYTES);
+        assert!(batch_size < 1000);
+        assert!(batch_size * 1536 * 4 <= MAX_FETCH_RESPONSE_BYTES);
+        assert_eq!(fetch_batch_size(Some(1_000_000), 1000, 1024), 1);
+    }
+
+    #[test]
+    fn test_split_by_count() {
+        let vectors = vectors(10, 4);
+        let batches = split_by_size(&vectors, 4, MAX_UPSERT_REQUEST_BYTES);
+        let sizes: Vec<usize> = batches.iter().map(|b| b.len()).collect();
+        assert_eq!(sizes, vec![4, 4, 2]);
+        assert!(split_by_size(&[], 4, MAX_UPSERT_REQUEST_BYTES).is_empty());
+    }
+
+    #[test]
+    fn test_split_by_size() {
+        // Each vector is estimated at a bit over 6KB
+        let vectors = vectors(1000, 1536);
+        let batches = split_by_size(&vectors, usize::MAX, MAX_UPSERT_REQUEST_BYTES);
+        assert!(batches.len() > 1);
+        assert_eq!(batches.iter().map(|b| b.len()).sum::<usize>(), 1000);
+        for batch in &batches {
+            let size: usize = batch.iter().map(estimated_encoded_len).sum();
+            assert!(size <= MAX_UPSERT_REQUEST_BYTES);
+        }
+        // Oversized vectors are still sent, one per batch
+        let batches = split_by_size(&vectors[..3], usize::MAX, 100);
+        assert_eq!(batches.len(), 3);
+    }
+}
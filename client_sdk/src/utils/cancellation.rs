@@ -0,0 +1,42 @@
+use std::future::Future;
+
+use tokio_util::sync::CancellationToken;
+
+use crate::utils::errors::{PineconeClientError, PineconeResult};
+
+/// Runs `fut` to completion, unless `cancellation_token` is cancelled first.
+/// On cancellation, `fut` is dropped (aborting any in-flight request) and a `Cancelled` error is returned.
+pub async fn run_cancellable<F, T>(
+    cancellation_token: Option<&CancellationToken>,
+    operation: &str,
+    fut: F,
+) -> PineconeResult<T>
+where
+    F: Future<Output = PineconeResult<T>>,
+{
+    let cancellation_token = match cancellation_token {
+        Some(token) => token,
+        None => return fut.await,
+    };
+    tokio::select! {
+        res = fut => res,
+        _ = cancellation_token.cancelled() => Err(PineconeClientError::Cancelled(operation.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_run_cancellable() {
+        let token = CancellationToken::new();
+        let res = run_cancellable(Some(&token), "test", async { Ok(1) }).await;
+        assert_eq!(res.unwrap(), 1);
+
+        token.cancel();
+        let res: PineconeResult<()> =
+            run_cancellable(Some(&token), "test", std::future::pending()).await;
+        assert!(matches!(res, Err(PineconeClientError::Cancelled(_))));
+    }
+}
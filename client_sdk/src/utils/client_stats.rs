@@ -0,0 +1,109 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::data_types::ClientStats;
+
+#[derive(Debug, Default)]
+struct Counters {
+    requests: BTreeMap<String, u64>,
+    bytes_sent: u64,
+    bytes_received: u64,
+    errors: u64,
+    retries: u64,
+    total_latency: Duration,
+}
+
+/// Counts the data plane requests made through an index. Shared between an index and all of its views.
+#[derive(Debug, Default)]
+pub struct ClientStatsRecorder(Mutex<Counters>);
+
+impl ClientStatsRecorder {
+    /// Records a single request.
+    ///
+    /// # Arguments
+    /// - `operation` - the request's operation, e.g. "upsert".
+    /// - `bytes_sent` - the encoded size of the request.
+    /// - `bytes_received` - the encoded size of the response, or `None` if the request failed.
+    /// - `latency` - the time from sending the request to receiving its response or error.
+    pub fn record_request(
+        &self,
+        operation: &str,
+        bytes_sent: usize,
+        bytes_received: Option<usize>,
+        latency: Duration,
+    ) {
+        let mut counters = self.lock();
+        *counters.requests.entry(operation.to_string()).or_default() += 1;
+        counters.bytes_sent += bytes_sent as u64;
+        match bytes_received {
+            Some(bytes) => counters.bytes_received += bytes as u64,
+            None => counters.errors += 1,
+        }
+        counters.total_latency += latency;
+    }
+
+    /// Records batches that were re-sent after the index reported it was overloaded.
+    pub fn record_retries(&self, retries: u32) {
+        self.lock().retries += u64::from(retries);
+    }
+
+    /// Returns the current counters.
+    pub fn snapshot(&self) -> ClientStats {
+        let counters = self.lock();
+        let total_requests: u64 = counters.requests.values().sum();
+        ClientStats {
+            requests: counters.requests.clone(),
+            total_requests,
+            bytes_sent: counters.bytes_sent,
+            bytes_received: counters.bytes_received,
+            errors: counters.errors,
+            retries: counters.retries,
+            average_latency: if total_requests > 0 {
+                counters.total_latency.as_secs_f64() / total_requests as f64
+            } else {
+                0.0
+            },
+        }
+    }
+
+    /// Resets all counters to zero.
+    pub fn reset(&self) {
+        *self.lock() = Counters::default();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Counters> {
+        // The counters are always left in a consistent state, so a poisoned lock can be safely reused
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_stats() {
+        let recorder = ClientStatsRecorder::default();
+        assert_eq!(recorder.snapshot(), ClientStats::default());
+
+        recorder.record_request("upsert", 100, Some(10), Duration::from_millis(30));
+        recorder.record_request("upsert", 50, None, Duration::from_millis(10));
+        recorder.record_request("query", 20, Some(200), Duration::from_millis(20));
+        recorder.record_retries(2);
+        let stats = recorder.snapshot();
+        assert_eq!(stats.requests["upsert"], 2);
+        assert_eq!(stats.requests["query"], 1);
+        assert_eq!(stats.total_requests, 3);
+        assert_eq!(stats.bytes_sent, 170);
+        assert_eq!(stats.bytes_received, 210);
+        assert_eq!(stats.errors, 1);
+        assert_eq!(stats.retries, 2);
+        assert!((stats.average_latency - 0.02).abs() < 1e-9);
+
+        recorder.reset();
+        assert_eq!(recorder.snapshot(), ClientStats::default());
+    }
+}
@@ -0,0 +1,436 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use futures::stream::{FuturesUnordered, StreamExt};
+
+use crate::data_types::UpsertReport;
+use crate::utils::errors::{PineconeClientError, PineconeResult};
+
+const DEFAULT_INITIAL_CONCURRENCY: f64 = 4.0;
+const DEFAULT_MAX_CONCURRENCY: f64 = 64.0;
+
+/// The concurrency limit is multiplied by this factor when the index is overloaded.
+const DECREASE_FACTOR: f64 = 0.5;
+
+/// A request taking longer than this factor times the smoothed latency is considered a latency spike.
+const LATENCY_SPIKE_FACTOR: f64 = 3.0;
+
+/// The weight given to each new latency sample in the smoothed latency.
+const LATENCY_SMOOTHING: f64 = 0.1;
+
+/// How many times a single batch is re-queued after the index reported `RESOURCE_EXHAUSTED`.
+const MAX_RESOURCE_EXHAUSTED_RETRIES: u32 = 5;
+const RESOURCE_EXHAUSTED_BACKOFF: Duration = Duration::from_millis(100);
+
+/// An AIMD (additive-increase, multiplicative-decrease) concurrency controller.
+///
+/// The concurrency limit grows by roughly one request per round-trip while requests are healthy,
+/// and is halved whenever the index is overloaded: either it responded with `RESOURCE_EXHAUSTED`,
+/// or a request took much longer than usual.
+#[derive(Debug, Clone)]
+pub struct AimdController {
+    limit: f64,
+    max_limit: f64,
+    smoothed_latency: Option<Duration>,
+    // Incremented on every decrease. Requests started before the last decrease don't trigger another one,
+    // so a single overload event doesn't collapse the limit multiple times.
+    generation: u64,
+}
+
+impl Default for AimdController {
+    fn default() -> Self {
+        AimdController::new(
+            DEFAULT_INITIAL_CONCURRENCY as usize,
+            DEFAULT_MAX_CONCURRENCY as usize,
+        )
+    }
+}
+
+impl AimdController {
+    pub fn new(initial_limit: usize, max_limit: usize) -> Self {
+        let max_limit = max_limit.max(1) as f64;
+        AimdController {
+            limit: (initial_limit as f64).clamp(1.0, max_limit),
+            max_limit,
+            smoothed_latency: None,
+            generation: 0,
+        }
+    }
+
+    /// The current number of requests allowed to be in flight.
+    pub fn limit(&self) -> usize {
+        self.limit as usize
+    }
+
+    fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Records a successful request, started at `generation`, that took `latency`.
+    pub fn on_success(&mut self, generation: u64, latency: Duration) {
+        let is_spike = match self.smoothed_latency {
+            Some(smoothed) => latency.as_secs_f64() > smoothed.as_secs_f64() * LATENCY_SPIKE_FACTOR,
+            None => false,
+        };
+        self.smoothed_latency = Some(match self.smoothed_latency {
+            Some(smoothed) => {
+                smoothed.mul_f64(1.0 - LATENCY_SMOOTHING) + latency.mul_f64(LATENCY_SMOOTHING)
+            }
+            None => latency,
+        });
+        if is_spike {
+            self.on_overload(generation);
+        } else {
+            self.limit = (self.limit + 1.0 / self.limit).min(self.max_limit);
+        }
+    }
+
+    /// Records that a request started at `generation` found the index overloaded.
+    pub fn on_overload(&mut self, generation: u64) {
+        if generation != self.generation {
+            return;
+        }
+        self.limit = (self.limit * DECREASE_FACTOR).max(1.0);
+        self.generation += 1;
+    }
+}
+
+fn is_resource_exhausted(err: &PineconeClientError) -> bool {
+    match err {
+        PineconeClientError::DataplaneOperationError(status) => {
+            status.code() == tonic::Code::ResourceExhausted
+        }
+        _ => false,
+    }
+}
+
+/// The `p`-th percentile (0 to 100) of `latencies`, using the nearest-rank method. Zero if there are no latencies.
+pub fn percentile(latencies: &[Duration], p: f64) -> Duration {
+    if latencies.is_empty() {
+        return Duration::ZERO;
+    }
+    let mut sorted = latencies.to_vec();
+    sorted.sort();
+    let rank = ((p.clamp(0.0, 100.0) / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.saturating_sub(1)]
+}
+
+/// Statistics collected while running a batched operation with [`run_adaptive_with_stats`].
+#[derive(Debug, Clone, Default)]
+pub struct BatchStats {
+    /// The number of times a batch was re-queued after the index reported `RESOURCE_EXHAUSTED`.
+    pub retries: u32,
+    /// The latency of every successful batch request, in completion order.
+    pub latencies: Vec<Duration>,
+}
+
+impl BatchStats {
+    /// The `p`-th percentile (0 to 100) of the batch latencies.
+    pub fn latency_percentile(&self, p: f64) -> Duration {
+        percentile(&self.latencies, p)
+    }
+
+    /// Summarizes a batched upsert of `total_vectors` vectors that took `duration` overall.
+    pub fn upsert_report(&self, total_vectors: u64, duration: Duration) -> UpsertReport {
+        let secs = duration.as_secs_f64();
+        UpsertReport {
+            total_vectors,
+            batch_count: self.latencies.len() as u32,
+            duration: secs,
+            throughput: if secs > 0.0 {
+                total_vectors as f64 / secs
+            } else {
+                0.0
+            },
+            retries: self.retries,
+            latency_p50: self.latency_percentile(50.0).as_secs_f64(),
+            latency_p90: self.latency_percentile(90.0).as_secs_f64(),
+            latency_p99: self.latency_percentile(99.0).as_secs_f64(),
+            latency_max: self.latency_percentile(100.0).as_secs_f64(),
+        }
+    }
+}
+
+/// Runs `f` on every item, keeping as many requests in flight as `controller` allows.
+/// Items rejected with `RESOURCE_EXHAUSTED` are re-queued after a short backoff.
+///
+/// # Returns
+/// The results in the same order as `items`, or the first non-retryable error.
+/// On error, the remaining in-flight requests are cancelled.
+pub async fn run_adaptive<'a, T, R, F, Fut>(
+    items: &'a [T],
+    controller: &Mutex<AimdController>,
+    f: F,
+) -> PineconeResult<Vec<R>>
+where
+    F: FnMut(&'a T) -> Fut,
+    Fut: Future<Output = PineconeResult<R>> + 'a,
+{
+    let (results, _) = run_adaptive_with_stats(items, controller, f).await?;
+    Ok(results)
+}
+
+/// Same as [`run_adaptive`], but also returns statistics about the run.
+pub async fn run_adaptive_with_stats<'a, T, R, F, Fut>(
+    items: &'a [T],
+    controller: &Mutex<AimdController>,
+    f: F,
+) -> PineconeResult<(Vec<R>, BatchStats)>
+where
+    F: FnMut(&'a T) -> Fut,
+    Fut: Future<Output = PineconeResult<R>> + 'a,
+{
+    run_adaptive_capped(items, controller, None, f).await
+}
+
+/// Same as [`run_adaptive_with_stats`], but never keeps more than `max_concurrency` requests in flight,
+/// regardless of the controller's limit.
+pub async fn run_adaptive_capped<'a, T, R, F, Fut>(
+    items: &'a [T],
+    controller: &Mutex<AimdController>,
+    max_concurrency: Option<usize>,
+    f: F,
+) -> PineconeResult<(Vec<R>, BatchStats)>
+where
+    F: FnMut(&'a T) -> Fut,
+    Fut: Future<Output = PineconeResult<R>> + 'a,
+{
+    let (results, stats) = run_adaptive_inner(items, controller, max_concurrency, true, f).await?;
+    let results = results.into_iter().collect::<PineconeResult<Vec<R>>>()?;
+    Ok((results, stats))
+}
+
+/// Same as [`run_adaptive_capped`], but runs every item to completion, even after some of them failed.
+///
+/// # Returns
+/// The result of every item, in the same order as `items`.
+pub async fn run_adaptive_settled<'a, T, R, F, Fut>(
+    items: &'a [T],
+    controller: &Mutex<AimdController>,
+    max_concurrency: Option<usize>,
+    f: F,
+) -> (Vec<PineconeResult<R>>, BatchStats)
+where
+    F: FnMut(&'a T) -> Fut,
+    Fut: Future<Output = PineconeResult<R>> + 'a,
+{
+    match run_adaptive_inner(items, controller, max_concurrency, false, f).await {
+        Ok(res) => res,
+        Err(_) => unreachable!("Errors are only returned when failing fast"),
+    }
+}
+
+async fn run_adaptive_inner<'a, T, R, F, Fut>(
+    items: &'a [T],
+    controller: &Mutex<AimdController>,
+    max_concurrency: Option<usize>,
+    fail_fast: bool,
+    mut f: F,
+) -> PineconeResult<(Vec<PineconeResult<R>>, BatchStats)>
+where
+    F: FnMut(&'a T) -> Fut,
+    Fut: Future<Output = PineconeResult<R>> + 'a,
+{
+    let max_concurrency = max_concurrency.unwrap_or(usize::MAX).max(1);
+    let mut stats = BatchStats::default();
+    let mut queue: VecDeque<(usize, u32)> = (0..items.len()).map(|i| (i, 0)).collect();
+    let mut results: Vec<Option<PineconeResult<R>>> = items.iter().map(|_| None).collect();
+    let mut in_flight = FuturesUnordered::new();
+
+    while !queue.is_empty() || !in_flight.is_empty() {
+        let limit = controller.lock().unwrap().limit().min(max_concurrency);
+        while in_flight.len() < limit {
+            let (idx, attempt) = match queue.pop_front() {
+                Some(next) => next,
+                None => break,
+            };
+            let request = f(&items[idx]);
+            let generation = controller.lock().unwrap().generation();
+            in_flight.push(async move {
+                if attempt > 0 {
+                    tokio::time::sleep(RESOURCE_EXHAUSTED_BACKOFF * attempt).await;
+                }
+                let start = Instant::now();
+                let res = request.await;
+                (idx, attempt, generation, start.elapsed(), res)
+            });
+        }
+
+        let (idx, attempt, generation, latency, res) = match in_flight.next().await {
+            Some(done) => done,
+            None => break,
+        };
+        match res {
+            Ok(r) => {
+                controller.lock().unwrap().on_success(generation, latency);
+                stats.latencies.push(latency);
+                results[idx] = Some(Ok(r));
+            }
+            Err(err) if is_resource_exhausted(&err) && attempt < MAX_RESOURCE_EXHAUSTED_RETRIES => {
+                controller.lock().unwrap().on_overload(generation);
+                stats.retries += 1;
+                queue.push_front((idx, attempt + 1));
+            }
+            Err(err) if fail_fast => return Err(err),
+            Err(err) => results[idx] = Some(Err(err)),
+        }
+    }
+
+    Ok((results.into_iter().flatten().collect(), stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_aimd_increase_and_decrease() {
+        let mut controller = AimdController::new(4, 8);
+        for _ in 0..100 {
+            controller.on_success(controller.generation(), Duration::from_millis(10));
+        }
+        assert_eq!(controller.limit(), 8);
+
+        controller.on_overload(controller.generation());
+        assert_eq!(controller.limit(), 4);
+        // A stale request doesn't decrease the limit again
+        controller.on_overload(0);
+        assert_eq!(controller.limit(), 4);
+    }
+
+    #[test]
+    fn test_aimd_latency_spike() {
+        let mut controller = AimdController::new(8, 8);
+        controller.on_success(0, Duration::from_millis(10));
+        controller.on_success(0, Duration::from_millis(100));
+        assert_eq!(controller.limit(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_run_adaptive_requeues_resource_exhausted() {
+        let controller = Mutex::new(AimdController::new(4, 4));
+        let calls = AtomicUsize::new(0);
+        let items: Vec<u32> = (0..10).collect();
+        let results = run_adaptive(&items, &controller, |item| {
+            let call = calls.fetch_add(1, Ordering::Relaxed);
+            async move {
+                if call == 3 {
+                    Err(tonic::Status::resource_exhausted("slow down").into())
+                } else {
+                    Ok(item * 2)
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(results, (0..10).map(|i| i * 2).collect::<Vec<_>>());
+        assert_eq!(calls.load(Ordering::Relaxed), 11);
+        // The limit may already have recovered through the successes that followed, but it was decreased
+        assert!(controller.lock().unwrap().generation() > 0);
+    }
+
+    #[tokio::test]
+    async fn test_run_adaptive_stats() {
+        let controller = Mutex::new(AimdController::new(1, 1));
+        let calls = AtomicUsize::new(0);
+        let items: Vec<u32> = (0..4).collect();
+        let (_, stats) = run_adaptive_with_stats(&items, &controller, |_| {
+            let call = calls.fetch_add(1, Ordering::Relaxed);
+            async move {
+                if call == 0 {
+                    Err(tonic::Status::resource_exhausted("slow down").into())
+                } else {
+                    Ok(())
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(stats.retries, 1);
+        assert_eq!(stats.latencies.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_run_adaptive_capped() {
+        let controller = Mutex::new(AimdController::new(8, 8));
+        let in_flight = AtomicUsize::new(0);
+        let max_in_flight = AtomicUsize::new(0);
+        let items: Vec<u32> = (0..20).collect();
+        let (results, _) = run_adaptive_capped(&items, &controller, Some(2), |item| {
+            let in_flight = &in_flight;
+            let max_in_flight = &max_in_flight;
+            async move {
+                let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(current, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(1)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok(*item)
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(results, items);
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_run_adaptive_settled() {
+        let controller = Mutex::new(AimdController::default());
+        let items: Vec<u32> = (0..6).collect();
+        let (results, _) = run_adaptive_settled(&items, &controller, None, |item| async move {
+            if item % 3 == 0 {
+                Err(tonic::Status::invalid_argument("bad vector").into())
+            } else {
+                Ok(*item)
+            }
+        })
+        .await;
+        let failed: Vec<usize> = results
+            .iter()
+            .enumerate()
+            .filter(|(_, res)| res.is_err())
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(failed, vec![0, 3]);
+        assert_eq!(results[4].as_ref().unwrap(), &4);
+    }
+
+    #[test]
+    fn test_latency_percentile() {
+        let stats = BatchStats {
+            retries: 0,
+            latencies: (1..=10).rev().map(Duration::from_millis).collect(),
+        };
+        assert_eq!(stats.latency_percentile(50.0), Duration::from_millis(5));
+        assert_eq!(stats.latency_percentile(90.0), Duration::from_millis(9));
+        assert_eq!(stats.latency_percentile(100.0), Duration::from_millis(10));
+        assert_eq!(
+            BatchStats::default().latency_percentile(99.0),
+            Duration::ZERO
+        );
+
+        let report = stats.upsert_report(1000, Duration::from_secs(2));
+        assert_eq!(report.batch_count, 10);
+        assert_eq!(report.throughput, 500.0);
+        assert_eq!(report.latency_max, 0.01);
+        assert!(report.to_json().contains("\"throughput\":500.0"));
+    }
+
+    #[tokio::test]
+    async fn test_run_adaptive_fails_on_other_errors() {
+        let controller = Mutex::new(AimdController::default());
+        let items: Vec<u32> = (0..10).collect();
+        let res = run_adaptive(&items, &controller, |item| async move {
+            if *item == 5 {
+                Err(tonic::Status::invalid_argument("bad vector").into())
+            } else {
+                Ok(())
+            }
+        })
+        .await;
+        assert!(res.is_err());
+    }
+}
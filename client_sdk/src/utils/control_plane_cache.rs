@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::data_types::Db;
+
+#[derive(Debug, Default)]
+struct Entries {
+    index_list: Option<(Instant, Vec<String>)>,
+    descriptions: HashMap<String, (Instant, Db)>,
+}
+
+/// A short-lived cache of control plane reads (`list_indexes()` and `describe_index()`), in which entries expire after a TTL.
+/// Meant for frameworks that call these repeatedly during startup, and would otherwise get rate limited.
+#[derive(Debug)]
+pub struct ControlPlaneCache {
+    entries: Mutex<Entries>,
+    ttl: Duration,
+}
+
+impl ControlPlaneCache {
+    pub fn new(ttl: Duration) -> Self {
+        ControlPlaneCache {
+            entries: Mutex::new(Entries::default()),
+            ttl,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Entries> {
+        // The cache is always left in a consistent state, so a poisoned lock can be safely reused
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn index_list(&self) -> Option<Vec<String>> {
+        match &self.lock().index_list {
+            Some((inserted_at, list)) if inserted_at.elapsed() < self.ttl => Some(list.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn set_index_list(&self, list: &[String]) {
+        self.lock().index_list = Some((Instant::now(), list.to_vec()));
+    }
+
+    pub fn description(&self, index_name: &str) -> Option<Db> {
+        match self.lock().descriptions.get(index_name) {
+            Some((inserted_at, db)) if inserted_at.elapsed() < self.ttl => Some(db.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn set_description(&self, index_name: &str, db: &Db) {
+        self.lock()
+            .descriptions
+            .insert(index_name.to_string(), (Instant::now(), db.clone()));
+    }
+
+    /// Drops the cached description of the given index, along with the index list, which it may have changed.
+    pub fn invalidate(&self, index_name: &str) {
+        let mut entries = self.lock();
+        entries.index_list = None;
+        entries.descriptions.remove(index_name);
+    }
+
+    pub fn clear(&self) {
+        *self.lock() = Entries::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn db(name: &str) -> Db {
+        Db {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_control_plane_cache() {
+        let cache = ControlPlaneCache::new(Duration::from_secs(60));
+        assert_eq!(cache.index_list(), None);
+        cache.set_index_list(&["a".to_string(), "b".to_string()]);
+        cache.set_description("a", &db("a"));
+        cache.set_description("b", &db("b"));
+        assert_eq!(cache.index_list().unwrap().len(), 2);
+        assert_eq!(cache.description("a").unwrap().name, "a");
+
+        cache.invalidate("a");
+        assert_eq!(cache.index_list(), None);
+        assert!(cache.description("a").is_none());
+        assert_eq!(cache.description("b").unwrap().name, "b");
+
+        cache.clear();
+        assert!(cache.description("b").is_none());
+    }
+
+    #[test]
+    fn test_control_plane_cache_expiry() {
+        let cache = ControlPlaneCache::new(Duration::ZERO);
+        cache.set_index_list(&["a".to_string()]);
+        cache.set_description("a", &db("a"));
+        assert_eq!(cache.index_list(), None);
+        assert!(cache.description("a").is_none());
+    }
+}
@@ -1,14 +1,34 @@
 use crate::client::grpc::{GrpcScoredVector, GrpcSparseValues, GrpcVector};
-use crate::data_types::{Collection, Db, MetadataValue, QueryResult, SparseValues, Vector};
+use crate::data_types::{
+    Collection, ConfigureIndexOptions, Db, DeleteResponse, ExtraFields, MetadataValue, QueryResult,
+    ReadCapacity, SparseValues, UpdateResponse, Vector,
+};
+use crate::proto;
 use crate::utils::errors::PineconeClientError::{MetadataError, MetadataValueError};
 use crate::utils::errors::{PineconeClientError, PineconeResult};
+use crate::utils::filter_validation::validate_filter;
 use index_service::models::IndexMetaStatus;
 use index_service::models::{
     CollectionMeta, CreateCollectionRequest, CreateRequest, CreateRequestMetadataConfig, IndexMeta,
+    PatchRequest,
 };
 use prost_types::value::Kind;
 use prost_types::{ListValue as ProstListValue, Struct, Value as ProstValue};
 use std::collections::BTreeMap;
+use std::str::FromStr;
+
+// The responses are currently empty. Fields the server adds in the future should be converted here.
+impl From<proto::UpdateResponse> for UpdateResponse {
+    fn from(_: proto::UpdateResponse) -> Self {
+        UpdateResponse {}
+    }
+}
+
+impl From<proto::DeleteResponse> for DeleteResponse {
+    fn from(_: proto::DeleteResponse) -> Self {
+        DeleteResponse {}
+    }
+}
 
 impl From<SparseValues> for GrpcSparseValues {
     fn from(value: SparseValues) -> Self {
@@ -111,14 +131,76 @@ impl From<MetadataValue> for ProstValue {
     }
 }
 
-impl From<Db> for CreateRequest {
-    fn from(index: Db) -> Self {
-        CreateRequest {
+const MAX_INDEX_NAME_LEN: usize = 45;
+const MAX_DIMENSION: i32 = 20_000;
+
+/// Validates the settings of a new index, so invalid combinations fail before the request is sent:
+/// - the name must be 1 to 45 lowercase alphanumeric characters or '-', and start and end with an alphanumeric character.
+/// - the dimension must be positive, or 0 if the index is created from a source collection.
+/// - the number of replicas, shards and pods must be positive, and the number of pods must be the number of shards
+///   times the number of replicas.
+pub fn validate_new_index(db: &Db) -> PineconeResult<()> {
+    let invalid = |msg: String| Err(PineconeClientError::ValueError(msg));
+    let name = &db.name;
+    let valid_name = !name.is_empty()
+        && name.len() <= MAX_INDEX_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && !name.starts_with('-')
+        && !name.ends_with('-');
+    if !valid_name {
+        return invalid(format!(
+            "Invalid index name '{name}'. Names must be 1 to {MAX_INDEX_NAME_LEN} lowercase alphanumeric characters or '-', \
+            and start and end with an alphanumeric character"
+        ));
+    }
+    if db.dimension < 0 || db.dimension > MAX_DIMENSION {
+        return invalid(format!("dimension must be between 1 and {MAX_DIMENSION}"));
+    }
+    if db.dimension == 0 && db.source_collection.is_none() {
+        return invalid(
+            "dimension is required, unless the index is created from a source_collection"
+                .to_string(),
+        );
+    }
+    for (setting, value) in [
+        ("replicas", db.replicas),
+        ("shards", db.shards),
+        ("pods", db.pods),
+    ] {
+        if value.is_some_and(|value| value < 1) {
+            return invalid(format!("{setting} must be greater than 0"));
+        }
+    }
+    if let Some(pods) = db.pods {
+        let replicas = db.replicas.unwrap_or(1);
+        match db.shards {
+            Some(shards) if pods != shards * replicas => {
+                return invalid(format!(
+                    "pods ({pods}) must be the number of shards ({shards}) times the number of replicas ({replicas})"
+                ))
+            }
+            None if pods % replicas != 0 => {
+                return invalid("pods must be a multiple of replicas".to_string())
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+impl TryFrom<Db> for CreateRequest {
+    type Error = PineconeClientError;
+    /// Validates the settings, see [`validate_new_index`], and converts them into the controller's request.
+    fn try_from(index: Db) -> Result<Self, Self::Error> {
+        validate_new_index(&index)?;
+        Ok(CreateRequest {
             name: index.name,
             dimension: index.dimension,
             replicas: index.replicas,
-            pod_type: index.pod_type,
-            metric: index.metric,
+            pod_type: index.pod_type.map(|pod_type| pod_type.to_string()),
+            metric: index.metric.map(|metric| metric.to_string()),
             pods: index.pods,
             shards: index.shards,
             source_collection: index.source_collection,
@@ -128,7 +210,110 @@ impl From<Db> for CreateRequest {
                 }))
             }),
             ..Default::default()
+        })
+    }
+}
+
+const MAX_TAGS: usize = 20;
+const MAX_TAG_KEY_LEN: usize = 80;
+const MAX_TAG_VALUE_LEN: usize = 120;
+
+impl TryFrom<ConfigureIndexOptions> for PatchRequest {
+    type Error = PineconeClientError;
+    /// Validates the combination of settings, and converts them into the controller's request.
+    fn try_from(options: ConfigureIndexOptions) -> Result<Self, Self::Error> {
+        let invalid = |msg: &str| Err(PineconeClientError::ValueError(msg.to_string()));
+        let pod_settings =
+            options.pod_type.is_some() || options.replicas.is_some() || options.pods.is_some();
+        let serverless_settings = options.embed.is_some() || options.read_capacity.is_some();
+        if !pod_settings
+            && !serverless_settings
+            && options.deletion_protection.is_none()
+            && options.tags.is_none()
+        {
+            return invalid("At least one setting must be changed");
+        }
+        if pod_settings && serverless_settings {
+            return invalid(
+                "Pod settings (pod_type, replicas, pods) can't be combined with serverless settings (embed, read_capacity)",
+            );
+        }
+        if options.replicas.is_some_and(|replicas| replicas < 1) {
+            return invalid("replicas must be greater than 0");
         }
+        if let Some(pods) = options.pods {
+            if pods < 1 {
+                return invalid("pods must be greater than 0");
+            }
+            if let Some(replicas) = options.replicas {
+                if pods % replicas != 0 {
+                    return invalid("pods must be a multiple of replicas");
+                }
+            }
+        }
+        if let Some(tags) = &options.tags {
+            if tags.len() > MAX_TAGS {
+                return Err(PineconeClientError::ValueError(format!(
+                    "An index can have at most {MAX_TAGS} tags"
+                )));
+            }
+            for (key, value) in tags {
+                let valid_key = !key.is_empty()
+                    && key.len() <= MAX_TAG_KEY_LEN
+                    && key
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+                if !valid_key {
+                    return Err(PineconeClientError::ValueError(format!(
+                        "Invalid tag key '{key}'. Keys must be 1 to {MAX_TAG_KEY_LEN} alphanumeric characters, '_' or '-'"
+                    )));
+                }
+                if value.len() > MAX_TAG_VALUE_LEN {
+                    return Err(PineconeClientError::ValueError(format!(
+                        "The value of tag '{key}' is longer than {MAX_TAG_VALUE_LEN} characters"
+                    )));
+                }
+            }
+        }
+        if let Some(embed) = &options.embed {
+            if embed.field_map.as_ref().is_some_and(BTreeMap::is_empty) {
+                return invalid("embed.field_map must map at least one field");
+            }
+        }
+        let read_capacity = match options.read_capacity {
+            Some(ReadCapacity::OnDemand) => Some(serde_json::json!({ "mode": "OnDemand" })),
+            Some(ReadCapacity::Dedicated {
+                node_type,
+                replicas,
+                shards,
+            }) => {
+                if node_type.is_empty() || replicas < 1 || shards < 1 {
+                    return invalid(
+                        "Dedicated read capacity requires a node_type, and at least one replica and shard",
+                    );
+                }
+                Some(serde_json::json!({
+                    "mode": "Dedicated",
+                    "dedicated": {
+                        "node_type": node_type,
+                        "scaling": "Manual",
+                        "manual": { "replicas": replicas, "shards": shards },
+                    },
+                }))
+            }
+            None => None,
+        };
+        Ok(PatchRequest {
+            replicas: options.replicas,
+            pod_type: options.pod_type.map(|pod_type| pod_type.to_string()),
+            pods: options.pods,
+            deletion_protection: options
+                .deletion_protection
+                .map(|enabled| if enabled { "enabled" } else { "disabled" }.to_string()),
+            tags: options.tags.map(|tags| tags.into_iter().collect()),
+            embed: options.embed.map(serde_json::to_value).transpose()?,
+            read_capacity,
+        })
     }
 }
 
@@ -148,11 +333,13 @@ impl TryFrom<IndexMeta> for Db {
                 })?;
                 let replicas = db.replicas;
                 let shards = db.shards;
-                let pod_type = db.pod_type;
+                // Values this version of the client doesn't know are kept in the extra fields, instead of failing
+                let mut extra = ExtraFields::default();
+                let pod_type = parse_or_keep(db.pod_type, "database.pod_type", &mut extra);
                 let dimension = db.dimension.ok_or_else(|| {
                     PineconeClientError::Other("Failed to parse db dimension".to_string())
                 })?;
-                let metric = db.metric;
+                let metric = parse_or_keep(db.metric, "database.metric", &mut extra);
                 let pods = db.pods;
                 let source_collection = db.source_collection;
                 let metadata_config = db.metadata_config.map(|config| {
@@ -173,6 +360,7 @@ impl TryFrom<IndexMeta> for Db {
                     source_collection,
                     metadata_config,
                     status,
+                    extra,
                 })
             }
             None => Err(PineconeClientError::Other("Failed to parse db".to_string())),
@@ -180,6 +368,23 @@ impl TryFrom<IndexMeta> for Db {
     }
 }
 
+fn parse_or_keep<T: FromStr>(
+    value: Option<String>,
+    path: &str,
+    extra: &mut ExtraFields,
+) -> Option<T> {
+    let value = value?;
+    match value.parse() {
+        Ok(parsed) => Some(parsed),
+        Err(_) => {
+            extra
+                .0
+                .insert(path.to_string(), serde_json::Value::String(value));
+            None
+        }
+    }
+}
+
 impl From<Collection> for CreateCollectionRequest {
     fn from(collection: Collection) -> Self {
         CreateCollectionRequest {
@@ -196,7 +401,9 @@ impl From<CollectionMeta> for Collection {
             source: "".to_string(),
             vector_count: None,
             size: collection_meta.size,
+            dimension: collection_meta.dimension,
             status: collection_meta.status,
+            extra: Default::default(),
         }
     }
 }
@@ -226,6 +433,105 @@ pub fn prost_struct_to_hashmap(dict: Struct) -> PineconeResult<BTreeMap<String,
     Ok(fields)
 }
 
+impl TryFrom<serde_json::Value> for MetadataValue {
+    type Error = PineconeClientError;
+
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        match value {
+            serde_json::Value::String(v) => Ok(MetadataValue::StringVal(v)),
+            serde_json::Value::Bool(v) => Ok(MetadataValue::BoolVal(v)),
+            serde_json::Value::Number(v) => match v.as_f64() {
+                Some(v) => Ok(MetadataValue::NumberVal(v)),
+                None => Err(MetadataValueError {
+                    val_type: format!("number {v}"),
+                }),
+            },
+            serde_json::Value::Array(values) => {
+                let mut inners = Vec::with_capacity(values.len());
+                for item in values {
+                    let new_val = item.try_into().map_err(|e| match e {
+                        MetadataValueError { val_type } => MetadataValueError {
+                            val_type: format!("{val_type} value in a list"),
+                        },
+                        _ => e,
+                    })?;
+                    inners.push(new_val);
+                }
+                Ok(MetadataValue::ListVal(inners))
+            }
+            serde_json::Value::Object(fields) => {
+                let mut inners = BTreeMap::new();
+                for (k, v) in fields {
+                    let new_val = v.try_into().map_err(|e| match e {
+                        MetadataValueError { val_type } => MetadataError {
+                            key: k.clone(),
+                            val_type,
+                        },
+                        MetadataError { key, val_type } => MetadataError {
+                            key: format!("{k}: {key}"),
+                            val_type,
+                        },
+                        _ => e,
+                    })?;
+                    inners.insert(k, new_val);
+                }
+                Ok(MetadataValue::DictVal(inners))
+            }
+            serde_json::Value::Null => Err(MetadataValueError {
+                val_type: "null".into(),
+            }),
+        }
+    }
+}
+
+/// Integral numbers are converted to JSON integers, so e.g. a `year` field round-trips as `2020` rather than `2020.0`.
+/// Non-finite numbers, which JSON can't represent, are converted to `null`.
+impl From<MetadataValue> for serde_json::Value {
+    fn from(value: MetadataValue) -> Self {
+        // Integers beyond 2^53 can't be represented exactly by an f64 anyway
+        const MAX_SAFE_INTEGER: f64 = 9007199254740992.0;
+        match value {
+            MetadataValue::StringVal(v) => serde_json::Value::String(v),
+            MetadataValue::BoolVal(v) => serde_json::Value::Bool(v),
+            MetadataValue::NumberVal(v) if v.fract() == 0.0 && v.abs() <= MAX_SAFE_INTEGER => {
+                serde_json::Value::from(v as i64)
+            }
+            MetadataValue::NumberVal(v) => serde_json::Number::from_f64(v)
+                .map(serde_json::Value::Number)
+                .unwrap_or(serde_json::Value::Null),
+            MetadataValue::ListVal(v) => {
+                serde_json::Value::Array(v.into_iter().map(Into::into).collect())
+            }
+            MetadataValue::DictVal(v) => {
+                serde_json::Value::Object(v.into_iter().map(|(k, v)| (k, v.into())).collect())
+            }
+        }
+    }
+}
+
+/// Converts a filter given as JSON (e.g. `json!({"genre": {"$eq": "drama"}})`) into a metadata filter,
+/// validating its operators and operand types.
+///
+/// # Arguments
+/// - `filter` - a JSON object in the metadata filter syntax, see <https://docs.pinecone.io/docs/metadata-filtering>
+///
+/// # Returns
+/// The filter, ready to be passed to `Index::query()`, `Index::describe_index_stats()` or `Index::delete_by_metadata()`.
+pub fn json_to_filter(
+    filter: serde_json::Value,
+) -> PineconeResult<BTreeMap<String, MetadataValue>> {
+    let filter = match filter.try_into()? {
+        MetadataValue::DictVal(filter) => filter,
+        _ => {
+            return Err(PineconeClientError::ValueError(
+                "A filter must be a JSON object".to_string(),
+            ))
+        }
+    };
+    validate_filter(&filter)?;
+    Ok(filter)
+}
+
 impl From<Vector> for GrpcVector {
     fn from(grpc_vector: Vector) -> Self {
         GrpcVector {
@@ -276,6 +582,182 @@ impl TryFrom<GrpcScoredVector> for QueryResult {
                 .metadata
                 .map(prost_struct_to_hashmap)
                 .transpose()?,
+            namespace: None,
         })
     }
 }
+
+/// Collects the fields of a control plane response that are missing from its typed model.
+///
+/// # Arguments
+/// - `content` - the raw JSON body of the response.
+/// - `typed` - the response, as parsed into its typed model.
+///
+/// # Returns
+/// The unknown fields, keyed by their dotted path in the response. Fields that are `null` are skipped.
+pub fn unknown_fields<T: serde::Serialize>(content: &str, typed: &T) -> ExtraFields {
+    let mut extra = BTreeMap::new();
+    if let (Ok(raw), Ok(known)) = (
+        serde_json::from_str::<serde_json::Value>(content),
+        serde_json::to_value(typed),
+    ) {
+        collect_unknown_fields(&raw, &known, "", &mut extra);
+    }
+    ExtraFields(extra)
+}
+
+fn collect_unknown_fields(
+    raw: &serde_json::Value,
+    known: &serde_json::Value,
+    prefix: &str,
+    extra: &mut BTreeMap<String, serde_json::Value>,
+) {
+    let (raw, known) = match (raw, known) {
+        (serde_json::Value::Object(raw), serde_json::Value::Object(known)) => (raw, known),
+        _ => return,
+    };
+    for (key, value) in raw {
+        if value.is_null() {
+            continue;
+        }
+        let path = format!("{prefix}{key}");
+        match known.get(key) {
+            Some(known_value) => {
+                collect_unknown_fields(value, known_value, &format!("{path}."), extra)
+            }
+            None => {
+                extra.insert(path, value.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_configure_index_options() {
+        let patch = PatchRequest::try_from(ConfigureIndexOptions {
+            replicas: Some(2),
+            pods: Some(4),
+            deletion_protection: Some(true),
+            tags: Some([("team".to_string(), "search".to_string())].into()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(patch.pods, Some(4));
+        assert_eq!(patch.deletion_protection.as_deref(), Some("enabled"));
+        assert_eq!(patch.tags.unwrap()["team"], "search");
+
+        let patch = PatchRequest::try_from(ConfigureIndexOptions {
+            read_capacity: Some(ReadCapacity::OnDemand),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(patch.read_capacity, Some(json!({"mode": "OnDemand"})));
+
+        let invalid = [
+            ConfigureIndexOptions::default(),
+            ConfigureIndexOptions {
+                replicas: Some(3),
+                pods: Some(4),
+                ..Default::default()
+            },
+            ConfigureIndexOptions {
+                replicas: Some(2),
+                read_capacity: Some(ReadCapacity::OnDemand),
+                ..Default::default()
+            },
+            ConfigureIndexOptions {
+                tags: Some([("bad key".to_string(), "v".to_string())].into()),
+                ..Default::default()
+            },
+        ];
+        for options in invalid {
+            assert!(PatchRequest::try_from(options).is_err());
+        }
+    }
+
+    #[test]
+    fn test_index_meta_with_unknown_settings() {
+        let index_meta: IndexMeta = serde_json::from_value(json!({
+            "database": {"name": "idx", "dimension": 8, "metric": "dotproduct", "pod_type": "s2.x1"}
+        }))
+        .unwrap();
+        let db = Db::try_from(index_meta).unwrap();
+        assert_eq!(db.metric, Some(crate::data_types::Metric::DotProduct));
+        assert_eq!(db.pod_type, None);
+        assert_eq!(db.extra.0["database.pod_type"], json!("s2.x1"));
+    }
+
+    #[test]
+    fn test_json_to_filter() {
+        let filter = json_to_filter(json!({
+            "genre": {"$in": ["drama", "comedy"]},
+            "$or": [{"year": {"$gte": 2020}}, {"featured": true}]
+        }))
+        .unwrap();
+        assert!(matches!(
+            filter.get("genre"),
+            Some(MetadataValue::DictVal(_))
+        ));
+        assert!(
+            matches!(filter.get("$or"), Some(MetadataValue::ListVal(clauses)) if clauses.len() == 2)
+        );
+
+        assert!(json_to_filter(json!(["genre"])).is_err());
+        assert!(json_to_filter(json!({"genre": null})).is_err());
+        assert!(matches!(
+            json_to_filter(json!({"year": {"$gt": "2020"}})),
+            Err(PineconeClientError::FilterError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_metadata_value_json_roundtrip() {
+        let json = json!({
+            "genre": "drama",
+            "year": 2020,
+            "rating": 4.5,
+            "tags": ["a", "b"],
+            "featured": false,
+            "nested": {"key": 1}
+        });
+        let metadata = MetadataValue::try_from(json.clone()).unwrap();
+        assert_eq!(serde_json::Value::from(metadata), json);
+
+        match MetadataValue::try_from(json!({"genre": {"key": null}})) {
+            Err(MetadataError { key, .. }) => assert_eq!(key, "genre: key"),
+            other => panic!("expected a MetadataError, got {other:?}"),
+        }
+        assert_eq!(
+            serde_json::Value::from(MetadataValue::NumberVal(f64::NAN)),
+            serde_json::Value::Null
+        );
+    }
+
+    #[test]
+    fn test_unknown_fields() {
+        let content = json!({
+            "database": {"name": "idx", "dimension": 8, "deletion_protection": "enabled", "pods": null},
+            "status": {"ready": true, "state": "Ready"},
+            "spec": {"serverless": {"cloud": "aws"}}
+        })
+        .to_string();
+        let typed: IndexMeta = serde_json::from_str(&content).unwrap();
+        let extra = unknown_fields(&content, &typed);
+        assert_eq!(
+            extra.0,
+            BTreeMap::from([
+                ("database.deletion_protection".to_string(), json!("enabled")),
+                ("spec".to_string(), json!({"serverless": {"cloud": "aws"}})),
+            ])
+        );
+
+        let content = json!({"name": "coll", "size": 10}).to_string();
+        let typed: CollectionMeta = serde_json::from_str(&content).unwrap();
+        assert_eq!(unknown_fields(&content, &typed), ExtraFields::default());
+    }
+}
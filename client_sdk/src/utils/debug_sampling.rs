@@ -0,0 +1,156 @@
+//! Sampling of debug logs, so request/response logging can be left on in production without flooding the logs,
+//! e.g. during bulk ingestion.
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+/// Controls which debug log messages are emitted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebugLogSampling {
+    /// The fraction of messages to emit, between 0 and 1. Messages are sampled evenly, e.g. 0.1 emits every 10th message.
+    pub sample_rate: f64,
+    /// The maximal number of messages to emit per second, after sampling. The number of suppressed messages is logged once the limit resets.
+    pub max_per_second: Option<u32>,
+    /// If set, only messages of these operations (e.g. `"query"`, `"upsert"`) are emitted.
+    pub operations: Option<Vec<String>>,
+}
+
+impl Default for DebugLogSampling {
+    fn default() -> Self {
+        DebugLogSampling {
+            sample_rate: 1.0,
+            max_per_second: None,
+            operations: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct SamplerState {
+    // The number of messages seen by the sampler, before rate limiting
+    seen: u64,
+    window_start: Instant,
+    emitted_in_window: u32,
+    suppressed: u64,
+}
+
+impl Default for SamplerState {
+    fn default() -> Self {
+        SamplerState {
+            seen: 0,
+            window_start: Instant::now(),
+            emitted_in_window: 0,
+            suppressed: 0,
+        }
+    }
+}
+
+/// The outcome of sampling a single message.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Sample {
+    Skip,
+    /// Emit the message. `suppressed` messages were dropped by the rate limit since the last emitted message.
+    Emit {
+        suppressed: u64,
+    },
+}
+
+/// Applies a [`DebugLogSampling`] to a stream of messages. Shared between clones of a client and its indexes.
+#[derive(Debug, Default)]
+pub(crate) struct DebugLogSampler {
+    settings: RwLock<DebugLogSampling>,
+    state: Mutex<SamplerState>,
+}
+
+impl DebugLogSampler {
+    pub(crate) fn settings(&self) -> DebugLogSampling {
+        self.settings.read().unwrap().clone()
+    }
+
+    pub(crate) fn set_settings(&self, settings: DebugLogSampling) {
+        *self.settings.write().unwrap() = settings;
+        *self.state.lock().unwrap() = SamplerState::default();
+    }
+
+    pub(crate) fn sample(&self, operation: &str) -> Sample {
+        let settings = self.settings.read().unwrap();
+        if let Some(operations) = &settings.operations {
+            if !operations.iter().any(|op| op == operation) {
+                return Sample::Skip;
+            }
+        }
+        let mut state = self.state.lock().unwrap();
+        // Emit a message whenever the running count of sampled messages crosses an integer
+        let rate = settings.sample_rate.clamp(0.0, 1.0);
+        let before = (state.seen as f64 * rate).floor();
+        state.seen += 1;
+        if (state.seen as f64 * rate).floor() <= before {
+            return Sample::Skip;
+        }
+        if let Some(max_per_second) = settings.max_per_second {
+            if state.window_start.elapsed() >= Duration::from_secs(1) {
+                state.window_start = Instant::now();
+                state.emitted_in_window = 0;
+            }
+            if state.emitted_in_window >= max_per_second {
+                state.suppressed += 1;
+                return Sample::Skip;
+            }
+            state.emitted_in_window += 1;
+        }
+        Sample::Emit {
+            suppressed: std::mem::take(&mut state.suppressed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn emitted(sampler: &DebugLogSampler, operation: &str, count: usize) -> usize {
+        (0..count)
+            .filter(|_| sampler.sample(operation) != Sample::Skip)
+            .count()
+    }
+
+    #[test]
+    fn test_sample_rate() {
+        let sampler = DebugLogSampler::default();
+        assert_eq!(emitted(&sampler, "query", 100), 100);
+        sampler.set_settings(DebugLogSampling {
+            sample_rate: 0.1,
+            ..Default::default()
+        });
+        assert_eq!(emitted(&sampler, "query", 100), 10);
+        sampler.set_settings(DebugLogSampling {
+            sample_rate: 0.0,
+            ..Default::default()
+        });
+        assert_eq!(emitted(&sampler, "query", 100), 0);
+    }
+
+    #[test]
+    fn test_operation_filter() {
+        let sampler = DebugLogSampler::default();
+        sampler.set_settings(DebugLogSampling {
+            operations: Some(vec!["query".to_string()]),
+            ..Default::default()
+        });
+        assert_eq!(emitted(&sampler, "upsert", 10), 0);
+        assert_eq!(emitted(&sampler, "query", 10), 10);
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let sampler = DebugLogSampler::default();
+        sampler.set_settings(DebugLogSampling {
+            max_per_second: Some(3),
+            ..Default::default()
+        });
+        assert_eq!(emitted(&sampler, "upsert", 10), 3);
+        // Start a new window, in which the first message reports the suppressed ones
+        sampler.state.lock().unwrap().window_start -= Duration::from_secs(1);
+        assert_eq!(sampler.sample("upsert"), Sample::Emit { suppressed: 7 });
+        assert_eq!(sampler.sample("upsert"), Sample::Emit { suppressed: 0 });
+    }
+}
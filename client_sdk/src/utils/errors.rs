@@ -1,6 +1,9 @@
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
+use std::time::Duration;
 use thiserror::Error;
 
+use crate::client::grpc::is_transport_error;
+
 #[derive(Error, Debug)]
 pub enum PineconeClientError {
     #[error("Invalid value for argument {name}: {found:?})")]
@@ -20,6 +23,12 @@ pub enum PineconeClientError {
         actual: String,
     },
 
+    #[error(
+        "Unknown region '{region}'. Did you mean '{suggestions}'? \
+        Use `list_supported_environments()` to list the available environments"
+    )]
+    UnknownRegionError { region: String, suggestions: String },
+
     #[error("Failed to connect to Pinecone's controller on region {region}. Please verify client configuration: API key, region and project_id. \
         See more info: https://docs.pinecone.io/docs/quickstart#2-get-and-verify-your-pinecone-api-key\n\
         Underlying Error: {err}")]
@@ -29,6 +38,22 @@ pub enum PineconeClientError {
         Underlying Error: {err}")]
     IndexConnectionError { index: String, err: String },
 
+    #[error("Invalid index endpoint '{url}': {err}")]
+    InvalidEndpointError { url: String, err: String },
+
+    #[error("Failed to resolve the address of '{url}'. Please verify that an index with that name exists using `client.list_indexes()`. \n\
+        Underlying Error: {err}")]
+    DnsResolutionError { url: String, err: String },
+
+    #[error("Connection to '{url}' was refused. \nUnderlying Error: {err}")]
+    ConnectionRefusedError { url: String, err: String },
+
+    #[error("TLS handshake with '{url}' failed. \nUnderlying Error: {err}")]
+    TlsError { url: String, err: String },
+
+    #[error("Failed to connect to '{url}'. \nUnderlying Error: {err}")]
+    EndpointConnectionError { url: String, err: String },
+
     #[error(transparent)]
     DataplaneOperationError(#[from] tonic::Status),
 
@@ -43,12 +68,31 @@ pub enum PineconeClientError {
     Please see https://docs.pinecone.io/docs/metadata-filtering#supported-metadata-types for allowed metadata types")]
     MetadataError { key: String, val_type: String },
 
+    #[error(
+        "Metadata key '{key}' has a None value, which is not supported. \
+    Remove the key, or pass `skip_null_metadata=True` to drop None-valued keys"
+    )]
+    NullMetadataError { key: String },
+
+    #[error(
+        "Invalid filter at '{path}': {msg}. \
+    Please see https://docs.pinecone.io/docs/metadata-filtering for the supported filter syntax"
+    )]
+    FilterError { path: String, msg: String },
+
     #[error("`{0}`")]
     Other(String),
 
     #[error("Operation failed with error code {status_code }. \nUnderlying Error: {err}")]
     ControlPlaneOperationError { err: String, status_code: String },
 
+    /// The controller answered with HTTP 429. `retry_after` is how long it asked the client to wait, if it said so.
+    #[error("The request was rate limited by the controller. \nUnderlying Error: {err}")]
+    ControlPlaneRateLimitError {
+        err: String,
+        retry_after: Option<Duration>,
+    },
+
     #[error("Failed to parse response contents")]
     ControlPlaneParsingError {},
 
@@ -57,6 +101,9 @@ pub enum PineconeClientError {
 
     #[error("`{0}`")]
     KeyboardInterrupt(String),
+
+    #[error("{0} was cancelled")]
+    Cancelled(String),
 }
 
 // TODO: Decide if we want to print the full formatted error on dubug
@@ -68,9 +115,175 @@ pub enum PineconeClientError {
 
 pub type PineconeResult<T> = Result<T, PineconeClientError>;
 
+/// The category of a [`PineconeClientError`], for callers that need to handle errors by class rather than by variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The API key is missing or invalid, or lacks the permissions for the operation.
+    Auth,
+    /// The index, collection, backup or project doesn't exist.
+    NotFound,
+    /// A rate limit or a quota was exceeded.
+    QuotaExceeded,
+    /// The request was rejected because of its content, or the client was misconfigured.
+    InvalidArgument,
+    /// The service couldn't be reached, or was temporarily unable to handle the request.
+    Unavailable,
+    /// The request didn't complete in time.
+    Timeout,
+    /// The operation was cancelled by the caller.
+    Cancelled,
+    /// Any other failure, e.g. an unexpected response or a server-side bug.
+    Internal,
+}
+
+impl ErrorKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorKind::Auth => "auth",
+            ErrorKind::NotFound => "not_found",
+            ErrorKind::QuotaExceeded => "quota_exceeded",
+            ErrorKind::InvalidArgument => "invalid_argument",
+            ErrorKind::Unavailable => "unavailable",
+            ErrorKind::Timeout => "timeout",
+            ErrorKind::Cancelled => "cancelled",
+            ErrorKind::Internal => "internal",
+        }
+    }
+
+    /// Whether an operation that failed with this kind of error may succeed if made again later: the service was unavailable,
+    /// the request timed out, or it was rate limited (in which case it should be retried after a backoff).
+    /// The SDK's own retries follow this rule, with rate limited requests retried on their own budget,
+    /// see [`RetryPolicy`](crate::client::config::RetryPolicy).
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            ErrorKind::Unavailable | ErrorKind::Timeout | ErrorKind::QuotaExceeded
+        )
+    }
+
+    pub(crate) fn from_grpc(status: &tonic::Status) -> Self {
+        use tonic::Code;
+        match status.code() {
+            Code::Unauthenticated | Code::PermissionDenied => ErrorKind::Auth,
+            Code::NotFound => ErrorKind::NotFound,
+            Code::ResourceExhausted => ErrorKind::QuotaExceeded,
+            Code::InvalidArgument
+            | Code::FailedPrecondition
+            | Code::OutOfRange
+            | Code::AlreadyExists => ErrorKind::InvalidArgument,
+            Code::Unavailable | Code::Aborted => ErrorKind::Unavailable,
+            // tonic enforces the request's timeout locally, and reports it as `Cancelled`
+            Code::DeadlineExceeded | Code::Cancelled => ErrorKind::Timeout,
+            _ if is_transport_error(status) => ErrorKind::Unavailable,
+            _ => ErrorKind::Internal,
+        }
+    }
+
+    /// Classifies an HTTP status code, given as a string starting with the code (e.g. "404 Not Found").
+    fn from_http(status_code: &str) -> Self {
+        match status_code
+            .get(..3)
+            .and_then(|code| code.parse::<u16>().ok())
+        {
+            Some(401 | 403) => ErrorKind::Auth,
+            Some(404) => ErrorKind::NotFound,
+            Some(429) => ErrorKind::QuotaExceeded,
+            Some(408 | 504) => ErrorKind::Timeout,
+            Some(502 | 503) => ErrorKind::Unavailable,
+            Some(400..=499) => ErrorKind::InvalidArgument,
+            _ => ErrorKind::Internal,
+        }
+    }
+
+    fn from_io(err: &std::io::Error) -> Self {
+        use std::io::ErrorKind as IoKind;
+        match err.kind() {
+            IoKind::TimedOut => ErrorKind::Timeout,
+            IoKind::ConnectionRefused
+            | IoKind::ConnectionReset
+            | IoKind::ConnectionAborted
+            | IoKind::NotConnected
+            | IoKind::BrokenPipe => ErrorKind::Unavailable,
+            IoKind::PermissionDenied => ErrorKind::Auth,
+            IoKind::NotFound => ErrorKind::NotFound,
+            IoKind::InvalidInput | IoKind::InvalidData => ErrorKind::InvalidArgument,
+            _ => ErrorKind::Internal,
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl PineconeClientError {
+    /// The category of the error.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            PineconeClientError::ArgumentError { .. }
+            | PineconeClientError::ValueError(_)
+            | PineconeClientError::UpsertKeyError { .. }
+            | PineconeClientError::UpsertValueError { .. }
+            | PineconeClientError::UnknownRegionError { .. }
+            | PineconeClientError::InvalidEndpointError { .. }
+            | PineconeClientError::MetadataValueError { .. }
+            | PineconeClientError::MetadataError { .. }
+            | PineconeClientError::NullMetadataError { .. }
+            | PineconeClientError::FilterError { .. } => ErrorKind::InvalidArgument,
+            PineconeClientError::ControlPlaneConnectionError { .. }
+            | PineconeClientError::IndexConnectionError { .. }
+            | PineconeClientError::DnsResolutionError { .. }
+            | PineconeClientError::ConnectionRefusedError { .. }
+            | PineconeClientError::EndpointConnectionError { .. } => ErrorKind::Unavailable,
+            // A failed handshake is a certificate or configuration problem, which retrying won't fix
+            PineconeClientError::TlsError { .. } => ErrorKind::Internal,
+            PineconeClientError::DataplaneOperationError(status) => ErrorKind::from_grpc(status),
+            PineconeClientError::IoError(err) => ErrorKind::from_io(err),
+            PineconeClientError::ControlPlaneOperationError { status_code, .. } => {
+                ErrorKind::from_http(status_code)
+            }
+            PineconeClientError::ControlPlaneRateLimitError { .. } => ErrorKind::QuotaExceeded,
+            PineconeClientError::ControlPlaneParsingError {}
+            | PineconeClientError::DeserializationError(_)
+            | PineconeClientError::Other(_) => ErrorKind::Internal,
+            PineconeClientError::KeyboardInterrupt(_) | PineconeClientError::Cancelled(_) => {
+                ErrorKind::Cancelled
+            }
+        }
+    }
+
+    /// Whether the failed operation may succeed if made again later, see [`ErrorKind::is_retryable`].
+    pub fn is_retryable(&self) -> bool {
+        self.kind().is_retryable()
+    }
+
+    /// The ID of the data plane request that failed, if it carried one.
+    /// See [`ClientConfig::request_ids`](crate::client::config::ClientConfig::request_ids).
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            PineconeClientError::DataplaneOperationError(status) => status
+                .metadata()
+                .get(crate::client::grpc::REQUEST_ID_HEADER)
+                .and_then(|value| value.to_str().ok()),
+            _ => None,
+        }
+    }
+}
+
 impl<T> From<index_service::apis::Error<T>> for PineconeClientError {
     fn from(err: index_service::apis::Error<T>) -> Self {
         match err {
+            index_service::apis::Error::ResponseError(response_error)
+                if response_error.status == reqwest::StatusCode::TOO_MANY_REQUESTS =>
+            {
+                // The generated client doesn't expose the response headers, so `Retry-After` is not available
+                PineconeClientError::ControlPlaneRateLimitError {
+                    err: response_error.content,
+                    retry_after: None,
+                }
+            }
             index_service::apis::Error::ResponseError(response_error) => {
                 PineconeClientError::ControlPlaneOperationError {
                     err: response_error.content,
@@ -97,3 +310,69 @@ impl<T> From<index_service::apis::Error<T>> for PineconeClientError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_kinds() {
+        let grpc = |status| PineconeClientError::DataplaneOperationError(status);
+        let http = |code: &str| PineconeClientError::ControlPlaneOperationError {
+            err: String::new(),
+            status_code: code.to_string(),
+        };
+        let cases = [
+            (grpc(tonic::Status::unauthenticated("")), ErrorKind::Auth),
+            (grpc(tonic::Status::not_found("")), ErrorKind::NotFound),
+            (
+                grpc(tonic::Status::resource_exhausted("")),
+                ErrorKind::QuotaExceeded,
+            ),
+            (
+                grpc(tonic::Status::invalid_argument("")),
+                ErrorKind::InvalidArgument,
+            ),
+            (grpc(tonic::Status::unavailable("")), ErrorKind::Unavailable),
+            (
+                grpc(tonic::Status::cancelled("Timeout expired")),
+                ErrorKind::Timeout,
+            ),
+            (grpc(tonic::Status::internal("")), ErrorKind::Internal),
+            (http("401 Unauthorized"), ErrorKind::Auth),
+            (http("404 Not Found"), ErrorKind::NotFound),
+            (http("400 Bad Request"), ErrorKind::InvalidArgument),
+            (http("503 Service Unavailable"), ErrorKind::Unavailable),
+            (http("504 Gateway Timeout"), ErrorKind::Timeout),
+            (http("unknown"), ErrorKind::Internal),
+            (
+                std::io::Error::from(std::io::ErrorKind::TimedOut).into(),
+                ErrorKind::Timeout,
+            ),
+            (
+                PineconeClientError::ValueError(String::new()),
+                ErrorKind::InvalidArgument,
+            ),
+            (
+                PineconeClientError::Cancelled(String::new()),
+                ErrorKind::Cancelled,
+            ),
+        ];
+        for (err, kind) in cases {
+            assert_eq!(err.kind(), kind, "{err:?}");
+        }
+        assert!(grpc(tonic::Status::unavailable("")).is_retryable());
+        assert!(grpc(tonic::Status::deadline_exceeded("")).is_retryable());
+        assert!(http("429 Too Many Requests").is_retryable());
+        assert!(http("503 Service Unavailable").is_retryable());
+        assert!(!http("500 Internal Server Error").is_retryable());
+        assert!(!http("404 Not Found").is_retryable());
+        assert!(PineconeClientError::ControlPlaneConnectionError {
+            region: String::new(),
+            err: String::new(),
+        }
+        .is_retryable());
+        assert!(!grpc(tonic::Status::invalid_argument("")).is_retryable());
+        assert!(!PineconeClientError::Other(String::new()).is_retryable());
+    }
+}
@@ -0,0 +1,136 @@
+//! Helpers for evaluating the recall of an index against known nearest neighbors,
+//! e.g. when comparing pod types, dimensions or hybrid search weights.
+use std::collections::HashSet;
+use std::time::Duration;
+
+use crate::data_types::{RecallReport, Vector};
+use crate::utils::concurrency::percentile;
+use crate::utils::errors::{PineconeClientError, PineconeResult};
+use crate::utils::similarity::{cosine_similarity, dot_product, squared_euclidean_distance};
+
+/// The fraction of the `k` true nearest neighbors found in the first `k` results.
+pub fn recall_at_k(result_ids: &[String], ground_truth: &[String], k: usize) -> f64 {
+    let truth: HashSet<&str> = ground_truth.iter().take(k).map(String::as_str).collect();
+    if truth.is_empty() {
+        return 1.0;
+    }
+    let found = result_ids
+        .iter()
+        .take(k)
+        .filter(|id| truth.contains(id.as_str()))
+        .count();
+    found as f64 / truth.len() as f64
+}
+
+/// Finds the exact `k` nearest neighbors of every query in `corpus`, by computing all the scores.
+///
+/// # Arguments
+/// - `queries` - the query vectors' dense values.
+/// - `corpus` - the vectors to search, typically exported from the index.
+/// - `k` - the number of neighbors to find per query.
+/// - `metric` - the index's metric: 'cosine', 'dotproduct' or 'euclidean'.
+///
+/// # Returns
+/// The ids of the nearest neighbors of each query, most similar first.
+pub fn brute_force_neighbors(
+    queries: &[Vec<f32>],
+    corpus: &[Vector],
+    k: usize,
+    metric: &str,
+) -> PineconeResult<Vec<Vec<String>>> {
+    // Scores are oriented so that higher is always more similar
+    let score: fn(&[f32], &[f32]) -> f32 = match metric {
+        "cosine" => cosine_similarity,
+        "dotproduct" => dot_product,
+        "euclidean" => |a, b| -squared_euclidean_distance(a, b),
+        _ => {
+            return Err(PineconeClientError::ValueError(format!(
+                "Unknown metric '{metric}'. Expected one of 'cosine', 'dotproduct' or 'euclidean'"
+            )))
+        }
+    };
+    Ok(queries
+        .iter()
+        .map(|query| {
+            let mut scored: Vec<(f32, &str)> = corpus
+                .iter()
+                .map(|v| (score(query, &v.values), v.id.as_str()))
+                .collect();
+            scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+            scored
+                .into_iter()
+                .take(k)
+                .map(|(_, id)| id.to_string())
+                .collect()
+        })
+        .collect())
+}
+
+/// Summarizes the per-query recalls and latencies of an evaluation.
+pub fn recall_report(top_k: u32, recalls: &[f64], latencies: &[Duration]) -> RecallReport {
+    let mean = |values: &[f64]| {
+        if values.is_empty() {
+            0.0
+        } else {
+            values.iter().sum::<f64>() / values.len() as f64
+        }
+    };
+    let latency_secs: Vec<f64> = latencies.iter().map(Duration::as_secs_f64).collect();
+    RecallReport {
+        top_k,
+        query_count: recalls.len() as u32,
+        recall: mean(recalls),
+        min_recall: recalls.iter().copied().reduce(f64::min).unwrap_or(0.0),
+        latency_mean: mean(&latency_secs),
+        latency_p50: percentile(latencies, 50.0).as_secs_f64(),
+        latency_p90: percentile(latencies, 90.0).as_secs_f64(),
+        latency_p99: percentile(latencies, 99.0).as_secs_f64(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_recall_at_k() {
+        let truth = ids(&["a", "b", "c", "d"]);
+        assert_eq!(recall_at_k(&ids(&["a", "x", "b"]), &truth, 2), 0.5);
+        assert_eq!(recall_at_k(&ids(&["c", "b", "a", "d"]), &truth, 4), 1.0);
+        assert_eq!(recall_at_k(&ids(&[]), &truth, 3), 0.0);
+    }
+
+    #[test]
+    fn test_brute_force_neighbors() {
+        let corpus: Vec<Vector> = [("x", [1.0, 0.0]), ("y", [0.0, 1.0]), ("z", [2.0, 2.0])]
+            .into_iter()
+            .map(|(id, values)| Vector {
+                id: id.to_string(),
+                values: values.to_vec(),
+                ..Default::default()
+            })
+            .collect();
+        let queries = vec![vec![1.0, 0.1]];
+        assert_eq!(
+            brute_force_neighbors(&queries, &corpus, 2, "cosine").unwrap(),
+            vec![ids(&["x", "z"])]
+        );
+        assert_eq!(
+            brute_force_neighbors(&queries, &corpus, 1, "dotproduct").unwrap(),
+            vec![ids(&["z"])]
+        );
+        assert_eq!(
+            brute_force_neighbors(&queries, &corpus, 3, "euclidean").unwrap(),
+            vec![ids(&["x", "y", "z"])]
+        );
+        assert!(brute_force_neighbors(&queries, &corpus, 1, "manhattan").is_err());
+
+        let report = recall_report(2, &[1.0, 0.5], &[Duration::from_millis(10)]);
+        assert_eq!(report.recall, 0.75);
+        assert_eq!(report.min_recall, 0.5);
+    }
+}
@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use lru::LruCache;
+
+use crate::data_types::Vector;
+
+type CacheKey = (String, String);
+
+/// An LRU cache of fetched vectors, keyed by (namespace, id), in which entries expire after a TTL.
+/// Only vectors that were found are cached, so a vector upserted after a miss is fetched on the next call.
+#[derive(Debug)]
+pub struct FetchCache {
+    entries: Mutex<LruCache<CacheKey, (Instant, Vector)>>,
+    ttl: Duration,
+}
+
+impl FetchCache {
+    pub fn new(capacity: NonZeroUsize, ttl: Duration) -> Self {
+        FetchCache {
+            entries: Mutex::new(LruCache::new(capacity)),
+            ttl,
+        }
+    }
+
+    /// Looks up the given ids, returning the cached vectors and the ids that need to be fetched.
+    pub fn get_many(
+        &self,
+        namespace: &str,
+        ids: &[String],
+    ) -> (HashMap<String, Vector>, Vec<String>) {
+        let mut entries = self.entries.lock().unwrap();
+        let mut found = HashMap::new();
+        let mut missing = Vec::new();
+        for id in ids {
+            let key = (namespace.to_string(), id.clone());
+            match entries.get(&key) {
+                Some((inserted_at, vector)) if inserted_at.elapsed() < self.ttl => {
+                    found.insert(id.clone(), vector.clone());
+                }
+                Some(_) => {
+                    entries.pop(&key);
+                    missing.push(id.clone());
+                }
+                None => missing.push(id.clone()),
+            }
+        }
+        (found, missing)
+    }
+
+    pub fn insert_many(&self, namespace: &str, vectors: &HashMap<String, Vector>) {
+        let mut entries = self.entries.lock().unwrap();
+        let now = Instant::now();
+        for (id, vector) in vectors {
+            entries.put((namespace.to_string(), id.clone()), (now, vector.clone()));
+        }
+    }
+
+    pub fn invalidate<'a>(&self, namespace: &str, ids: impl IntoIterator<Item = &'a str>) {
+        let mut entries = self.entries.lock().unwrap();
+        for id in ids {
+            entries.pop(&(namespace.to_string(), id.to_string()));
+        }
+    }
+
+    pub fn invalidate_namespace(&self, namespace: &str) {
+        let mut entries = self.entries.lock().unwrap();
+        let keys: Vec<CacheKey> = entries
+            .iter()
+            .filter(|((ns, _), _)| ns == namespace)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in keys {
+            entries.pop(&key);
+        }
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vector(id: &str) -> (String, Vector) {
+        let vector = Vector {
+            id: id.to_string(),
+            values: vec![0.1, 0.2],
+            ..Default::default()
+        };
+        (id.to_string(), vector)
+    }
+
+    #[test]
+    fn test_fetch_cache() {
+        let cache = FetchCache::new(NonZeroUsize::new(2).unwrap(), Duration::from_secs(60));
+        cache.insert_many("ns", &[vector("1"), vector("2")].into_iter().collect());
+
+        let (found, missing) = cache.get_many("ns", &["1".to_string(), "3".to_string()]);
+        assert!(found.contains_key("1"));
+        assert_eq!(missing, vec!["3".to_string()]);
+        // Other namespaces are cached separately
+        let (found, _) = cache.get_many("other", &["1".to_string()]);
+        assert!(found.is_empty());
+
+        cache.invalidate("ns", ["1"]);
+        let (found, _) = cache.get_many("ns", &["1".to_string(), "2".to_string()]);
+        assert_eq!(found.len(), 1);
+        cache.invalidate_namespace("ns");
+        let (found, _) = cache.get_many("ns", &["2".to_string()]);
+        assert!(found.is_empty());
+    }
+
+    #[test]
+    fn test_fetch_cache_ttl_and_capacity() {
+        let cache = FetchCache::new(NonZeroUsize::new(1).unwrap(), Duration::ZERO);
+        cache.insert_many("ns", &[vector("1")].into_iter().collect());
+        let (found, missing) = cache.get_many("ns", &["1".to_string()]);
+        assert!(found.is_empty());
+        assert_eq!(missing.len(), 1);
+
+        let cache = FetchCache::new(NonZeroUsize::new(1).unwrap(), Duration::from_secs(60));
+        cache.insert_many("ns", &[vector("1")].into_iter().collect());
+        cache.insert_many("ns", &[vector("2")].into_iter().collect());
+        let (found, _) = cache.get_many("ns", &["1".to_string(), "2".to_string()]);
+        assert_eq!(found.len(), 1);
+        assert!(found.contains_key("2"));
+    }
+}
@@ -0,0 +1,413 @@
+//! A parser for SQL-like filter expressions, e.g. `genre = 'drama' AND year >= 2020 AND tag IN ('a', 'b')`.
+//!
+//! Supported syntax:
+//! - Comparisons: `field = value`, `!=` (or `<>`), `>`, `>=`, `<`, `<=`
+//! - Set membership: `field IN (v1, v2, ...)`, `field NOT IN (...)`
+//! - Existence: `field IS NULL`, `field IS NOT NULL`
+//! - Logical operators: `AND`, `OR` and parentheses. `AND` binds tighter than `OR`.
+//!
+//! Values are single- or double-quoted strings, numbers, or `TRUE`/`FALSE`. Keywords are case-insensitive.
+//! Field names containing other characters than letters, digits, `_`, `.` and `-` can be quoted with backticks.
+use std::collections::BTreeMap;
+
+use crate::data_types::MetadataValue;
+use crate::utils::errors::{PineconeClientError, PineconeResult};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Number(f64),
+    Op(&'static str),
+    LParen,
+    RParen,
+    Comma,
+}
+
+fn parse_error(pos: usize, msg: impl std::fmt::Display) -> PineconeClientError {
+    PineconeClientError::ValueError(format!(
+        "Failed to parse filter expression at position {pos}: {msg}"
+    ))
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '.' || c == '-'
+}
+
+fn starts_number(chars: &[char], i: usize) -> bool {
+    match chars[i] {
+        '-' | '+' => matches!(chars.get(i + 1), Some(c) if c.is_ascii_digit() || *c == '.'),
+        c => c.is_ascii_digit(),
+    }
+}
+
+/// Splits the expression into tokens, each paired with its (character) position in the expression.
+fn tokenize(expr: &str) -> PineconeResult<Vec<(usize, Token)>> {
+    let chars: Vec<char> = expr.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        let token = match c {
+            '(' => {
+                i += 1;
+                Token::LParen
+            }
+            ')' => {
+                i += 1;
+                Token::RParen
+            }
+            ',' => {
+                i += 1;
+                Token::Comma
+            }
+            '\'' | '"' | '`' => {
+                let quote = c;
+                let mut value = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => return Err(parse_error(start, "unterminated quoted string")),
+                        Some('\\') if i + 1 < chars.len() => {
+                            value.push(chars[i + 1]);
+                            i += 2;
+                        }
+                        Some(&ch) if ch == quote => {
+                            i += 1;
+                            break;
+                        }
+                        Some(&ch) => {
+                            value.push(ch);
+                            i += 1;
+                        }
+                    }
+                }
+                if quote == '`' {
+                    Token::Ident(value)
+                } else {
+                    Token::Str(value)
+                }
+            }
+            '=' | '!' | '<' | '>' => {
+                let next = chars.get(i + 1).copied();
+                let (op, len) = match (c, next) {
+                    ('=', Some('=')) => ("$eq", 2),
+                    ('=', _) => ("$eq", 1),
+                    ('!', Some('=')) => ("$ne", 2),
+                    ('<', Some('>')) => ("$ne", 2),
+                    ('<', Some('=')) => ("$lte", 2),
+                    ('<', _) => ("$lt", 1),
+                    ('>', Some('=')) => ("$gte", 2),
+                    ('>', _) => ("$gt", 1),
+                    _ => return Err(parse_error(start, format!("unexpected character '{c}'"))),
+                };
+                i += len;
+                Token::Op(op)
+            }
+            _ if starts_number(&chars, i) => {
+                i += 1;
+                while i < chars.len()
+                    && (chars[i].is_ascii_alphanumeric() || chars[i] == '.' || chars[i] == '-')
+                {
+                    // Only allow a sign right after an exponent, e.g. 1e-5
+                    if chars[i] == '-' && !matches!(chars[i - 1], 'e' | 'E') {
+                        break;
+                    }
+                    i += 1;
+                }
+                let literal: String = chars[start..i].iter().collect();
+                let number = literal
+                    .parse::<f64>()
+                    .map_err(|_| parse_error(start, format!("invalid number '{literal}'")))?;
+                Token::Number(number)
+            }
+            c if is_ident_char(c) => {
+                while i < chars.len() && is_ident_char(chars[i]) {
+                    i += 1;
+                }
+                Token::Ident(chars[start..i].iter().collect())
+            }
+            _ => return Err(parse_error(start, format!("unexpected character '{c}'"))),
+        };
+        tokens.push((start, token));
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(_, token)| token)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .map(|(pos, _)| *pos)
+            .unwrap_or(self.end)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).map(|(_, token)| token.clone());
+        self.pos += 1;
+        token
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Ident(ident)) if ident.eq_ignore_ascii_case(keyword))
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> PineconeResult<()> {
+        if self.peek_keyword(keyword) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(parse_error(self.position(), format!("expected {keyword}")))
+        }
+    }
+
+    fn expect(&mut self, expected: Token, description: &str) -> PineconeResult<()> {
+        if self.peek() == Some(&expected) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(parse_error(
+                self.position(),
+                format!("expected {description}"),
+            ))
+        }
+    }
+
+    fn parse_or(&mut self) -> PineconeResult<MetadataValue> {
+        let mut clauses = vec![self.parse_and()?];
+        while self.peek_keyword("OR") {
+            self.pos += 1;
+            clauses.push(self.parse_and()?);
+        }
+        Ok(combine("$or", clauses))
+    }
+
+    fn parse_and(&mut self) -> PineconeResult<MetadataValue> {
+        let mut clauses = vec![self.parse_term()?];
+        while self.peek_keyword("AND") {
+            self.pos += 1;
+            clauses.push(self.parse_term()?);
+        }
+        Ok(combine("$and", clauses))
+    }
+
+    fn parse_term(&mut self) -> PineconeResult<MetadataValue> {
+        if self.peek() == Some(&Token::LParen) {
+            self.pos += 1;
+            let inner = self.parse_or()?;
+            self.expect(Token::RParen, "')'")?;
+            return Ok(inner);
+        }
+
+        let field_pos = self.position();
+        let field = match self.next() {
+            Some(Token::Ident(field)) => field,
+            _ => return Err(parse_error(field_pos, "expected a field name")),
+        };
+        let (op, operand) = if self.peek_keyword("IS") {
+            self.pos += 1;
+            let exists = if self.peek_keyword("NOT") {
+                self.pos += 1;
+                true
+            } else {
+                false
+            };
+            self.expect_keyword("NULL")?;
+            ("$exists", MetadataValue::BoolVal(exists))
+        } else if self.peek_keyword("NOT") {
+            self.pos += 1;
+            self.expect_keyword("IN")?;
+            ("$nin", self.parse_list()?)
+        } else if self.peek_keyword("IN") {
+            self.pos += 1;
+            ("$in", self.parse_list()?)
+        } else {
+            let op_pos = self.position();
+            let op = match self.next() {
+                Some(Token::Op(op)) => op,
+                _ => {
+                    return Err(parse_error(
+                        op_pos,
+                        format!("expected an operator after '{field}'"),
+                    ))
+                }
+            };
+            (op, self.parse_value()?)
+        };
+
+        let condition = BTreeMap::from([(op.to_string(), operand)]);
+        Ok(MetadataValue::DictVal(BTreeMap::from([(
+            field,
+            MetadataValue::DictVal(condition),
+        )])))
+    }
+
+    fn parse_list(&mut self) -> PineconeResult<MetadataValue> {
+        self.expect(Token::LParen, "'('")?;
+        let mut values = vec![self.parse_value()?];
+        while self.peek() == Some(&Token::Comma) {
+            self.pos += 1;
+            values.push(self.parse_value()?);
+        }
+        self.expect(Token::RParen, "')'")?;
+        Ok(MetadataValue::ListVal(values))
+    }
+
+    fn parse_value(&mut self) -> PineconeResult<MetadataValue> {
+        let pos = self.position();
+        match self.next() {
+            Some(Token::Str(value)) => Ok(MetadataValue::StringVal(value)),
+            Some(Token::Number(value)) => Ok(MetadataValue::NumberVal(value)),
+            Some(Token::Ident(ident)) if ident.eq_ignore_ascii_case("true") => {
+                Ok(MetadataValue::BoolVal(true))
+            }
+            Some(Token::Ident(ident)) if ident.eq_ignore_ascii_case("false") => {
+                Ok(MetadataValue::BoolVal(false))
+            }
+            _ => Err(parse_error(
+                pos,
+                "expected a value (a quoted string, a number, TRUE or FALSE)",
+            )),
+        }
+    }
+}
+
+fn combine(op: &str, mut clauses: Vec<MetadataValue>) -> MetadataValue {
+    if clauses.len() == 1 {
+        return clauses.remove(0);
+    }
+    MetadataValue::DictVal(BTreeMap::from([(
+        op.to_string(),
+        MetadataValue::ListVal(clauses),
+    )]))
+}
+
+/// Parses a SQL-like filter expression into a metadata filter.
+///
+/// # Arguments
+/// - `expression` - the filter expression, e.g. `genre = 'drama' AND (year >= 2020 OR tag IN ('a', 'b'))`.
+///
+/// # Returns
+/// The equivalent metadata filter, or a `ValueError` pointing at the position of the syntax error.
+pub fn parse_filter(expression: &str) -> PineconeResult<BTreeMap<String, MetadataValue>> {
+    let tokens = tokenize(expression)?;
+    if tokens.is_empty() {
+        return Err(parse_error(0, "empty expression"));
+    }
+    let mut parser = Parser {
+        tokens,
+        pos: 0,
+        end: expression.chars().count(),
+    };
+    let filter = parser.parse_or()?;
+    if parser.pos < parser.tokens.len() {
+        return Err(parse_error(
+            parser.position(),
+            "unexpected input after the end of the expression",
+        ));
+    }
+    match filter {
+        MetadataValue::DictVal(filter) => Ok(filter),
+        // Every clause is parsed into a dict
+        _ => unreachable!(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn condition(field: &str, op: &str, operand: MetadataValue) -> MetadataValue {
+        MetadataValue::DictVal(BTreeMap::from([(
+            field.to_string(),
+            MetadataValue::DictVal(BTreeMap::from([(op.to_string(), operand)])),
+        )]))
+    }
+
+    fn as_debug(filter: &BTreeMap<String, MetadataValue>) -> String {
+        format!("{filter:?}")
+    }
+
+    #[test]
+    fn test_parse_filter() {
+        let filter =
+            parse_filter("genre = 'drama' AND year >= 2020 AND tag IN ('a', \"b\")").unwrap();
+        let expected = combine(
+            "$and",
+            vec![
+                condition(
+                    "genre",
+                    "$eq",
+                    MetadataValue::StringVal("drama".to_string()),
+                ),
+                condition("year", "$gte", MetadataValue::NumberVal(2020.0)),
+                condition(
+                    "tag",
+                    "$in",
+                    MetadataValue::ListVal(vec![
+                        MetadataValue::StringVal("a".to_string()),
+                        MetadataValue::StringVal("b".to_string()),
+                    ]),
+                ),
+            ],
+        );
+        match expected {
+            MetadataValue::DictVal(expected) => assert_eq!(as_debug(&filter), as_debug(&expected)),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_parse_filter_precedence_and_keywords() {
+        let filter =
+            parse_filter("a = 1 or (b <> -2.5 and c is not null) OR d not in (1e3) or e = true")
+                .unwrap();
+        let clauses = match filter.get("$or") {
+            Some(MetadataValue::ListVal(clauses)) => clauses,
+            other => panic!("expected an $or, got {other:?}"),
+        };
+        assert_eq!(clauses.len(), 4);
+        let nested = format!("{:?}", clauses[1]);
+        assert!(nested.contains("$and"));
+        assert!(nested.contains("NumberVal(-2.5)"));
+        assert!(nested.contains("\"$exists\": BoolVal(true)"));
+        assert!(format!("{:?}", clauses[2]).contains("$nin"));
+        assert!(format!("{:?}", clauses[3]).contains("BoolVal(true)"));
+    }
+
+    #[test]
+    fn test_parse_filter_errors() {
+        for (expression, position) in [
+            ("", 0),
+            ("genre = ", 8),
+            ("genre 'drama'", 6),
+            ("genre = 'drama", 8),
+            ("(a = 1", 6),
+            ("a = 1 b = 2", 6),
+            ("a IN 1", 5),
+        ] {
+            match parse_filter(expression) {
+                Err(PineconeClientError::ValueError(msg)) => assert!(
+                    msg.contains(&format!("position {position}:")),
+                    "{expression}: {msg}"
+                ),
+                other => panic!("{expression}: expected a ValueError, got {other:?}"),
+            }
+        }
+    }
+}
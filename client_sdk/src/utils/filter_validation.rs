@@ -0,0 +1,249 @@
+//! Client-side validation of metadata filters, so malformed filters fail with a precise error
+//! instead of a generic `Bad Request` from the server.
+use std::collections::BTreeMap;
+
+use crate::data_types::MetadataValue;
+use crate::utils::errors::{PineconeClientError, PineconeResult};
+
+const COMPARISON_OPERATORS: [&str; 4] = ["$gt", "$gte", "$lt", "$lte"];
+
+fn filter_error(path: &str, msg: String) -> PineconeClientError {
+    PineconeClientError::FilterError {
+        path: path.to_string(),
+        msg,
+    }
+}
+
+fn type_name(value: &MetadataValue) -> &'static str {
+    match value {
+        MetadataValue::StringVal(_) => "string",
+        MetadataValue::BoolVal(_) => "bool",
+        MetadataValue::NumberVal(_) => "number",
+        MetadataValue::ListVal(_) => "list",
+        MetadataValue::DictVal(_) => "dict",
+    }
+}
+
+fn is_scalar(value: &MetadataValue) -> bool {
+    matches!(
+        value,
+        MetadataValue::StringVal(_) | MetadataValue::NumberVal(_) | MetadataValue::BoolVal(_)
+    )
+}
+
+fn join_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+/// Validates a metadata filter against the supported operators and operand types.
+///
+/// # Arguments
+/// - `filter` - the filter to validate, as passed to `query()`, `describe_index_stats()` or `delete_by_metadata()`.
+///
+/// # Returns
+/// `Ok(())` if the filter is valid, or a `FilterError` naming the offending key or operator.
+pub fn validate_filter(filter: &BTreeMap<String, MetadataValue>) -> PineconeResult<()> {
+    validate_filter_at(filter, "")
+}
+
+fn validate_filter_at(filter: &BTreeMap<String, MetadataValue>, path: &str) -> PineconeResult<()> {
+    for (key, value) in filter {
+        let key_path = join_path(path, key);
+        match key.as_str() {
+            "$and" | "$or" => validate_logical(value, &key_path)?,
+            op if op.starts_with('$') => {
+                return Err(filter_error(
+                    &key_path,
+                    format!("'{op}' is not supported at this level, expected '$and', '$or' or a metadata field"),
+                ))
+            }
+            _ => validate_field(value, &key_path)?,
+        }
+    }
+    Ok(())
+}
+
+fn validate_logical(value: &MetadataValue, path: &str) -> PineconeResult<()> {
+    let clauses = match value {
+        MetadataValue::ListVal(clauses) if !clauses.is_empty() => clauses,
+        _ => {
+            return Err(filter_error(
+                path,
+                format!(
+                    "expected a non-empty list of filters, found {}",
+                    type_name(value)
+                ),
+            ))
+        }
+    };
+    for (i, clause) in clauses.iter().enumerate() {
+        let clause_path = format!("{path}[{i}]");
+        match clause {
+            MetadataValue::DictVal(clause) => validate_filter_at(clause, &clause_path)?,
+            other => {
+                return Err(filter_error(
+                    &clause_path,
+                    format!("expected a filter dict, found {}", type_name(other)),
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
+fn validate_field(value: &MetadataValue, path: &str) -> PineconeResult<()> {
+    // A bare value is a shorthand for `$eq`
+    if is_scalar(value) {
+        return Ok(());
+    }
+    let operators = match value {
+        MetadataValue::DictVal(operators) => operators,
+        _ => {
+            return Err(filter_error(
+                path,
+                "expected a value or a dict of operators, found list. Use '$in' to match any of several values".to_string(),
+            ))
+        }
+    };
+    if operators.is_empty() {
+        return Err(filter_error(
+            path,
+            "expected at least one operator".to_string(),
+        ));
+    }
+    for (op, operand) in operators {
+        let op_path = join_path(path, op);
+        let valid = match op.as_str() {
+            "$eq" | "$ne" => is_scalar(operand),
+            op if COMPARISON_OPERATORS.contains(&op) => {
+                matches!(operand, MetadataValue::NumberVal(_))
+            }
+            "$in" | "$nin" => match operand {
+                MetadataValue::ListVal(values) => values.iter().all(|v| {
+                    matches!(v, MetadataValue::StringVal(_) | MetadataValue::NumberVal(_))
+                }),
+                _ => false,
+            },
+            "$exists" => matches!(operand, MetadataValue::BoolVal(_)),
+            _ => {
+                return Err(filter_error(
+                    &op_path,
+                    format!("unsupported operator '{op}'. Supported operators are $eq, $ne, $gt, $gte, $lt, $lte, $in, $nin and $exists"),
+                ))
+            }
+        };
+        if !valid {
+            return Err(filter_error(
+                &op_path,
+                format!(
+                    "'{op}' expects {expected}, found {found}",
+                    expected = expected_operand(op),
+                    found = type_name(operand)
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn expected_operand(op: &str) -> &'static str {
+    match op {
+        "$eq" | "$ne" => "a string, number or bool",
+        "$in" | "$nin" => "a list of strings or numbers",
+        "$exists" => "a bool",
+        _ => "a number",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dict(entries: Vec<(&str, MetadataValue)>) -> MetadataValue {
+        MetadataValue::DictVal(
+            entries
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect(),
+        )
+    }
+
+    fn filter(entries: Vec<(&str, MetadataValue)>) -> BTreeMap<String, MetadataValue> {
+        match dict(entries) {
+            MetadataValue::DictVal(filter) => filter,
+            _ => unreachable!(),
+        }
+    }
+
+    fn error_path(filter: BTreeMap<String, MetadataValue>) -> String {
+        match validate_filter(&filter) {
+            Err(PineconeClientError::FilterError { path, .. }) => path,
+            other => panic!("expected a FilterError, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_valid_filters() {
+        let valid = filter(vec![
+            ("genre", MetadataValue::StringVal("drama".to_string())),
+            (
+                "year",
+                dict(vec![
+                    ("$gte", MetadataValue::NumberVal(2000.0)),
+                    ("$lt", MetadataValue::NumberVal(2020.0)),
+                ]),
+            ),
+            (
+                "$or",
+                MetadataValue::ListVal(vec![
+                    dict(vec![(
+                        "tags",
+                        dict(vec![(
+                            "$in",
+                            MetadataValue::ListVal(vec![MetadataValue::StringVal("a".to_string())]),
+                        )]),
+                    )]),
+                    dict(vec![(
+                        "draft",
+                        dict(vec![("$exists", MetadataValue::BoolVal(false))]),
+                    )]),
+                ]),
+            ),
+        ]);
+        assert!(validate_filter(&valid).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_filters() {
+        let unknown_op = filter(vec![(
+            "year",
+            dict(vec![("$between", MetadataValue::NumberVal(1.0))]),
+        )]);
+        assert_eq!(error_path(unknown_op), "year.$between");
+
+        let bad_operand = filter(vec![(
+            "year",
+            dict(vec![("$gt", MetadataValue::StringVal("2000".to_string()))]),
+        )]);
+        assert_eq!(error_path(bad_operand), "year.$gt");
+
+        let bad_clause = filter(vec![(
+            "$and",
+            MetadataValue::ListVal(vec![
+                dict(vec![("a", MetadataValue::BoolVal(true))]),
+                dict(vec![(
+                    "b",
+                    dict(vec![("$in", MetadataValue::NumberVal(1.0))]),
+                )]),
+            ]),
+        )]);
+        assert_eq!(error_path(bad_clause), "$and[1].b.$in");
+
+        let top_level_op = filter(vec![("$eq", MetadataValue::NumberVal(1.0))]);
+        assert_eq!(error_path(top_level_op), "$eq");
+    }
+}
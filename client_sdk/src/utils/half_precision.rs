@@ -0,0 +1,78 @@
+/// Converts the bits of an IEEE 754 half-precision (fp16) float into an f32.
+/// Every fp16 value is exactly representable as an f32, so the conversion is lossless.
+pub fn f16_bits_to_f32(bits: u16) -> f32 {
+    let negative = bits & 0x8000 != 0;
+    let exponent = u32::from((bits >> 10) & 0x1f);
+    let mantissa = u32::from(bits & 0x03ff);
+    let sign = if negative { 0x8000_0000 } else { 0 };
+    match exponent {
+        // Zero and subnormals: mantissa * 2^-24
+        0 => {
+            let value = mantissa as f32 * f32::powi(2.0, -24);
+            if negative {
+                -value
+            } else {
+                value
+            }
+        }
+        // Infinity and NaN
+        0x1f => f32::from_bits(sign | 0x7f80_0000 | (mantissa << 13)),
+        _ => f32::from_bits(sign | ((exponent + 127 - 15) << 23) | (mantissa << 13)),
+    }
+}
+
+/// Converts the bits of a bfloat16 into an f32.
+/// bfloat16 is the upper half of an f32, so the conversion is lossless.
+pub fn bf16_bits_to_f32(bits: u16) -> f32 {
+    f32::from_bits(u32::from(bits) << 16)
+}
+
+/// Converts half-precision (fp16) values into f32 values.
+#[cfg(feature = "half")]
+pub fn f16_to_f32(values: &[half::f16]) -> Vec<f32> {
+    values.iter().map(|v| v.to_f32()).collect()
+}
+
+/// Converts bfloat16 values into f32 values.
+#[cfg(feature = "half")]
+pub fn bf16_to_f32(values: &[half::bf16]) -> Vec<f32> {
+    values.iter().map(|v| v.to_f32()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_f16_bits_to_f32() {
+        assert_eq!(f16_bits_to_f32(0x0000), 0.0);
+        assert!(f16_bits_to_f32(0x8000).is_sign_negative());
+        assert_eq!(f16_bits_to_f32(0x3c00), 1.0);
+        assert_eq!(f16_bits_to_f32(0xc000), -2.0);
+        assert_eq!(f16_bits_to_f32(0x3555), 0.33325195);
+        assert_eq!(f16_bits_to_f32(0x7bff), 65504.0);
+        // Smallest subnormal
+        assert_eq!(f16_bits_to_f32(0x0001), f32::powi(2.0, -24));
+        assert_eq!(f16_bits_to_f32(0x7c00), f32::INFINITY);
+        assert_eq!(f16_bits_to_f32(0xfc00), f32::NEG_INFINITY);
+        assert!(f16_bits_to_f32(0x7e00).is_nan());
+    }
+
+    #[test]
+    fn test_bf16_bits_to_f32() {
+        assert_eq!(bf16_bits_to_f32(0x0000), 0.0);
+        assert_eq!(bf16_bits_to_f32(0x3f80), 1.0);
+        assert_eq!(bf16_bits_to_f32(0xc000), -2.0);
+        assert_eq!(bf16_bits_to_f32(0x7f80), f32::INFINITY);
+        assert!(bf16_bits_to_f32(0x7fc0).is_nan());
+    }
+
+    #[cfg(feature = "half")]
+    #[test]
+    fn test_slices_to_f32() {
+        let values = [half::f16::from_f32(0.5), half::f16::from_f32(-3.0)];
+        assert_eq!(f16_to_f32(&values), vec![0.5, -3.0]);
+        let values = [half::bf16::from_f32(0.5), half::bf16::from_f32(-3.0)];
+        assert_eq!(bf16_to_f32(&values), vec![0.5, -3.0]);
+    }
+}
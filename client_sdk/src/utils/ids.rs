@@ -0,0 +1,74 @@
+//! Helpers for deriving stable vector ids from content, so re-running an ingestion job
+//! overwrites the vectors it created before instead of duplicating them.
+use crate::utils::errors::{PineconeClientError, PineconeResult};
+use sha1_smol::Sha1;
+use uuid::Uuid;
+
+/// The RFC 4122 namespace for URLs, e.g. the source document of a chunk of text.
+pub const NAMESPACE_URL: &str = "6ba7b811-9dad-11d1-80b4-00c04fd430c8";
+/// The RFC 4122 namespace for domain names.
+pub const NAMESPACE_DNS: &str = "6ba7b810-9dad-11d1-80b4-00c04fd430c8";
+
+/// Derives a name-based (version 5) UUID from a namespace and a name, as defined by RFC 4122.
+/// The same namespace and name always produce the same id.
+///
+/// # Arguments
+/// - `namespace` - a UUID in its hyphenated form, e.g. [`NAMESPACE_URL`], or one generated for the dataset.
+/// - `name` - the content to derive the id from, e.g. a document's URL or a chunk's text.
+///
+/// # Returns
+/// The UUID in its hyphenated form, or a `ValueError` if `namespace` is not a valid UUID.
+pub fn uuid_v5(namespace: &str, name: &str) -> PineconeResult<String> {
+    let namespace = Uuid::parse_str(namespace).map_err(|_| {
+        PineconeClientError::ValueError(format!("'{namespace}' is not a valid UUID"))
+    })?;
+    Ok(Uuid::new_v5(&namespace, name.as_bytes()).to_string())
+}
+
+/// Derives an id from a hash of the content, as the hex SHA-1 digest of its bytes.
+/// SHA-1 is not collision resistant against an attacker, but is fine for deriving ids.
+pub fn content_hash_id(content: &[u8]) -> String {
+    Sha1::from(content).digest().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_hash_id() {
+        assert_eq!(
+            content_hash_id(b"abc"),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+        assert_eq!(
+            content_hash_id(b""),
+            "da39a3ee5e6b4b0d3255bfef95601890afd80709"
+        );
+        // Spans more than one block
+        assert_eq!(
+            content_hash_id(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+            "84983e441c3bd26ebaae4aa1f95129e5e54670f1"
+        );
+    }
+
+    #[test]
+    fn test_uuid_v5() {
+        // Matches Python's uuid.uuid5(uuid.NAMESPACE_DNS, "python.org")
+        assert_eq!(
+            uuid_v5(NAMESPACE_DNS, "python.org").unwrap(),
+            "886313e1-3b8a-5372-9b90-0c9aee199e5d"
+        );
+        assert_eq!(
+            uuid_v5(NAMESPACE_URL, "doc-1").unwrap(),
+            uuid_v5(NAMESPACE_URL, "doc-1").unwrap()
+        );
+        assert_ne!(
+            uuid_v5(NAMESPACE_URL, "doc-1").unwrap(),
+            uuid_v5(NAMESPACE_DNS, "doc-1").unwrap()
+        );
+        assert!(uuid_v5("not-a-uuid", "doc-1").is_err());
+        assert_eq!(NAMESPACE_URL, Uuid::NAMESPACE_URL.to_string());
+        assert_eq!(NAMESPACE_DNS, Uuid::NAMESPACE_DNS.to_string());
+    }
+}
@@ -0,0 +1,113 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::data_types::LatencyBreakdown;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    InputConversion,
+    Serialization,
+    Network,
+    Deserialization,
+    PythonConversion,
+}
+
+impl LatencyBreakdown {
+    pub fn add(&mut self, phase: Phase, duration: Duration) {
+        let secs = duration.as_secs_f64();
+        match phase {
+            Phase::InputConversion => self.input_conversion += secs,
+            Phase::Serialization => self.serialization += secs,
+            Phase::Network => self.network += secs,
+            Phase::Deserialization => self.deserialization += secs,
+            Phase::PythonConversion => self.python_conversion += secs,
+        }
+    }
+
+    /// Adds time spent outside of the SDK (e.g. converting the operation's output by the caller) to `phase`,
+    /// and to the operation's total.
+    pub fn add_external(&mut self, phase: Phase, duration: Duration) {
+        self.add(phase, duration);
+        self.total += duration.as_secs_f64();
+    }
+}
+
+/// Accumulates the phase timings of a single operation. Cheap to clone, clones record into the same breakdown.
+#[derive(Debug, Clone, Default)]
+pub struct LatencyRecorder(Arc<Mutex<LatencyBreakdown>>);
+
+impl LatencyRecorder {
+    pub fn record(&self, phase: Phase, duration: Duration) {
+        self.0.lock().unwrap().add(phase, duration);
+    }
+
+    /// Runs `f`, recording its duration under `phase`.
+    pub fn time<T>(&self, phase: Phase, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let res = f();
+        self.record(phase, start.elapsed());
+        res
+    }
+}
+
+/// Times a single operation. When dropped, the operation's breakdown is published to `last_latency`,
+/// so it is recorded for every outcome: success, error or cancellation.
+pub(crate) struct CallLatency {
+    recorder: LatencyRecorder,
+    start: Instant,
+    last_latency: Arc<Mutex<Option<LatencyBreakdown>>>,
+}
+
+impl CallLatency {
+    pub(crate) fn start(
+        operation: &str,
+        last_latency: Arc<Mutex<Option<LatencyBreakdown>>>,
+    ) -> Self {
+        let recorder = LatencyRecorder::default();
+        recorder.0.lock().unwrap().operation = operation.to_string();
+        CallLatency {
+            recorder,
+            start: Instant::now(),
+            last_latency,
+        }
+    }
+
+    pub(crate) fn recorder(&self) -> &LatencyRecorder {
+        &self.recorder
+    }
+
+    /// The operation's breakdown so far.
+    pub(crate) fn breakdown(&self) -> LatencyBreakdown {
+        let mut breakdown = self.recorder.0.lock().unwrap().clone();
+        breakdown.total = self.start.elapsed().as_secs_f64();
+        breakdown
+    }
+}
+
+impl Drop for CallLatency {
+    fn drop(&mut self) {
+        *self.last_latency.lock().unwrap() = Some(self.breakdown());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_call_latency_published_on_drop() {
+        let last_latency = Arc::new(Mutex::new(None));
+        {
+            let call = CallLatency::start("fetch()", last_latency.clone());
+            call.recorder()
+                .record(Phase::Network, Duration::from_millis(10));
+            call.recorder()
+                .record(Phase::Network, Duration::from_millis(5));
+            assert!(last_latency.lock().unwrap().is_none());
+        }
+        let breakdown = last_latency.lock().unwrap().clone().unwrap();
+        assert_eq!(breakdown.operation, "fetch()");
+        assert!((breakdown.network - 0.015).abs() < 1e-9);
+        assert!(breakdown.total >= 0.0);
+    }
+}
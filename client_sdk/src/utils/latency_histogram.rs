@@ -0,0 +1,162 @@
+//! In-process latency histograms of a client's requests, for visibility without a metrics stack,
+//! see [`PineconeClient::metrics`](crate::client::pinecone_client::PineconeClient::metrics).
+//!
+//! Latencies are counted into logarithmic buckets, each an eighth of an octave wide, so the memory used doesn't grow
+//! with the number of requests, and percentiles are accurate to within 10%.
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::data_types::OperationMetrics;
+
+const BUCKETS_PER_OCTAVE: f64 = 8.0;
+// The upper bound of the first bucket. The buckets go up to about 70 minutes, longer latencies fall in the last one.
+const MIN_LATENCY_SECS: f64 = 1e-6;
+const BUCKET_COUNT: usize = 32 * BUCKETS_PER_OCTAVE as usize;
+
+#[derive(Debug)]
+struct Histogram {
+    buckets: Vec<u64>,
+    requests: u64,
+    errors: u64,
+    total_secs: f64,
+    max_secs: f64,
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Histogram {
+            buckets: vec![0; BUCKET_COUNT],
+            requests: 0,
+            errors: 0,
+            total_secs: 0.0,
+            max_secs: 0.0,
+        }
+    }
+}
+
+impl Histogram {
+    fn record(&mut self, latency: Duration, failed: bool) {
+        let secs = latency.as_secs_f64();
+        self.buckets[bucket(secs)] += 1;
+        self.requests += 1;
+        self.errors += u64::from(failed);
+        self.total_secs += secs;
+        self.max_secs = self.max_secs.max(secs);
+    }
+
+    /// The upper bound of the bucket holding the `p`th percentile, capped to the largest latency recorded.
+    fn percentile(&self, p: f64) -> f64 {
+        let rank = ((p.clamp(0.0, 100.0) / 100.0) * self.requests as f64).ceil() as u64;
+        let mut seen = 0;
+        for (bucket, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= rank.max(1) {
+                return upper_bound(bucket).min(self.max_secs);
+            }
+        }
+        self.max_secs
+    }
+
+    fn snapshot(&self) -> OperationMetrics {
+        OperationMetrics {
+            requests: self.requests,
+            errors: self.errors,
+            latency_mean: self.total_secs / self.requests.max(1) as f64,
+            latency_p50: self.percentile(50.0),
+            latency_p90: self.percentile(90.0),
+            latency_p99: self.percentile(99.0),
+            latency_max: self.max_secs,
+        }
+    }
+}
+
+fn bucket(secs: f64) -> usize {
+    if secs <= MIN_LATENCY_SECS {
+        return 0;
+    }
+    let bucket = ((secs / MIN_LATENCY_SECS).log2() * BUCKETS_PER_OCTAVE).ceil() as usize;
+    bucket.min(BUCKET_COUNT - 1)
+}
+
+fn upper_bound(bucket: usize) -> f64 {
+    MIN_LATENCY_SECS * (bucket as f64 / BUCKETS_PER_OCTAVE).exp2()
+}
+
+/// The latency histograms of a client's requests, by operation. Shared between a client and all of its indexes.
+#[derive(Debug, Default)]
+pub struct LatencyHistograms(Mutex<BTreeMap<String, Histogram>>);
+
+impl LatencyHistograms {
+    /// Records a single request of `operation`, e.g. "query", that took `latency`.
+    pub fn record(&self, operation: &str, latency: Duration, failed: bool) {
+        let mut histograms = self.lock();
+        match histograms.get_mut(operation) {
+            Some(histogram) => histogram.record(latency, failed),
+            None => histograms
+                .entry(operation.to_string())
+                .or_default()
+                .record(latency, failed),
+        }
+    }
+
+    /// Returns the metrics of every operation with recorded requests.
+    pub fn snapshot(&self) -> BTreeMap<String, OperationMetrics> {
+        self.lock()
+            .iter()
+            .map(|(operation, histogram)| (operation.clone(), histogram.snapshot()))
+            .collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, Histogram>> {
+        // The histograms are always left in a consistent state, so a poisoned lock can be safely reused
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_histograms() {
+        let histograms = LatencyHistograms::default();
+        assert!(histograms.snapshot().is_empty());
+
+        for millis in 1..=100 {
+            histograms.record("query", Duration::from_millis(millis), millis > 95);
+        }
+        histograms.record("upsert", Duration::from_secs(2), false);
+        let metrics = histograms.snapshot();
+        let query = &metrics["query"];
+        assert_eq!(query.requests, 100);
+        assert_eq!(query.errors, 5);
+        assert!((query.latency_mean - 0.0505).abs() < 1e-9);
+        assert_eq!(query.latency_max, 0.1);
+        for (percentile, expected) in [
+            (query.latency_p50, 0.05),
+            (query.latency_p90, 0.09),
+            (query.latency_p99, 0.099),
+        ] {
+            assert!(percentile >= expected && percentile <= expected * 1.1);
+        }
+
+        // A single request's percentiles are its latency
+        let upsert = &metrics["upsert"];
+        assert_eq!(upsert.requests, 1);
+        assert_eq!(upsert.latency_p50, 2.0);
+        assert_eq!(upsert.latency_p99, 2.0);
+    }
+
+    #[test]
+    fn test_bucket_bounds() {
+        assert_eq!(bucket(0.0), 0);
+        assert_eq!(bucket(1e6), BUCKET_COUNT - 1);
+        for secs in [1e-5, 0.003, 0.25, 7.0] {
+            let bucket = bucket(secs);
+            assert!(upper_bound(bucket - 1) < secs && secs <= upper_bound(bucket) * (1.0 + 1e-9));
+        }
+    }
+}
@@ -0,0 +1,220 @@
+//! Conversion of user-defined structs to and from vector metadata.
+//!
+//! With the `derive` feature, `#[derive(PineconeMetadata)]` implements [`PineconeMetadata`] for a struct with named fields,
+//! whose types all implement [`MetadataField`]:
+//! ```ignore
+//! #[derive(PineconeMetadata)]
+//! struct Movie {
+//!     genre: String,
+//!     year: u32,
+//!     #[pinecone(rename = "tags")]
+//!     keywords: Vec<String>,
+//!     rating: Option<f64>,
+//! }
+//!
+//! let vector = Vector { id: "m1".to_string(), values, metadata: Some(movie.to_metadata()), ..Default::default() };
+//! let movie = Movie::from_metadata(vector.metadata.as_ref().unwrap())?;
+//! ```
+use std::collections::BTreeMap;
+
+use crate::data_types::MetadataValue;
+use crate::utils::errors::{PineconeClientError, PineconeResult};
+
+/// A type that is stored as vector metadata, usually implemented with `#[derive(PineconeMetadata)]`.
+pub trait PineconeMetadata: Sized {
+    fn to_metadata(&self) -> BTreeMap<String, MetadataValue>;
+
+    /// Fails if a required field is missing from `metadata`, or has a value of another type.
+    fn from_metadata(metadata: &BTreeMap<String, MetadataValue>) -> PineconeResult<Self>;
+}
+
+/// A type that can be stored as the value of a metadata key: strings, booleans, numbers and lists of strings.
+/// `Option` fields are left out of the metadata when `None`, and are `None` when missing from it.
+pub trait MetadataField: Sized {
+    /// The metadata value, or `None` if the key should be left out.
+    fn to_value(&self) -> Option<MetadataValue>;
+
+    /// Converts the value of metadata key `key`, or `None` if the key is missing.
+    fn from_value(key: &str, value: Option<&MetadataValue>) -> PineconeResult<Self>;
+}
+
+fn required<'a>(key: &str, value: Option<&'a MetadataValue>) -> PineconeResult<&'a MetadataValue> {
+    value.ok_or_else(|| PineconeClientError::ValueError(format!("Metadata key '{key}' is missing")))
+}
+
+fn mismatch(key: &str, value: &MetadataValue) -> PineconeClientError {
+    let val_type = match value {
+        MetadataValue::StringVal(_) => "string",
+        MetadataValue::BoolVal(_) => "bool",
+        MetadataValue::NumberVal(_) => "number",
+        MetadataValue::ListVal(_) => "list",
+        MetadataValue::DictVal(_) => "dict",
+    };
+    PineconeClientError::MetadataError {
+        key: key.to_string(),
+        val_type: val_type.to_string(),
+    }
+}
+
+impl<T: MetadataField> MetadataField for Option<T> {
+    fn to_value(&self) -> Option<MetadataValue> {
+        self.as_ref()?.to_value()
+    }
+
+    fn from_value(key: &str, value: Option<&MetadataValue>) -> PineconeResult<Self> {
+        value
+            .map(|value| T::from_value(key, Some(value)))
+            .transpose()
+    }
+}
+
+impl MetadataField for MetadataValue {
+    fn to_value(&self) -> Option<MetadataValue> {
+        Some(self.clone())
+    }
+
+    fn from_value(key: &str, value: Option<&MetadataValue>) -> PineconeResult<Self> {
+        required(key, value).cloned()
+    }
+}
+
+impl MetadataField for String {
+    fn to_value(&self) -> Option<MetadataValue> {
+        Some(MetadataValue::StringVal(self.clone()))
+    }
+
+    fn from_value(key: &str, value: Option<&MetadataValue>) -> PineconeResult<Self> {
+        let value = required(key, value)?;
+        value
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| mismatch(key, value))
+    }
+}
+
+impl MetadataField for bool {
+    fn to_value(&self) -> Option<MetadataValue> {
+        Some(MetadataValue::BoolVal(*self))
+    }
+
+    fn from_value(key: &str, value: Option<&MetadataValue>) -> PineconeResult<Self> {
+        let value = required(key, value)?;
+        value.as_bool().ok_or_else(|| mismatch(key, value))
+    }
+}
+
+impl MetadataField for Vec<String> {
+    fn to_value(&self) -> Option<MetadataValue> {
+        Some(MetadataValue::ListVal(
+            self.iter().cloned().map(MetadataValue::StringVal).collect(),
+        ))
+    }
+
+    fn from_value(key: &str, value: Option<&MetadataValue>) -> PineconeResult<Self> {
+        let value = required(key, value)?;
+        value
+            .as_list_str()
+            .map(|list| list.into_iter().map(str::to_string).collect())
+            .ok_or_else(|| mismatch(key, value))
+    }
+}
+
+// Metadata numbers are doubles, so integers are only read back from whole numbers in their range
+macro_rules! impl_metadata_number {
+    (float: $($float:ty),*; int: $($int:ty),*) => {
+        $(
+            impl MetadataField for $float {
+                fn to_value(&self) -> Option<MetadataValue> {
+                    Some(MetadataValue::NumberVal(*self as f64))
+                }
+
+                fn from_value(key: &str, value: Option<&MetadataValue>) -> PineconeResult<Self> {
+                    let value = required(key, value)?;
+                    value.as_f64().map(|number| number as $float).ok_or_else(|| mismatch(key, value))
+                }
+            }
+        )*
+        $(
+            impl MetadataField for $int {
+                fn to_value(&self) -> Option<MetadataValue> {
+                    Some(MetadataValue::NumberVal(*self as f64))
+                }
+
+                fn from_value(key: &str, value: Option<&MetadataValue>) -> PineconeResult<Self> {
+                    let value = required(key, value)?;
+                    let number = value.as_f64().ok_or_else(|| mismatch(key, value))?;
+                    if number.fract() != 0.0 || number < <$int>::MIN as f64 || number > <$int>::MAX as f64 {
+                        return Err(PineconeClientError::ValueError(format!(
+                            "Metadata key '{key}' has the value {number}, which isn't a valid {}",
+                            stringify!($int)
+                        )));
+                    }
+                    Ok(number as $int)
+                }
+            }
+        )*
+    };
+}
+
+impl_metadata_number!(float: f32, f64; int: i8, i16, i32, i64, u8, u16, u32, u64, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_types::MetadataMapExt;
+    use client_sdk_derive::PineconeMetadata;
+
+    #[derive(PineconeMetadata, Debug, PartialEq)]
+    struct Movie {
+        genre: String,
+        year: u32,
+        #[pinecone(rename = "tags")]
+        keywords: Vec<String>,
+        rating: Option<f64>,
+        featured: bool,
+        #[pinecone(skip)]
+        cached_embedding: Vec<f32>,
+    }
+
+    #[test]
+    fn test_derive_pinecone_metadata() {
+        let movie = Movie {
+            genre: "drama".to_string(),
+            year: 2020,
+            keywords: vec!["award".to_string()],
+            rating: None,
+            featured: true,
+            cached_embedding: vec![0.5],
+        };
+        let metadata = movie.to_metadata();
+        assert_eq!(metadata.get_str("genre"), Some("drama"));
+        assert_eq!(metadata.get_f64("year"), Some(2020.0));
+        assert_eq!(metadata.get_list_str("tags"), Some(vec!["award"]));
+        assert!(!metadata.contains_key("rating"));
+        assert!(!metadata.contains_key("cached_embedding"));
+
+        let parsed = Movie::from_metadata(&metadata).unwrap();
+        assert_eq!(
+            parsed,
+            Movie {
+                cached_embedding: Vec::new(),
+                ..movie
+            }
+        );
+
+        let mut invalid = metadata.clone();
+        invalid.insert("year".to_string(), MetadataValue::NumberVal(2020.5));
+        assert!(Movie::from_metadata(&invalid).is_err());
+        invalid.insert(
+            "year".to_string(),
+            MetadataValue::StringVal("2020".to_string()),
+        );
+        assert!(matches!(
+            Movie::from_metadata(&invalid),
+            Err(PineconeClientError::MetadataError { .. })
+        ));
+        let mut missing = metadata;
+        missing.remove("genre");
+        assert!(Movie::from_metadata(&missing).is_err());
+    }
+}
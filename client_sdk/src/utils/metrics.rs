@@ -0,0 +1,173 @@
+//! Prometheus metrics of the SDK's requests, enabled by the `prometheus` feature.
+//!
+//! The metrics are registered into a [`Registry`] the application supplies, and are labeled with the request's
+//! operation (e.g. `upsert`) and plane (`data` for index requests, `control` for controller requests):
+//! - `pinecone_client_requests_total` - the requests sent, counting every attempt of retried requests.
+//! - `pinecone_client_request_errors_total` - the requests that failed, also labeled with the error's [`ErrorKind`].
+//! - `pinecone_client_request_retries_total` - the requests that were retried.
+//! - `pinecone_client_request_duration_seconds` - a histogram of the requests' latency.
+//!
+//! Set them on the client with [`ClientConfig::metrics`](crate::client::config::ClientConfig::metrics).
+//! Organization admin requests aren't measured, and nothing is recorded if the client's diagnostics are disabled.
+use std::fmt;
+use std::time::Duration;
+
+use prometheus::{exponential_buckets, HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry};
+
+use crate::utils::errors::{ErrorKind, PineconeClientError, PineconeResult};
+
+/// Which service a request was sent to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Plane {
+    Data,
+    Control,
+}
+
+impl Plane {
+    fn as_str(self) -> &'static str {
+        match self {
+            Plane::Data => "data",
+            Plane::Control => "control",
+        }
+    }
+}
+
+/// The SDK's request metrics. Cloning is cheap, and clones record into the same metrics.
+#[derive(Clone)]
+pub struct PrometheusMetrics {
+    requests: IntCounterVec,
+    errors: IntCounterVec,
+    retries: IntCounterVec,
+    duration: HistogramVec,
+}
+
+impl fmt::Debug for PrometheusMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrometheusMetrics").finish_non_exhaustive()
+    }
+}
+
+impl PrometheusMetrics {
+    /// Creates the metrics and registers them into `registry`.
+    /// Fails if the registry already has metrics with the same names, e.g. if it was called twice with the same registry.
+    pub fn register(registry: &Registry) -> PineconeResult<Self> {
+        let metrics = Self::new().map_err(registration_error)?;
+        registry
+            .register(Box::new(metrics.requests.clone()))
+            .and_then(|_| registry.register(Box::new(metrics.errors.clone())))
+            .and_then(|_| registry.register(Box::new(metrics.retries.clone())))
+            .and_then(|_| registry.register(Box::new(metrics.duration.clone())))
+            .map_err(registration_error)?;
+        Ok(metrics)
+    }
+
+    fn new() -> prometheus::Result<Self> {
+        Ok(PrometheusMetrics {
+            requests: IntCounterVec::new(
+                Opts::new(
+                    "pinecone_client_requests_total",
+                    "Requests sent to Pinecone, counting every attempt of retried requests",
+                ),
+                &["operation", "plane"],
+            )?,
+            errors: IntCounterVec::new(
+                Opts::new(
+                    "pinecone_client_request_errors_total",
+                    "Requests to Pinecone that failed",
+                ),
+                &["operation", "plane", "kind"],
+            )?,
+            retries: IntCounterVec::new(
+                Opts::new(
+                    "pinecone_client_request_retries_total",
+                    "Requests to Pinecone that were retried",
+                ),
+                &["operation", "plane"],
+            )?,
+            // From 1 ms to about 16 s
+            duration: HistogramVec::new(
+                HistogramOpts::new(
+                    "pinecone_client_request_duration_seconds",
+                    "Latency of the requests to Pinecone",
+                )
+                .buckets(exponential_buckets(0.001, 2.0, 15)?),
+                &["operation", "plane"],
+            )?,
+        })
+    }
+
+    /// Records a single request attempt that took `latency`, and failed with an error of kind `error` if set.
+    pub(crate) fn record_request(
+        &self,
+        operation: &str,
+        plane: Plane,
+        latency: Duration,
+        error: Option<ErrorKind>,
+    ) {
+        let labels = [operation, plane.as_str()];
+        self.requests.with_label_values(&labels).inc();
+        self.duration
+            .with_label_values(&labels)
+            .observe(latency.as_secs_f64());
+        if let Some(kind) = error {
+            self.errors
+                .with_label_values(&[operation, plane.as_str(), kind.as_str()])
+                .inc();
+        }
+    }
+
+    /// Records that a request is about to be retried.
+    pub(crate) fn record_retry(&self, operation: &str, plane: Plane) {
+        self.retries
+            .with_label_values(&[operation, plane.as_str()])
+            .inc();
+    }
+}
+
+fn registration_error(err: prometheus::Error) -> PineconeClientError {
+    PineconeClientError::ValueError(format!("Failed to register the Prometheus metrics: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics() {
+        let registry = Registry::new();
+        let metrics = PrometheusMetrics::register(&registry).unwrap();
+        assert!(PrometheusMetrics::register(&registry).is_err());
+
+        metrics.record_request("upsert", Plane::Data, Duration::from_millis(5), None);
+        metrics.record_request(
+            "upsert",
+            Plane::Data,
+            Duration::from_millis(50),
+            Some(ErrorKind::Unavailable),
+        );
+        metrics.record_retry("upsert", Plane::Data);
+        metrics.record_request("list_indexes", Plane::Control, Duration::ZERO, None);
+
+        let labels = ["upsert", "data"];
+        assert_eq!(metrics.requests.with_label_values(&labels).get(), 2);
+        assert_eq!(metrics.retries.with_label_values(&labels).get(), 1);
+        assert_eq!(
+            metrics
+                .errors
+                .with_label_values(&["upsert", "data", "unavailable"])
+                .get(),
+            1
+        );
+        let duration = metrics.duration.with_label_values(&labels);
+        assert_eq!(duration.get_sample_count(), 2);
+        assert!((duration.get_sample_sum() - 0.055).abs() < 1e-9);
+        assert_eq!(
+            metrics
+                .requests
+                .with_label_values(&["list_indexes", "control"])
+                .get(),
+            1
+        );
+        assert_eq!(registry.gather().len(), 4);
+    }
+}
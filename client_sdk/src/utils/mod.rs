@@ -1,3 +1,27 @@
+pub mod batching;
+pub mod cancellation;
+pub mod client_stats;
+pub mod concurrency;
+pub mod control_plane_cache;
 pub mod conversions;
+pub mod debug_sampling;
 pub mod errors;
+pub mod evaluation;
+pub mod fetch_cache;
+pub mod filter_parser;
+pub mod filter_validation;
+pub mod half_precision;
+pub mod ids;
+pub mod latency;
+pub mod latency_histogram;
+pub mod metadata_mapping;
+#[cfg(feature = "prometheus")]
+pub mod metrics;
+pub mod normalization;
+pub mod payload_dump;
 pub mod python_conversions;
+pub mod rate_limit;
+pub mod redaction;
+pub mod regions;
+pub mod similarity;
+pub mod telemetry;
@@ -0,0 +1,181 @@
+use crate::data_types::{QueryResult, Vector};
+use crate::utils::errors::{PineconeClientError, PineconeResult};
+
+/// Maximal number of vectors inspected by [`sample_mean_norm`].
+const NORM_SAMPLE_SIZE: usize = 16;
+
+/// Average norms outside of this range are considered far from unit norm.
+const MIN_EXPECTED_NORM: f32 = 0.5;
+const MAX_EXPECTED_NORM: f32 = 2.0;
+
+/// Returns the L2 (euclidean) norm of the given values.
+pub fn l2_norm(values: &[f32]) -> f32 {
+    values.iter().map(|v| v * v).sum::<f32>().sqrt()
+}
+
+/// L2-normalizes `values` in place, so that the resulting vector has a norm of 1.
+/// Zero vectors have no direction and can't be normalized, so they are rejected with a `ValueError`.
+pub fn l2_normalize(values: &mut [f32]) -> PineconeResult<()> {
+    let norm = l2_norm(values);
+    if norm == 0.0 || !norm.is_finite() {
+        return Err(PineconeClientError::ValueError(
+            "Cannot normalize a zero vector. Please make sure all vector values are finite and at least one of them is non-zero".to_string(),
+        ));
+    }
+    values.iter_mut().for_each(|v| *v /= norm);
+    Ok(())
+}
+
+/// L2-normalizes the dense values of every vector in place.
+/// Vectors without dense values (sparse-only vectors) are left untouched.
+pub fn normalize_vectors(vectors: &mut [Vector]) -> PineconeResult<()> {
+    for vector in vectors.iter_mut() {
+        if vector.values.is_empty() {
+            continue;
+        }
+        l2_normalize(&mut vector.values).map_err(|_| {
+            PineconeClientError::ValueError(format!(
+                "Cannot normalize vector '{id}': all of its values are zero",
+                id = vector.id
+            ))
+        })?;
+    }
+    Ok(())
+}
+
+/// Returns the average L2 norm of an evenly spaced sample of the vectors' dense values,
+/// or `None` if none of the sampled vectors has dense values.
+pub fn sample_mean_norm(vectors: &[Vector]) -> Option<f32> {
+    let step = (vectors.len() / NORM_SAMPLE_SIZE).max(1);
+    let norms: Vec<f32> = vectors
+        .iter()
+        .step_by(step)
+        .take(NORM_SAMPLE_SIZE)
+        .filter(|v| !v.values.is_empty())
+        .map(|v| l2_norm(&v.values))
+        .collect();
+    if norms.is_empty() {
+        return None;
+    }
+    Some(norms.iter().sum::<f32>() / norms.len() as f32)
+}
+
+/// A heuristic check for vectors that are wildly far from being unit-normed.
+pub fn is_far_from_unit_norm(norm: f32) -> bool {
+    !(MIN_EXPECTED_NORM..=MAX_EXPECTED_NORM).contains(&norm)
+}
+
+/// Maps a query score to a 0-1 relevance scale, where higher is more relevant, so scores from indexes
+/// with different metrics can be compared, combined or thresholded consistently.
+///
+/// - `cosine` scores (-1 to 1) are mapped linearly: `(score + 1) / 2`.
+/// - `dotproduct` scores are unbounded, and are squashed with a logistic function: `1 / (1 + e^-score)`.
+/// - `euclidean` scores are (squared) distances, where lower is better, and are mapped to `1 / (1 + score)`.
+///
+/// # Arguments
+/// - `score` - a score returned by a query.
+/// - `metric` - the metric of the queried index, as returned by `Index::metric()`.
+///
+/// # Returns
+/// The normalized score, or a `ValueError` for an unknown metric.
+pub fn normalize_score(score: f32, metric: &str) -> PineconeResult<f32> {
+    let normalized = match metric {
+        "cosine" => (score + 1.0) / 2.0,
+        "dotproduct" => 1.0 / (1.0 + (-score).exp()),
+        "euclidean" => 1.0 / (1.0 + score.max(0.0)),
+        _ => {
+            return Err(PineconeClientError::ValueError(format!(
+                "Unknown metric '{metric}'. Expected one of 'cosine', 'dotproduct' or 'euclidean'"
+            )))
+        }
+    };
+    Ok(normalized.clamp(0.0, 1.0))
+}
+
+/// Normalizes the scores of query results in place, see [`normalize_score`].
+pub fn normalize_scores(results: &mut [QueryResult], metric: &str) -> PineconeResult<()> {
+    for result in results.iter_mut() {
+        result.score = normalize_score(result.score, metric)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_score() {
+        assert_eq!(normalize_score(1.0, "cosine").unwrap(), 1.0);
+        assert_eq!(normalize_score(-1.0, "cosine").unwrap(), 0.0);
+        assert_eq!(normalize_score(0.0, "dotproduct").unwrap(), 0.5);
+        assert!(normalize_score(10.0, "dotproduct").unwrap() > 0.99);
+        assert_eq!(normalize_score(0.0, "euclidean").unwrap(), 1.0);
+        assert_eq!(normalize_score(3.0, "euclidean").unwrap(), 0.25);
+        // Closer vectors are more relevant
+        assert!(
+            normalize_score(1.0, "euclidean").unwrap() > normalize_score(2.0, "euclidean").unwrap()
+        );
+        assert!(normalize_score(0.5, "hamming").is_err());
+    }
+
+    #[test]
+    fn test_l2_normalize() {
+        let mut values = vec![3.0, 4.0];
+        l2_normalize(&mut values).unwrap();
+        assert_eq!(values, vec![0.6, 0.8]);
+        assert!((l2_norm(&values) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_l2_normalize_zero_vector() {
+        let mut values = vec![0.0; 4];
+        assert!(l2_normalize(&mut values).is_err());
+    }
+
+    #[test]
+    fn test_normalize_vectors_skips_sparse_only() {
+        let mut vectors = vec![
+            Vector {
+                id: "1".to_string(),
+                values: vec![2.0, 0.0],
+                ..Default::default()
+            },
+            Vector {
+                id: "2".to_string(),
+                values: vec![],
+                ..Default::default()
+            },
+        ];
+        normalize_vectors(&mut vectors).unwrap();
+        assert_eq!(vectors[0].values, vec![1.0, 0.0]);
+        assert!(vectors[1].values.is_empty());
+    }
+
+    #[test]
+    fn test_normalize_vectors_zero_vector() {
+        let mut vectors = vec![Vector {
+            id: "zero".to_string(),
+            values: vec![0.0, 0.0],
+            ..Default::default()
+        }];
+        let err = normalize_vectors(&mut vectors).unwrap_err();
+        assert!(err.to_string().contains("zero"));
+    }
+
+    #[test]
+    fn test_sample_mean_norm() {
+        let vectors: Vec<Vector> = (0..100)
+            .map(|i| Vector {
+                id: i.to_string(),
+                values: vec![30.0, 40.0],
+                ..Default::default()
+            })
+            .collect();
+        let norm = sample_mean_norm(&vectors).unwrap();
+        assert!((norm - 50.0).abs() < 1e-3);
+        assert!(is_far_from_unit_norm(norm));
+        assert!(!is_far_from_unit_norm(1.05));
+        assert_eq!(sample_mean_norm(&[]), None);
+    }
+}
@@ -0,0 +1,128 @@
+//! Dumps of the payloads sent and received by a single call, to troubleshoot requests the service rejects
+//! (e.g. a `create_index` failing with "400 Bad Request").
+//!
+//! Dumping is scoped to a call: either a future run with [`scope`], or the operations of an index view returned by
+//! [`Index::with_payload_dump`](crate::index::Index::with_payload_dump). Unlike the debug logs, payloads are dumped
+//! whole, vector values included, so the sink must be trusted with the data. API keys are never dumped.
+//!
+//! Dumped are the data plane requests and responses, the requests that create, configure or delete indexes and
+//! collections, and the requests to endpoints the generated control plane client doesn't cover (e.g. backups).
+//! Control plane requests are dumped as JSON, data plane messages in their `Debug` format.
+use std::fmt;
+use std::future::Future;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+use crate::data_types::PayloadDump;
+
+tokio::task_local! {
+    static SINK: PayloadSink;
+}
+
+/// Receives the payloads dumped by a call, see the [module documentation](self).
+#[derive(Clone)]
+pub struct PayloadSink(Arc<dyn Fn(&PayloadDump) + Send + Sync>);
+
+impl PayloadSink {
+    /// A sink that invokes `callback` with every payload.
+    pub fn callback(callback: impl Fn(&PayloadDump) + Send + Sync + 'static) -> Self {
+        PayloadSink(Arc::new(callback))
+    }
+
+    /// A sink that writes every payload to `writer`, preceded by a line naming its operation and target.
+    /// Write errors are logged and otherwise ignored.
+    pub fn writer(writer: impl Write + Send + 'static) -> Self {
+        let writer = Mutex::new(writer);
+        Self::callback(move |dump| {
+            let mut writer = writer
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            let res = writeln!(
+                writer,
+                "--- {} {} {}\n{}",
+                dump.direction, dump.operation, dump.target, dump.payload
+            )
+            .and_then(|_| writer.flush());
+            if let Err(err) = res {
+                log::warn!("Failed to write a payload dump: {err}");
+            }
+        })
+    }
+
+    pub(crate) fn dump(&self, dump: &PayloadDump) {
+        (self.0)(dump)
+    }
+}
+
+impl fmt::Debug for PayloadSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PayloadSink").finish_non_exhaustive()
+    }
+}
+
+/// Runs `call`, dumping the payloads of all of its requests into `sink`.
+pub async fn scope<F: Future>(sink: PayloadSink, call: F) -> F::Output {
+    SINK.scope(sink, call).await
+}
+
+/// The sink of the call being run, if it is dumped.
+pub(crate) fn current_sink() -> Option<PayloadSink> {
+    SINK.try_with(PayloadSink::clone).ok()
+}
+
+/// Dumps a payload into the sink of the call being run, if any. The payload is only built if it is dumped.
+pub(crate) fn dump(
+    direction: &str,
+    operation: &str,
+    target: &str,
+    payload: impl FnOnce() -> String,
+) {
+    if let Some(sink) = current_sink() {
+        sink.dump(&PayloadDump {
+            operation: operation.to_string(),
+            direction: direction.to_string(),
+            target: target.to_string(),
+            payload: payload(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scope() {
+        let buffer = SharedBuffer::default();
+        let sink = PayloadSink::writer(buffer.clone());
+        dump("request", "upsert", "https://idx", || "ignored".to_string());
+        scope(sink, async {
+            dump("request", "create_index", "https://controller", || {
+                "{\"name\":\"idx\"}".to_string()
+            });
+            dump("response", "create_index", "https://controller", || {
+                "400 Bad Request".to_string()
+            });
+        })
+        .await;
+        dump("request", "upsert", "https://idx", || "ignored".to_string());
+        assert_eq!(
+            String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap(),
+            "--- request create_index https://controller\n{\"name\":\"idx\"}\n\
+             --- response create_index https://controller\n400 Bad Request\n"
+        );
+    }
+}
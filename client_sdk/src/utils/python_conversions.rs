@@ -1,12 +1,100 @@
-use crate::data_types::{MetadataValue, NamespaceStats, SparseValues, Vector};
+use crate::data_types::{
+    ColumnarResult, ExtraFields, MetadataValue, Metric, NamespaceStats, PodType, QueryResult,
+    SparseValues, Vector,
+};
 use crate::utils::errors::PineconeClientError;
-use pyo3::types::{IntoPyDict, PyDict};
-use pyo3::{IntoPy, PyObject, Python, ToPyObject};
-use std::collections::{BTreeMap, HashSet};
+use crate::utils::half_precision::{bf16_bits_to_f32, f16_bits_to_f32};
+use pyo3::buffer::PyBuffer;
+use pyo3::exceptions::PyValueError;
+use pyo3::types::{IntoPyDict, PyBool, PyByteArray, PyDict, PyList, PyTuple};
+use pyo3::{FromPyObject, IntoPy, PyAny, PyObject, PyResult, Python, ToPyObject};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 const SPARSE_KEYS: &[&str] = &["indices", "values"];
 const VECTOR_KEYS: &[&str] = &["id", "values", "sparse_values", "metadata"];
 
+/// Dense vector values extracted from Python.
+/// Besides a list of floats, accepts any one dimensional object implementing the buffer protocol
+/// with float32 or float64 items (e.g. `memoryview`, `array.array`, numpy arrays), one dimensional half-precision
+/// numpy arrays (`float16`, or `bfloat16` from `ml_dtypes`) and objects exposing `__array__` (e.g. torch tensors).
+/// Values are read directly from the underlying buffer, without an intermediate list.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DenseValues(pub Vec<f32>);
+
+impl From<DenseValues> for Vec<f32> {
+    fn from(values: DenseValues) -> Self {
+        values.0
+    }
+}
+
+impl<'source> FromPyObject<'source> for DenseValues {
+    fn extract(ob: &'source PyAny) -> PyResult<Self> {
+        if let Ok(list) = ob.downcast::<PyList>() {
+            return list.extract::<Vec<f32>>().map(DenseValues);
+        }
+        if let Some(values) = extract_from_buffer(ob)? {
+            return Ok(DenseValues(values));
+        }
+        if ob.hasattr("__array__")? {
+            let array = ob.call_method0("__array__")?;
+            if let Some(values) = extract_from_buffer(array)? {
+                return Ok(DenseValues(values));
+            }
+        }
+        // Any other sequence of floats
+        ob.extract::<Vec<f32>>().map(DenseValues)
+    }
+}
+
+/// Reads f32 values from an object implementing the buffer protocol.
+/// Returns `None` if the object doesn't expose a buffer of a supported float type.
+fn extract_from_buffer(ob: &PyAny) -> PyResult<Option<Vec<f32>>> {
+    let py = ob.py();
+    let convert: fn(u16) -> f32 = match half_precision_dtype(ob).as_deref() {
+        Some("float16") => f16_bits_to_f32,
+        Some("bfloat16") => bf16_bits_to_f32,
+        _ => {
+            if let Ok(buffer) = PyBuffer::<f32>::get(ob) {
+                check_dimensions(buffer.dimensions())?;
+                return buffer.to_vec(py).map(Some);
+            }
+            if let Ok(buffer) = PyBuffer::<f64>::get(ob) {
+                check_dimensions(buffer.dimensions())?;
+                let values = buffer.to_vec(py)?;
+                return Ok(Some(values.into_iter().map(|v| v as f32).collect()));
+            }
+            return Ok(None);
+        }
+    };
+    check_dimensions(ob.getattr("ndim")?.extract::<usize>()?)?;
+    // Reinterpret the (contiguous) array as raw 16 bit words, and convert each of them to f32
+    let bits = ob
+        .call_method0("ravel")?
+        .call_method1("view", ("uint16",))?;
+    let buffer = PyBuffer::<u16>::get(bits)?;
+    let values = buffer.to_vec(py)?;
+    Ok(Some(values.into_iter().map(convert).collect()))
+}
+
+fn check_dimensions(dimensions: usize) -> PyResult<()> {
+    if dimensions != 1 {
+        return Err(PyValueError::new_err(format!(
+            "Vector values must be one dimensional, got an array with {dimensions} dimensions"
+        )));
+    }
+    Ok(())
+}
+
+/// Returns the dtype name of a numpy array-like object, if it is a 16 bit float type.
+fn half_precision_dtype(ob: &PyAny) -> Option<String> {
+    let name = ob
+        .getattr("dtype")
+        .and_then(|dtype| dtype.getattr("name"))
+        .and_then(|name| name.extract::<String>())
+        .ok()?;
+    matches!(name.as_str(), "float16" | "bfloat16").then_some(name)
+}
+
 impl TryFrom<&PyDict> for SparseValues {
     type Error = PineconeClientError;
 
@@ -56,15 +144,14 @@ impl TryFrom<&PyDict> for SparseValues {
                     vec_num: 0,
                 })
             }
-            Some(v) => {
-                v.extract::<Vec<f32>>()
-                    .map_err(|_| PineconeClientError::UpsertValueError {
-                        key: "values".into(),
-                        vec_num: 0,
-                        expected_type: "List[float]".into(),
-                        actual: format!("{:?}", v),
-                    })?
-            }
+            Some(v) => v.extract::<DenseValues>().map(Vec::from).map_err(|_| {
+                PineconeClientError::UpsertValueError {
+                    key: "values".into(),
+                    vec_num: 0,
+                    expected_type: "List[float]".into(),
+                    actual: format!("{:?}", v),
+                }
+            })?,
         };
 
         Ok(SparseValues { indices, values })
@@ -82,114 +169,181 @@ impl ToPyObject for SparseValues {
     }
 }
 
+/// Extracts a metadata dict from Python.
+///
+/// # Arguments
+/// - `dict` - the Python metadata dict.
+/// - `skip_null` - whether to drop keys with a `None` value (including in nested dicts). Otherwise, such keys are rejected with a `NullMetadataError`.
+pub fn extract_metadata(
+    dict: &PyDict,
+    skip_null: bool,
+) -> Result<BTreeMap<String, MetadataValue>, PineconeClientError> {
+    let mut metadata = BTreeMap::new();
+    for (key, val) in dict {
+        let key = key.extract::<String>().map_err(|_| {
+            PineconeClientError::ValueError(format!("Metadata keys must be strings, found {key}"))
+        })?;
+        let new_val = if val.is_none() {
+            if skip_null {
+                continue;
+            }
+            return Err(PineconeClientError::NullMetadataError { key });
+        } else if let Ok(inner) = val.downcast::<PyDict>() {
+            let inner = extract_metadata(inner, skip_null).map_err(|e| match e {
+                PineconeClientError::NullMetadataError { key: inner_key } => {
+                    PineconeClientError::NullMetadataError {
+                        key: format!("{key}: {inner_key}"),
+                    }
+                }
+                PineconeClientError::MetadataError {
+                    key: inner_key,
+                    val_type,
+                } => PineconeClientError::MetadataError {
+                    key: format!("{key}: {inner_key}"),
+                    val_type,
+                },
+                _ => e,
+            })?;
+            MetadataValue::DictVal(inner)
+        } else {
+            val.extract::<MetadataValue>()
+                .map_err(|_| PineconeClientError::MetadataError {
+                    key: key.clone(),
+                    val_type: type_name(val),
+                })?
+        };
+        metadata.insert(key, new_val);
+    }
+    Ok(metadata)
+}
+
+fn type_name(val: &PyAny) -> String {
+    val.get_type()
+        .name()
+        .map(str::to_string)
+        .unwrap_or_else(|_| "unknown".into())
+}
+
 impl TryFrom<&PyDict> for Vector {
     type Error = PineconeClientError;
 
     fn try_from(dict: &PyDict) -> Result<Self, Self::Error> {
-        let allowed_keys: HashSet<String> = VECTOR_KEYS.iter().map(|x| (*x).into()).collect();
-        let actual_keys: HashSet<String> = dict
-            .keys()
-            .into_iter()
-            .map(|x| x.extract::<String>())
-            .collect::<Result<HashSet<_>, _>>()
-            .map_err(|_| {
-                PineconeClientError::ValueError("Couldn't retrieve dictionary keys".into())
-            })?;
+        vector_from_dict(dict, false)
+    }
+}
 
-        let excess_keys = actual_keys
-            .difference(&allowed_keys)
-            .collect::<Vec<&String>>();
-        if !excess_keys.is_empty() {
-            return Err(PineconeClientError::ValueError(format!(
-                "Found unexpected keys: {excess_keys:?}",
-                excess_keys = excess_keys
-            )));
-        }
+/// Converts a Python dict into a Vector.
+///
+/// # Arguments
+/// - `dict` - a dict with the keys 'id', 'values', and optionally 'sparse_values' and 'metadata'.
+/// - `skip_null_metadata` - whether to drop metadata keys with a `None` value, see [`extract_metadata`].
+pub fn vector_from_dict(
+    dict: &PyDict,
+    skip_null_metadata: bool,
+) -> Result<Vector, PineconeClientError> {
+    let allowed_keys: HashSet<String> = VECTOR_KEYS.iter().map(|x| (*x).into()).collect();
+    let actual_keys: HashSet<String> = dict
+        .keys()
+        .into_iter()
+        .map(|x| x.extract::<String>())
+        .collect::<Result<HashSet<_>, _>>()
+        .map_err(|_| PineconeClientError::ValueError("Couldn't retrieve dictionary keys".into()))?;
 
-        Ok(Vector {
-            id: match dict.get_item("id") {
-                None => {
-                    return Err(PineconeClientError::UpsertKeyError {
-                        key: "id".into(),
-                        vec_num: 0,
-                    })
-                }
-                Some(id) => {
-                    id.extract::<String>()
-                        .map_err(|_| PineconeClientError::UpsertValueError {
-                            key: "id".into(),
-                            vec_num: 0,
-                            expected_type: "String".into(),
-                            actual: format!("{:?}", id),
-                        })
-                }
-            }?,
-            values: match dict.get_item("values") {
-                None => {
-                    return Err(PineconeClientError::UpsertKeyError {
-                        key: "values".into(),
-                        vec_num: 0,
-                    })
-                }
-                Some(values) => values.extract::<Vec<f32>>().map_err(|_| {
+    let excess_keys = actual_keys
+        .difference(&allowed_keys)
+        .collect::<Vec<&String>>();
+    if !excess_keys.is_empty() {
+        return Err(PineconeClientError::ValueError(format!(
+            "Found unexpected keys: {excess_keys:?}",
+            excess_keys = excess_keys
+        )));
+    }
+
+    Ok(Vector {
+        id: match dict.get_item("id") {
+            None => {
+                return Err(PineconeClientError::UpsertKeyError {
+                    key: "id".into(),
+                    vec_num: 0,
+                })
+            }
+            Some(id) => id
+                .extract::<String>()
+                .map_err(|_| PineconeClientError::UpsertValueError {
+                    key: "id".into(),
+                    vec_num: 0,
+                    expected_type: "String".into(),
+                    actual: format!("{:?}", id),
+                }),
+        }?,
+        values: match dict.get_item("values") {
+            None => {
+                return Err(PineconeClientError::UpsertKeyError {
+                    key: "values".into(),
+                    vec_num: 0,
+                })
+            }
+            Some(values) => values
+                .extract::<DenseValues>()
+                .map(Vec::from)
+                .map_err(|_| PineconeClientError::UpsertValueError {
+                    key: "values".into(),
+                    vec_num: 0,
+                    expected_type: "List[float]".into(),
+                    actual: format!("{:?}", values),
+                })?,
+        },
+        sparse_values: dict
+            .get_item("sparse_values")
+            .map(|val| {
+                let val = val.extract::<&PyDict>().map_err(|_| {
                     PineconeClientError::UpsertValueError {
-                        key: "values".into(),
+                        key: "sparse_values".into(),
                         vec_num: 0,
-                        expected_type: "List[float]".into(),
-                        actual: format!("{:?}", values),
+                        expected_type: "dict".into(),
+                        actual: format!("{:?}", val),
                     }
-                })?,
-            },
-            sparse_values: dict
-                .get_item("sparse_values")
-                .map(|val| {
-                    let val = val.extract::<&PyDict>().map_err(|_| {
-                        PineconeClientError::UpsertValueError {
-                            key: "sparse_values".into(),
-                            vec_num: 0,
-                            expected_type: "dict".into(),
-                            actual: format!("{:?}", val),
-                        }
-                    })?;
-                    val.try_into().map_err(|e| match e {
-                        PineconeClientError::UpsertKeyError { key, vec_num } => {
-                            PineconeClientError::UpsertKeyError {
-                                key: format!("sparse_values: {key}", key = key),
-                                vec_num,
-                            }
-                        }
-                        PineconeClientError::UpsertValueError {
-                            key,
-                            vec_num,
-                            actual,
-                            expected_type,
-                        } => PineconeClientError::UpsertValueError {
+                })?;
+                val.try_into().map_err(|e| match e {
+                    PineconeClientError::UpsertKeyError { key, vec_num } => {
+                        PineconeClientError::UpsertKeyError {
                             key: format!("sparse_values: {key}", key = key),
                             vec_num,
-                            actual,
-                            expected_type,
-                        },
-                        _ => PineconeClientError::ValueError(format!(
-                            "Error in 'sparse_values: {e}",
-                            e = e
-                        )),
-                    })
-                })
-                .transpose()?,
-            metadata: dict
-                .get_item("metadata")
-                .map(|val| {
-                    val.extract::<BTreeMap<String, MetadataValue>>()
-                        .map_err(|_| PineconeClientError::UpsertValueError {
-                            key: "metadata".into(),
-                            vec_num: 0,
-                            expected_type: "dict".into(),
-                            actual: format!("{:?}", val),
-                        })
+                        }
+                    }
+                    PineconeClientError::UpsertValueError {
+                        key,
+                        vec_num,
+                        actual,
+                        expected_type,
+                    } => PineconeClientError::UpsertValueError {
+                        key: format!("sparse_values: {key}", key = key),
+                        vec_num,
+                        actual,
+                        expected_type,
+                    },
+                    _ => PineconeClientError::ValueError(format!(
+                        "Error in 'sparse_values: {e}",
+                        e = e
+                    )),
                 })
-                .transpose()?,
-        })
-    }
+            })
+            .transpose()?,
+        metadata: dict
+            .get_item("metadata")
+            .map(|val| {
+                let val = val.downcast::<PyDict>().map_err(|_| {
+                    PineconeClientError::UpsertValueError {
+                        key: "metadata".into(),
+                        vec_num: 0,
+                        expected_type: "dict".into(),
+                        actual: format!("{:?}", val),
+                    }
+                })?;
+                extract_metadata(val, skip_null_metadata)
+            })
+            .transpose()?,
+    })
 }
 
 impl ToPyObject for NamespaceStats {
@@ -221,3 +375,226 @@ impl IntoPy<PyObject> for MetadataValue {
         }
     }
 }
+
+// Enums that Python passes and receives by name, e.g. `'cosine'` or `'s1.x2'`
+macro_rules! impl_py_str_conversions {
+    ($($ty:ident),*) => {$(
+        impl ToPyObject for $ty {
+            fn to_object(&self, py: Python<'_>) -> PyObject {
+                self.to_string().to_object(py)
+            }
+        }
+
+        impl IntoPy<PyObject> for $ty {
+            fn into_py(self, py: Python<'_>) -> PyObject {
+                self.to_object(py)
+            }
+        }
+
+        impl<'source> FromPyObject<'source> for $ty {
+            fn extract(ob: &'source PyAny) -> PyResult<Self> {
+                ob.extract::<&str>()?
+                    .parse()
+                    .map_err(|err: PineconeClientError| PyValueError::new_err(err.to_string()))
+            }
+        }
+    )*};
+}
+
+impl_py_str_conversions!(Metric, PodType);
+
+impl ToPyObject for ExtraFields {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        self.0
+            .iter()
+            .map(|(key, value)| (key, json_to_py(py, value)))
+            .into_py_dict(py)
+            .to_object(py)
+    }
+}
+
+impl IntoPy<PyObject> for ExtraFields {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl<'source> FromPyObject<'source> for ExtraFields {
+    fn extract(ob: &'source PyAny) -> PyResult<Self> {
+        let fields: BTreeMap<String, &PyAny> = ob.extract()?;
+        fields
+            .into_iter()
+            .map(|(key, value)| Ok((key, py_to_json(value)?)))
+            .collect::<PyResult<_>>()
+            .map(ExtraFields)
+    }
+}
+
+fn json_to_py(py: Python, value: &serde_json::Value) -> PyObject {
+    match value {
+        serde_json::Value::Null => py.None(),
+        serde_json::Value::Bool(v) => v.to_object(py),
+        serde_json::Value::Number(v) => match v.as_i64() {
+            Some(v) => v.to_object(py),
+            None => v.as_f64().to_object(py),
+        },
+        serde_json::Value::String(v) => v.to_object(py),
+        serde_json::Value::Array(v) => v
+            .iter()
+            .map(|v| json_to_py(py, v))
+            .collect::<Vec<_>>()
+            .to_object(py),
+        serde_json::Value::Object(v) => v
+            .iter()
+            .map(|(key, value)| (key, json_to_py(py, value)))
+            .into_py_dict(py)
+            .to_object(py),
+    }
+}
+
+// The inverse of `json_to_py`
+fn py_to_json(value: &PyAny) -> PyResult<serde_json::Value> {
+    if value.is_none() {
+        Ok(serde_json::Value::Null)
+    } else if let Ok(v) = value.downcast::<PyBool>() {
+        Ok(v.is_true().into())
+    } else if let Ok(v) = value.extract::<i64>() {
+        Ok(v.into())
+    } else if let Ok(v) = value.extract::<f64>() {
+        Ok(v.into())
+    } else if let Ok(v) = value.extract::<String>() {
+        Ok(v.into())
+    } else if let Ok(v) = value.downcast::<PyDict>() {
+        v.iter()
+            .map(|(key, value)| Ok((key.extract::<String>()?, py_to_json(value)?)))
+            .collect::<PyResult<serde_json::Map<_, _>>>()
+            .map(serde_json::Value::Object)
+    } else {
+        value
+            .iter()?
+            .map(|item| py_to_json(item?))
+            .collect::<PyResult<Vec<_>>>()
+            .map(serde_json::Value::Array)
+    }
+}
+
+/// A single result, as an input row of [`build_columnar`].
+struct ColumnarRow {
+    id: String,
+    score: Option<f32>,
+    values: Option<Vec<f32>>,
+    sparse_values: Option<SparseValues>,
+    metadata: Option<BTreeMap<String, MetadataValue>>,
+}
+
+/// Converts query results into a [`ColumnarResult`].
+///
+/// # Arguments
+/// - `results` - the query results, in order.
+/// - `include_values` - whether to build the values matrix. All results must then have the same number of dense values.
+/// - `include_metadata` - whether to build the metadata column.
+pub fn columnar_query_results(
+    py: Python,
+    results: Vec<QueryResult>,
+    include_values: bool,
+    include_metadata: bool,
+) -> PyResult<ColumnarResult> {
+    let rows = results.into_iter().map(|result| ColumnarRow {
+        id: result.id,
+        score: Some(result.score),
+        values: result.values,
+        sparse_values: result.sparse_values,
+        metadata: result.metadata,
+    });
+    build_columnar(py, rows, true, include_values, include_metadata)
+}
+
+/// Converts fetched vectors into a [`ColumnarResult`], ordered like the requested `ids`.
+/// Ids that weren't found are skipped.
+pub fn columnar_vectors(
+    py: Python,
+    ids: &[String],
+    mut vectors: HashMap<String, Vector>,
+) -> PyResult<ColumnarResult> {
+    let rows: Vec<ColumnarRow> = ids
+        .iter()
+        .filter_map(|id| vectors.remove(id))
+        .map(|vector| ColumnarRow {
+            id: vector.id,
+            score: None,
+            values: Some(vector.values),
+            sparse_values: vector.sparse_values,
+            metadata: vector.metadata,
+        })
+        .collect();
+    build_columnar(py, rows.into_iter(), false, true, true)
+}
+
+fn build_columnar(
+    py: Python,
+    rows: impl ExactSizeIterator<Item = ColumnarRow>,
+    include_scores: bool,
+    include_values: bool,
+    include_metadata: bool,
+) -> PyResult<ColumnarResult> {
+    let num_rows = rows.len();
+    let ids = PyList::empty(py);
+    let mut scores: Vec<f32> = Vec::with_capacity(num_rows);
+    let mut values: Vec<f32> = Vec::new();
+    let mut dimension: Option<usize> = None;
+    let sparse_values = PyList::empty(py);
+    let mut has_sparse_values = false;
+    let metadata = PyList::empty(py);
+
+    for row in rows {
+        if include_values {
+            let row_values = row.values.unwrap_or_default();
+            let expected = *dimension.get_or_insert(row_values.len());
+            if row_values.len() != expected {
+                return Err(PyValueError::new_err(format!(
+                    "Cannot build a values matrix: vector '{id}' has {len} values, while previous vectors have {expected}",
+                    id = row.id,
+                    len = row_values.len(),
+                )));
+            }
+            values.extend(row_values);
+        }
+        if let Some(score) = row.score {
+            scores.push(score);
+        }
+        has_sparse_values |= row.sparse_values.is_some();
+        sparse_values.append(row.sparse_values.into_py(py))?;
+        if include_metadata {
+            metadata.append(row.metadata.into_py(py))?;
+        }
+        ids.append(row.id)?;
+    }
+
+    Ok(ColumnarResult {
+        ids: ids.into(),
+        scores: include_scores
+            .then(|| numpy_f32_array(py, &scores, &[num_rows]))
+            .transpose()?,
+        values: include_values
+            .then(|| numpy_f32_array(py, &values, &[num_rows, dimension.unwrap_or(0)]))
+            .transpose()?,
+        sparse_values: has_sparse_values.then(|| sparse_values.into()),
+        metadata: include_metadata.then(|| metadata.into()),
+        timing: None,
+    })
+}
+
+/// Copies f32 values into a new, writable numpy array of the given shape.
+fn numpy_f32_array(py: Python, values: &[f32], shape: &[usize]) -> PyResult<PyObject> {
+    let numpy = py.import("numpy")?;
+    let bytes = PyByteArray::new_with(py, std::mem::size_of_val(values), |bytes| {
+        for (chunk, value) in bytes.chunks_exact_mut(4).zip(values) {
+            chunk.copy_from_slice(&value.to_ne_bytes());
+        }
+        Ok(())
+    })?;
+    let array = numpy.call_method1("frombuffer", (bytes, "float32"))?;
+    Ok(array
+        .call_method1("reshape", (PyTuple::new(py, shape),))?
+        .into())
+}
@@ -0,0 +1,186 @@
+//! Detects rate-limited requests, and how long the service asked the client to wait before sending them again.
+//!
+//! The data plane answers with `RESOURCE_EXHAUSTED`, optionally with a `retry-after` metadata entry or a
+//! `google.rpc.RetryInfo` detail. The control plane answers with HTTP 429, optionally with a `Retry-After` header.
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use prost::Message;
+use tonic::Status;
+
+use crate::utils::errors::{ErrorKind, PineconeClientError};
+
+/// Waits requested by the service are capped to this, so a bogus value can't stall the client indefinitely.
+pub const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+const RETRY_INFO_TYPE_URL: &str = "type.googleapis.com/google.rpc.RetryInfo";
+
+/// The `google.rpc.Status` message carried in the `grpc-status-details-bin` trailer.
+#[derive(Clone, PartialEq, Message)]
+struct RpcStatus {
+    #[prost(int32, tag = "1")]
+    code: i32,
+    #[prost(string, tag = "2")]
+    message: String,
+    #[prost(message, repeated, tag = "3")]
+    details: Vec<prost_types::Any>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct RetryInfo {
+    #[prost(message, optional, tag = "1")]
+    retry_delay: Option<prost_types::Duration>,
+}
+
+/// Whether the request failed because the client exceeded a rate limit or a quota.
+pub fn is_rate_limited(err: &PineconeClientError) -> bool {
+    err.kind() == ErrorKind::QuotaExceeded
+}
+
+/// How long to wait before retrying a request that failed with `err`, if the service said so.
+pub fn retry_after(err: &PineconeClientError) -> Option<Duration> {
+    match err {
+        PineconeClientError::DataplaneOperationError(status) => grpc_retry_after(status),
+        PineconeClientError::ControlPlaneRateLimitError { retry_after, .. } => *retry_after,
+        _ => None,
+    }
+}
+
+/// How long a data plane request that failed with `status` should wait before being retried, if the index said so.
+pub fn grpc_retry_after(status: &Status) -> Option<Duration> {
+    let from_metadata = status
+        .metadata()
+        .get("retry-after")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| parse_retry_after(value, SystemTime::now()));
+    from_metadata
+        .or_else(|| {
+            let details = RpcStatus::decode(status.details()).ok()?;
+            details
+                .details
+                .iter()
+                .filter(|detail| detail.type_url == RETRY_INFO_TYPE_URL)
+                .find_map(|detail| RetryInfo::decode(detail.value.as_slice()).ok()?.retry_delay)
+                .and_then(|delay| {
+                    let seconds = u64::try_from(delay.seconds).ok()?;
+                    Some(
+                        Duration::new(seconds, 0)
+                            .saturating_add(Duration::from_nanos(delay.nanos.max(0) as u64)),
+                    )
+                })
+        })
+        .map(|delay| delay.min(MAX_RETRY_AFTER))
+}
+
+/// Parses a `Retry-After` value: either a number of seconds, or an HTTP date (e.g. `Wed, 21 Oct 2015 07:28:00 GMT`),
+/// which is converted into the time remaining from `now`.
+pub fn parse_retry_after(value: &str, now: SystemTime) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<f64>() {
+        return (seconds.is_finite() && seconds >= 0.0)
+            .then(|| Duration::from_secs_f64(seconds.min(MAX_RETRY_AFTER.as_secs_f64())));
+    }
+    let date = parse_http_date(value)?;
+    let now = now.duration_since(UNIX_EPOCH).ok()?.as_secs();
+    Some(Duration::from_secs(date.saturating_sub(now)).min(MAX_RETRY_AFTER))
+}
+
+/// Parses an IMF-fixdate, the HTTP date format servers must send, into seconds since the Unix epoch.
+fn parse_http_date(value: &str) -> Option<u64> {
+    let parts: Vec<&str> = value.split_whitespace().collect();
+    let [_weekday, day, month, year, time, "GMT"] = parts.as_slice() else {
+        return None;
+    };
+    let month = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ]
+    .iter()
+    .position(|name| name == month)? as i64
+        + 1;
+    let day: i64 = day.parse().ok()?;
+    let year: i64 = year.parse().ok()?;
+    let mut time = time.split(':').map(|part| part.parse::<u64>().ok());
+    let (hours, minutes, seconds) = (time.next()??, time.next()??, time.next()??);
+
+    // Days since the epoch of a proleptic Gregorian date, see http://howardhinnant.github.io/date_algorithms.html
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let year_of_era = y - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = u64::try_from(era * 146097 + day_of_era - 719468).ok()?;
+    Some(days * 86400 + hours * 3600 + minutes * 60 + seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tonic::metadata::MetadataMap;
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = UNIX_EPOCH + Duration::from_secs(1445412470);
+        assert_eq!(parse_retry_after("2", now), Some(Duration::from_secs(2)));
+        assert_eq!(
+            parse_retry_after("0.5", now),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(parse_retry_after("3600", now), Some(MAX_RETRY_AFTER));
+        // Too large to fit in a `Duration`
+        assert_eq!(parse_retry_after("1e30", now), Some(MAX_RETRY_AFTER));
+        // 10 seconds after `now`
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT", now),
+            Some(Duration::from_secs(10))
+        );
+        // Dates in the past mean no wait
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:00:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("-1", now), None);
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[test]
+    fn test_grpc_retry_after() {
+        let mut metadata = MetadataMap::new();
+        metadata.insert("retry-after", "3".parse().unwrap());
+        let status = Status::with_metadata(tonic::Code::ResourceExhausted, "slow down", metadata);
+        assert_eq!(grpc_retry_after(&status), Some(Duration::from_secs(3)));
+
+        let retry_info = RetryInfo {
+            retry_delay: Some(prost_types::Duration {
+                seconds: 1,
+                nanos: 500_000_000,
+            }),
+        };
+        let details = RpcStatus {
+            code: tonic::Code::ResourceExhausted as i32,
+            message: "slow down".to_string(),
+            details: vec![prost_types::Any {
+                type_url: RETRY_INFO_TYPE_URL.to_string(),
+                value: retry_info.encode_to_vec(),
+            }],
+        };
+        let status = Status::with_details(
+            tonic::Code::ResourceExhausted,
+            "slow down",
+            details.encode_to_vec().into(),
+        );
+        assert_eq!(grpc_retry_after(&status), Some(Duration::from_millis(1500)));
+        assert_eq!(
+            grpc_retry_after(&Status::resource_exhausted("slow down")),
+            None
+        );
+
+        assert!(is_rate_limited(
+            &PineconeClientError::DataplaneOperationError(status)
+        ));
+        let err = PineconeClientError::ControlPlaneRateLimitError {
+            err: "".to_string(),
+            retry_after: Some(Duration::from_secs(2)),
+        };
+        assert!(is_rate_limited(&err));
+        assert_eq!(retry_after(&err), Some(Duration::from_secs(2)));
+    }
+}
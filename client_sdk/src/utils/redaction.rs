@@ -0,0 +1,93 @@
+//! Keeps secrets and bulky payloads out of the request/response debug logs.
+//!
+//! API keys are always redacted, down to their last characters, which is enough to tell keys apart.
+//! Lists of numbers, e.g. vector values and sparse indices, are truncated unless
+//! [`ClientConfig::log_full_payloads`](crate::client::config::ClientConfig::log_full_payloads) is set.
+
+/// The number of items logged of every list of numbers, unless full payloads are logged.
+pub const MAX_LOGGED_LIST_ITEMS: usize = 3;
+
+// Keys shorter than this are redacted entirely, so only a small part of any key is revealed
+const MIN_PARTIALLY_REDACTED_LEN: usize = 16;
+
+/// Redacts an API key, keeping its last 4 characters.
+pub fn redact_api_key(api_key: &str) -> String {
+    let chars: Vec<char> = api_key.chars().collect();
+    if chars.len() < MIN_PARTIALLY_REDACTED_LEN {
+        return "****".to_string();
+    }
+    let suffix: String = chars[chars.len() - 4..].iter().collect();
+    format!("****{suffix}")
+}
+
+/// Formats a request or response for the debug logs, truncating its lists of numbers unless `full` is set.
+pub fn format_payload(payload: &impl std::fmt::Debug, full: bool) -> String {
+    let debug = format!("{payload:?}");
+    if full {
+        debug
+    } else {
+        truncate_lists(&debug, MAX_LOGGED_LIST_ITEMS)
+    }
+}
+
+/// Truncates every list of more than `max_items` numbers in the `Debug` output of a value, e.g.
+/// `[0.1, 0.2, 0.3, 0.4]` becomes `[0.1, 0.2, ... 2 more]` with `max_items` 2. Other lists are kept whole.
+pub fn truncate_lists(debug: &str, max_items: usize) -> String {
+    let mut truncated = String::with_capacity(debug.len().min(1024));
+    let mut rest = debug;
+    while let Some(start) = rest.find('[') {
+        truncated.push_str(&rest[..=start]);
+        rest = &rest[start + 1..];
+        // Nested lists are handled by the next iterations
+        let Some(end) = rest.find(['[', ']']) else {
+            break;
+        };
+        if rest[end..].starts_with(']') {
+            let items: Vec<&str> = rest[..end].split(',').map(str::trim).collect();
+            if items.len() > max_items && items.iter().all(|item| item.parse::<f64>().is_ok()) {
+                truncated.push_str(&items[..max_items].join(", "));
+                truncated.push_str(&format!(", ... {} more", items.len() - max_items));
+                rest = &rest[end..];
+            }
+        }
+    }
+    truncated.push_str(rest);
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_api_key() {
+        assert_eq!(
+            redact_api_key("0a1b2c3d-4e5f-6a7b-8c9d-0e1f2a3b4c5d"),
+            "****4c5d"
+        );
+        assert_eq!(redact_api_key("short-key"), "****");
+        assert_eq!(redact_api_key(""), "****");
+    }
+
+    #[test]
+    fn test_truncate_lists() {
+        assert_eq!(
+            truncate_lists("Vector { id: \"a\", values: [0.1, 0.2, 0.3, 0.4, 0.5] }", 2),
+            "Vector { id: \"a\", values: [0.1, 0.2, ... 3 more] }"
+        );
+        assert_eq!(
+            truncate_lists("[[1, 2, 3, 4], [5, 6]]", 3),
+            "[[1, 2, 3, ... 1 more], [5, 6]]"
+        );
+        // Only lists of numbers are truncated
+        assert_eq!(
+            truncate_lists("ids: [\"a\", \"b\", \"c\", \"d\"]", 2),
+            "ids: [\"a\", \"b\", \"c\", \"d\"]"
+        );
+        assert_eq!(truncate_lists("values: []", 2), "values: []");
+        assert_eq!(
+            truncate_lists("unbalanced [1, 2, 3", 2),
+            "unbalanced [1, 2, 3"
+        );
+    }
+}
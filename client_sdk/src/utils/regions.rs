@@ -0,0 +1,131 @@
+use crate::utils::errors::PineconeClientError;
+
+/// The publicly available Pinecone environments (regions).
+/// The control plane doesn't expose this list, so it is maintained here.
+pub const SUPPORTED_ENVIRONMENTS: &[&str] = &[
+    "us-west1-gcp",
+    "us-west1-gcp-free",
+    "us-west4-gcp",
+    "us-central1-gcp",
+    "us-east1-gcp",
+    "us-east4-gcp",
+    "northamerica-northeast1-gcp",
+    "asia-northeast1-gcp",
+    "asia-southeast1-gcp",
+    "asia-southeast1-gcp-free",
+    "eu-west1-gcp",
+    "eu-west4-gcp",
+    "gcp-starter",
+    "us-east-1-aws",
+    "eastus-azure",
+];
+
+// Unknown regions at most this many edits away from a supported environment are considered typos
+const MAX_TYPO_DISTANCE: usize = 3;
+
+/// Returns the supported environments closest to `region`, closest first.
+/// Only environments that are likely to be what was meant (a few typos away) are returned.
+pub fn suggest_environments(region: &str) -> Vec<&'static str> {
+    let region = region.to_lowercase();
+    let mut candidates: Vec<(usize, &'static str)> = SUPPORTED_ENVIRONMENTS
+        .iter()
+        .map(|env| (edit_distance(&region, env), *env))
+        .filter(|(distance, _)| *distance <= MAX_TYPO_DISTANCE)
+        .collect();
+    candidates.sort();
+    candidates.into_iter().map(|(_, env)| env).collect()
+}
+
+/// Logs a warning if `region` looks like a typo of a supported environment.
+/// The region is never rejected upfront, since it may be an environment the built-in list doesn't include yet.
+/// Once its controller turns out to be unreachable, see [`unreachable_region_error`].
+pub fn check_region(region: &str) {
+    if SUPPORTED_ENVIRONMENTS.contains(&region) {
+        return;
+    }
+    let suggestions = suggest_environments(region);
+    if suggestions.is_empty() {
+        log::info!("Region '{region}' is not a known public Pinecone environment, assuming it is a private environment");
+    } else {
+        log::warn!(
+            "Region '{region}' is not a known public Pinecone environment. Did you mean '{}'?",
+            suggestions.join("', '")
+        );
+    }
+}
+
+/// Turns the failure to reach the controller of `region` into an `UnknownRegionError` with suggestions,
+/// if `region` looks like a typo of a supported environment. Other errors are returned as is.
+pub fn unreachable_region_error(region: &str, err: PineconeClientError) -> PineconeClientError {
+    if !matches!(err, PineconeClientError::ControlPlaneConnectionError { .. })
+        || SUPPORTED_ENVIRONMENTS.contains(&region)
+    {
+        return err;
+    }
+    let suggestions = suggest_environments(region);
+    if suggestions.is_empty() {
+        return err;
+    }
+    PineconeClientError::UnknownRegionError {
+        region: region.to_string(),
+        suggestions: suggestions.join("', '"),
+    }
+}
+
+/// The Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut curr = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ca != *cb);
+            curr[j + 1] = substitution.min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        prev = curr;
+    }
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("abc", ""), 3);
+        assert_eq!(edit_distance("us-west1gcp", "us-west1-gcp"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn test_unreachable_region_error() {
+        let unreachable = || PineconeClientError::ControlPlaneConnectionError {
+            region: String::new(),
+            err: String::new(),
+        };
+        let err = unreachable_region_error("us-west1gcp", unreachable());
+        assert!(matches!(
+            err,
+            PineconeClientError::UnknownRegionError { .. }
+        ));
+        assert!(err.to_string().contains("'us-west1-gcp'"));
+        // Known, or far from any public environment (so assumed to be private)
+        for region in ["us-west1-gcp", "internal-beta"] {
+            assert!(matches!(
+                unreachable_region_error(region, unreachable()),
+                PineconeClientError::ControlPlaneConnectionError { .. }
+            ));
+        }
+        // The controller was reached, so the region exists
+        assert!(matches!(
+            unreachable_region_error(
+                "us-west1gcp",
+                PineconeClientError::ValueError(String::new())
+            ),
+            PineconeClientError::ValueError(_)
+        ));
+        assert_eq!(suggest_environments("US-EAST-1-AWS")[0], "us-east-1-aws");
+    }
+}
@@ -0,0 +1,289 @@
+//! Computes similarity scores locally, with the same metric semantics as the service,
+//! e.g. to re-score, verify or rank fetched vectors.
+//!
+//! - `cosine` - the cosine of the angle between the dense values, from -1 to 1. Higher is more similar.
+//! - `dotproduct` - the dot product of the dense values, plus the dot product of the sparse values. Higher is more similar.
+//! - `euclidean` - the squared euclidean distance between the dense values. Lower is more similar.
+use std::collections::HashMap;
+
+use crate::data_types::{QueryResult, SparseValues, Vector};
+use crate::utils::errors::{PineconeClientError, PineconeResult};
+
+/// The number of independent accumulators used by the dense kernels.
+/// Splitting the sum lets the compiler vectorize the loops into SIMD instructions.
+const LANES: usize = 8;
+
+/// Sums `f(a[i], b[i])` over both slices, which must have the same length.
+#[inline]
+fn lane_sum(a: &[f32], b: &[f32], f: impl Fn(f32, f32) -> f32) -> f32 {
+    let mut acc = [0.0f32; LANES];
+    let a_chunks = a.chunks_exact(LANES);
+    let b_chunks = b.chunks_exact(LANES);
+    let tail: f32 = a_chunks
+        .remainder()
+        .iter()
+        .zip(b_chunks.remainder())
+        .map(|(x, y)| f(*x, *y))
+        .sum();
+    for (a_chunk, b_chunk) in a_chunks.zip(b_chunks) {
+        for i in 0..LANES {
+            acc[i] += f(a_chunk[i], b_chunk[i]);
+        }
+    }
+    acc.iter().sum::<f32>() + tail
+}
+
+/// The dot product of two dense vectors of the same dimension.
+pub fn dot_product(a: &[f32], b: &[f32]) -> f32 {
+    lane_sum(a, b, |x, y| x * y)
+}
+
+/// The cosine similarity of two dense vectors of the same dimension. Zero vectors have a similarity of 0.
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let norms = (dot_product(a, a) * dot_product(b, b)).sqrt();
+    if norms == 0.0 {
+        0.0
+    } else {
+        dot_product(a, b) / norms
+    }
+}
+
+/// The squared euclidean distance between two dense vectors of the same dimension.
+pub fn squared_euclidean_distance(a: &[f32], b: &[f32]) -> f32 {
+    lane_sum(a, b, |x, y| (x - y) * (x - y))
+}
+
+/// The dot product of two sparse vectors. Indices that appear in only one of them contribute nothing.
+pub fn sparse_dot_product(a: &SparseValues, b: &SparseValues) -> f32 {
+    let (small, large) = if a.indices.len() <= b.indices.len() {
+        (a, b)
+    } else {
+        (b, a)
+    };
+    let weights: HashMap<u32, f32> = small
+        .indices
+        .iter()
+        .copied()
+        .zip(small.values.iter().copied())
+        .collect();
+    large
+        .indices
+        .iter()
+        .zip(&large.values)
+        .filter_map(|(index, value)| weights.get(index).map(|weight| weight * value))
+        .sum()
+}
+
+/// Whether a higher score means more similar vectors for the given metric.
+pub fn higher_is_better(metric: &str) -> PineconeResult<bool> {
+    match metric {
+        "cosine" | "dotproduct" => Ok(true),
+        "euclidean" => Ok(false),
+        _ => Err(unknown_metric(metric)),
+    }
+}
+
+fn unknown_metric(metric: &str) -> PineconeClientError {
+    PineconeClientError::ValueError(format!(
+        "Unknown metric '{metric}'. Expected one of 'cosine', 'dotproduct' or 'euclidean'"
+    ))
+}
+
+/// Scores a pair of vectors, as the service would score them in an index with the given metric.
+///
+/// # Arguments
+/// - `a`, `b` - the vectors to score. Their dense values must have the same dimension.
+/// - `metric` - 'cosine', 'dotproduct' or 'euclidean'. Sparse values are only supported with 'dotproduct'.
+///
+/// # Returns
+/// The score, or a `ValueError` for an unknown metric, mismatching dimensions or unsupported sparse values.
+pub fn score(a: &Vector, b: &Vector, metric: &str) -> PineconeResult<f32> {
+    score_values(
+        &a.values,
+        a.sparse_values.as_ref(),
+        &b.values,
+        b.sparse_values.as_ref(),
+        metric,
+    )
+}
+
+fn score_values(
+    a: &[f32],
+    a_sparse: Option<&SparseValues>,
+    b: &[f32],
+    b_sparse: Option<&SparseValues>,
+    metric: &str,
+) -> PineconeResult<f32> {
+    if a.len() != b.len() {
+        return Err(PineconeClientError::ValueError(format!(
+            "Cannot score vectors of different dimensions ({} and {})",
+            a.len(),
+            b.len()
+        )));
+    }
+    let has_sparse = a_sparse.is_some() || b_sparse.is_some();
+    match metric {
+        "dotproduct" => {
+            let sparse = match (a_sparse, b_sparse) {
+                (Some(a_sparse), Some(b_sparse)) => sparse_dot_product(a_sparse, b_sparse),
+                _ => 0.0,
+            };
+            Ok(dot_product(a, b) + sparse)
+        }
+        "cosine" | "euclidean" if has_sparse => Err(PineconeClientError::ValueError(format!(
+            "Sparse values are only supported with the 'dotproduct' metric, not '{metric}'"
+        ))),
+        "cosine" => Ok(cosine_similarity(a, b)),
+        "euclidean" => Ok(squared_euclidean_distance(a, b)),
+        _ => Err(unknown_metric(metric)),
+    }
+}
+
+/// Scores `candidates` against `query` and sorts them most similar first, like the results of a query.
+///
+/// # Arguments
+/// - `query` - the query vector.
+/// - `candidates` - the vectors to rank, e.g. fetched from the index.
+/// - `metric` - the index's metric, see [`score`].
+/// - `top_k` - if set, only the `top_k` most similar candidates are returned.
+///
+/// # Returns
+/// The ranked candidates, with their values and metadata.
+pub fn rank(
+    query: &Vector,
+    candidates: Vec<Vector>,
+    metric: &str,
+    top_k: Option<usize>,
+) -> PineconeResult<Vec<QueryResult>> {
+    let results = candidates
+        .into_iter()
+        .map(|candidate| {
+            Ok(QueryResult {
+                score: score(query, &candidate, metric)?,
+                id: candidate.id,
+                values: Some(candidate.values),
+                sparse_values: candidate.sparse_values,
+                metadata: candidate.metadata,
+                namespace: None,
+            })
+        })
+        .collect::<PineconeResult<Vec<_>>>()?;
+    sort_results(results, metric, top_k)
+}
+
+/// Sorts query results most similar first, e.g. to merge the results of several queries into one ranking.
+/// Results with equal scores keep their relative order.
+///
+/// # Arguments
+/// - `results` - the results to sort, scored with the same `metric`.
+/// - `metric` - the index's metric, see [`score`].
+/// - `top_k` - if set, only the `top_k` most similar results are returned.
+pub fn sort_results(
+    mut results: Vec<QueryResult>,
+    metric: &str,
+    top_k: Option<usize>,
+) -> PineconeResult<Vec<QueryResult>> {
+    if higher_is_better(metric)? {
+        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+    } else {
+        results.sort_by(|a, b| a.score.total_cmp(&b.score));
+    }
+    if let Some(top_k) = top_k {
+        results.truncate(top_k);
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::python_conversions::DenseValues;
+
+    fn vector(id: &str, values: &[f32]) -> Vector {
+        Vector {
+            id: id.to_string(),
+            values: values.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_dense_metrics() {
+        // Longer than LANES, so both the chunks and the remainder are used
+        let a: Vec<f32> = (0..11).map(|i| i as f32).collect();
+        let b: Vec<f32> = (0..11).map(|i| (10 - i) as f32).collect();
+        let naive_dot: f32 = a.iter().zip(&b).map(|(x, y)| x * y).sum();
+        assert_eq!(dot_product(&a, &b), naive_dot);
+        assert_eq!(squared_euclidean_distance(&[1.0, 2.0], &[4.0, 6.0]), 25.0);
+        assert!((cosine_similarity(&[1.0, 0.0], &[2.0, 2.0]) - 0.70710677).abs() < 1e-6);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 1.0]), 0.0);
+    }
+
+    #[test]
+    fn test_score() {
+        let a = Vector {
+            sparse_values: Some(SparseValues::new(
+                vec![1, 5, 9],
+                DenseValues(vec![1.0, 2.0, 3.0]),
+            )),
+            ..vector("a", &[1.0, 2.0])
+        };
+        let b = Vector {
+            sparse_values: Some(SparseValues::new(
+                vec![5, 9, 12],
+                DenseValues(vec![0.5, 2.0, 7.0]),
+            )),
+            ..vector("b", &[3.0, 4.0])
+        };
+        assert_eq!(score(&a, &b, "dotproduct").unwrap(), 11.0 + 1.0 + 6.0);
+        assert!(score(&a, &b, "cosine").is_err());
+        assert!(score(&vector("a", &[1.0]), &vector("b", &[1.0, 2.0]), "cosine").is_err());
+        assert!(score(&vector("a", &[1.0]), &vector("b", &[1.0]), "manhattan").is_err());
+    }
+
+    #[test]
+    fn test_rank() {
+        let query = vector("q", &[1.0, 0.0]);
+        let candidates = vec![
+            vector("far", &[-1.0, 0.0]),
+            vector("near", &[0.9, 0.1]),
+            vector("mid", &[0.0, 1.0]),
+        ];
+        let ids = |results: Vec<QueryResult>| -> Vec<String> {
+            results.into_iter().map(|r| r.id).collect()
+        };
+        assert_eq!(
+            ids(rank(&query, candidates.clone(), "cosine", None).unwrap()),
+            vec!["near", "mid", "far"]
+        );
+        assert_eq!(
+            ids(rank(&query, candidates, "euclidean", Some(2)).unwrap()),
+            vec!["near", "mid"]
+        );
+    }
+
+    #[test]
+    fn test_sort_results() {
+        let result = |id: &str, score: f32| QueryResult {
+            id: id.to_string(),
+            score,
+            values: None,
+            sparse_values: None,
+            metadata: None,
+            namespace: None,
+        };
+        let results = vec![result("a", 0.5), result("b", 0.9), result("c", 0.5)];
+        let ids = |results: Vec<QueryResult>| -> Vec<String> {
+            results.into_iter().map(|r| r.id).collect()
+        };
+        assert_eq!(
+            ids(sort_results(results.clone(), "dotproduct", None).unwrap()),
+            vec!["b", "a", "c"]
+        );
+        assert_eq!(
+            ids(sort_results(results.clone(), "euclidean", Some(2)).unwrap()),
+            vec!["a", "c"]
+        );
+        assert!(sort_results(results, "manhattan", None).is_err());
+    }
+}
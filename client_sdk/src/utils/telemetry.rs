@@ -0,0 +1,166 @@
+//! OpenTelemetry tracing of the SDK's requests, enabled by the `otel` feature.
+//!
+//! Every data plane request attempt and every control plane request gets a client span, started as a child of the
+//! caller's current OpenTelemetry context. The span's context is propagated to the service with the globally configured
+//! propagator (e.g. W3C `traceparent` headers), as gRPC metadata for data plane requests, and as headers for the control
+//! plane requests the SDK builds itself (e.g. backups). Requests made through the generated control plane client
+//! (e.g. `create_index`) get a span, but the generated client doesn't allow setting their headers.
+//!
+//! Spans are disabled, and no trace context is propagated, if the client's diagnostics are disabled,
+//! see [`ClientConfig::diagnostics`](crate::client::config::ClientConfig::diagnostics).
+//!
+//! Without the feature, spans are no-ops that compile to nothing.
+use std::fmt::Display;
+use std::future::Future;
+
+#[cfg(feature = "otel")]
+use opentelemetry::{
+    global,
+    propagation::Injector,
+    trace::{FutureExt, SpanKind, Status, TraceContextExt, Tracer},
+    Context, KeyValue,
+};
+
+#[cfg(feature = "otel")]
+const TRACER_NAME: &str = "pinecone-client";
+
+/// The span of a single request. Ended explicitly, with the request's outcome.
+pub(crate) struct RequestSpan {
+    // `None` if the span is disabled
+    #[cfg(feature = "otel")]
+    cx: Option<Context>,
+}
+
+impl RequestSpan {
+    /// A span that isn't recorded, and whose requests don't propagate any trace context.
+    pub(crate) fn disabled() -> Self {
+        RequestSpan {
+            #[cfg(feature = "otel")]
+            cx: None,
+        }
+    }
+
+    /// Starts the span of a data plane request to the index at `url`.
+    #[cfg_attr(not(feature = "otel"), allow(unused_variables))]
+    pub(crate) fn grpc(operation: &str, url: &str) -> Self {
+        #[cfg(feature = "otel")]
+        {
+            let host = url.split("://").last().unwrap_or(url);
+            Self::start(
+                format!("VectorService/{operation}"),
+                vec![
+                    KeyValue::new("rpc.system", "grpc"),
+                    KeyValue::new("rpc.service", "VectorService"),
+                    KeyValue::new("rpc.method", operation.to_string()),
+                    KeyValue::new("server.address", host.to_string()),
+                ],
+            )
+        }
+        #[cfg(not(feature = "otel"))]
+        RequestSpan {}
+    }
+
+    /// Starts the span of a control plane request.
+    #[cfg_attr(not(feature = "otel"), allow(unused_variables))]
+    pub(crate) fn http(operation: &str) -> Self {
+        #[cfg(feature = "otel")]
+        {
+            Self::start(
+                format!("ControlPlane/{operation}"),
+                vec![KeyValue::new("pinecone.operation", operation.to_string())],
+            )
+        }
+        #[cfg(not(feature = "otel"))]
+        RequestSpan {}
+    }
+
+    #[cfg(feature = "otel")]
+    fn start(name: String, attributes: Vec<KeyValue>) -> Self {
+        let tracer = global::tracer(TRACER_NAME);
+        let parent = Context::current();
+        let span = tracer
+            .span_builder(name)
+            .with_kind(SpanKind::Client)
+            .with_attributes(attributes)
+            .start_with_context(&tracer, &parent);
+        RequestSpan {
+            cx: Some(parent.with_span(span)),
+        }
+    }
+
+    /// Adds the span's trace context to the metadata of a gRPC request.
+    #[cfg_attr(not(feature = "otel"), allow(unused_variables))]
+    pub(crate) fn inject(&self, metadata: &mut tonic::metadata::MetadataMap) {
+        #[cfg(feature = "otel")]
+        if let Some(cx) = &self.cx {
+            global::get_text_map_propagator(|propagator| {
+                propagator.inject_context(cx, &mut MetadataInjector(metadata))
+            });
+        }
+    }
+
+    /// Ends the span, marking it as failed with `error` if set.
+    #[cfg_attr(not(feature = "otel"), allow(unused_variables))]
+    pub(crate) fn end(self, error: Option<&dyn Display>) {
+        #[cfg(feature = "otel")]
+        if let Some(cx) = &self.cx {
+            let span = cx.span();
+            if let Some(error) = error {
+                span.set_status(Status::error(error.to_string()));
+            }
+            span.end();
+        }
+    }
+
+    /// Runs `request` with the span as the current context, so the requests it makes propagate the span,
+    /// see [`inject_current`]. Ends the span with the request's outcome.
+    /// A disabled span runs `request` with an empty context, so the caller's trace context isn't propagated either.
+    pub(crate) async fn run<T, E: Display>(
+        self,
+        request: impl Future<Output = Result<T, E>>,
+    ) -> Result<T, E> {
+        #[cfg(feature = "otel")]
+        let res = request
+            .with_context(self.cx.clone().unwrap_or_default())
+            .await;
+        #[cfg(not(feature = "otel"))]
+        let res = request.await;
+        self.end(res.as_ref().err().map(|err| err as &dyn Display));
+        res
+    }
+}
+
+/// Adds the current trace context to the headers of an HTTP request.
+#[cfg_attr(not(feature = "otel"), allow(unused_variables))]
+pub(crate) fn inject_current(headers: &mut reqwest::header::HeaderMap) {
+    #[cfg(feature = "otel")]
+    global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&Context::current(), &mut HeaderInjector(headers))
+    });
+}
+
+#[cfg(feature = "otel")]
+struct MetadataInjector<'a>(&'a mut tonic::metadata::MetadataMap);
+
+#[cfg(feature = "otel")]
+impl Injector for MetadataInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        let key = tonic::metadata::MetadataKey::from_bytes(key.as_bytes());
+        if let (Ok(key), Ok(value)) = (key, value.parse()) {
+            self.0.insert(key, value);
+        }
+    }
+}
+
+#[cfg(feature = "otel")]
+struct HeaderInjector<'a>(&'a mut reqwest::header::HeaderMap);
+
+#[cfg(feature = "otel")]
+impl Injector for HeaderInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        let key = reqwest::header::HeaderName::from_bytes(key.as_bytes());
+        if let (Ok(key), Ok(value)) = (key, value.parse()) {
+            self.0.insert(key, value);
+        }
+    }
+}
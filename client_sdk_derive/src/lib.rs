@@ -0,0 +1,127 @@
+//! Derive macros of `client_sdk`, enabled by its `derive` feature and used through its re-exports.
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr};
+
+/// Implements `client_sdk::utils::metadata_mapping::PineconeMetadata` for a struct with named fields,
+/// mapping every field to the metadata key of the same name.
+///
+/// Every field's type must implement `MetadataField`, so structs with unsupported field types don't compile.
+/// Fields can be customized with:
+/// - `#[pinecone(rename = "key")]` - maps the field to another metadata key.
+/// - `#[pinecone(skip)]` - leaves the field out of the metadata. It is set to its `Default` when converting back.
+#[proc_macro_derive(PineconeMetadata, attributes(pinecone))]
+pub fn derive_pinecone_metadata(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_pinecone_metadata(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+struct MetadataField {
+    ident: syn::Ident,
+    ty: syn::Type,
+    key: String,
+    skip: bool,
+}
+
+fn expand_pinecone_metadata(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let named = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new(
+                    input.ident.span(),
+                    "PineconeMetadata can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "PineconeMetadata can only be derived for structs",
+            ))
+        }
+    };
+    let fields = named
+        .iter()
+        .map(|field| {
+            // Named fields always have an ident
+            let ident = field.ident.clone().unwrap();
+            let mut parsed = MetadataField {
+                key: ident.to_string(),
+                ident,
+                ty: field.ty.clone(),
+                skip: false,
+            };
+            for attr in field
+                .attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("pinecone"))
+            {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("rename") {
+                        parsed.key = meta.value()?.parse::<LitStr>()?.value();
+                        Ok(())
+                    } else if meta.path.is_ident("skip") {
+                        parsed.skip = true;
+                        Ok(())
+                    } else {
+                        Err(meta.error("expected `rename = \"...\"` or `skip`"))
+                    }
+                })?;
+            }
+            Ok(parsed)
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let module = quote!(::client_sdk::utils::metadata_mapping);
+    let to_metadata = fields.iter().filter(|field| !field.skip).map(|field| {
+        let MetadataField { ident, ty, key, .. } = field;
+        // Spanned to the field's type, so a type without a `MetadataField` implementation is reported on the field
+        quote_spanned! {ty.span()=>
+            if let Some(value) = <#ty as #module::MetadataField>::to_value(&self.#ident) {
+                metadata.insert(#key.to_string(), value);
+            }
+        }
+    });
+    let from_metadata = fields.iter().map(|field| {
+        let MetadataField {
+            ident,
+            ty,
+            key,
+            skip,
+        } = field;
+        if *skip {
+            quote!(#ident: ::std::default::Default::default())
+        } else {
+            quote_spanned! {ty.span()=>
+                #ident: <#ty as #module::MetadataField>::from_value(#key, metadata.get(#key))?
+            }
+        }
+    });
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #module::PineconeMetadata for #name #ty_generics #where_clause {
+            fn to_metadata(
+                &self,
+            ) -> ::std::collections::BTreeMap<::std::string::String, ::client_sdk::data_types::MetadataValue> {
+                let mut metadata = ::std::collections::BTreeMap::new();
+                #(#to_metadata)*
+                metadata
+            }
+
+            fn from_metadata(
+                metadata: &::std::collections::BTreeMap<::std::string::String, ::client_sdk::data_types::MetadataValue>,
+            ) -> ::client_sdk::utils::errors::PineconeResult<Self> {
+                ::std::result::Result::Ok(Self {
+                    #(#from_metadata,)*
+                })
+            }
+        }
+    })
+}
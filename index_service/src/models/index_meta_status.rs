@@ -0,0 +1,35 @@
+/*
+ * defaultTitle
+ *
+ * defaultDescription
+ *
+ * The version of the OpenAPI document: 0.1
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct IndexMetaStatus {
+    #[serde(rename = "ready", skip_serializing_if = "Option::is_none")]
+    pub ready: Option<bool>,
+    #[serde(rename = "message", skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(rename = "state", skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+}
+
+impl IndexMetaStatus {
+    pub fn new() -> IndexMetaStatus {
+        IndexMetaStatus {
+            ready: None,
+            message: None,
+            state: None,
+        }
+    }
+}
+
+
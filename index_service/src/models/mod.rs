@@ -0,0 +1,24 @@
+pub mod approximated_config;
+pub use self::approximated_config::ApproximatedConfig;
+pub mod collection_meta;
+pub use self::collection_meta::CollectionMeta;
+pub mod create_collection_request;
+pub use self::create_collection_request::CreateCollectionRequest;
+pub mod create_request;
+pub use self::create_request::CreateRequest;
+pub mod create_request_index_config;
+pub use self::create_request_index_config::CreateRequestIndexConfig;
+pub mod create_request_metadata_config;
+pub use self::create_request_metadata_config::CreateRequestMetadataConfig;
+pub mod hnsw_config;
+pub use self::hnsw_config::HnswConfig;
+pub mod index_meta;
+pub use self::index_meta::IndexMeta;
+pub mod index_meta_database;
+pub use self::index_meta_database::IndexMetaDatabase;
+pub mod index_meta_database_metadata_config;
+pub use self::index_meta_database_metadata_config::IndexMetaDatabaseMetadataConfig;
+pub mod index_meta_status;
+pub use self::index_meta_status::IndexMetaStatus;
+pub mod patch_request;
+pub use self::patch_request::PatchRequest;
@@ -0,0 +1,54 @@
+/*
+ * defaultTitle
+ *
+ * defaultDescription
+ *
+ * The version of the OpenAPI document: 0.1
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct PatchRequest {
+    /// The desired number of replicas for the index.
+    #[serde(rename = "replicas", skip_serializing_if = "Option::is_none")]
+    pub replicas: Option<i32>,
+    /// The new pod type for the index. One of `s1`, `p1`, or `p2` appended with `.` and one of `x1`, `x2`, `x4`, or `x8`.
+    #[serde(rename = "pod_type", skip_serializing_if = "Option::is_none")]
+    pub pod_type: Option<String>,
+    /// The desired number of pods for the index. Must be a multiple of the number of replicas.
+    #[serde(rename = "pods", skip_serializing_if = "Option::is_none")]
+    pub pods: Option<i32>,
+    /// Whether the index can be deleted. One of `enabled` or `disabled`.
+    #[serde(rename = "deletion_protection", skip_serializing_if = "Option::is_none")]
+    pub deletion_protection: Option<String>,
+    /// Tags to add or change. A tag with an empty value is removed.
+    #[serde(rename = "tags", skip_serializing_if = "Option::is_none")]
+    pub tags: Option<::std::collections::HashMap<String, String>>,
+    /// The integrated embedding settings of a serverless index.
+    #[serde(rename = "embed", skip_serializing_if = "Option::is_none")]
+    pub embed: Option<serde_json::Value>,
+    /// The read capacity of a serverless index.
+    #[serde(rename = "read_capacity", skip_serializing_if = "Option::is_none")]
+    pub read_capacity: Option<serde_json::Value>,
+}
+
+impl PatchRequest {
+    pub fn new() -> PatchRequest {
+        PatchRequest {
+            replicas: None,
+            pod_type: None,
+            pods: None,
+            deletion_protection: None,
+            tags: None,
+            embed: None,
+            read_capacity: None,
+        }
+    }
+}
+
+
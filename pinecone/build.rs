@@ -0,0 +1,642 @@
+//! Generates `pinecone.pyi`, the type stubs of the Python module, from the pyo3 bindings, so IDEs and type checkers
+//! see the module's classes and functions with their signatures and docstrings.
+//!
+//! The stubs cover the classes, exceptions and functions registered in the `#[pymodule]` of `src/lib.rs`.
+//! Signatures are taken from the `text_signature` or `signature` attributes, falling back to the Rust parameters,
+//! and Rust types are mapped to their Python counterparts, or to `Any` if there is none (e.g. Python callables).
+//! The stubs are generated into `OUT_DIR`, so builds never modify the source tree. The copy maturin packages is the one
+//! checked in next to `Cargo.toml`, which the `PINECONE_STUBS` environment variable keeps in sync:
+//! - `PINECONE_STUBS=update` overwrites it with the generated stubs (`make stubs`).
+//! - `PINECONE_STUBS=check` fails the build if it differs from the generated stubs, which CI does.
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::error::Error;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use syn::{
+    Attribute, Expr, ExprLit, FnArg, GenericArgument, ImplItem, ImplItemFn, Item, ItemFn,
+    ItemStruct, Lit, Meta, Pat, PathArguments, ReturnType, Signature, Stmt, Type,
+};
+
+const SOURCE_DIRS: &[&str] = &["src", "../client_sdk/src"];
+const STUB_FILE: &str = "pinecone.pyi";
+const STUBS_ENV: &str = "PINECONE_STUBS";
+
+const HEADER: &str = "\
+# Type stubs of the `pinecone` module, generated by `pinecone/build.rs` from the pyo3 bindings. Do not edit.
+from typing import Any, Dict, List, Optional, Tuple
+";
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let manifest_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR")?);
+    let mut items = Bindings::default();
+    for dir in SOURCE_DIRS {
+        println!("cargo:rerun-if-changed={dir}");
+        let mut files = Vec::new();
+        collect_sources(&manifest_dir.join(dir), &mut files)?;
+        for path in files {
+            let file = syn::parse_file(&fs::read_to_string(&path)?)
+                .map_err(|err| format!("Failed to parse {}: {err}", path.display()))?;
+            items.collect(file.items);
+        }
+    }
+    let lib = syn::parse_file(&fs::read_to_string(manifest_dir.join("src/lib.rs"))?)?;
+    let module = Module::from_lib(&lib.items).ok_or("No #[pymodule] found in src/lib.rs")?;
+    let stubs = items.stubs(&module);
+
+    fs::write(
+        PathBuf::from(std::env::var("OUT_DIR")?).join(STUB_FILE),
+        &stubs,
+    )?;
+
+    println!("cargo:rerun-if-env-changed={STUBS_ENV}");
+    println!("cargo:rerun-if-changed={STUB_FILE}");
+    let checked_in = manifest_dir.join(STUB_FILE);
+    let up_to_date = fs::read_to_string(&checked_in).ok().as_deref() == Some(stubs.as_str());
+    match std::env::var(STUBS_ENV).as_deref() {
+        Ok("update") if !up_to_date => fs::write(checked_in, stubs)?,
+        Ok("check") if !up_to_date => {
+            return Err(format!(
+                "{} is out of date, run `make stubs` to regenerate it",
+                checked_in.display()
+            )
+            .into())
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn collect_sources(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    let mut entries = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            collect_sources(&path, files)?;
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// What the module registers, in registration order.
+struct Module {
+    classes: Vec<String>,
+    exceptions: Vec<String>,
+    functions: Vec<String>,
+}
+
+impl Module {
+    fn from_lib(items: &[Item]) -> Option<Self> {
+        let init = items.iter().find_map(|item| match item {
+            Item::Fn(func) if has_attr(&func.attrs, "pymodule") => Some(func),
+            _ => None,
+        })?;
+        let mut module = Module {
+            classes: Vec::new(),
+            exceptions: Vec::new(),
+            functions: Vec::new(),
+        };
+        for stmt in &init.block.stmts {
+            let Stmt::Expr(Expr::Try(call), _) = stmt else {
+                continue;
+            };
+            let Expr::MethodCall(call) = &*call.expr else {
+                continue;
+            };
+            match call.method.to_string().as_str() {
+                "add_class" => {
+                    let class = call.turbofish.as_ref().and_then(|turbofish| {
+                        match turbofish.args.first() {
+                            Some(GenericArgument::Type(ty)) => type_name(ty),
+                            _ => None,
+                        }
+                    });
+                    module.classes.extend(class);
+                }
+                "add_function" => {
+                    if let Some(Expr::Try(arg)) = call.args.first() {
+                        if let Expr::Macro(mac) = &*arg.expr {
+                            let tokens = mac.mac.tokens.to_string();
+                            let path = tokens.split(',').next().unwrap_or_default();
+                            let name = path.rsplit("::").next().unwrap_or_default().trim();
+                            module.functions.push(name.to_string());
+                        }
+                    }
+                }
+                // Only exceptions are added as plain attributes
+                "add" => {
+                    if let Some(Expr::Lit(ExprLit {
+                        lit: Lit::Str(name),
+                        ..
+                    })) = call.args.first()
+                    {
+                        module.exceptions.push(name.value());
+                    }
+                }
+                _ => {}
+            }
+        }
+        Some(module)
+    }
+}
+
+/// The pyo3 items of the parsed sources, by name.
+#[derive(Default)]
+struct Bindings {
+    structs: HashMap<String, ItemStruct>,
+    methods: HashMap<String, Vec<ImplItemFn>>,
+    functions: HashMap<String, ItemFn>,
+}
+
+impl Bindings {
+    fn collect(&mut self, items: Vec<Item>) {
+        for item in items {
+            match item {
+                Item::Struct(item) if has_attr(&item.attrs, "pyclass") => {
+                    self.structs.insert(item.ident.to_string(), item);
+                }
+                Item::Impl(item) if has_attr(&item.attrs, "pymethods") => {
+                    let Some(name) = type_name(&item.self_ty) else {
+                        continue;
+                    };
+                    let methods = item.items.into_iter().filter_map(|item| match item {
+                        ImplItem::Fn(method) => Some(method),
+                        _ => None,
+                    });
+                    self.methods.entry(name).or_default().extend(methods);
+                }
+                Item::Fn(item) if has_attr(&item.attrs, "pyfunction") => {
+                    self.functions.insert(item.sig.ident.to_string(), item);
+                }
+                Item::Mod(item) if !has_attr(&item.attrs, "cfg") => {
+                    if let Some((_, items)) = item.content {
+                        self.collect(items);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn stubs(&self, module: &Module) -> String {
+        let types = TypeMap {
+            classes: module.classes.iter().cloned().collect(),
+            self_name: None,
+            shadowed: HashSet::new(),
+        };
+        let mut out = HEADER.to_string();
+        let mut shadowed = BTreeSet::new();
+        for exception in &module.exceptions {
+            write!(out, "\nclass {exception}(Exception): ...\n").unwrap();
+        }
+        for class in &module.classes {
+            if let Some(item) = self.structs.get(class) {
+                out.push('\n');
+                shadowed.extend(self.write_class(&mut out, item, &types));
+            }
+        }
+        for function in &module.functions {
+            if let Some(item) = self.functions.get(function) {
+                out.push('\n');
+                write_function(
+                    &mut out,
+                    "",
+                    &item.attrs,
+                    &item.sig,
+                    FnKind::Function,
+                    &types,
+                );
+            }
+        }
+        if !shadowed.is_empty() {
+            out.push_str("\n# Aliases of the classes that methods of the same name shadow within a class, e.g. `Client.Index`\n");
+            for class in shadowed {
+                writeln!(out, "_{class} = {class}").unwrap();
+            }
+        }
+        out
+    }
+
+    /// Writes the stubs of a class, returning the classes its methods shadow.
+    fn write_class(&self, out: &mut String, item: &ItemStruct, types: &TypeMap) -> HashSet<String> {
+        let name = item.ident.to_string();
+        let class_signature = pyo3_arg(&item.attrs, "text_signature");
+        let methods: Vec<(&ImplItemFn, FnKind)> = self
+            .methods
+            .get(&name)
+            .into_iter()
+            .flatten()
+            .filter_map(|method| Some((method, FnKind::of(method, class_signature.as_deref())?)))
+            .collect();
+        let members: HashSet<String> = methods
+            .iter()
+            .map(|(method, kind)| python_name(&method.attrs, &method.sig, *kind))
+            .collect();
+        let types = TypeMap {
+            classes: types.classes.clone(),
+            self_name: Some(name.clone()),
+            shadowed: types.classes.intersection(&members).cloned().collect(),
+        };
+        writeln!(out, "class {name}:").unwrap();
+        let mut empty = true;
+        if let Some(docs) = docs(&item.attrs) {
+            write_docstring(out, "    ", &docs);
+            empty = false;
+        }
+        let get_all = pyo3_args(&item.attrs)
+            .iter()
+            .any(|(key, _)| key == "get_all");
+        for field in &item.fields {
+            let Some(ident) = &field.ident else {
+                continue;
+            };
+            let get = get_all || pyo3_args(&field.attrs).iter().any(|(key, _)| key == "get");
+            // Methods take precedence over fields of the same name, e.g. `Vector.values()`
+            if get && !members.contains(&ident.to_string()) {
+                writeln!(out, "    {ident}: {}", types.py(&field.ty)).unwrap();
+                empty = false;
+            }
+        }
+        for (method, kind) in methods {
+            if !empty {
+                out.push('\n');
+            }
+            write_function(out, "    ", &method.attrs, &method.sig, kind, &types);
+            empty = false;
+        }
+        if empty {
+            out.push_str("    ...\n");
+        }
+        types.shadowed
+    }
+}
+
+#[derive(Clone, Copy)]
+enum FnKind<'a> {
+    Function,
+    Method,
+    Constructor(Option<&'a str>),
+    Static,
+    Class,
+    Getter,
+    Setter,
+}
+
+impl<'a> FnKind<'a> {
+    /// The kind of a `#[pymethods]` method, or None if it isn't exposed in the stubs.
+    fn of(method: &ImplItemFn, class_signature: Option<&'a str>) -> Option<Self> {
+        let name = method.sig.ident.to_string();
+        // Private helpers, e.g. the class methods unpickling relies on
+        if name.starts_with('_') && !name.starts_with("__") {
+            return None;
+        }
+        let attrs = &method.attrs;
+        Some(if has_attr(attrs, "new") {
+            FnKind::Constructor(class_signature)
+        } else if has_attr(attrs, "staticmethod") {
+            FnKind::Static
+        } else if has_attr(attrs, "classmethod") {
+            FnKind::Class
+        } else if has_attr(attrs, "getter") {
+            FnKind::Getter
+        } else if has_attr(attrs, "setter") {
+            FnKind::Setter
+        } else {
+            FnKind::Method
+        })
+    }
+}
+
+fn write_function(
+    out: &mut String,
+    indent: &str,
+    attrs: &[Attribute],
+    sig: &Signature,
+    kind: FnKind,
+    types: &TypeMap,
+) {
+    let name = python_name(attrs, sig, kind);
+    let params = python_params(attrs, sig, kind);
+    let rust_types: HashMap<String, &Type> = rust_params(sig, kind)
+        .into_iter()
+        .map(|(name, ty)| (name.trim_start_matches('_').to_string(), ty))
+        .collect();
+    let mut py_params: Vec<String> = match kind {
+        FnKind::Function | FnKind::Static => Vec::new(),
+        FnKind::Class => vec!["cls".to_string()],
+        _ => vec!["self".to_string()],
+    };
+    for (param, default) in params {
+        let bare = param.trim_start_matches('*');
+        let typed = match rust_types.get(bare) {
+            Some(ty) if !param.starts_with('*') => {
+                format!("{param}: {}", types.py(ty))
+            }
+            _ => param.clone(),
+        };
+        py_params.push(match default {
+            Some(default) => format!("{typed} = {default}"),
+            None => typed,
+        });
+    }
+    let returns = match (kind, &sig.output) {
+        (FnKind::Constructor(_) | FnKind::Setter, _) | (_, ReturnType::Default) => {
+            "None".to_string()
+        }
+        (_, ReturnType::Type(_, ty)) => types.py(ty),
+    };
+    match kind {
+        FnKind::Static => writeln!(out, "{indent}@staticmethod").unwrap(),
+        FnKind::Class => writeln!(out, "{indent}@classmethod").unwrap(),
+        FnKind::Getter => writeln!(out, "{indent}@property").unwrap(),
+        FnKind::Setter => writeln!(out, "{indent}@{name}.setter").unwrap(),
+        _ => {}
+    }
+    write!(
+        out,
+        "{indent}def {name}({}) -> {returns}:",
+        py_params.join(", ")
+    )
+    .unwrap();
+    match docs(attrs) {
+        Some(docs) => {
+            out.push('\n');
+            write_docstring(out, &format!("{indent}    "), &docs);
+            writeln!(out, "{indent}    ...").unwrap();
+        }
+        None => out.push_str(" ...\n"),
+    }
+}
+
+fn python_name(attrs: &[Attribute], sig: &Signature, kind: FnKind) -> String {
+    let rust_name = sig.ident.to_string();
+    pyo3_arg(attrs, "name").unwrap_or_else(|| match kind {
+        FnKind::Constructor(_) => "__init__".to_string(),
+        FnKind::Getter => rust_name.trim_start_matches("get_").to_string(),
+        FnKind::Setter => rust_name.trim_start_matches("set_").to_string(),
+        _ => rust_name,
+    })
+}
+
+/// The Python parameters of a function, with their defaults, excluding `self` and `cls`.
+fn python_params(
+    attrs: &[Attribute],
+    sig: &Signature,
+    kind: FnKind,
+) -> Vec<(String, Option<String>)> {
+    let class_signature = match kind {
+        FnKind::Constructor(signature) => signature.map(str::to_string),
+        _ => None,
+    };
+    if let Some(signature) = pyo3_arg(attrs, "text_signature").or(class_signature) {
+        return split_params(&signature)
+            .into_iter()
+            .filter(|(name, _)| !name.starts_with('$'))
+            .collect();
+    }
+    if let Some(signature) = pyo3_arg(attrs, "signature") {
+        return split_params(&signature)
+            .into_iter()
+            .map(|(name, default)| (name, default.map(|default| python_default(&default))))
+            .collect();
+    }
+    // Without a signature, pyo3 makes trailing optional parameters default to None
+    let params = rust_params(sig, kind);
+    let required = params
+        .iter()
+        .rposition(|(_, ty)| type_name(ty).as_deref() != Some("Option"))
+        .map_or(0, |last| last + 1);
+    params
+        .into_iter()
+        .enumerate()
+        .map(|(i, (name, _))| (name, (i >= required).then(|| "None".to_string())))
+        .collect()
+}
+
+/// The parameters of a function that Python callers pass, with their Rust types.
+fn rust_params<'a>(sig: &'a Signature, kind: FnKind) -> Vec<(String, &'a Type)> {
+    let mut params: Vec<(String, &Type)> = sig
+        .inputs
+        .iter()
+        .filter_map(|input| match input {
+            FnArg::Typed(arg) => match &*arg.pat {
+                Pat::Ident(ident) => Some((ident.ident.to_string(), &*arg.ty)),
+                _ => None,
+            },
+            FnArg::Receiver(_) => None,
+        })
+        .filter(|(name, ty)| name != "slf" && type_name(ty).as_deref() != Some("Python"))
+        .collect();
+    if let (FnKind::Class, false) = (kind, params.is_empty()) {
+        params.remove(0);
+    }
+    params
+}
+
+/// Splits a signature, e.g. `($self, a, b=None, *, c=1)`, into its parameters and their defaults.
+fn split_params(signature: &str) -> Vec<(String, Option<String>)> {
+    let inner = signature
+        .trim()
+        .trim_start_matches('(')
+        .trim_end_matches(')');
+    let mut params = Vec::new();
+    let (mut depth, mut quoted, mut start) = (0, false, 0);
+    for (i, c) in inner.char_indices().chain([(inner.len(), ',')]) {
+        match c {
+            '"' | '\'' => quoted = !quoted,
+            '(' | '[' | '{' if !quoted => depth += 1,
+            ')' | ']' | '}' if !quoted => depth -= 1,
+            ',' if !quoted && depth == 0 => {
+                let param = inner[start..i].trim();
+                start = i + 1;
+                if param.is_empty() {
+                    continue;
+                }
+                params.push(match param.split_once('=') {
+                    Some((name, default)) => (
+                        name.split_whitespace().collect(),
+                        Some(default.trim().to_string()),
+                    ),
+                    None => (param.split_whitespace().collect(), None),
+                });
+            }
+            _ => {}
+        }
+    }
+    params
+}
+
+/// Translates a Rust default of a `signature` attribute into Python, or `...` if it isn't a literal.
+fn python_default(default: &str) -> String {
+    match default {
+        "None" => default.to_string(),
+        "true" => "True".to_string(),
+        "false" => "False".to_string(),
+        _ if default.starts_with('"') => default.to_string(),
+        _ => {
+            let number = default.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+            if number.parse::<f64>().is_ok() {
+                number.to_string()
+            } else {
+                "...".to_string()
+            }
+        }
+    }
+}
+
+/// Maps Rust types to the Python types of the stubs.
+struct TypeMap {
+    /// The classes of the module. Other types without a Python counterpart are mapped to `Any`.
+    classes: HashSet<String>,
+    /// The class `Self` refers to, within a class.
+    self_name: Option<String>,
+    /// The classes shadowed by methods of the same name within a class, referred to by their aliases.
+    shadowed: HashSet<String>,
+}
+
+impl TypeMap {
+    fn py(&self, ty: &Type) -> String {
+        let path = match ty {
+            Type::Reference(reference) => return self.py(&reference.elem),
+            Type::Tuple(tuple) if tuple.elems.is_empty() => return "None".to_string(),
+            Type::Tuple(tuple) => {
+                let elems: Vec<String> = tuple.elems.iter().map(|elem| self.py(elem)).collect();
+                return format!("Tuple[{}]", elems.join(", "));
+            }
+            Type::Path(path) => path,
+            _ => return "Any".to_string(),
+        };
+        let Some(segment) = path.path.segments.last() else {
+            return "Any".to_string();
+        };
+        let args: Vec<String> = match &segment.arguments {
+            PathArguments::AngleBracketed(args) => args
+                .args
+                .iter()
+                .filter_map(|arg| match arg {
+                    GenericArgument::Type(ty) => Some(self.py(ty)),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        let name = segment.ident.to_string();
+        match (name.as_str(), args.as_slice()) {
+            ("Option", [inner]) => format!("Optional[{inner}]"),
+            ("Vec" | "HashSet" | "BTreeSet", [inner]) => format!("List[{inner}]"),
+            ("HashMap" | "BTreeMap", [key, value]) => format!("Dict[{key}, {value}]"),
+            (
+                "Result" | "PyResult" | "PineconeResult" | "Py" | "PyRef" | "PyRefMut",
+                [inner, ..],
+            ) => inner.clone(),
+            // `Metric` and `PodType` are passed by name
+            ("String" | "str" | "PathBuf" | "Metric" | "PodType", _) => "str".to_string(),
+            ("bool", _) => "bool".to_string(),
+            ("f32" | "f64", _) => "float".to_string(),
+            (
+                "u8" | "u16" | "u32" | "u64" | "usize" | "i8" | "i16" | "i32" | "i64" | "isize",
+                _,
+            ) => "int".to_string(),
+            ("PyDict", _) => "Dict[str, Any]".to_string(),
+            ("PyList", _) => "List[Any]".to_string(),
+            ("PyType", _) => "type".to_string(),
+            ("Self", _) => match &self.self_name {
+                Some(class) => self.class(class),
+                None => "Any".to_string(),
+            },
+            (name, _) if self.classes.contains(name) => self.class(name),
+            _ => "Any".to_string(),
+        }
+    }
+
+    fn class(&self, name: &str) -> String {
+        if self.shadowed.contains(name) {
+            format!("_{name}")
+        } else {
+            name.to_string()
+        }
+    }
+}
+
+fn type_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(path) => path.path.segments.last().map(|s| s.ident.to_string()),
+        Type::Reference(reference) => type_name(&reference.elem),
+        _ => None,
+    }
+}
+
+fn has_attr(attrs: &[Attribute], name: &str) -> bool {
+    attrs.iter().any(|attr| attr.path().is_ident(name))
+}
+
+/// The arguments of the `#[pyo3(...)]` attributes, as keys and their (unparsed) values.
+fn pyo3_args(attrs: &[Attribute]) -> Vec<(String, Option<String>)> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("pyo3"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::List(list) => Some(list.tokens.to_string()),
+            _ => None,
+        })
+        .flat_map(|tokens| split_params(&tokens))
+        .collect()
+}
+
+/// The value of a `#[pyo3(key = ...)]` argument, unquoted if it is a string.
+fn pyo3_arg(attrs: &[Attribute], key: &str) -> Option<String> {
+    let value = pyo3_args(attrs).into_iter().find(|(k, _)| k == key)?.1?;
+    Some(match syn::parse_str::<syn::LitStr>(&value) {
+        Ok(lit) => lit.value(),
+        Err(_) => value,
+    })
+}
+
+fn docs(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(meta) if meta.path.is_ident("doc") => match &meta.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(doc), ..
+                }) => Some(doc.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .map(|line| {
+            line.strip_prefix(' ')
+                .unwrap_or(&line)
+                .trim_end()
+                .to_string()
+        })
+        .collect();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+fn write_docstring(out: &mut String, indent: &str, docs: &str) {
+    let docs = docs.replace('\\', "\\\\").replace("\"\"\"", "\\\"\\\"\\\"");
+    // A quote right before the closing quotes would end the docstring early
+    let docs = if docs.ends_with('"') {
+        format!("{docs} ")
+    } else {
+        docs
+    };
+    let mut lines = docs.lines();
+    write!(out, "{indent}\"\"\"{}", lines.next().unwrap_or_default()).unwrap();
+    for line in lines {
+        out.push('\n');
+        if !line.is_empty() {
+            write!(out, "{indent}{line}").unwrap();
+        }
+    }
+    if docs.contains('\n') {
+        write!(out, "\n{indent}").unwrap();
+    }
+    out.push_str("\"\"\"\n");
+}
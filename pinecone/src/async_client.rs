@@ -0,0 +1,521 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use client_sdk::client::pinecone_client::{self as core_client, ProgressCallback};
+use client_sdk::data_types::{ConfigureIndexOptions, Db, Metric, OperationMetrics, PodType};
+use client_sdk::utils::errors as core_errors;
+use client_sdk::utils::payload_dump;
+use pyo3::prelude::*;
+
+use crate::async_index::AsyncIndex;
+use crate::client::{client_builder, py_progress_callback};
+use crate::index::Index;
+use crate::utils::asyncio::sdk_future_into_py;
+use crate::utils::errors::PineconeClientError;
+use crate::utils::payload_dump::py_payload_sink;
+use crate::utils::runtime::RuntimeHandle;
+
+/// A Pinecone client whose operations all return `asyncio` awaitables, for FastAPI and other asyncio services
+/// that must never block the event loop. Create one with `await AsyncClient.connect()`.
+///
+/// Every operation takes the same arguments as its `Client` counterpart and returns an awaitable of the same result,
+/// except that indexes are returned as `AsyncIndex` objects. Requests run on the runtime shared by all clients.
+#[pyclass]
+pub struct AsyncClient {
+    inner: core_client::PineconeClient,
+    runtime: RuntimeHandle,
+}
+
+#[pymethods]
+impl AsyncClient {
+    #[staticmethod]
+    #[pyo3(signature = (api_key=None, region=None, project_id=None, diagnostics=None, warm_indexes=None, profile=None, controller_url=None, user_agent=None, max_retries=None, proxy=None, ca_certificates=None, client_certificate=None, client_key=None, connect_timeout=None, request_timeout=None, operation_timeout=None, request_ids=false, source_tag=None, event_handler=None))]
+    #[pyo3(
+        text_signature = "(api_key=None, region=None, project_id=None, diagnostics=None, warm_indexes=None, profile=None, controller_url=None, user_agent=None, max_retries=None, proxy=None, ca_certificates=None, client_certificate=None, client_key=None, connect_timeout=None, request_timeout=None, operation_timeout=None, request_ids=False, source_tag=None, event_handler=None)"
+    )]
+    /// Creates an async Pinecone client.
+    ///
+    /// Takes the same arguments as `Client()`, except `worker_threads`.
+    ///
+    /// Examples:
+    ///     >>> client = await AsyncClient.connect(api_key='...', region='us-west1-gcp')
+    ///     >>> index = await client.get_index('my-index')
+    ///     >>> results = await index.query(top_k=10, values=embedding)
+    ///
+    /// Returns:
+    ///     An awaitable of an `AsyncClient`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn connect<'a>(
+        py: Python<'a>,
+        api_key: Option<&str>,
+        region: Option<&str>,
+        project_id: Option<&str>,
+        diagnostics: Option<bool>,
+        warm_indexes: Option<Vec<String>>,
+        profile: Option<String>,
+        controller_url: Option<String>,
+        user_agent: Option<String>,
+        max_retries: Option<u32>,
+        proxy: Option<&str>,
+        ca_certificates: Option<PathBuf>,
+        client_certificate: Option<PathBuf>,
+        client_key: Option<PathBuf>,
+        connect_timeout: Option<f64>,
+        request_timeout: Option<f64>,
+        operation_timeout: Option<f64>,
+        request_ids: bool,
+        source_tag: Option<String>,
+        event_handler: Option<PyObject>,
+    ) -> PyResult<&'a PyAny> {
+        let runtime = RuntimeHandle::new(None)?;
+        let builder = client_builder(
+            api_key,
+            region,
+            project_id,
+            diagnostics,
+            profile,
+            controller_url,
+            user_agent,
+            max_retries,
+            proxy,
+            ca_certificates,
+            client_certificate,
+            client_key,
+            connect_timeout,
+            request_timeout,
+            operation_timeout,
+            request_ids,
+            source_tag,
+            event_handler,
+        )?;
+        sdk_future_into_py(py, async move {
+            let client = builder.build().await?.with_runtime((*runtime).clone());
+            if let Some(names) = warm_indexes {
+                let names: Vec<&str> = names.iter().map(String::as_str).collect();
+                // Failures are already logged, and the failed indexes are dialed again on first use
+                client.warm(&names).await;
+            }
+            Ok(AsyncClient {
+                inner: client,
+                runtime,
+            })
+        })
+    }
+
+    pub fn __repr__(&self) -> String {
+        let api_key = self.inner.api_key.split('-').next_back().unwrap_or("None");
+        format!(
+            "AsyncClient:\n  API key: ****************-{api_key}\n  region: {region}\n  project_id: {project_id}",
+            region = self.inner.region,
+            project_id = self.inner.project_id
+        )
+    }
+
+    /// Get an AsyncIndex object for interacting with a Pinecone index, see `Client.get_index()`.
+    ///
+    /// Returns:
+    ///     An awaitable of the `AsyncIndex`.
+    #[pyo3(signature = (index_name, normalize=false))]
+    #[pyo3(text_signature = "($self, index_name, normalize=False)")]
+    pub fn get_index<'a>(
+        &self,
+        py: Python<'a>,
+        index_name: &str,
+        normalize: bool,
+    ) -> PyResult<&'a PyAny> {
+        let inner = self.inner.clone();
+        let runtime = self.runtime.clone();
+        let index_name = index_name.to_owned();
+        sdk_future_into_py(py, async move {
+            let mut inner_index = inner.get_index(&index_name).await?;
+            inner_index.set_normalize(normalize);
+            Ok(AsyncIndex::new(Index::new(inner_index, runtime)))
+        })
+    }
+
+    /// Get an AsyncIndex object for an index with a known host, without any control plane request.
+    /// See `Client.index_from_host()`.
+    ///
+    /// Returns:
+    ///     AsyncIndex: The index object.
+    #[pyo3(signature = (host, index_name, normalize=false))]
+    #[pyo3(text_signature = "($self, host, index_name, normalize=False)")]
+    pub fn index_from_host<'a>(
+        &self,
+        py: Python<'a>,
+        host: &str,
+        index_name: &str,
+        normalize: bool,
+    ) -> PyResult<&'a PyAny> {
+        let inner = self.inner.clone();
+        let runtime = self.runtime.clone();
+        let (host, index_name) = (host.to_owned(), index_name.to_owned());
+        sdk_future_into_py(py, async move {
+            let mut inner_index = inner.index_from_host(&host, &index_name).await?;
+            inner_index.set_normalize(normalize);
+            Ok(AsyncIndex::new(Index::new(inner_index, runtime)))
+        })
+    }
+
+    /// Get the latency percentiles and counts of the requests made by this client and all of its indexes.
+    /// See `Client.metrics()`.
+    #[pyo3(text_signature = "($self)")]
+    pub fn metrics(&self) -> BTreeMap<String, OperationMetrics> {
+        self.inner.metrics()
+    }
+
+    /// Creates an index, see `Client.create_index()`. Cancelling the awaitable stops waiting for the index to be ready,
+    /// but doesn't cancel its creation.
+    ///
+    /// Returns:
+    ///     An awaitable of the `AsyncIndex`, once the index is ready.
+    #[pyo3(signature = (name, dimension=None, metric=None, replicas=None, shards=None, pods=None, pod_type=None, metadata_config=None, source_collection=None, timeout=None, payload_dump=None, progress=None))]
+    #[pyo3(
+        text_signature = "($self, name, dimension=None, metric=None, replicas=None, shards=None, pods=None, pod_type=None, metadata_config=None, source_collection=None, timeout=None, payload_dump=None, progress=None)"
+    )]
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_index<'a>(
+        &self,
+        py: Python<'a>,
+        name: &str,
+        dimension: Option<i32>,
+        metric: Option<Metric>,
+        replicas: Option<i32>,
+        shards: Option<i32>,
+        pods: Option<i32>,
+        pod_type: Option<PodType>,
+        metadata_config: Option<BTreeMap<String, Vec<String>>>,
+        source_collection: Option<String>,
+        timeout: Option<i32>,
+        payload_dump: Option<PyObject>,
+        progress: Option<PyObject>,
+    ) -> PyResult<&'a PyAny> {
+        let db = Db {
+            name: name.into(),
+            // 0 means unset, and is inferred from the source collection
+            dimension: dimension.unwrap_or(0),
+            metric,
+            replicas,
+            shards,
+            pods,
+            pod_type,
+            metadata_config,
+            source_collection,
+            ..Default::default()
+        };
+        let inner = self.inner.clone();
+        let runtime = self.runtime.clone();
+        let progress = progress.map(py_progress_callback);
+        let name = name.to_owned();
+        sdk_future_into_py(py, async move {
+            let create = inner.create_index(
+                db,
+                timeout,
+                false,
+                None,
+                progress
+                    .as_ref()
+                    .map(|progress| progress as ProgressCallback),
+            );
+            match payload_dump {
+                Some(callback) => payload_dump::scope(py_payload_sink(callback), create).await?,
+                None => create.await?,
+            }
+            let inner_index = inner.get_index(&name).await?;
+            Ok(AsyncIndex::new(Index::new(inner_index, runtime)))
+        })
+    }
+
+    /// Delete an index, see `Client.delete_index()`.
+    ///
+    /// Returns:
+    ///     An awaitable of None.
+    #[pyo3(signature = (name, timeout=None))]
+    #[pyo3(text_signature = "($self, name, timeout=None)")]
+    pub fn delete_index<'a>(
+        &self,
+        py: Python<'a>,
+        name: &str,
+        timeout: Option<i32>,
+    ) -> PyResult<&'a PyAny> {
+        let inner = self.inner.clone();
+        let name = name.to_owned();
+        sdk_future_into_py(
+            py,
+            async move { inner.delete_index(&name, timeout, None).await },
+        )
+    }
+
+    /// List all indexes
+    ///
+    /// Returns:
+    ///     An awaitable of the names of all indexes in the project.
+    #[pyo3(text_signature = "($self)")]
+    pub fn list_indexes<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
+        let inner = self.inner.clone();
+        sdk_future_into_py(py, async move { inner.list_indexes().await })
+    }
+
+    /// List all indexes, with their full descriptions, see `Client.list_indexes_full()`.
+    ///
+    /// Returns:
+    ///     An awaitable of objects describing the configuration of every index in the project, sorted by name.
+    #[pyo3(text_signature = "($self)")]
+    pub fn list_indexes_full<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
+        let inner = self.inner.clone();
+        sdk_future_into_py(py, async move { inner.list_indexes_full().await })
+    }
+
+    /// Describe an index.
+    ///
+    /// Returns:
+    ///     An awaitable of an object describing the index configuration.
+    #[pyo3(text_signature = "($self, name)")]
+    pub fn describe_index<'a>(&self, py: Python<'a>, name: &str) -> PyResult<&'a PyAny> {
+        let inner = self.inner.clone();
+        let name = name.to_owned();
+        sdk_future_into_py(py, async move { inner.describe_index(&name).await })
+    }
+
+    /// Describe several indexes concurrently, see `Client.describe_indexes()`.
+    ///
+    /// Returns:
+    ///     An awaitable of a mapping from each index name to an object describing its configuration,
+    ///     or to the raised exception if describing it failed.
+    #[pyo3(text_signature = "($self, names)")]
+    pub fn describe_indexes<'a>(&self, py: Python<'a>, names: Vec<String>) -> PyResult<&'a PyAny> {
+        let inner = self.inner.clone();
+        sdk_future_into_py(py, async move {
+            let names: Vec<&str> = names.iter().map(String::as_str).collect();
+            let res = inner.describe_indexes(&names).await;
+            Ok(Python::with_gil(|py| {
+                res.into_iter()
+                    .map(|(name, db)| {
+                        let obj = match db {
+                            Ok(db) => db.into_py(py),
+                            Err(err) => PyErr::from(PineconeClientError::from(err)).into_py(py),
+                        };
+                        (name, obj)
+                    })
+                    .collect::<BTreeMap<String, PyObject>>()
+            }))
+        })
+    }
+
+    /// Connect to several indexes up front, see `Client.warm()`.
+    ///
+    /// Returns:
+    ///     An awaitable of a mapping from each index name to None if it was warmed up successfully,
+    ///     or to the raised exception otherwise.
+    #[pyo3(text_signature = "($self, names)")]
+    pub fn warm<'a>(&self, py: Python<'a>, names: Vec<String>) -> PyResult<&'a PyAny> {
+        let inner = self.inner.clone();
+        sdk_future_into_py(py, async move {
+            let names: Vec<&str> = names.iter().map(String::as_str).collect();
+            let res = inner.warm(&names).await;
+            Ok(Python::with_gil(|py| {
+                res.into_iter()
+                    .map(|(name, res)| {
+                        let obj = match res {
+                            Ok(()) => py.None(),
+                            Err(err) => PyErr::from(PineconeClientError::from(err)).into_py(py),
+                        };
+                        (name, obj)
+                    })
+                    .collect::<BTreeMap<String, PyObject>>()
+            }))
+        })
+    }
+
+    #[pyo3(signature = (name, replicas=None, pod_type=None, pods=None, deletion_protection=None, tags=None))]
+    #[pyo3(
+        text_signature = "($self, name, replicas=None, pod_type=None, pods=None, deletion_protection=None, tags=None)"
+    )]
+    /// Configure an index, see `Client.configure_index()`.
+    ///
+    /// Returns:
+    ///     An awaitable of None.
+    #[allow(clippy::too_many_arguments)]
+    pub fn configure_index<'a>(
+        &self,
+        py: Python<'a>,
+        name: &str,
+        replicas: Option<i32>,
+        pod_type: Option<PodType>,
+        pods: Option<i32>,
+        deletion_protection: Option<bool>,
+        tags: Option<BTreeMap<String, String>>,
+    ) -> PyResult<&'a PyAny> {
+        let options = ConfigureIndexOptions {
+            pod_type,
+            replicas,
+            pods,
+            deletion_protection,
+            tags,
+            ..Default::default()
+        };
+        let inner = self.inner.clone();
+        let name = name.to_owned();
+        sdk_future_into_py(py, async move {
+            inner.configure_index_with(&name, options).await
+        })
+    }
+
+    #[pyo3(signature = (name, replicas=None, pod_type=None))]
+    #[pyo3(text_signature = "($self, name, replicas=None, pod_type=None)")]
+    /// Rescale an index, see `Client.scale_index()`.
+    ///
+    /// Returns:
+    ///     An awaitable of None.
+    pub fn scale_index<'a>(
+        &self,
+        py: Python<'a>,
+        name: &str,
+        replicas: Option<i32>,
+        pod_type: Option<PodType>,
+    ) -> PyResult<&'a PyAny> {
+        // at least one of replicas or pod_type must be set
+        if replicas.is_none() && pod_type.is_none() {
+            return Err(
+                PineconeClientError::from(core_errors::PineconeClientError::ValueError(
+                    "At least one of replicas or pod_type must be set".into(),
+                ))
+                .into(),
+            );
+        }
+        let inner = self.inner.clone();
+        let name = name.to_owned();
+        sdk_future_into_py(py, async move {
+            inner.configure_index(&name, pod_type, replicas).await
+        })
+    }
+
+    /// Create a new collection.
+    ///
+    /// Returns:
+    ///     An awaitable of None.
+    #[pyo3(text_signature = "($self, name, source_index)")]
+    pub fn create_collection<'a>(
+        &self,
+        py: Python<'a>,
+        name: &str,
+        source_index: &str,
+    ) -> PyResult<&'a PyAny> {
+        let inner = self.inner.clone();
+        let (name, source_index) = (name.to_owned(), source_index.to_owned());
+        sdk_future_into_py(py, async move {
+            inner.create_collection(&name, &source_index).await
+        })
+    }
+
+    /// Describe a collection
+    ///
+    /// Returns:
+    ///     An awaitable of the collection description.
+    #[pyo3(text_signature = "($self, name)")]
+    pub fn describe_collection<'a>(&self, py: Python<'a>, name: &str) -> PyResult<&'a PyAny> {
+        let inner = self.inner.clone();
+        let name = name.to_owned();
+        sdk_future_into_py(py, async move { inner.describe_collection(&name).await })
+    }
+
+    /// List all collections
+    ///
+    /// Returns:
+    ///     An awaitable of the names of all collections.
+    #[pyo3(text_signature = "($self)")]
+    pub fn list_collections<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
+        let inner = self.inner.clone();
+        sdk_future_into_py(py, async move { inner.list_collections().await })
+    }
+
+    /// Delete a collection
+    ///
+    /// Returns:
+    ///     An awaitable of None.
+    #[pyo3(text_signature = "($self, name)")]
+    pub fn delete_collection<'a>(&self, py: Python<'a>, name: &str) -> PyResult<&'a PyAny> {
+        let inner = self.inner.clone();
+        let name = name.to_owned();
+        sdk_future_into_py(py, async move { inner.delete_collection(&name).await })
+    }
+
+    /// Create a backup of an index, see `Client.create_backup()`.
+    ///
+    /// Returns:
+    ///     An awaitable of the new `Backup`.
+    #[pyo3(signature = (index_name, name=None, description=None))]
+    #[pyo3(text_signature = "($self, index_name, name=None, description=None)")]
+    pub fn create_backup<'a>(
+        &self,
+        py: Python<'a>,
+        index_name: &str,
+        name: Option<String>,
+        description: Option<String>,
+    ) -> PyResult<&'a PyAny> {
+        let inner = self.inner.clone();
+        let index_name = index_name.to_owned();
+        sdk_future_into_py(py, async move {
+            inner
+                .create_backup(&index_name, name.as_deref(), description.as_deref())
+                .await
+        })
+    }
+
+    /// List backups, see `Client.list_backups()`.
+    ///
+    /// Returns:
+    ///     An awaitable of the backups of all indexes in the project, or of `index_name`.
+    #[pyo3(signature = (index_name=None))]
+    #[pyo3(text_signature = "($self, index_name=None)")]
+    pub fn list_backups<'a>(
+        &self,
+        py: Python<'a>,
+        index_name: Option<String>,
+    ) -> PyResult<&'a PyAny> {
+        let inner = self.inner.clone();
+        sdk_future_into_py(py, async move {
+            inner.list_backups(index_name.as_deref()).await
+        })
+    }
+
+    /// Describe a backup
+    ///
+    /// Returns:
+    ///     An awaitable of the backup description.
+    #[pyo3(text_signature = "($self, backup_id)")]
+    pub fn describe_backup<'a>(&self, py: Python<'a>, backup_id: &str) -> PyResult<&'a PyAny> {
+        let inner = self.inner.clone();
+        let backup_id = backup_id.to_owned();
+        sdk_future_into_py(py, async move { inner.describe_backup(&backup_id).await })
+    }
+
+    /// Delete a backup
+    ///
+    /// Returns:
+    ///     An awaitable of None.
+    #[pyo3(text_signature = "($self, backup_id)")]
+    pub fn delete_backup<'a>(&self, py: Python<'a>, backup_id: &str) -> PyResult<&'a PyAny> {
+        let inner = self.inner.clone();
+        let backup_id = backup_id.to_owned();
+        sdk_future_into_py(py, async move { inner.delete_backup(&backup_id).await })
+    }
+
+    /// Create a new index, restored from a backup.
+    ///
+    /// Returns:
+    ///     An awaitable of None.
+    #[pyo3(text_signature = "($self, backup_id, name)")]
+    pub fn create_index_from_backup<'a>(
+        &self,
+        py: Python<'a>,
+        backup_id: &str,
+        name: &str,
+    ) -> PyResult<&'a PyAny> {
+        let inner = self.inner.clone();
+        let (backup_id, name) = (backup_id.to_owned(), name.to_owned());
+        sdk_future_into_py(py, async move {
+            inner.create_index_from_backup(&backup_id, &name).await
+        })
+    }
+}
@@ -0,0 +1,401 @@
+use std::collections::BTreeMap;
+
+use pyo3::prelude::*;
+
+use crate::data_types::{QueryBatchItem, UpsertRecord};
+use crate::index::Index;
+use crate::utils::asyncio::sdk_future_into_py;
+use crate::utils::errors::{PineconeClientError, PineconeResult};
+use client_sdk::data_types as core_data_types;
+use client_sdk::utils::errors::PineconeClientError as core_error;
+use client_sdk::utils::python_conversions::DenseValues;
+
+/// An index whose operations all return `asyncio` awaitables, for asyncio services that must never block the event loop.
+/// Get one from `AsyncClient.get_index()`.
+///
+/// Every operation takes the same arguments as its `Index` counterpart, except `async_req`, and returns an awaitable
+/// of the same result. Cancelling the awaitable aborts the in-flight requests.
+#[pyclass]
+pub struct AsyncIndex {
+    index: Index,
+}
+
+impl AsyncIndex {
+    pub fn new(index: Index) -> Self {
+        Self { index }
+    }
+}
+
+#[pymethods]
+impl AsyncIndex {
+    pub fn __repr__(&self) -> String {
+        format!("Async{}", self.index.__repr__())
+    }
+
+    /// The namespace operations default to.
+    #[getter]
+    pub fn namespace(&self) -> String {
+        self.index.namespace()
+    }
+
+    /// The client-side latency breakdown of the most recent data operation made through this index, see `Index.last_latency`.
+    #[getter]
+    pub fn last_latency(&self) -> Option<core_data_types::LatencyBreakdown> {
+        self.index.last_latency()
+    }
+
+    /// Counters of the data plane requests made through this index, see `Index.client_stats`.
+    #[getter]
+    pub fn client_stats(&self) -> core_data_types::ClientStats {
+        self.index.client_stats()
+    }
+
+    #[pyo3(text_signature = "($self, namespace)")]
+    /// Returns a lightweight view of this index, in which every operation defaults to the given namespace.
+    /// See `Index.with_namespace()`.
+    ///
+    /// Returns:
+    ///     AsyncIndex: An index object bound to the given namespace.
+    pub fn with_namespace(&self, namespace: &str) -> AsyncIndex {
+        AsyncIndex::new(self.index.with_namespace(namespace))
+    }
+
+    #[pyo3(text_signature = "($self, timeout)")]
+    /// Returns a lightweight view of this index, in which every operation must complete within the given timeout.
+    /// See `Index.with_timeout()`.
+    ///
+    /// Returns:
+    ///     AsyncIndex: An index object bound to the given timeout.
+    pub fn with_timeout(&self, timeout: f64) -> PineconeResult<AsyncIndex> {
+        Ok(AsyncIndex::new(self.index.with_timeout(timeout)?))
+    }
+
+    #[pyo3(
+        signature = (vectors, namespace=None, normalize=None, batch_size=None, timeout=None, skip_null_metadata=false, return_ids=false, id_fn=None, max_concurrency=None, allow_partial_failure=false, request_id=None, max_retries=None)
+    )]
+    #[pyo3(
+        text_signature = "($self, vectors, namespace=None, normalize=None, batch_size=None, timeout=None, skip_null_metadata=False, return_ids=False, id_fn=None, max_concurrency=None, allow_partial_failure=False, request_id=None, max_retries=None)"
+    )]
+    /// Upsert vectors, see `Index.upsert()`.
+    ///
+    /// Returns:
+    ///     An awaitable of an `UpsertResponse`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn upsert<'a>(
+        &self,
+        py: Python<'a>,
+        vectors: Vec<UpsertRecord>,
+        namespace: Option<&'a str>,
+        normalize: Option<bool>,
+        batch_size: Option<u32>,
+        timeout: Option<f64>,
+        skip_null_metadata: bool,
+        return_ids: bool,
+        id_fn: Option<PyObject>,
+        max_concurrency: Option<usize>,
+        allow_partial_failure: bool,
+        request_id: Option<String>,
+        max_retries: Option<u32>,
+    ) -> PyResult<&'a PyAny> {
+        self.index.upsert(
+            py,
+            vectors,
+            namespace,
+            true,
+            normalize,
+            batch_size,
+            timeout,
+            skip_null_metadata,
+            return_ids,
+            id_fn,
+            max_concurrency,
+            allow_partial_failure,
+            request_id,
+            max_retries,
+        )
+    }
+
+    #[pyo3(signature = (top_k, values=None, sparse_values=None, namespace=None, filter=None, include_values=false, include_metadata=false, normalize=None, columnar=false, timeout=None, max_retries=None))]
+    #[pyo3(
+        text_signature = "($self, top_k, values=None, sparse_values=None, namespace=None, filter=None, include_values=False, include_metadata=False, normalize=None, columnar=False, timeout=None, max_retries=None)"
+    )]
+    /// Query, see `Index.query()`.
+    ///
+    /// Returns:
+    ///     An awaitable of a QueryResponse, or of a ColumnarResult if `columnar` is set.
+    #[allow(clippy::too_many_arguments)]
+    pub fn query(
+        &self,
+        py: Python,
+        top_k: i32,
+        values: Option<DenseValues>,
+        sparse_values: Option<core_data_types::SparseValues>,
+        namespace: Option<&str>,
+        filter: Option<BTreeMap<String, core_data_types::MetadataValue>>,
+        include_values: bool,
+        include_metadata: bool,
+        normalize: Option<bool>,
+        columnar: bool,
+        timeout: Option<f64>,
+        max_retries: Option<u32>,
+    ) -> PyResult<PyObject> {
+        self.index.query(
+            py,
+            top_k,
+            values,
+            sparse_values,
+            namespace,
+            filter,
+            include_values,
+            include_metadata,
+            normalize,
+            columnar,
+            timeout,
+            max_retries,
+            true,
+        )
+    }
+
+    #[pyo3(signature = (id, top_k, namespace=None, filter=None, include_values=false, include_metadata=false))]
+    #[pyo3(
+        text_signature = "($self, id, top_k, namespace=None, filter=None, include_values=False, include_metadata=False)"
+    )]
+    /// Query by id, see `Index.query_by_id()`.
+    ///
+    /// Returns:
+    ///     An awaitable of a QueryResponse.
+    #[allow(clippy::too_many_arguments)]
+    pub fn query_by_id(
+        &self,
+        py: Python,
+        id: &str,
+        top_k: i32,
+        namespace: Option<&str>,
+        filter: Option<BTreeMap<String, core_data_types::MetadataValue>>,
+        include_values: bool,
+        include_metadata: bool,
+    ) -> PyResult<PyObject> {
+        self.index.query_by_id(
+            py,
+            id,
+            top_k,
+            namespace,
+            filter,
+            include_values,
+            include_metadata,
+            true,
+        )
+    }
+
+    #[pyo3(signature = (queries, top_k, namespace=None, filter=None, include_values=false, include_metadata=false, normalize=None))]
+    #[pyo3(
+        text_signature = "($self, queries, top_k, namespace=None, filter=None, include_values=False, include_metadata=False, normalize=None)"
+    )]
+    /// Query batch, see `Index.query_batch()`.
+    ///
+    /// Returns:
+    ///     An awaitable of a list of lists of QueryResults, one list per query, in the same order as `queries`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn query_batch<'a>(
+        &self,
+        py: Python<'a>,
+        queries: Vec<QueryBatchItem>,
+        top_k: i32,
+        namespace: Option<&str>,
+        filter: Option<BTreeMap<String, core_data_types::MetadataValue>>,
+        include_values: bool,
+        include_metadata: bool,
+        normalize: Option<bool>,
+    ) -> PyResult<&'a PyAny> {
+        if top_k < 1 {
+            return Err(PineconeClientError::from(core_error::ValueError(
+                "top_k must be greater than 0".to_string(),
+            ))
+            .into());
+        }
+        let inner = self.index.inner.clone();
+        let queries: Vec<core_data_types::QueryInput> =
+            queries.into_iter().map(Into::into).collect();
+        let namespace = namespace.map(str::to_owned);
+        sdk_future_into_py(py, async move {
+            inner
+                .query_batch(
+                    namespace.as_deref(),
+                    &queries,
+                    top_k as u32,
+                    filter,
+                    include_values,
+                    include_metadata,
+                    normalize,
+                )
+                .await
+        })
+    }
+
+    #[pyo3(signature = (ids, namespace=None, timeout=None, columnar=false, max_retries=None))]
+    #[pyo3(
+        text_signature = "($self, ids, namespace=None, timeout=None, columnar=False, max_retries=None)"
+    )]
+    /// Fetch, see `Index.fetch()`.
+    ///
+    /// Returns:
+    ///     An awaitable of a FetchResponse, or of a ColumnarResult if `columnar` is set.
+    #[allow(clippy::too_many_arguments)]
+    pub fn fetch(
+        &self,
+        py: Python,
+        ids: Vec<String>,
+        namespace: Option<&str>,
+        timeout: Option<f64>,
+        columnar: bool,
+        max_retries: Option<u32>,
+    ) -> PyResult<PyObject> {
+        self.index
+            .fetch(py, ids, namespace, timeout, columnar, max_retries, true)
+    }
+
+    #[pyo3(signature = (namespace=None, prefix=None, limit=None, pagination_token=None))]
+    #[pyo3(
+        text_signature = "($self, namespace=None, prefix=None, limit=None, pagination_token=None)"
+    )]
+    /// List, see `Index.list()`.
+    ///
+    /// Returns:
+    ///     An awaitable of the page's ids, and the token of the next page, or None if this is the last page.
+    pub fn list<'a>(
+        &self,
+        py: Python<'a>,
+        namespace: Option<&str>,
+        prefix: Option<&str>,
+        limit: Option<u32>,
+        pagination_token: Option<&str>,
+    ) -> PyResult<&'a PyAny> {
+        let inner = self.index.inner.clone();
+        let namespace = namespace.map(str::to_owned);
+        let prefix = prefix.map(str::to_owned);
+        let pagination_token = pagination_token.map(str::to_owned);
+        sdk_future_into_py(py, async move {
+            inner
+                .list(
+                    namespace.as_deref(),
+                    prefix.as_deref(),
+                    limit,
+                    pagination_token.as_deref(),
+                )
+                .await
+        })
+    }
+
+    #[pyo3(signature = (id, values=None, sparse_values=None, set_metadata=None, namespace=None, unset_metadata=None))]
+    #[pyo3(
+        text_signature = "($self, id, values=None, sparse_values=None, set_metadata=None, namespace=None, unset_metadata=None)"
+    )]
+    /// Update, see `Index.update()`.
+    ///
+    /// Returns:
+    ///     An awaitable of an `UpdateResponse`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn update(
+        &self,
+        py: Python,
+        id: &str,
+        values: Option<DenseValues>,
+        sparse_values: Option<core_data_types::SparseValues>,
+        set_metadata: Option<BTreeMap<String, core_data_types::MetadataValue>>,
+        namespace: Option<&str>,
+        unset_metadata: Option<Vec<String>>,
+    ) -> PyResult<PyObject> {
+        self.index.update(
+            py,
+            id,
+            values,
+            sparse_values,
+            set_metadata,
+            namespace,
+            unset_metadata,
+            true,
+        )
+    }
+
+    #[pyo3(signature = (ids, namespace=None, max_retries=None))]
+    #[pyo3(text_signature = "($self, ids, namespace=None, max_retries=None)")]
+    /// Delete vectors by ID, see `Index.delete()`.
+    ///
+    /// Returns:
+    ///     An awaitable of a `DeleteResponse`.
+    pub fn delete(
+        &self,
+        py: Python,
+        ids: Vec<String>,
+        namespace: Option<&str>,
+        max_retries: Option<u32>,
+    ) -> PyResult<PyObject> {
+        self.index.delete(py, ids, namespace, max_retries, true)
+    }
+
+    #[pyo3(signature = (filter, namespace=None, max_retries=None))]
+    #[pyo3(text_signature = "($self, filter, namespace=None, max_retries=None)")]
+    /// Delete by filter, see `Index.delete_by_metadata()`.
+    ///
+    /// Returns:
+    ///     An awaitable of a `DeleteResponse`.
+    pub fn delete_by_metadata(
+        &self,
+        py: Python,
+        filter: Option<BTreeMap<String, core_data_types::MetadataValue>>,
+        namespace: Option<&str>,
+        max_retries: Option<u32>,
+    ) -> PyResult<PyObject> {
+        self.index
+            .delete_by_metadata(py, filter, namespace, max_retries, true)
+    }
+
+    #[pyo3(signature = (namespace=None, max_retries=None))]
+    #[pyo3(text_signature = "($self, namespace=None, max_retries=None)")]
+    /// Delete all the vectors of a namespace, see `Index.delete_all()`.
+    ///
+    /// Returns:
+    ///     An awaitable of a `DeleteResponse`.
+    pub fn delete_all(
+        &self,
+        py: Python,
+        namespace: Option<&str>,
+        max_retries: Option<u32>,
+    ) -> PyResult<PyObject> {
+        self.index.delete_all(py, namespace, max_retries, true)
+    }
+
+    #[pyo3(signature = (filter=None))]
+    #[pyo3(text_signature = "($self, filter=None)")]
+    /// Describe index stats, see `Index.describe_index_stats()`.
+    ///
+    /// Returns:
+    ///     An awaitable of an `IndexStats` object.
+    pub fn describe_index_stats(
+        &self,
+        py: Python,
+        filter: Option<BTreeMap<String, core_data_types::MetadataValue>>,
+    ) -> PyResult<PyObject> {
+        self.index.describe_index_stats(py, filter, true)
+    }
+
+    /// Ping the index, see `Index.ping()`.
+    ///
+    /// Returns:
+    ///     An awaitable of a `PingResult`.
+    #[pyo3(text_signature = "($self)")]
+    pub fn ping<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
+        let inner = self.index.inner.clone();
+        sdk_future_into_py(py, async move { inner.ping().await })
+    }
+
+    /// Describe the index, see `Index.describe()`.
+    ///
+    /// Returns:
+    ///     An awaitable of a `DB` object describing the index configuration.
+    #[pyo3(text_signature = "($self)")]
+    pub fn describe<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
+        let inner = self.index.inner.clone();
+        sdk_future_into_py(py, async move { inner.describe().await })
+    }
+}
@@ -1,37 +1,63 @@
 use pyo3::types::PyDict;
 use pyo3::{FromPyObject, PyAny};
-use std::collections::BTreeMap;
 
 use crate::utils::errors::{PineconeClientError, PineconeResult};
 use client_sdk::data_types as core_data_types;
 use client_sdk::utils::errors::PineconeClientError as core_error;
+use client_sdk::utils::python_conversions::{extract_metadata, vector_from_dict, DenseValues};
 
 #[derive(FromPyObject, Debug, Clone)]
 pub enum UpsertRecord<'a> {
     Vector(core_data_types::Vector),
-    TwoTuple((String, Vec<f32>)),
-    ThreeTuple(
-        (
-            String,
-            Vec<f32>,
-            BTreeMap<String, core_data_types::MetadataValue>,
-        ),
-    ),
+    TwoTuple((String, DenseValues)),
+    ThreeTuple((String, DenseValues, &'a PyDict)),
     Dict(&'a PyDict),
     #[pyo3(transparent)]
     Other(&'a PyAny), // This extraction never fails
 }
 
+/// A single query passed to `query_batch()`.
+#[derive(FromPyObject, Debug, Clone)]
+pub enum QueryBatchItem {
+    Id(String),
+    Hybrid((DenseValues, core_data_types::SparseValues)),
+    Values(DenseValues),
+}
+
+impl From<QueryBatchItem> for core_data_types::QueryInput {
+    fn from(item: QueryBatchItem) -> Self {
+        match item {
+            QueryBatchItem::Id(id) => core_data_types::QueryInput::Id(id),
+            QueryBatchItem::Hybrid((values, sparse_values)) => {
+                core_data_types::QueryInput::Values {
+                    values: values.into(),
+                    sparse_values: Some(sparse_values),
+                }
+            }
+            QueryBatchItem::Values(values) => core_data_types::QueryInput::Values {
+                values: values.into(),
+                sparse_values: None,
+            },
+        }
+    }
+}
+
+/// Converts the vectors passed to `upsert()` into Vectors.
+/// If `skip_null_metadata` is set, metadata keys with a `None` value are dropped instead of failing the conversion.
 pub fn convert_upsert_enum_to_vectors(
     vectors: Vec<UpsertRecord>,
+    skip_null_metadata: bool,
 ) -> PineconeResult<Vec<core_data_types::Vector>> {
     let vectors_to_upsert: Vec<core_data_types::Vector> = vectors.into_iter().enumerate().map(|(i, vec)| {
             let new_vec: PineconeResult<core_data_types::Vector> = match vec.to_owned() {
                 UpsertRecord::Vector(v) => Ok(v),
-                UpsertRecord::TwoTuple(t) => Ok(core_data_types::Vector{ id: t.0, values: t.1 , ..Default::default()}),
-                UpsertRecord::ThreeTuple(t) => Ok(core_data_types::Vector{ id: t.0, values: t.1 , metadata: Some(t.2),  ..Default::default()}),
+                UpsertRecord::TwoTuple(t) => Ok(core_data_types::Vector{ id: t.0, values: t.1.into(), ..Default::default()}),
+                UpsertRecord::ThreeTuple(t) => Ok(core_data_types::Vector{ id: t.0, values: t.1.into(),
+                    metadata: Some(extract_metadata(t.2, skip_null_metadata)
+                        .map_err(|e| core_error::ValueError(format!("Error in vector number {i}: {e}", i=i, e=e)))?),
+                    ..Default::default()}),
                 UpsertRecord::Dict(d) => Ok(
-                    d.try_into()
+                    vector_from_dict(d, skip_null_metadata)
                         .map_err(|e| match e{
                             core_error::UpsertKeyError { key, vec_num: _ } =>
                                 core_error::UpsertKeyError {key, vec_num: i},
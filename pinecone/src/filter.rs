@@ -0,0 +1,27 @@
+use crate::utils::errors::PineconeResult;
+use client_sdk::data_types as core_data_types;
+use client_sdk::utils::filter_parser;
+use pyo3::prelude::*;
+use std::collections::BTreeMap;
+
+#[pyfunction]
+#[pyo3(text_signature = "(expression)")]
+/// Parses a SQL-like filter expression into a metadata filter dictionary.
+///
+/// Supports comparisons (`=`, `!=`, `<>`, `>`, `>=`, `<`, `<=`), `IN (...)`, `NOT IN (...)`, `IS NULL`, `IS NOT NULL`,
+/// `AND`, `OR` and parentheses. Values are quoted strings, numbers, or TRUE/FALSE.
+///
+/// Args:
+///     expression (str): The filter expression.
+///
+/// Examples:
+///     >>> parse_filter("genre = 'drama' AND year >= 2020 AND tag IN ('a', 'b')")
+///     {'$and': [{'genre': {'$eq': 'drama'}}, {'year': {'$gte': 2020.0}}, {'tag': {'$in': ['a', 'b']}}]}
+///
+/// Returns:
+///     dict: A filter that can be passed to `Index.query()`, `Index.describe_index_stats()` or `Index.delete_by_metadata()`.
+pub fn parse_filter(
+    expression: &str,
+) -> PineconeResult<BTreeMap<String, core_data_types::MetadataValue>> {
+    Ok(filter_parser::parse_filter(expression)?)
+}
@@ -0,0 +1,39 @@
+use crate::utils::errors::PineconeResult;
+use client_sdk::utils::ids;
+use pyo3::prelude::*;
+
+#[pyfunction]
+#[pyo3(signature = (name, namespace=ids::NAMESPACE_URL))]
+#[pyo3(text_signature = "(name, namespace=...)")]
+/// Derives a stable vector id from content, as a name-based (version 5) UUID.
+/// Re-running an ingestion job with ids derived this way overwrites the vectors it created before, instead of duplicating them.
+///
+/// Args:
+///     name (str): The content to derive the id from, e.g. a document's URL or a chunk's text.
+///     namespace (str): A UUID scoping the ids, e.g. one generated per dataset. Defaults to the RFC 4122 URL namespace.
+///
+/// Examples:
+///     >>> uuid5("https://example.com/doc-1#chunk-3")
+///
+/// Returns:
+///     str: The UUID, in its hyphenated form. Identical to `str(uuid.uuid5(uuid.UUID(namespace), name))`.
+pub fn uuid5(name: &str, namespace: &str) -> PineconeResult<String> {
+    Ok(ids::uuid_v5(namespace, name)?)
+}
+
+#[pyfunction]
+#[pyo3(text_signature = "(content)")]
+/// Derives a stable vector id from a hash of the content.
+///
+/// Args:
+///     content (Union[str, bytes]): The content to derive the id from. Strings are hashed as UTF-8.
+///
+/// Returns:
+///     str: The hex SHA-1 digest of the content.
+pub fn content_id(content: &PyAny) -> PyResult<String> {
+    let bytes: Vec<u8> = match content.extract::<&str>() {
+        Ok(text) => text.as_bytes().to_vec(),
+        Err(_) => content.extract()?,
+    };
+    Ok(ids::content_hash_id(&bytes))
+}
@@ -1,33 +1,316 @@
+use crate::client::positive_duration;
 use crate::data_types::convert_upsert_enum_to_vectors;
-use crate::data_types::UpsertRecord;
+use crate::data_types::{QueryBatchItem, UpsertRecord};
+use crate::utils::asyncio::cancellable_future_into_py;
 use crate::utils::errors::{PineconeClientError, PineconeResult};
+use crate::utils::payload_dump::py_payload_sink;
+use crate::utils::runtime::RuntimeHandle;
+use client_sdk::client::config::RetryPolicy;
 use client_sdk::data_types as core_data_types;
 use client_sdk::index as core_index;
 use client_sdk::utils::errors::PineconeClientError as core_error;
+use client_sdk::utils::evaluation;
+use client_sdk::utils::latency::Phase;
+use client_sdk::utils::python_conversions::{
+    columnar_query_results, columnar_vectors, DenseValues,
+};
 use pyo3::prelude::*;
-use std::collections::{BTreeMap, HashMap};
-use tokio::runtime::Handle;
+use std::collections::{BTreeMap, VecDeque};
+use std::future::Future;
+use std::time::{Duration, Instant};
 
 #[pyclass]
 pub struct Index {
-    inner: core_index::Index,
-    runtime: Handle,
+    pub(crate) inner: core_index::Index,
+    runtime: RuntimeHandle,
 }
 
 impl Index {
-    pub fn new(inner: core_index::Index, runtime: Handle) -> Self {
+    pub fn new(inner: core_index::Index, runtime: RuntimeHandle) -> Self {
         Self { inner, runtime }
     }
+
+    /// Returns the inner index, bound to the given timeout (in seconds) and number of retries, if set.
+    fn inner_for_call(
+        &self,
+        timeout: Option<f64>,
+        max_retries: Option<u32>,
+    ) -> PineconeResult<core_index::Index> {
+        let inner = match timeout {
+            Some(timeout) => self
+                .inner
+                .with_timeout(positive_duration("timeout", timeout)?),
+            None => self.inner.clone(),
+        };
+        Ok(match max_retries {
+            Some(max_retries) => inner.with_retry_policy(RetryPolicy {
+                max_retries,
+                ..inner.retry_policy().clone()
+            }),
+            None => inner,
+        })
+    }
+
+    /// Runs an operation to completion, or returns an `asyncio` awaitable of it if `async_req` is set.
+    /// `convert` turns the operation's result into a Python object, holding the GIL.
+    fn run<T, E, F, C>(
+        &self,
+        py: Python,
+        async_req: bool,
+        operation: F,
+        convert: C,
+    ) -> PyResult<PyObject>
+    where
+        F: Future<Output = Result<T, E>> + Send + 'static,
+        PineconeClientError: From<E>,
+        E: Send + 'static,
+        T: Send + 'static,
+        C: FnOnce(Python, T) -> PyResult<PyObject> + Send + 'static,
+    {
+        if async_req {
+            let fut = async move {
+                let res = operation.await.map_err(PineconeClientError::from)?;
+                Python::with_gil(|py| convert(py, res))
+            };
+            return Ok(cancellable_future_into_py(py, fut)?.into());
+        }
+        let res = self
+            .runtime
+            .block_on(operation)
+            .map_err(PineconeClientError::from)?;
+        convert(py, res)
+    }
+}
+
+/// Converts an operation's result into a Python object, recording the conversion time in the operation's latency breakdown.
+fn to_py_timed(index: &core_index::Index, py: Python, res: impl IntoPy<PyObject>) -> PyObject {
+    let start = Instant::now();
+    let obj = res.into_py(py);
+    index.record_latency(Phase::PythonConversion, start.elapsed());
+    obj
+}
+
+/// Builds a columnar result, recording the build time in the operation's latency breakdown,
+/// and in the call's own breakdown, which the result carries.
+fn columnar_timed(
+    index: &core_index::Index,
+    py: Python,
+    timing: Option<core_data_types::LatencyBreakdown>,
+    build: impl FnOnce() -> PyResult<core_data_types::ColumnarResult>,
+) -> PyResult<PyObject> {
+    let start = Instant::now();
+    let mut columnar = build()?;
+    let elapsed = start.elapsed();
+    index.record_latency(Phase::PythonConversion, elapsed);
+    columnar.timing = timing.map(|mut timing| {
+        timing.add_external(Phase::PythonConversion, elapsed);
+        timing
+    });
+    Ok(columnar.into_py(py))
+}
+
+/// Adds the time spent converting the upserted vectors from Python objects to the upsert's own latency breakdown.
+fn with_input_conversion(
+    mut response: core_data_types::UpsertResponse,
+    input_conversion: Duration,
+) -> core_data_types::UpsertResponse {
+    if let Some(timing) = response.timing.as_mut() {
+        timing.add_external(Phase::InputConversion, input_conversion);
+    }
+    response
 }
 
 #[pymethods]
 impl Index {
     pub fn __repr__(&self) -> String {
-        format!("Index: \"{name}\"", name = self.inner.name)
+        if self.inner.namespace().is_empty() {
+            format!("Index: \"{name}\"", name = self.inner.name)
+        } else {
+            format!(
+                "Index: \"{name}\", namespace: \"{namespace}\"",
+                name = self.inner.name,
+                namespace = self.inner.namespace()
+            )
+        }
+    }
+
+    #[pyo3(text_signature = "($self, namespace)")]
+    /// Returns a lightweight view of this index, in which every operation defaults to the given namespace.
+    /// The namespace can still be overridden by passing `namespace` explicitly to any operation.
+    /// The view shares the underlying connection with the original index.
+    ///
+    /// Args:
+    ///     namespace (str): The namespace that operations on the returned index will default to.
+    ///
+    /// Examples:
+    ///     >>> tenant_index = index.with_namespace('tenant-a')
+    ///     >>> tenant_index.upsert([('id1', [1.0, 2.0, 3.0])])
+    ///     >>> tenant_index.query(top_k=10, values=[1.0, 2.0, 3.0])
+    ///
+    /// Returns:
+    ///     Index: An index object bound to the given namespace.
+    pub fn with_namespace(&self, namespace: &str) -> Index {
+        Index::new(self.inner.with_namespace(namespace), self.runtime.clone())
+    }
+
+    #[pyo3(text_signature = "($self, context)")]
+    /// Returns a lightweight view of this index, in which the audit events of mutating operations carry the given context,
+    /// in addition to the context passed to `Client.set_audit_hook()`.
+    /// The view shares the underlying connection with the original index.
+    ///
+    /// Args:
+    ///     context (Dict[str, str]): The context, e.g. the user or job on whose behalf the operations are made.
+    ///
+    /// Examples:
+    ///     >>> job_index = index.with_audit_context({'job': 'nightly-ingest'})
+    ///     >>> job_index.delete(ids=['id1'])
+    ///
+    /// Returns:
+    ///     Index: An index object bound to the given audit context.
+    pub fn with_audit_context(&self, context: BTreeMap<String, String>) -> Index {
+        Index::new(self.inner.with_audit_context(context), self.runtime.clone())
+    }
+
+    #[pyo3(text_signature = "($self, timeout)")]
+    /// Returns a lightweight view of this index, in which every operation must complete within the given timeout.
+    /// For operations split into multiple requests (e.g. `upsert` with `batch_size`), the timeout applies to the whole operation.
+    /// The view shares the underlying connection with the original index.
+    ///
+    /// Args:
+    ///     timeout (float): The timeout in seconds.
+    ///
+    /// Returns:
+    ///     Index: An index object bound to the given timeout.
+    pub fn with_timeout(&self, timeout: f64) -> PineconeResult<Index> {
+        Ok(Index::new(
+            self.inner_for_call(Some(timeout), None)?,
+            self.runtime.clone(),
+        ))
+    }
+
+    /// Returns a lightweight view of this index, which calls `callback` with the payload of every request it sends and
+    /// every response it receives, e.g. to troubleshoot requests the index rejects. Payloads are dumped whole,
+    /// vector values included. The view shares the underlying connection with the original index.
+    ///
+    /// Args:
+    ///     callback (Callable[[PayloadDump], None]): Called with every payload, e.g. `print`.
+    ///                                               Exceptions raised by the callback are printed and otherwise ignored.
+    ///
+    /// Examples:
+    ///     >>> index.with_payload_dump(print).upsert([('id1', [0.1, 0.2, 0.3])])
+    ///
+    /// Returns:
+    ///     Index: An index object that dumps its payloads.
+    #[pyo3(text_signature = "($self, callback)")]
+    pub fn with_payload_dump(&self, callback: PyObject) -> Index {
+        Index::new(
+            self.inner.with_payload_dump(py_payload_sink(callback)),
+            self.runtime.clone(),
+        )
+    }
+
+    #[pyo3(signature = (max_retries=None, initial_backoff=None, max_backoff=None, max_rate_limit_retries=None))]
+    #[pyo3(
+        text_signature = "($self, max_retries=None, initial_backoff=None, max_backoff=None, max_rate_limit_retries=None)"
+    )]
+    /// Returns a lightweight view of this index, whose operations are retried according to the given settings
+    /// instead of the Client's, e.g. without retries for `delete_all()`, or with more retries for `fetch()`.
+    /// Settings that aren't given keep their current value. The view shares the underlying connection with the original index.
+    ///
+    /// Args:
+    ///     max_retries (Optional[int]): How many times requests that failed transiently are retried.
+    ///     initial_backoff (Optional[float]): The backoff before the first retry, in seconds. Doubled after every retry.
+    ///     max_backoff (Optional[float]): The longest backoff between retries, in seconds.
+    ///     max_rate_limit_retries (Optional[int]): How many times rate-limited requests are retried.
+    ///
+    /// Returns:
+    ///     Index: An index object bound to the given retry settings.
+    pub fn with_retry_policy(
+        &self,
+        max_retries: Option<u32>,
+        initial_backoff: Option<f64>,
+        max_backoff: Option<f64>,
+        max_rate_limit_retries: Option<u32>,
+    ) -> PineconeResult<Index> {
+        let mut retry_policy = self.inner.retry_policy().clone();
+        if let Some(max_retries) = max_retries {
+            retry_policy.max_retries = max_retries;
+        }
+        if let Some(backoff) = initial_backoff {
+            retry_policy.initial_backoff = positive_duration("initial_backoff", backoff)?;
+        }
+        if let Some(backoff) = max_backoff {
+            retry_policy.max_backoff = positive_duration("max_backoff", backoff)?;
+        }
+        if let Some(max_rate_limit_retries) = max_rate_limit_retries {
+            retry_policy.max_rate_limit_retries = max_rate_limit_retries;
+        }
+        Ok(Index::new(
+            self.inner.with_retry_policy(retry_policy),
+            self.runtime.clone(),
+        ))
+    }
+
+    #[pyo3(signature = (capacity=10000, ttl=60.0))]
+    #[pyo3(text_signature = "($self, capacity=10000, ttl=60.0)")]
+    /// Enable a local cache of fetched vectors.
+    ///
+    /// Repeated `fetch()` calls for the same ids are served from the cache, until the cached vectors expire.
+    /// Cached vectors are invalidated when they are upserted, updated or deleted through this Index.
+    /// Changes made by other clients are only seen after the cached vectors expire.
+    ///
+    /// Args:
+    ///     capacity (int): The maximal number of cached vectors. The least recently used vectors are evicted first. Defaults to 10000.
+    ///     ttl (float): The time, in seconds, after which a cached vector expires. Defaults to 60 seconds.
+    ///
+    /// Returns:
+    ///     None
+    pub fn enable_fetch_cache(&mut self, capacity: usize, ttl: f64) -> PineconeResult<()> {
+        if !(ttl >= 0.0 && ttl.is_finite()) {
+            return Err(core_error::ValueError(format!(
+                "ttl must be a non-negative number of seconds, found: {ttl}"
+            ))
+            .into());
+        }
+        self.inner
+            .enable_fetch_cache(capacity, Duration::from_secs_f64(ttl))?;
+        Ok(())
     }
 
-    #[pyo3(signature = (vectors, namespace="", async_req=false))]
-    #[pyo3(text_signature = "(vectors, namespace='', async_req=False)")]
+    /// Disable the local cache of fetched vectors.
+    pub fn disable_fetch_cache(&mut self) {
+        self.inner.disable_fetch_cache();
+    }
+
+    /// Remove all vectors from the local cache of fetched vectors.
+    pub fn clear_fetch_cache(&self) {
+        self.inner.clear_fetch_cache();
+    }
+
+    /// The namespace used by operations that don't explicitly specify one.
+    #[getter]
+    pub fn namespace(&self) -> String {
+        self.inner.namespace().to_string()
+    }
+
+    /// Whether dense vector values are L2-normalized before upserting or querying.
+    /// Can be overridden per call using the `normalize` argument.
+    #[getter]
+    pub fn get_normalize(&self) -> bool {
+        self.inner.normalize()
+    }
+
+    #[setter]
+    pub fn set_normalize(&mut self, normalize: bool) {
+        self.inner.set_normalize(normalize);
+    }
+
+    #[pyo3(
+        signature = (vectors, namespace=None, async_req=false, normalize=None, batch_size=None, timeout=None, skip_null_metadata=false, return_ids=false, id_fn=None, max_concurrency=None, allow_partial_failure=false, request_id=None, max_retries=None)
+    )]
+    #[pyo3(
+        text_signature = "(vectors, namespace=None, async_req=False, normalize=None, batch_size=None, timeout=None, skip_null_metadata=False, return_ids=False, id_fn=None, max_concurrency=None, allow_partial_failure=False, request_id=None, max_retries=None)"
+    )]
     /// The `Upsert` operation writes vectors into a namespace.
     /// If a new value is upserted for an existing vector id, it will overwrite the previous value.
     ///
@@ -38,9 +321,31 @@ impl Index {
     ///         - A tuple of the form (id: str, vector: List[float]) or (id: str, vector: List[float], metadata: Dict[str, Union[str, float, int, bool, List[str]]]])
     ///         - A dictionary with the keys 'id' (str), 'values' (List[float]), 'sparse_values' (optional dict in the format {'indices': List[int], 'values': List[float]}), 'metadata' (Optional[Dict[str, Any]])
     ///         Note: sparse values are not supported when using a tuple. Please use a dictionary or a `Vector` object instead.
+    ///         Dense values, and the values of sparse values, can also be given as any one dimensional buffer of floats (e.g. a numpy array,
+    ///         `array.array` or `memoryview`), or an object exposing `__array__` (e.g. a torch tensor).
+    ///         float16 and bfloat16 arrays are converted to float32 on the fly.
     ///
-    ///     namespace (Optional[str]): Optional namespace to which data will be upserted.
+    ///     namespace (Optional[str]): Optional namespace to which data will be upserted. Defaults to the Index's namespace.
     ///     async_req (bool): When set to True, the upsert request will be performed asynchronously, and a "future" (asyncio coroutine) will be returned.
+    ///         Cancelling the returned future aborts the in-flight request.
+    ///     normalize (Optional[bool]): Whether to L2-normalize the vectors' dense values before upserting. Defaults to the Index's `normalize` setting.
+    ///     batch_size (Optional[int]): If set, the vectors are split into batches of this size, which are upserted concurrently.
+    ///         The number of concurrent requests is tuned automatically, backing off when the index is overloaded.
+    ///         Batches are split further to stay under the request size limit, which also applies when `batch_size` isn't set.
+    ///     timeout (Optional[float]): A timeout in seconds for the whole upsert, including all of its batches.
+    ///     skip_null_metadata (bool): Whether to drop metadata keys with a `None` value. Otherwise, such keys raise a ValueError.
+    ///         Only applies to vectors given as tuples or dictionaries.
+    ///     return_ids (bool): Whether to return the ids of the upserted vectors in the response's `upserted_ids` field.
+    ///     id_fn (Optional[Callable[[Vector], str]]): If set, called with every vector before upserting, and its return value replaces the vector's id.
+    ///         Deriving ids from the content (see `uuid5()` and `content_id()`) makes re-running an ingestion job overwrite its vectors instead of duplicating them.
+    ///     max_concurrency (Optional[int]): If set, at most this many batches are upserted concurrently.
+    ///         Otherwise, the concurrency is only bounded by the automatically tuned limit.
+    ///     allow_partial_failure (bool): Whether a batched upsert keeps going after some of its batches failed, instead of raising on the first error.
+    ///         The response's `batches` then hold the status of every batch, and `failed_ids` the ids of the vectors to retry.
+    ///     request_id (Optional[str]): Sent as the upsert request's `x-request-id` metadata, so retrying a failed upsert with the same ID
+    ///         lets the service and observability tooling recognize it as the same upsert. Batches carry `{request_id}-{batch number}`.
+    ///         Errors include the ID. Defaults to a generated ID if the Client was created with `request_ids=True`.
+    ///     max_retries (Optional[int]): How many times each request is retried if it fails transiently. Defaults to the Index's retry policy.
     ///
     /// Examples:
     ///     ```python
@@ -56,50 +361,89 @@ impl Index {
     ///     # Mixing different vector representations is also allowed
     ///     index.upsert([ {'id': 'id1', 'values': [1.0, 2.0, 3.0], 'metadata': {'key': 'value'}, 'sparse_values': {'indices': [1, 2], 'values': [0.2, 0.4]}},
     ///                    ('id2', [1.0, 2.0, 3.0]), ])
+    ///
+    ///     # Derive the ids from the chunks' text
+    ///     index.upsert([ ('', embedding, {'text': text}) for text, embedding in chunks ],
+    ///                  id_fn=lambda v: uuid5(v.metadata['text']))
     ///     ```
     ///
     /// Returns:
     ///     - If `async_req=False`:
     ///         UpsertResponse: An upsert response object. Currently has an 'upserted_count' field with vector count. Might be extended in the future.
+    ///         When the vectors are upserted in several batches, its `report` field holds an `UpsertReport` with the upsert's duration, throughput, retry count
+    ///         and batch latency percentiles. Use `report.to_json()` to publish it.
+    ///         Its `timing` field holds the upsert's client-side `LatencyBreakdown`, including converting the vectors.
     ///     - If `async_req=True`:
     ///         An `asyncio` coroutine that can be awaited using `await` or `asyncio.gather()`.
+    #[allow(clippy::too_many_arguments)]
     pub fn upsert<'a>(
-        &mut self,
+        &self,
         py: Python<'a>,
         vectors: Vec<UpsertRecord>,
-        namespace: &'a str,
+        namespace: Option<&'a str>,
         async_req: bool,
+        normalize: Option<bool>,
+        batch_size: Option<u32>,
+        timeout: Option<f64>,
+        skip_null_metadata: bool,
+        return_ids: bool,
+        id_fn: Option<PyObject>,
+        max_concurrency: Option<usize>,
+        allow_partial_failure: bool,
+        request_id: Option<String>,
+        max_retries: Option<u32>,
     ) -> PyResult<&'a PyAny> {
         // According to tonic's documentation, cloning the generated client is actually quite cheap,
         // and that's the recommended behavior: https://docs.rs/tonic/latest/tonic/transport/struct.Channel.html#multiplexing-requests
-        let mut inner_index = self.inner.clone();
+        let inner_index = self.inner_for_call(timeout, max_retries)?;
 
-        let namespace = namespace.to_owned();
-        let vectors_to_upsert =
-            convert_upsert_enum_to_vectors(vectors).map_err(PineconeClientError::from)?;
+        let namespace = namespace.map(str::to_owned);
+        let start = Instant::now();
+        let mut vectors_to_upsert = convert_upsert_enum_to_vectors(vectors, skip_null_metadata)
+            .map_err(PineconeClientError::from)?;
+        if let Some(id_fn) = id_fn {
+            for vector in vectors_to_upsert.iter_mut() {
+                vector.id = id_fn.call1(py, (vector.clone(),))?.extract(py)?;
+            }
+        }
+        let input_conversion = start.elapsed();
+        let options = core_index::UpsertOptions {
+            batch_size,
+            max_concurrency,
+            normalize,
+            return_ids,
+            allow_partial_failure,
+            request_id,
+            ..Default::default()
+        };
 
         if async_req {
-            pyo3_asyncio::tokio::future_into_py(py, async move {
+            cancellable_future_into_py(py, async move {
                 let res = inner_index
-                    .upsert(&namespace, &vectors_to_upsert, None)
-                    .await
-                    .map_err(PineconeClientError::from)?;
-                Ok(res)
+                    .upsert(namespace.as_deref(), &vectors_to_upsert, options)
+                    .await;
+                inner_index.record_latency(Phase::InputConversion, input_conversion);
+                Ok(with_input_conversion(
+                    res.map_err(PineconeClientError::from)?,
+                    input_conversion,
+                ))
             })
         } else {
-            pyo3_asyncio::tokio::get_runtime().block_on(async move {
-                let res = inner_index
-                    .upsert(&namespace, &vectors_to_upsert, None)
+            let res = self.runtime.block_on(async {
+                inner_index
+                    .upsert(namespace.as_deref(), &vectors_to_upsert, options)
                     .await
-                    .map_err(PineconeClientError::from)?;
-                Ok(res.into_py(py).into_ref(py))
-            })
+            });
+            inner_index.record_latency(Phase::InputConversion, input_conversion);
+            let res =
+                with_input_conversion(res.map_err(PineconeClientError::from)?, input_conversion);
+            Ok(to_py_timed(&self.inner, py, res).into_ref(py))
         }
     }
 
-    #[pyo3(signature = (top_k, values=None, sparse_values=None, namespace="", filter=None, include_values=false, include_metadata=false))]
+    #[pyo3(signature = (top_k, values=None, sparse_values=None, namespace=None, filter=None, include_values=false, include_metadata=false, normalize=None, columnar=false, timeout=None, max_retries=None, async_req=false))]
     #[pyo3(
-        text_signature = "($self, top_k, values=None, sparse_values=None, namespace='', filter=None, include_values=False, include_metadata=False)"
+        text_signature = "($self, top_k, values=None, sparse_values=None, namespace=None, filter=None, include_values=False, include_metadata=False, normalize=None, columnar=False, timeout=None, max_retries=None, async_req=False)"
     )]
     /// Query
     ///
@@ -110,43 +454,267 @@ impl Index {
     /// Args:
     ///     top_k (int): The number of results to return for each query.
     ///     values (Optional[List[float]]): The values for a new, unseen query vector. This should be the same length as the dimension of the index being queried. The results will be the `top_k` vectors closest to the given vector. Can not be used together with `id`.
+    ///         Any one dimensional buffer of floats (e.g. a numpy array or `memoryview`), or an object exposing `__array__`, is also accepted.
     ///     sparse_values (Optional[SparseValues]): The query vector's sparse values.
-    ///     namespace (Optional[str]): Optional namespace in which vectors will be queried.
+    ///     namespace (Optional[str]): Optional namespace in which vectors will be queried. Defaults to the Index's namespace.
     ///     filter (Optional[dict]): The filter to apply. You can use vector metadata to limit your search. See <https://www.pinecone.io/docs/metadata-filtering/>
     ///     include_values (bool): Indicates whether vector values are included in the response.
     ///     include_metadata (bool): Indicates whether metadata is included in the response as well as the ids.
+    ///     normalize (Optional[bool]): Whether to L2-normalize the query vector's dense values. Defaults to the Index's `normalize` setting.
+    ///     columnar (bool): Whether to return the results as a single `ColumnarResult`, holding a list of ids, a numpy array of scores,
+    ///         a 2D numpy matrix of values (if `include_values` is set) and a list of metadata dicts (if `include_metadata` is set).
+    ///         Requires numpy. Much cheaper than a list of QueryResults when retrieving many results.
+    ///     timeout (Optional[float]): A timeout in seconds for the query, including its retries. Defaults to the Index's timeout.
+    ///     max_retries (Optional[int]): How many times the request is retried if it fails transiently. Defaults to the Index's retry policy.
+    ///     async_req (bool): When set to True, the query is performed asynchronously, and an `asyncio` awaitable of its result is returned instead.
+    ///         Cancelling the awaitable aborts the in-flight request.
     ///
     /// Returns:
-    ///     list of QueryResults
+    ///     QueryResponse, which can be used like the list of its QueryResults, or a ColumnarResult if `columnar` is set.
+    ///     Both carry the query's client-side `LatencyBreakdown` in their `timing` field. Unlike `Index.last_latency`,
+    ///     the breakdown is the query's own, even when other operations run concurrently.
     #[allow(clippy::too_many_arguments)]
     pub fn query(
-        &mut self,
+        &self,
+        py: Python,
         top_k: i32,
-        values: Option<Vec<f32>>,
+        values: Option<DenseValues>,
         sparse_values: Option<core_data_types::SparseValues>,
-        namespace: &str,
+        namespace: Option<&str>,
+        filter: Option<BTreeMap<String, core_data_types::MetadataValue>>,
+        include_values: bool,
+        include_metadata: bool,
+        normalize: Option<bool>,
+        columnar: bool,
+        timeout: Option<f64>,
+        max_retries: Option<u32>,
+        async_req: bool,
+    ) -> PyResult<PyObject> {
+        if top_k < 1 {
+            return Err(PineconeClientError::from(core_error::ValueError(
+                "top_k must be greater than 0".to_string(),
+            ))
+            .into());
+        }
+        let inner_index = self.inner_for_call(timeout, max_retries)?;
+        let namespace = namespace.map(str::to_owned);
+        let values = values.map(Vec::from);
+        let query = async move {
+            inner_index
+                .query(
+                    namespace.as_deref(),
+                    values,
+                    sparse_values,
+                    top_k as u32,
+                    filter,
+                    include_values,
+                    include_metadata,
+                    normalize,
+                )
+                .await
+        };
+        let index = self.inner.clone();
+        self.run(py, async_req, query, move |py, res| {
+            if columnar {
+                return columnar_timed(&index, py, res.timing, || {
+                    columnar_query_results(py, res.results, include_values, include_metadata)
+                });
+            }
+            Ok(to_py_timed(&index, py, res))
+        })
+    }
+
+    #[pyo3(signature = (queries, top_k, namespace=None, filter=None, include_values=false, include_metadata=false, normalize=None))]
+    #[pyo3(
+        text_signature = "($self, queries, top_k, namespace=None, filter=None, include_values=False, include_metadata=False, normalize=None)"
+    )]
+    /// Query batch
+    ///
+    /// Runs several queries concurrently over the index's shared connection, all with the same options.
+    /// The number of concurrent queries is tuned automatically, backing off when the index is overloaded.
+    ///
+    /// Args:
+    ///     queries (List[Union[str, List[float], Tuple[List[float], SparseValues]]]): The queries. Each query is either the id of a vector
+    ///         already upserted to the namespace, a new vector's values, or a tuple of a new vector's values and sparse values.
+    ///         Values can also be given as any one dimensional buffer of floats, see `Index.query()`.
+    ///     top_k (int): The number of results to return for each query.
+    ///     namespace (Optional[str]): Optional namespace in which vectors will be queried. Defaults to the Index's namespace.
+    ///     filter (Optional[dict]): The filter to apply to every query. See <https://www.pinecone.io/docs/metadata-filtering/>
+    ///     include_values (bool): Indicates whether vector values are included in the response.
+    ///     include_metadata (bool): Indicates whether metadata is included in the response as well as the ids.
+    ///     normalize (Optional[bool]): Whether to L2-normalize the query vectors' dense values. Defaults to the Index's `normalize` setting.
+    ///
+    /// Examples:
+    ///     >>> results = index.query_batch([embedding_1, embedding_2, 'id3'], top_k=10)
+    ///
+    /// Returns:
+    ///     list of lists of QueryResults, one list per query, in the same order as `queries`
+    #[allow(clippy::too_many_arguments)]
+    pub fn query_batch(
+        &self,
+        py: Python,
+        queries: Vec<QueryBatchItem>,
+        top_k: i32,
+        namespace: Option<&str>,
         filter: Option<BTreeMap<String, core_data_types::MetadataValue>>,
         include_values: bool,
         include_metadata: bool,
-    ) -> PineconeResult<Vec<core_data_types::QueryResult>> {
+        normalize: Option<bool>,
+    ) -> PineconeResult<PyObject> {
         if top_k < 1 {
             return Err(core_error::ValueError("top_k must be greater than 0".to_string()).into());
         }
-        let res = self.runtime.block_on(self.inner.query(
+        let queries: Vec<core_data_types::QueryInput> =
+            queries.into_iter().map(Into::into).collect();
+        let res = self.runtime.block_on(self.inner.query_batch(
             namespace,
-            values,
+            &queries,
+            top_k as u32,
+            filter,
+            include_values,
+            include_metadata,
+            normalize,
+        ))?;
+        Ok(to_py_timed(&self.inner, py, res))
+    }
+
+    #[pyo3(signature = (namespaces, top_k, values=None, sparse_values=None, filter=None, include_values=false, include_metadata=false, normalize=None, metric=None))]
+    #[pyo3(
+        text_signature = "($self, namespaces, top_k, values=None, sparse_values=None, filter=None, include_values=False, include_metadata=False, normalize=None, metric=None)"
+    )]
+    /// Query namespaces
+    ///
+    /// Runs the same query against several namespaces concurrently, and merges the results into a single ranking by score.
+    ///
+    /// Args:
+    ///     namespaces (List[str]): The namespaces to query.
+    ///     top_k (int): The number of results to return, across all namespaces.
+    ///     values (Optional[List[float]]): The query vector's values, see `Index.query()`.
+    ///     sparse_values (Optional[SparseValues]): The query vector's sparse values.
+    ///     filter (Optional[dict]): The filter to apply in every namespace. See <https://www.pinecone.io/docs/metadata-filtering/>
+    ///     include_values (bool): Indicates whether vector values are included in the response.
+    ///     include_metadata (bool): Indicates whether metadata is included in the response as well as the ids.
+    ///     normalize (Optional[bool]): Whether to L2-normalize the query vector's dense values. Defaults to the Index's `normalize` setting.
+    ///     metric (Optional[str]): The index's metric, which orders the merged results: 'cosine', 'euclidean' or 'dotproduct'.
+    ///         Defaults to the metric of the Index's cached description. Required if the Index wasn't described yet.
+    ///
+    /// Examples:
+    ///     >>> results = index.query_namespaces(['tenant-a', 'tenant-b'], top_k=10, values=embedding)
+    ///     >>> results[0].namespace
+    ///
+    /// Returns:
+    ///     list of the `top_k` most similar QueryResults across all namespaces, each with its `namespace` set
+    #[allow(clippy::too_many_arguments)]
+    pub fn query_namespaces(
+        &self,
+        py: Python,
+        namespaces: Vec<String>,
+        top_k: i32,
+        values: Option<DenseValues>,
+        sparse_values: Option<core_data_types::SparseValues>,
+        filter: Option<BTreeMap<String, core_data_types::MetadataValue>>,
+        include_values: bool,
+        include_metadata: bool,
+        normalize: Option<bool>,
+        metric: Option<core_data_types::Metric>,
+    ) -> PineconeResult<PyObject> {
+        if top_k < 1 {
+            return Err(core_error::ValueError("top_k must be greater than 0".to_string()).into());
+        }
+        let res = self.runtime.block_on(self.inner.query_namespaces(
+            &namespaces,
+            values.map(Vec::from),
             sparse_values,
             top_k as u32,
             filter,
             include_values,
             include_metadata,
+            normalize,
+            metric,
         ))?;
-        Ok(res)
+        Ok(to_py_timed(&self.inner, py, res))
     }
 
-    #[pyo3(signature = (id, top_k, namespace="", filter=None, include_values=false, include_metadata=false))]
+    #[pyo3(signature = (top_k, values=None, sparse_values=None, namespace=None, filter=None, include_values=false, include_metadata=true, normalize=None, select=None))]
     #[pyo3(
-        text_signature = "($self, id, top_k, namespace='', filter=None, include_values=False, include_metadata=False)"
+        text_signature = "($self, top_k, values=None, sparse_values=None, namespace=None, filter=None, include_values=False, include_metadata=True, normalize=None, select=None)"
+    )]
+    /// Query, then fetch
+    ///
+    /// Runs the query without values and metadata, narrows the results down with `select`, and then fetches values and metadata
+    /// only for the selected results. With a large `top_k`, e.g. the candidates of a reranking pipeline, this keeps the query's
+    /// response small, as values and metadata are only transferred for the final results.
+    ///
+    /// Args:
+    ///     top_k (int): The number of candidates to query for.
+    ///     values (Optional[List[float]]): The query vector's values, see `Index.query()`.
+    ///     sparse_values (Optional[SparseValues]): The query vector's sparse values.
+    ///     namespace (Optional[str]): Optional namespace in which vectors will be queried. Defaults to the Index's namespace.
+    ///     filter (Optional[dict]): The filter to apply. See <https://www.pinecone.io/docs/metadata-filtering/>
+    ///     include_values (bool): Whether to fetch the selected results' values.
+    ///     include_metadata (bool): Whether to fetch the selected results' metadata.
+    ///     normalize (Optional[bool]): Whether to L2-normalize the query vector's dense values. Defaults to the Index's `normalize` setting.
+    ///     select (Optional[Callable[[List[QueryResult]], List[QueryResult]]]): Picks the final results from the candidates, which only have ids and scores.
+    ///         Defaults to keeping all of them.
+    ///
+    /// Examples:
+    ///     >>> results = index.query_then_fetch(top_k=1000, values=vec, select=lambda candidates: rerank(candidates)[:20])
+    ///
+    /// Returns:
+    ///     list of QueryResults, in the order returned by `select`
+    #[allow(clippy::too_many_arguments)]
+    pub fn query_then_fetch(
+        &self,
+        py: Python,
+        top_k: i32,
+        values: Option<DenseValues>,
+        sparse_values: Option<core_data_types::SparseValues>,
+        namespace: Option<&str>,
+        filter: Option<BTreeMap<String, core_data_types::MetadataValue>>,
+        include_values: bool,
+        include_metadata: bool,
+        normalize: Option<bool>,
+        select: Option<PyObject>,
+    ) -> PyResult<PyObject> {
+        if top_k < 1 {
+            return Err(PineconeClientError::from(core_error::ValueError(
+                "top_k must be greater than 0".to_string(),
+            ))
+            .into());
+        }
+        let candidates = self
+            .runtime
+            .block_on(self.inner.query(
+                namespace,
+                values.map(Vec::from),
+                sparse_values,
+                top_k as u32,
+                filter,
+                false,
+                false,
+                normalize,
+            ))
+            .map_err(PineconeClientError::from)?
+            .results;
+        let selected: Vec<core_data_types::QueryResult> = match select {
+            Some(select) => select.call1(py, (candidates,))?.extract(py)?,
+            None => candidates,
+        };
+        let res = self
+            .runtime
+            .block_on(self.inner.fetch_into_results(
+                namespace,
+                selected,
+                include_values,
+                include_metadata,
+            ))
+            .map_err(PineconeClientError::from)?;
+        Ok(to_py_timed(&self.inner, py, res))
+    }
+
+    #[pyo3(signature = (id, top_k, namespace=None, filter=None, include_values=false, include_metadata=false, async_req=false))]
+    #[pyo3(
+        text_signature = "($self, id, top_k, namespace=None, filter=None, include_values=False, include_metadata=False, async_req=False)"
     )]
     /// Query by id
     ///
@@ -157,38 +725,198 @@ impl Index {
     /// Args:
     ///     id (str): An id of a vector already upserted to the relevant namespace. The results will be the `top_k` nearest neighbours of the vector with the given id. Cannot be used together with `values`.
     ///     top_k (int): The number of results to return for each query.
-    ///     namespace (Optional[str]): Optional namespace in which vectors will be queried.
+    ///     namespace (Optional[str]): Optional namespace in which vectors will be queried. Defaults to the Index's namespace.
     ///     filter (Optional[dict]): The filter to apply. You can use vector metadata to limit your search. See <https://www.pinecone.io/docs/metadata-filtering/>
     ///     include_values (bool): Indicates whether vector values are included in the response.
     ///     include_metadata (bool): Indicates whether metadata is included in the response as well as the ids.
+    ///     async_req (bool): When set to True, the query is performed asynchronously, and an `asyncio` awaitable of its result is returned instead.
     ///
     /// Returns:
-    ///     list of QueryResults
+    ///     QueryResponse, which can be used like the list of its QueryResults. Its `timing` field holds the query's client-side `LatencyBreakdown`.
+    #[allow(clippy::too_many_arguments)]
     pub fn query_by_id(
-        &mut self,
+        &self,
+        py: Python,
         id: &str,
         top_k: i32,
-        namespace: &str,
+        namespace: Option<&str>,
         filter: Option<BTreeMap<String, core_data_types::MetadataValue>>,
         include_values: bool,
         include_metadata: bool,
-    ) -> PineconeResult<Vec<core_data_types::QueryResult>> {
+        async_req: bool,
+    ) -> PyResult<PyObject> {
         if top_k < 1 {
-            return Err(core_error::ValueError("top_k must be greater than 0".to_string()).into());
+            return Err(PineconeClientError::from(core_error::ValueError(
+                "top_k must be greater than 0".to_string(),
+            ))
+            .into());
         }
-        let res = self.runtime.block_on(self.inner.query_by_id(
+        let inner_index = self.inner.clone();
+        let namespace = namespace.map(str::to_owned);
+        let id = id.to_owned();
+        let query = async move {
+            inner_index
+                .query_by_id(
+                    namespace.as_deref(),
+                    &id,
+                    top_k as u32,
+                    filter,
+                    include_values,
+                    include_metadata,
+                )
+                .await
+        };
+        let index = self.inner.clone();
+        self.run(py, async_req, query, move |py, res| {
+            Ok(to_py_timed(&index, py, res))
+        })
+    }
+
+    /// The current state of the connection to the index.
+    /// One of 'idle', 'connecting', 'ready' or 'transient_failure'.
+    /// The state reflects the outcome of the most recent request made through this index.
+    #[getter]
+    pub fn connection_state(&self) -> &'static str {
+        self.inner.connection_state().as_str()
+    }
+
+    /// The client-side latency breakdown of the most recent data operation made through this index (or any of its views),
+    /// or None if no operation was made yet. All timings are in seconds.
+    /// For operations split into multiple requests, the per-phase timings are summed across all requests.
+    #[getter]
+    pub fn last_latency(&self) -> Option<core_data_types::LatencyBreakdown> {
+        self.inner.last_latency()
+    }
+
+    /// Counters of the data plane requests made through this index (and all of its views): requests by operation,
+    /// bytes sent and received, errors, retries and the average request latency (in seconds).
+    /// Counted since the index was connected, or since the last `reset_client_stats()` call.
+    #[getter]
+    pub fn client_stats(&self) -> core_data_types::ClientStats {
+        self.inner.client_stats()
+    }
+
+    /// Resets the counters reported by `client_stats`.
+    #[pyo3(text_signature = "($self)")]
+    pub fn reset_client_stats(&self) {
+        self.inner.reset_client_stats();
+    }
+
+    /// Ping the index.
+    ///
+    /// Checks the connection to the index with a single lightweight request, which isn't retried.
+    /// Meant for verifying connectivity at startup, or in a service's health check.
+    ///
+    /// Returns:
+    ///     PingResult: The round-trip latency in seconds, and the resulting connection state.
+    #[pyo3(text_signature = "($self)")]
+    pub fn ping(&self) -> PineconeResult<core_data_types::PingResult> {
+        Ok(self.runtime.block_on(self.inner.ping())?)
+    }
+
+    /// Reconnect to the index.
+    ///
+    /// Tears down the underlying connection and establishes a new one, re-resolving the index endpoint.
+    /// Useful for recovering from persistent connection errors without recreating the Client.
+    ///
+    /// Returns:
+    ///     None
+    pub fn reconnect(&self) -> PineconeResult<()> {
+        self.runtime.block_on(self.inner.reconnect())?;
+        Ok(())
+    }
+
+    /// Describe the index.
+    ///
+    /// Calls the control plane to get the index configuration, and refreshes the cached `dimension` and `metric` properties.
+    ///
+    /// Returns:
+    ///     DB: An object describing the index configuration.
+    pub fn describe(&self) -> PineconeResult<core_data_types::Db> {
+        let res = self.runtime.block_on(self.inner.describe())?;
+        Ok(res)
+    }
+
+    /// The dimension of the index's vectors.
+    #[getter]
+    pub fn dimension(&self) -> PineconeResult<i32> {
+        let res = self.runtime.block_on(self.inner.dimension())?;
+        Ok(res)
+    }
+
+    /// The distance metric used by the index.
+    #[getter]
+    pub fn metric(&self) -> PineconeResult<Option<core_data_types::Metric>> {
+        let res = self.runtime.block_on(self.inner.metric())?;
+        Ok(res)
+    }
+
+    #[pyo3(signature = (queries, top_k=10, ground_truth=None, corpus=None, namespace=None, filter=None))]
+    #[pyo3(
+        text_signature = "($self, queries, top_k=10, ground_truth=None, corpus=None, namespace=None, filter=None)"
+    )]
+    /// Evaluate recall
+    ///
+    /// Runs the given queries against the index one at a time, and computes their recall@k against the true nearest neighbors.
+    /// Useful for comparing pod types, dimensions or hybrid search weights.
+    ///
+    /// Args:
+    ///     queries (List[List[float]]): The query vectors. Each can also be a numpy array, see `Index.query()`,
+    ///         and the queries can be given as a single two dimensional numpy array, with one query per row.
+    ///     top_k (int): The number of results to request per query, i.e. the `k` of recall@k. Defaults to 10.
+    ///     ground_truth (Optional[List[List[str]]]): The ids of each query's true nearest neighbors, most similar first.
+    ///     corpus (Optional[List[Vector]]): If `ground_truth` isn't given, the true nearest neighbors are found by comparing
+    ///         every query to every vector in `corpus`, using the index's metric. Accepts the same formats as `upsert()`.
+    ///     namespace (Optional[str]): The namespace to query. Defaults to the Index's namespace.
+    ///     filter (Optional[dict]): An optional filter applied to every query.
+    ///
+    /// Returns:
+    ///     RecallReport: The mean and minimal recall@k, along with query latency statistics.
+    #[allow(clippy::too_many_arguments)]
+    pub fn evaluate_recall(
+        &self,
+        queries: Vec<DenseValues>,
+        top_k: u32,
+        ground_truth: Option<Vec<Vec<String>>>,
+        corpus: Option<Vec<UpsertRecord>>,
+        namespace: Option<&str>,
+        filter: Option<BTreeMap<String, core_data_types::MetadataValue>>,
+    ) -> PineconeResult<core_data_types::RecallReport> {
+        let queries: Vec<Vec<f32>> = queries.into_iter().map(Vec::from).collect();
+        let ground_truth = match (ground_truth, corpus) {
+            (Some(ground_truth), _) => ground_truth,
+            (None, Some(corpus)) => {
+                let corpus = convert_upsert_enum_to_vectors(corpus, true)?;
+                let metric = self
+                    .runtime
+                    .block_on(self.inner.metric())?
+                    .unwrap_or_default();
+                evaluation::brute_force_neighbors(
+                    &queries,
+                    &corpus,
+                    top_k as usize,
+                    metric.as_str(),
+                )?
+            }
+            (None, None) => {
+                return Err(core_error::ValueError(
+                    "Either ground_truth or corpus must be provided".to_string(),
+                )
+                .into())
+            }
+        };
+        let res = self.runtime.block_on(self.inner.evaluate_recall(
             namespace,
-            id,
-            top_k as u32,
+            &queries,
+            &ground_truth,
+            top_k,
             filter,
-            include_values,
-            include_metadata,
         ))?;
         Ok(res)
     }
 
-    #[pyo3(signature = (filter=None))]
-    #[pyo3(text_signature = "(filter=None)")]
+    #[pyo3(signature = (filter=None, async_req=false))]
+    #[pyo3(text_signature = "($self, filter=None, async_req=False)")]
     /// Describe index stats.
     ///
     /// The `DescribeIndexStats` operation returns the number of vectors present in the index, for all the namespaces
@@ -198,21 +926,92 @@ impl Index {
     ///     filter (Dict[str, Union[str, float, int, bool, List, dict]]):
     ///     If this parameter is present, the operation only returns statistics for vectors that satisfy the filter.
     ///     See https://www.pinecone.io/docs/metadata-filtering/.. [optional]
+    ///     async_req (bool): When set to True, the stats are fetched asynchronously, and an `asyncio` awaitable of them is returned instead. [optional]
     ///
     /// Returns:
     ///     An `IndexStats` object containing index statistics.
     pub fn describe_index_stats(
-        &mut self,
+        &self,
+        py: Python,
         filter: Option<BTreeMap<String, core_data_types::MetadataValue>>,
-    ) -> PineconeResult<core_data_types::IndexStats> {
-        let res = self
-            .runtime
-            .block_on(self.inner.describe_index_stats(filter))?;
+        async_req: bool,
+    ) -> PyResult<PyObject> {
+        let inner_index = self.inner.clone();
+        let stats = async move { inner_index.describe_index_stats(filter).await };
+        self.run(py, async_req, stats, |py, res| Ok(res.into_py(py)))
+    }
+
+    #[pyo3(signature = (namespace=None, prefix=None, limit=None, pagination_token=None))]
+    #[pyo3(
+        text_signature = "($self, namespace=None, prefix=None, limit=None, pagination_token=None)"
+    )]
+    /// List
+    ///
+    /// Lists a page of the ids of the vectors in a namespace. To iterate over the vectors themselves, use `Index.iter_namespace()`.
+    ///
+    /// Args:
+    ///     namespace (Optional[str]): The namespace to list. Defaults to the Index's namespace.
+    ///     prefix (Optional[str]): If set, only ids starting with this prefix are listed.
+    ///     limit (Optional[int]): The maximal number of ids in the page. Defaults to the server's page size.
+    ///     pagination_token (Optional[str]): The token returned with the previous page. Starts from the first page if None.
+    ///
+    /// Returns:
+    ///     Tuple[List[str], Optional[str]]: The page's ids, and the token of the next page, or None if this is the last page.
+    pub fn list(
+        &self,
+        namespace: Option<&str>,
+        prefix: Option<&str>,
+        limit: Option<u32>,
+        pagination_token: Option<&str>,
+    ) -> PineconeResult<(Vec<String>, Option<String>)> {
+        let res =
+            self.runtime
+                .block_on(self.inner.list(namespace, prefix, limit, pagination_token))?;
         Ok(res)
     }
 
-    #[pyo3(signature = (ids, namespace=""))]
-    #[pyo3(text_signature = "($self, ids, namespace='')")]
+    #[pyo3(signature = (namespace=None, page_size=100))]
+    #[pyo3(text_signature = "($self, namespace=None, page_size=100)")]
+    /// Iterates over every vector in a namespace, e.g. to export or re-embed it.
+    ///
+    /// Pages of `page_size` ids are listed and then fetched, one page at a time, as the iterator is consumed.
+    /// Vectors upserted during the iteration may or may not be included.
+    ///
+    /// Args:
+    ///     namespace (Optional[str]): The namespace to scan. Defaults to the Index's namespace.
+    ///     page_size (int): The number of vectors listed and fetched per page. Defaults to 100.
+    ///
+    /// Examples:
+    ///     >>> for vector in index.iter_namespace('my_namespace'):
+    ///     ...     export(vector.id, vector.values, vector.metadata)
+    ///
+    /// Returns:
+    ///     Iterator[Vector]
+    pub fn iter_namespace(
+        &self,
+        namespace: Option<&str>,
+        page_size: u32,
+    ) -> PineconeResult<NamespaceIterator> {
+        if page_size == 0 {
+            return Err(
+                core_error::ValueError("page_size must be greater than 0".to_string()).into(),
+            );
+        }
+        Ok(NamespaceIterator {
+            inner: self.inner.clone(),
+            runtime: self.runtime.clone(),
+            namespace: namespace.map(str::to_owned),
+            page_size,
+            page: VecDeque::new(),
+            next_token: None,
+            done: false,
+        })
+    }
+
+    #[pyo3(signature = (ids, namespace=None, timeout=None, columnar=false, max_retries=None, async_req=false))]
+    #[pyo3(
+        text_signature = "($self, ids, namespace=None, timeout=None, columnar=False, max_retries=None, async_req=False)"
+    )]
     /// Fetch
     ///
     /// The fetch operation looks up and returns vectors, by ID, from a single namespace.
@@ -221,112 +1020,225 @@ impl Index {
     /// Args:
     ///     ids (List[str]): The vector IDs to fetch.
     ///     namespace (str): The namespace to fetch vectors from.
-    ///                      If not specified, the default namespace is used. [optional]
+    ///                      If not specified, the Index's namespace is used. [optional]
+    ///     timeout (float): A timeout in seconds for the whole fetch. Large fetches are split into multiple requests,
+    ///                      which all have to complete within the timeout. [optional]
+    ///     columnar (bool): Whether to return the vectors as a single `ColumnarResult`, holding a list of ids, a 2D numpy matrix of values
+    ///                      and a list of metadata dicts, ordered like `ids` (ids that weren't found are skipped). Requires numpy. [optional]
+    ///     max_retries (int): How many times each request is retried if it fails transiently. Defaults to the Index's retry policy. [optional]
+    ///     async_req (bool): When set to True, the fetch is performed asynchronously, and an `asyncio` awaitable of its result is returned instead. [optional]
     ///
     /// Examples:
     ///     >>> index.fetch(ids=['id1', 'id2'], namespace='my_namespace')
     ///     >>> index.fetch(ids=['id1', 'id2'])
+    ///     >>> index.fetch(ids=['id1', 'id2'], columnar=True).values.shape
+    ///     >>> index.fetch(ids=['id1', 'id2']).timing.network
     ///
-    /// Returns: a dictionary of vector IDs to the fetched vectors.
+    /// Returns: a FetchResponse, which can be used like a dictionary of vector IDs to the fetched vectors,
+    ///     or a ColumnarResult if `columnar` is set. Both carry the fetch's client-side `LatencyBreakdown` in their `timing` field.
+    #[allow(clippy::too_many_arguments)]
     pub fn fetch(
-        &mut self,
+        &self,
+        py: Python,
         ids: Vec<String>,
-        namespace: &str,
-    ) -> PineconeResult<HashMap<String, core_data_types::Vector>> {
-        let res = self.runtime.block_on(self.inner.fetch(namespace, &ids))?;
-        Ok(res)
+        namespace: Option<&str>,
+        timeout: Option<f64>,
+        columnar: bool,
+        max_retries: Option<u32>,
+        async_req: bool,
+    ) -> PyResult<PyObject> {
+        let inner_index = self.inner_for_call(timeout, max_retries)?;
+        let namespace = namespace.map(str::to_owned);
+        let fetched_ids = ids.clone();
+        let fetch = async move { inner_index.fetch(namespace.as_deref(), &fetched_ids).await };
+        let index = self.inner.clone();
+        self.run(py, async_req, fetch, move |py, res| {
+            if columnar {
+                return columnar_timed(&index, py, res.timing, || {
+                    columnar_vectors(py, &ids, res.vectors)
+                });
+            }
+            Ok(to_py_timed(&index, py, res))
+        })
     }
 
-    #[pyo3(signature = (id, values=None, sparse_values=None, set_metadata=None, namespace=""))]
+    #[pyo3(signature = (id, values=None, sparse_values=None, set_metadata=None, namespace=None, unset_metadata=None, async_req=false))]
     #[pyo3(
-        text_signature = "($self, id, values=None, sparse_values=None, set_metadata=None, namespace='')"
+        text_signature = "($self, id, values=None, sparse_values=None, set_metadata=None, namespace=None, unset_metadata=None, async_req=False)"
     )]
     /// Update
     /// The Update operation updates vector in a namespace.
     /// If a value is included, it will overwrite the previous value.
     /// If a set_metadata is included,
     /// the values of the fields specified in it will be added or overwrite the previous value.
+    /// If unset_metadata is included, the specified fields are removed from the vector's metadata.
     ///
     /// Examples:
     ///     >>> index.update(id='id1', values=[1, 2, 3], namespace='my_namespace')
     ///     >>> index.update(id='id1', set_metadata={'key': 'value'}, namespace='my_namespace')
     ///     >>> index.update(id='id1', values=[1, 2, 3], sparse_values=SparseValues(indices=[1, 2], values=[0.2, 0.4]),
     ///                      namespace='my_namespace')
+    ///     >>> index.update(id='id1', unset_metadata=['stale_key'], namespace='my_namespace')
     ///
     /// Args:
     ///     id (str): Vector's unique id.
-    ///     values (List[float]): vector values to set. [optional]
+    ///     values (List[float]): vector values to set, or a one dimensional numpy array of floats, see `Index.upsert()`. [optional]
     ///     sparse_values: (SparseValues): sparse values to update for the vector.
     ///     set_metadata (Dict[str, Union[str, float, int, bool, List[str]]]]): metadata to set for vector. [optional]
-    ///     namespace (str): Namespace name where to update the vector.. [optional]
+    ///     namespace (str): Namespace name where to update the vector. Defaults to the Index's namespace. [optional]
+    ///     unset_metadata (List[str]): metadata keys to remove from the vector. [optional]
+    ///         The API can't remove metadata keys, so the vector is fetched, modified and upserted back instead.
+    ///         Concurrent writes to the same vector may be lost in between.
+    ///     async_req (bool): When set to True, the update is performed asynchronously, and an `asyncio` awaitable of its response is returned instead. [optional]
     ///
+    /// Returns:
+    ///    UpdateResponse: The server's response. Currently empty.
+    #[allow(clippy::too_many_arguments)]
     pub fn update(
-        &mut self,
+        &self,
+        py: Python,
         id: &str,
-        values: Option<Vec<f32>>,
+        values: Option<DenseValues>,
         sparse_values: Option<core_data_types::SparseValues>,
         set_metadata: Option<BTreeMap<String, core_data_types::MetadataValue>>,
-        namespace: &str,
-    ) -> PineconeResult<()> {
-        self.runtime.block_on(self.inner.update(
-            id,
-            values.as_ref(),
-            sparse_values,
-            set_metadata,
-            namespace,
-        ))?;
-        Ok(())
+        namespace: Option<&str>,
+        unset_metadata: Option<Vec<String>>,
+        async_req: bool,
+    ) -> PyResult<PyObject> {
+        let inner_index = self.inner.clone();
+        let id = id.to_owned();
+        let values = values.map(Vec::from);
+        let namespace = namespace.map(str::to_owned);
+        let update = async move {
+            inner_index
+                .update(
+                    &id,
+                    values.as_ref(),
+                    sparse_values,
+                    set_metadata,
+                    &unset_metadata.unwrap_or_default(),
+                    namespace.as_deref(),
+                )
+                .await
+        };
+        self.run(py, async_req, update, |py, res| Ok(res.into_py(py)))
     }
 
-    #[pyo3(signature = (ids, namespace=""))]
-    #[pyo3(text_signature = "($self, ids, namespace='')")]
+    #[pyo3(signature = (ids, namespace=None, max_retries=None, async_req=false))]
+    #[pyo3(text_signature = "($self, ids, namespace=None, max_retries=None, async_req=False)")]
     /// Delete
     /// Delete vectors by ID from a given namespace.
     ///
     /// Args:
     ///     ids (List[str]): A list of IDs for vectors to be deleted.
-    ///     namespace (str): The name of the namespace from which vectors will be deleted. If None, the default namespace will be used.
+    ///     namespace (str): The name of the namespace from which vectors will be deleted. If None, the Index's namespace will be used.
+    ///     max_retries (Optional[int]): How many times the request is retried if it fails transiently. Defaults to the Index's retry policy.
+    ///     async_req (bool): When set to True, the delete is performed asynchronously, and an `asyncio` awaitable of its response is returned instead.
     ///
     /// Returns:
-    ///    None
-    pub fn delete(&mut self, ids: Vec<String>, namespace: &str) -> PineconeResult<()> {
-        self.runtime.block_on(self.inner.delete(ids, namespace))?;
-        Ok(())
+    ///    DeleteResponse: The server's response. Currently empty.
+    pub fn delete(
+        &self,
+        py: Python,
+        ids: Vec<String>,
+        namespace: Option<&str>,
+        max_retries: Option<u32>,
+        async_req: bool,
+    ) -> PyResult<PyObject> {
+        let inner_index = self.inner_for_call(None, max_retries)?;
+        let namespace = namespace.map(str::to_owned);
+        let delete = async move { inner_index.delete(ids, namespace.as_deref()).await };
+        self.run(py, async_req, delete, |py, res| Ok(res.into_py(py)))
     }
 
-    #[pyo3(signature = (filter, namespace=""))]
-    #[pyo3(text_signature = "($self, filter, namespace='')")]
+    #[pyo3(signature = (filter, namespace=None, max_retries=None, async_req=false))]
+    #[pyo3(text_signature = "($self, filter, namespace=None, max_retries=None, async_req=False)")]
     /// Delete by filter
     /// The delete by filter operation deletes a list of vectors from a given namespace that match the filter.
     ///
     /// Args:
     ///     filter (Dict[str, Union[str, float, int, bool, List, dict]]): filter to be applied to delete the vectors. See https://www.pinecone.io/docs/metadata-filtering/
-    ///     namespace (Optional[str]): The name of the namespace from which vectors will be deleted. If None, the default namespace will be used.
+    ///     namespace (Optional[str]): The name of the namespace from which vectors will be deleted. If None, the Index's namespace will be used.
+    ///     max_retries (Optional[int]): How many times the request is retried if it fails transiently. Defaults to the Index's retry policy.
+    ///     async_req (bool): When set to True, the delete is performed asynchronously, and an `asyncio` awaitable of its response is returned instead.
     ///
     /// Returns:
-    ///    None
+    ///    DeleteResponse: The server's response. Currently empty.
     pub fn delete_by_metadata(
-        &mut self,
+        &self,
+        py: Python,
         filter: Option<BTreeMap<String, core_data_types::MetadataValue>>,
-        namespace: &str,
-    ) -> PineconeResult<()> {
-        self.runtime
-            .block_on(self.inner.delete_by_metadata(filter, namespace))?;
-        Ok(())
+        namespace: Option<&str>,
+        max_retries: Option<u32>,
+        async_req: bool,
+    ) -> PyResult<PyObject> {
+        let inner_index = self.inner_for_call(None, max_retries)?;
+        let namespace = namespace.map(str::to_owned);
+        let delete = async move {
+            inner_index
+                .delete_by_metadata(filter, namespace.as_deref())
+                .await
+        };
+        self.run(py, async_req, delete, |py, res| Ok(res.into_py(py)))
     }
 
-    #[pyo3(signature = (namespace=""))]
-    #[pyo3(text_signature = "($self, namespace='')")]
+    #[pyo3(signature = (namespace=None, max_retries=None, async_req=false))]
+    #[pyo3(text_signature = "($self, namespace=None, max_retries=None, async_req=False)")]
     /// Delete all
     /// The delete all operation deletes all the vectors from a given namespace.
     ///
     /// Args:
-    ///     namespace (str): The name of the namespace from which vectors will be deleted. If None, the default namespace will be used.
+    ///     namespace (str): The name of the namespace from which vectors will be deleted. If None, the Index's namespace will be used.
+    ///     max_retries (Optional[int]): How many times the request is retried if it fails transiently. Defaults to the Index's retry policy.
+    ///     async_req (bool): When set to True, the delete is performed asynchronously, and an `asyncio` awaitable of its response is returned instead.
     ///
     /// Returns:
-    ///    None
-    pub fn delete_all(&mut self, namespace: &str) -> PineconeResult<()> {
-        self.runtime.block_on(self.inner.delete_all(namespace))?;
-        Ok(())
+    ///    DeleteResponse: The server's response. Currently empty.
+    pub fn delete_all(
+        &self,
+        py: Python,
+        namespace: Option<&str>,
+        max_retries: Option<u32>,
+        async_req: bool,
+    ) -> PyResult<PyObject> {
+        let inner_index = self.inner_for_call(None, max_retries)?;
+        let namespace = namespace.map(str::to_owned);
+        let delete = async move { inner_index.delete_all(namespace.as_deref()).await };
+        self.run(py, async_req, delete, |py, res| Ok(res.into_py(py)))
+    }
+}
+
+/// Iterates over the vectors of a namespace, see `Index.iter_namespace()`.
+#[pyclass]
+pub struct NamespaceIterator {
+    inner: core_index::Index,
+    runtime: RuntimeHandle,
+    namespace: Option<String>,
+    page_size: u32,
+    // The remaining vectors of the current page
+    page: VecDeque<core_data_types::Vector>,
+    next_token: Option<String>,
+    done: bool,
+}
+
+#[pymethods]
+impl NamespaceIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self) -> PineconeResult<Option<core_data_types::Vector>> {
+        // Pages may come back empty, if all of their vectors were deleted before being fetched
+        while self.page.is_empty() && !self.done {
+            let (page, next_token) = self.runtime.block_on(self.inner.scan_page(
+                self.namespace.as_deref(),
+                self.page_size,
+                self.next_token.as_deref(),
+            ))?;
+            self.page = page.into();
+            self.done = next_token.is_none();
+            self.next_token = next_token;
+        }
+        Ok(self.page.pop_front())
     }
 }
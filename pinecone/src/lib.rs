@@ -3,9 +3,15 @@
 
 use pyo3::prelude::*;
 
+pub mod async_client;
+pub mod async_index;
 pub mod client;
 pub mod data_types;
+pub mod filter;
+pub mod ids;
 pub mod index;
+pub mod results;
+pub mod scores;
 pub mod utils;
 
 use crate::index::Index;
@@ -15,16 +21,48 @@ use utils::errors;
 
 #[pymodule]
 fn pinecone(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
+    // Forward the SDK's log messages (e.g. warnings) to Python's `logging` module
+    pyo3_log::init();
     m.add_class::<Client>()?;
+    m.add_class::<async_client::AsyncClient>()?;
     m.add_class::<core_data_types::Vector>()?;
     m.add_class::<core_data_types::SparseValues>()?;
     m.add_class::<core_data_types::QueryResult>()?;
+    m.add_class::<core_data_types::QueryResponse>()?;
+    m.add_class::<core_data_types::FetchResponse>()?;
+    m.add_class::<core_data_types::ColumnarResult>()?;
     m.add_class::<core_data_types::NamespaceStats>()?;
     m.add_class::<core_data_types::IndexStats>()?;
+    m.add_class::<core_data_types::LatencyBreakdown>()?;
+    m.add_class::<core_data_types::ClientStats>()?;
+    m.add_class::<core_data_types::PingResult>()?;
+    m.add_class::<core_data_types::OperationMetrics>()?;
+    m.add_class::<core_data_types::PayloadDump>()?;
+    m.add_class::<core_data_types::IndexProgress>()?;
+    m.add_class::<core_data_types::UpsertReport>()?;
+    m.add_class::<core_data_types::UpsertBatchStatus>()?;
+    m.add_class::<core_data_types::RecallReport>()?;
+    m.add_class::<core_data_types::AuditEvent>()?;
+    m.add_class::<core_data_types::Backup>()?;
+    m.add_class::<core_data_types::UpsertResponse>()?;
+    m.add_class::<core_data_types::UpdateResponse>()?;
+    m.add_class::<core_data_types::DeleteResponse>()?;
+    m.add_class::<core_data_types::Db>()?;
+    m.add_class::<core_data_types::Collection>()?;
     m.add(
         "PineconeOpError",
         <errors::PineconeOpError as pyo3::PyTypeInfo>::type_object(_py),
     )?;
     m.add_class::<Index>()?;
+    m.add_class::<async_index::AsyncIndex>()?;
+    m.add_class::<index::NamespaceIterator>()?;
+    m.add_function(wrap_pyfunction!(filter::parse_filter, m)?)?;
+    m.add_function(wrap_pyfunction!(scores::normalize_score, m)?)?;
+    m.add_function(wrap_pyfunction!(scores::similarity, m)?)?;
+    m.add_function(wrap_pyfunction!(scores::rank, m)?)?;
+    m.add_function(wrap_pyfunction!(ids::uuid5, m)?)?;
+    m.add_function(wrap_pyfunction!(ids::content_id, m)?)?;
+    m.add_function(wrap_pyfunction!(results::to_dict, m)?)?;
+    m.add_function(wrap_pyfunction!(results::to_json, m)?)?;
     Ok(())
 }
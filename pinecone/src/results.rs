@@ -0,0 +1,72 @@
+use pyo3::prelude::*;
+use pyo3::types::{IntoPyDict, PyDict, PyList, PyTuple};
+
+#[pyfunction]
+#[pyo3(text_signature = "(results)")]
+/// Converts results, or a collection of results, into plain Python structures, e.g. for logging or caching.
+///
+/// Every object with a `to_dict()` method (e.g. `QueryResult`, `Vector` or `IndexStats`) is replaced by its dict,
+/// at any depth of lists, tuples and dicts. The NumPy arrays of a `ColumnarResult` are converted into lists.
+///
+/// Args:
+///     results: A single result, or a collection of results, e.g. the list returned by `Index.query()`,
+///         the dict returned by `Index.fetch()`, or the lists of lists returned by `Index.query_batch()`.
+///
+/// Examples:
+///     >>> to_dict(index.fetch(ids=["id1", "id2"]))
+///     {'id1': {'id': 'id1', 'values': [...], 'sparse_values': None, 'metadata': None}, 'id2': {...}}
+///
+/// Returns:
+///     The results, with every result converted into a dict.
+pub fn to_dict(py: Python, results: &PyAny) -> PyResult<PyObject> {
+    plain(py, results)
+}
+
+#[pyfunction]
+#[pyo3(signature = (results, indent=None))]
+#[pyo3(text_signature = "(results, indent=None)")]
+/// Serializes results, or a collection of results, into a JSON string, see `to_dict()`.
+///
+/// Args:
+///     results: A single result, or a collection of results, e.g. the list returned by `Index.query()`.
+///     indent (Optional[int]): If set, the JSON is pretty-printed with this indentation. Defaults to a compact single line.
+///
+/// Examples:
+///     >>> logger.info("Query results: %s", to_json(index.query(values=vec, top_k=10)))
+///
+/// Returns:
+///     str: The JSON string.
+pub fn to_json(py: Python, results: &PyAny, indent: Option<usize>) -> PyResult<String> {
+    let kwargs = [("indent", indent.to_object(py))].into_py_dict(py);
+    py.import("json")?
+        .call_method("dumps", (plain(py, results)?,), Some(kwargs))?
+        .extract()
+}
+
+fn plain(py: Python, obj: &PyAny) -> PyResult<PyObject> {
+    if let Ok(dict) = obj.downcast::<PyDict>() {
+        let converted = PyDict::new(py);
+        for (key, value) in dict {
+            converted.set_item(key, plain(py, value)?)?;
+        }
+        Ok(converted.into())
+    } else if let Ok(list) = obj.downcast::<PyList>() {
+        list_of(py, list.iter())
+    } else if let Ok(tuple) = obj.downcast::<PyTuple>() {
+        list_of(py, tuple.iter())
+    } else if obj.hasattr("to_dict")? {
+        plain(py, obj.call_method0("to_dict")?)
+    } else if obj.hasattr("tolist")? {
+        // A NumPy array, e.g. the scores of a `ColumnarResult`
+        Ok(obj.call_method0("tolist")?.into())
+    } else {
+        Ok(obj.into())
+    }
+}
+
+fn list_of<'a>(py: Python, items: impl Iterator<Item = &'a PyAny>) -> PyResult<PyObject> {
+    let items = items
+        .map(|item| plain(py, item))
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(PyList::new(py, items).into())
+}
@@ -0,0 +1,75 @@
+use crate::utils::errors::PineconeResult;
+use client_sdk::data_types as core_data_types;
+use client_sdk::utils::normalization;
+use client_sdk::utils::similarity as local_similarity;
+use pyo3::prelude::*;
+
+#[pyfunction]
+#[pyo3(text_signature = "(score, metric)")]
+/// Maps a query score to a 0-1 relevance scale, where higher is more relevant, so scores from indexes
+/// with different metrics can be compared, combined or thresholded consistently.
+///
+/// 'cosine' scores are mapped linearly from [-1, 1], 'dotproduct' scores are squashed with a logistic function,
+/// and 'euclidean' distances are mapped to 1 / (1 + distance).
+///
+/// Args:
+///     score (float): A score returned by a query.
+///     metric (str): The metric of the queried index, as returned by `Index.metric`.
+///
+/// Examples:
+///     >>> metric = index.metric
+///     >>> relevant = [r for r in index.query(values=vec, top_k=10) if normalize_score(r.score, metric) > 0.8]
+///
+/// Returns:
+///     float: The normalized score.
+pub fn normalize_score(score: f32, metric: &str) -> PineconeResult<f32> {
+    Ok(normalization::normalize_score(score, metric)?)
+}
+
+#[pyfunction]
+#[pyo3(text_signature = "(a, b, metric)")]
+/// Scores a pair of vectors locally, exactly as the service scores them in an index with the given metric.
+///
+/// 'cosine' and 'dotproduct' scores are higher for more similar vectors, while 'euclidean' scores are squared distances,
+/// which are lower for more similar vectors. Sparse values are only supported with 'dotproduct'.
+///
+/// Args:
+///     a (Vector): The first vector.
+///     b (Vector): The second vector. Its dense values must have the same dimension as `a`'s.
+///     metric (str): 'cosine', 'dotproduct' or 'euclidean'.
+///
+/// Returns:
+///     float: The score.
+pub fn similarity(
+    a: core_data_types::Vector,
+    b: core_data_types::Vector,
+    metric: &str,
+) -> PineconeResult<f32> {
+    Ok(local_similarity::score(&a, &b, metric)?)
+}
+
+#[pyfunction]
+#[pyo3(signature = (query, candidates, metric, top_k=None))]
+#[pyo3(text_signature = "(query, candidates, metric, top_k=None)")]
+/// Ranks vectors locally by their similarity to a query vector, most similar first, like the results of a query.
+///
+/// Args:
+///     query (Vector): The query vector.
+///     candidates (List[Vector]): The vectors to rank, e.g. fetched from the index.
+///     metric (str): 'cosine', 'dotproduct' or 'euclidean', see `similarity()`.
+///     top_k (Optional[int]): If set, only the `top_k` most similar candidates are returned.
+///
+/// Examples:
+///     >>> candidates = list(index.fetch(ids).values())
+///     >>> results = rank(Vector(id="q", values=vec), candidates, index.metric, top_k=10)
+///
+/// Returns:
+///     List[QueryResult]: The ranked candidates with their scores.
+pub fn rank(
+    query: core_data_types::Vector,
+    candidates: Vec<core_data_types::Vector>,
+    metric: &str,
+    top_k: Option<usize>,
+) -> PineconeResult<Vec<core_data_types::QueryResult>> {
+    Ok(local_similarity::rank(&query, candidates, metric, top_k)?)
+}
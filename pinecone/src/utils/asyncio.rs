@@ -0,0 +1,56 @@
+use std::future::Future;
+
+use client_sdk::utils::errors::PineconeResult;
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::prelude::*;
+use tokio_util::sync::CancellationToken;
+
+use crate::utils::errors::PineconeClientError;
+
+/// Converts a Rust future into a Python awaitable, just like `pyo3_asyncio::tokio::future_into_py()`.
+/// In addition, cancelling the returned awaitable (e.g. by `task.cancel()` or `asyncio.wait_for()` timing out)
+/// drops the Rust future, which aborts any in-flight gRPC request instead of letting it complete in the background.
+pub fn cancellable_future_into_py<F, T>(py: Python<'_>, fut: F) -> PyResult<&PyAny>
+where
+    F: Future<Output = PyResult<T>> + Send + 'static,
+    T: IntoPy<PyObject>,
+{
+    let token = CancellationToken::new();
+    let cancelled = token.clone();
+    let py_fut = pyo3_asyncio::tokio::future_into_py(py, async move {
+        tokio::select! {
+            res = fut => res,
+            // The Python future was already cancelled, so this error is never seen by the user
+            _ = cancelled.cancelled() => Err(PyRuntimeError::new_err("Operation cancelled")),
+        }
+    })?;
+    py_fut.call_method1("add_done_callback", (CancelOnDone { token },))?;
+    Ok(py_fut)
+}
+
+/// Like [`cancellable_future_into_py`], for a future of the SDK, whose errors are raised as Pinecone exceptions.
+pub fn sdk_future_into_py<F, T>(py: Python<'_>, fut: F) -> PyResult<&PyAny>
+where
+    F: Future<Output = PineconeResult<T>> + Send + 'static,
+    T: IntoPy<PyObject>,
+{
+    cancellable_future_into_py(py, async move {
+        Ok(fut.await.map_err(PineconeClientError::from)?)
+    })
+}
+
+/// A done-callback for Python futures, which cancels the Rust side if the Python future was cancelled.
+#[pyclass]
+struct CancelOnDone {
+    token: CancellationToken,
+}
+
+#[pymethods]
+impl CancelOnDone {
+    fn __call__(&self, fut: &PyAny) -> PyResult<()> {
+        if fut.call_method0("cancelled")?.is_true()? {
+            self.token.cancel();
+        }
+        Ok(())
+    }
+}
@@ -20,52 +20,91 @@ impl From<core_errors::PineconeClientError> for PineconeClientError {
 }
 
 impl From<PineconeClientError> for PyErr {
+    /// Converts the error into the matching Python exception, with the error's category as its `kind` attribute
+    /// (e.g. 'not_found' or 'unavailable'), and whether retrying may succeed as its `retryable` attribute.
     fn from(err: PineconeClientError) -> PyErr {
-        match err.inner {
+        let py_err = err.to_py_err();
+        Python::with_gil(|py| {
+            let value = py_err.value(py);
+            // Setting attributes on a fresh exception instance can't fail in practice
+            let _ = value.setattr("kind", err.inner.kind().as_str());
+            let _ = value.setattr("retryable", err.inner.is_retryable());
+        });
+        py_err
+    }
+}
+
+impl PineconeClientError {
+    fn to_py_err(&self) -> PyErr {
+        match self.inner {
             core_errors::PineconeClientError::ArgumentError { .. } => {
-                exceptions::PyValueError::new_err(err.inner.to_string())
+                exceptions::PyValueError::new_err(self.inner.to_string())
+            }
+            core_errors::PineconeClientError::UnknownRegionError { .. } => {
+                exceptions::PyValueError::new_err(self.inner.to_string())
             }
             core_errors::PineconeClientError::ControlPlaneConnectionError { .. } => {
-                exceptions::PyConnectionError::new_err(err.inner.to_string())
+                exceptions::PyConnectionError::new_err(self.inner.to_string())
             }
             core_errors::PineconeClientError::IndexConnectionError { .. } => {
-                exceptions::PyConnectionError::new_err(err.inner.to_string())
+                exceptions::PyConnectionError::new_err(self.inner.to_string())
+            }
+            core_errors::PineconeClientError::InvalidEndpointError { .. } => {
+                exceptions::PyValueError::new_err(self.inner.to_string())
+            }
+            core_errors::PineconeClientError::DnsResolutionError { .. }
+            | core_errors::PineconeClientError::ConnectionRefusedError { .. }
+            | core_errors::PineconeClientError::TlsError { .. }
+            | core_errors::PineconeClientError::EndpointConnectionError { .. } => {
+                exceptions::PyConnectionError::new_err(self.inner.to_string())
             }
             core_errors::PineconeClientError::DataplaneOperationError(_) => {
-                PineconeOpError::new_err(err.inner.to_string())
+                PineconeOpError::new_err(self.inner.to_string())
             }
             core_errors::PineconeClientError::IoError(_) => {
-                exceptions::PyIOError::new_err(err.inner.to_string())
+                exceptions::PyIOError::new_err(self.inner.to_string())
             }
             core_errors::PineconeClientError::MetadataValueError { .. } => {
-                exceptions::PyValueError::new_err(err.inner.to_string())
+                exceptions::PyValueError::new_err(self.inner.to_string())
             }
             core_errors::PineconeClientError::MetadataError { .. } => {
-                exceptions::PyValueError::new_err(err.inner.to_string())
+                exceptions::PyValueError::new_err(self.inner.to_string())
+            }
+            core_errors::PineconeClientError::NullMetadataError { .. } => {
+                exceptions::PyValueError::new_err(self.inner.to_string())
+            }
+            core_errors::PineconeClientError::FilterError { .. } => {
+                exceptions::PyValueError::new_err(self.inner.to_string())
             }
             core_errors::PineconeClientError::Other(_) => {
-                exceptions::PyRuntimeError::new_err(err.inner.to_string())
+                exceptions::PyRuntimeError::new_err(self.inner.to_string())
             }
             core_errors::PineconeClientError::ControlPlaneOperationError { .. } => {
-                PineconeOpError::new_err(err.inner.to_string())
+                PineconeOpError::new_err(self.inner.to_string())
+            }
+            core_errors::PineconeClientError::ControlPlaneRateLimitError { .. } => {
+                PineconeOpError::new_err(self.inner.to_string())
             }
             core_errors::PineconeClientError::ControlPlaneParsingError { .. } => {
-                PineconeOpError::new_err(err.inner.to_string())
+                PineconeOpError::new_err(self.inner.to_string())
             }
             core_errors::PineconeClientError::DeserializationError(_) => {
-                PineconeOpError::new_err(err.inner.to_string())
+                PineconeOpError::new_err(self.inner.to_string())
             }
             core_errors::PineconeClientError::ValueError(_) => {
-                exceptions::PyValueError::new_err(err.inner.to_string())
+                exceptions::PyValueError::new_err(self.inner.to_string())
             }
             core_errors::PineconeClientError::UpsertValueError { .. } => {
-                exceptions::PyValueError::new_err(err.inner.to_string())
+                exceptions::PyValueError::new_err(self.inner.to_string())
             }
             core_errors::PineconeClientError::UpsertKeyError { .. } => {
-                exceptions::PyValueError::new_err(err.inner.to_string())
+                exceptions::PyValueError::new_err(self.inner.to_string())
             }
             core_errors::PineconeClientError::KeyboardInterrupt(_) => {
-                exceptions::PyKeyboardInterrupt::new_err(err.inner.to_string())
+                exceptions::PyKeyboardInterrupt::new_err(self.inner.to_string())
+            }
+            core_errors::PineconeClientError::Cancelled(_) => {
+                exceptions::asyncio::CancelledError::new_err(self.inner.to_string())
             }
         }
     }
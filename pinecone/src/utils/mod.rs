@@ -1 +1,4 @@
+pub mod asyncio;
 pub mod errors;
+pub mod payload_dump;
+pub mod runtime;
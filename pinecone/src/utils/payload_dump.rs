@@ -0,0 +1,15 @@
+use client_sdk::data_types::PayloadDump;
+use client_sdk::utils::payload_dump::PayloadSink;
+use pyo3::prelude::*;
+
+/// A sink that calls a Python callable with every dumped payload. Exceptions raised by the callable are printed and
+/// otherwise ignored.
+pub(crate) fn py_payload_sink(callback: PyObject) -> PayloadSink {
+    PayloadSink::callback(move |dump: &PayloadDump| {
+        Python::with_gil(|py| {
+            if let Err(err) = callback.call1(py, (dump.clone(),)) {
+                err.print(py);
+            }
+        })
+    })
+}
@@ -0,0 +1,65 @@
+use std::future::Future;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use client_sdk::utils::errors::PineconeClientError as core_error;
+use pyo3::Python;
+use tokio::runtime::{Builder, Handle, Runtime};
+
+use crate::utils::errors::PineconeResult;
+
+/// A handle to the tokio runtime a `Client` and its indexes run on.
+/// Either the runtime shared by all clients (and by `async_req=True` calls), or a runtime dedicated to one client,
+/// which is kept alive for as long as the client or any of its indexes is.
+#[derive(Clone, Debug)]
+pub struct RuntimeHandle {
+    handle: Handle,
+    _owned: Option<Arc<Runtime>>,
+}
+
+impl RuntimeHandle {
+    /// Returns a handle to the shared runtime if `worker_threads` is `None`, or creates a dedicated runtime with that many worker threads.
+    pub fn new(worker_threads: Option<usize>) -> PineconeResult<Self> {
+        match worker_threads {
+            None => Ok(Self {
+                handle: pyo3_asyncio::tokio::get_runtime().handle().clone(),
+                _owned: None,
+            }),
+            Some(0) => Err(core_error::ArgumentError {
+                name: "worker_threads".to_string(),
+                found: "0".to_string(),
+            }
+            .into()),
+            Some(worker_threads) => {
+                let runtime = Builder::new_multi_thread()
+                    .worker_threads(worker_threads)
+                    .enable_all()
+                    .build()
+                    .map_err(core_error::IoError)?;
+                Ok(Self {
+                    handle: runtime.handle().clone(),
+                    _owned: Some(Arc::new(runtime)),
+                })
+            }
+        }
+    }
+
+    /// Runs a future to completion on the calling thread, releasing the GIL while it runs,
+    /// so other Python threads aren't blocked while waiting on the network.
+    /// Python callbacks invoked by the future (e.g. progress callbacks) reacquire the GIL with `Python::with_gil`.
+    pub fn block_on<F>(&self, fut: F) -> F::Output
+    where
+        F: Future + Send,
+        F::Output: Send,
+    {
+        Python::with_gil(|py| py.allow_threads(|| self.handle.block_on(fut)))
+    }
+}
+
+impl Deref for RuntimeHandle {
+    type Target = Handle;
+
+    fn deref(&self) -> &Handle {
+        &self.handle
+    }
+}
@@ -0,0 +1,72 @@
+import asyncio
+import inspect
+import socket
+import time
+
+import pytest
+
+from pinecone import Client, PineconeOpError
+
+
+def unreachable_index(name):
+    # Nothing listens on port 1, so every request fails right away, without a control plane call
+    client = Client(api_key='test-api-key', region='us-west1-gcp', project_id='test-project', max_retries=0)
+    return client.index_from_host('http://127.0.0.1:1', name)
+
+
+def silent_server():
+    # Accepts connections (through the listen backlog) but never responds, so requests hang until cancelled
+    server = socket.socket(socket.AF_INET, socket.SOCK_STREAM)
+    server.bind(('127.0.0.1', 0))
+    server.listen(16)
+    return server
+
+
+def async_requests(index):
+    return [
+        index.query(top_k=5, values=[0.5, 0.5], async_req=True),
+        index.query_by_id(id='vec1', top_k=5, async_req=True),
+        index.fetch(ids=['vec1', 'vec2'], async_req=True),
+        index.update(id='vec1', values=[0.5, 0.5], async_req=True),
+        index.delete(ids=['vec1'], async_req=True),
+        index.describe_index_stats(async_req=True),
+    ]
+
+
+@pytest.mark.asyncio
+async def test_async_req_returns_awaitables():
+    index = unreachable_index('async-req-await')
+    for request in async_requests(index):
+        assert inspect.isawaitable(request)
+        with pytest.raises((PineconeOpError, ConnectionError)):
+            await request
+
+
+@pytest.mark.asyncio
+async def test_async_req_gather():
+    index = unreachable_index('async-req-gather')
+    results = await asyncio.gather(*async_requests(index), return_exceptions=True)
+    assert len(results) == 6
+    for result in results:
+        assert isinstance(result, (PineconeOpError, ConnectionError))
+
+
+@pytest.mark.asyncio
+async def test_async_req_cancellation_aborts_request():
+    server = silent_server()
+    try:
+        client = Client(api_key='test-api-key', region='us-west1-gcp', project_id='test-project', max_retries=0)
+        host = 'http://127.0.0.1:{}'.format(server.getsockname()[1])
+        index = client.index_from_host(host, 'async-req-cancel')
+        with pytest.raises(asyncio.TimeoutError):
+            await asyncio.wait_for(index.query(top_k=5, values=[0.5, 0.5], async_req=True), timeout=0.5)
+        # The operation's latency is published when the Rust future is dropped, which only happens once the
+        # cancellation reaches it, since the server never responds
+        deadline = time.monotonic() + 5
+        while index.last_latency is None and time.monotonic() < deadline:
+            await asyncio.sleep(0.05)
+        latency = index.last_latency
+        assert latency is not None
+        assert latency.operation == 'query()'
+    finally:
+        server.close()
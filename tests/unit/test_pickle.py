@@ -0,0 +1,107 @@
+import copy
+import pickle
+
+import pytest
+
+from pinecone import Vector, SparseValues, QueryResult, QueryResponse, FetchResponse, IndexStats, NamespaceStats
+
+
+def sparse_values():
+    return SparseValues(indices=[1, 5, 9], values=[0.5, 0.25, 0.125])
+
+
+def query_result():
+    # Query results are only returned by the index, so they are built from their pickled state
+    return QueryResult._from_state({
+        'id': 'vec1',
+        'score': 0.75,
+        'values': [0.5, 1.5],
+        'sparse_values': sparse_values(),
+        'metadata': {'genre': 'drama', 'year': 2020, 'tags': ['a', 'b']},
+        'namespace': 'ns1',
+    })
+
+
+def index_stats():
+    return IndexStats._from_state({
+        'namespaces': {'ns1': NamespaceStats._from_state({'vector_count': 10})},
+        'dimension': 2,
+        'index_fullness': 0.5,
+        'total_vector_count': 10,
+    })
+
+
+def round_trips(obj):
+    return [pickle.loads(pickle.dumps(obj)), copy.deepcopy(obj)]
+
+
+def test_pickle_sparse_values():
+    for restored in round_trips(sparse_values()):
+        assert isinstance(restored, SparseValues)
+        assert restored.indices == [1, 5, 9]
+        assert restored.values == [0.5, 0.25, 0.125]
+
+
+def test_pickle_vector():
+    vector = Vector(id='vec1', values=[0.5, 1.5], sparse_values=sparse_values(), metadata={'genre': 'drama'})
+    for restored in round_trips(vector):
+        assert isinstance(restored, Vector)
+        assert restored.id == 'vec1'
+        # `values` is also the name of a mapping method, so it's read as an item
+        assert restored['values'] == [0.5, 1.5]
+        assert restored.sparse_values.indices == [1, 5, 9]
+        assert restored.metadata == {'genre': 'drama'}
+
+
+def test_pickle_query_result():
+    for restored in round_trips(query_result()):
+        assert isinstance(restored, QueryResult)
+        assert restored.to_dict() == query_result().to_dict()
+        assert restored.namespace == 'ns1'
+
+
+def test_pickle_query_response():
+    other = QueryResult._from_state({
+        'id': 'vec2', 'score': 0.5, 'values': None, 'sparse_values': None, 'metadata': None, 'namespace': None,
+    })
+    response = QueryResponse._from_state({'results': [query_result(), other], 'timing': None})
+    for restored in round_trips(response):
+        assert isinstance(restored, QueryResponse)
+        assert restored.timing is None
+        # The response is used like the list of its results
+        assert len(restored) == 2
+        assert restored[0].id == 'vec1'
+        assert restored[-1].id == 'vec2'
+        assert [r.id for r in restored] == ['vec1', 'vec2']
+        with pytest.raises(IndexError):
+            restored[2]
+
+
+def test_pickle_fetch_response():
+    vector = Vector(id='vec1', values=[0.5, 1.5], metadata={'genre': 'drama'})
+    response = FetchResponse._from_state({'vectors': {'vec1': vector}, 'timing': None})
+    for restored in round_trips(response):
+        assert isinstance(restored, FetchResponse)
+        assert restored.timing is None
+        # The response is used like the dict of its vectors
+        assert 'vec1' in restored
+        assert list(restored) == ['vec1']
+        assert restored['vec1'].metadata == {'genre': 'drama'}
+        assert restored.get('missing') is None
+        with pytest.raises(KeyError):
+            restored['missing']
+
+
+def test_pickle_index_stats():
+    for restored in round_trips(index_stats()):
+        assert isinstance(restored, IndexStats)
+        assert restored.dimension == 2
+        assert restored.total_vector_count == 10
+        assert restored.index_fullness == pytest.approx(0.5)
+        assert restored.namespaces['ns1'].vector_count == 10
+
+
+def test_pickle_highest_protocol():
+    results = [query_result(), query_result()]
+    restored = pickle.loads(pickle.dumps(results, protocol=pickle.HIGHEST_PROTOCOL))
+    assert [r.id for r in restored] == ['vec1', 'vec1']
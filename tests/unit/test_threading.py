@@ -0,0 +1,42 @@
+import socket
+import threading
+
+from pinecone import Client, PineconeOpError
+
+
+def silent_server():
+    # Accepts connections (through the listen backlog) but never responds, so requests hang until they time out
+    server = socket.socket(socket.AF_INET, socket.SOCK_STREAM)
+    server.bind(('127.0.0.1', 0))
+    server.listen(16)
+    return server
+
+
+def test_index_shared_between_threads():
+    server = silent_server()
+    try:
+        client = Client(api_key='test-api-key', region='us-west1-gcp', project_id='test-project', max_retries=0)
+        host = 'http://127.0.0.1:{}'.format(server.getsockname()[1])
+        index = client.index_from_host(host, 'shared-between-threads')
+        barrier = threading.Barrier(2)
+        errors = []
+
+        def query():
+            barrier.wait()
+            try:
+                index.query(top_k=5, values=[0.5, 0.5], timeout=1.0)
+            except Exception as e:
+                errors.append(e)
+
+        threads = [threading.Thread(target=query) for _ in range(2)]
+        for thread in threads:
+            thread.start()
+        for thread in threads:
+            thread.join()
+
+        # Both queries are in flight at the same time, and both time out, rather than one failing to borrow the index
+        assert len(errors) == 2
+        for error in errors:
+            assert isinstance(error, (PineconeOpError, ConnectionError)), repr(error)
+    finally:
+        server.close()